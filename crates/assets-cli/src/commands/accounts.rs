@@ -1,5 +1,6 @@
 use anyhow::Result;
 use assets_core::{AccountService, AccountSubtype, AccountType, Database, NewAccount};
+use clap::Args;
 use rust_decimal::Decimal;
 use std::io::{self, Write};
 use std::str::FromStr;
@@ -295,6 +296,7 @@ pub async fn create_account_interactive() -> Result<()> {
         purchase_date,
         purchase_price,
         currency: "EUR".to_string(), // Default currency, consider making this a prompt
+        is_category: false,
         notes,
     };
     println!("\n🔄 Creating account...");
@@ -457,20 +459,42 @@ pub async fn show_account_ownership(account_id_str: &str) -> Result<()> {
     Ok(())
 }
 
+#[derive(Args)]
+pub struct CreateAccountArgs {
+    /// Account name
+    #[arg(long)]
+    name: Option<String>,
+    /// Account type (Asset, Liability, Equity, Income, Expense)
+    #[arg(long)]
+    account_type: Option<String>,
+    /// Account subtype
+    #[arg(long)]
+    subtype: Option<String>,
+    /// Parent account path (e.g., "Assets:Current Assets")
+    #[arg(long)]
+    parent: Option<String>,
+    /// Stock/ETF symbol (for investment accounts)
+    #[arg(long)]
+    symbol: Option<String>,
+    /// Currency code (default: EUR)
+    #[arg(long, default_value = "EUR")]
+    currency: String,
+    /// Group other accounts only; entries cannot be posted to it
+    #[arg(long)]
+    category: bool,
+    /// Account notes
+    #[arg(long)]
+    notes: Option<String>,
+}
+
 /// Create account with command-line arguments or interactively
-pub async fn create_account(
-    name: Option<&str>,
-    account_type: Option<&str>,
-    subtype: Option<&str>,
-    parent: Option<&str>,
-    symbol: Option<&str>,
-    currency: &str,
-    notes: Option<&str>,
-) -> Result<()> {
+pub async fn create_account(args: CreateAccountArgs) -> Result<()> {
     // If any required argument is missing, fall back to interactive mode
-    if name.is_none() || account_type.is_none() || subtype.is_none() {
+    let (Some(name), Some(account_type), Some(subtype)) =
+        (args.name, args.account_type, args.subtype)
+    else {
         return create_account_interactive().await;
-    }
+    };
 
     println!("🏗️  Create New Account (Command Line)");
     println!("=====================================\n");
@@ -479,7 +503,7 @@ pub async fn create_account(
     let account_service = AccountService::new(db.pool().clone());
 
     // Parse account type
-    let account_type = match account_type.unwrap().to_lowercase().as_str() {
+    let account_type = match account_type.to_lowercase().as_str() {
         "asset" => AccountType::Asset,
         "liability" => AccountType::Liability,
         "equity" => AccountType::Equity,
@@ -493,7 +517,7 @@ pub async fn create_account(
     };
 
     // Parse account subtype
-    let account_subtype = match subtype.unwrap().to_lowercase().as_str() {
+    let account_subtype = match subtype.to_lowercase().as_str() {
         // Asset subtypes
         "cash" => AccountSubtype::Cash,
         "checking" => AccountSubtype::Checking,
@@ -534,8 +558,8 @@ pub async fn create_account(
     };
 
     // Parse parent account if provided
-    let parent_id = if let Some(parent_path) = parent {
-        match account_service.get_account_by_path(parent_path).await {
+    let parent_id = if let Some(parent_path) = args.parent {
+        match account_service.get_account_by_path(&parent_path).await {
             Ok(parent_account) => Some(parent_account.id),
             Err(_) => {
                 return Err(anyhow::anyhow!(
@@ -550,18 +574,19 @@ pub async fn create_account(
 
     // Create the account
     let new_account = NewAccount {
-        name: name.unwrap().to_string(),
+        name,
         account_type,
         account_subtype,
         parent_id,
-        symbol: symbol.map(|s| s.to_string()),
+        symbol: args.symbol,
         quantity: None,
         average_cost: None,
         address: None,
         purchase_date: None,
         purchase_price: None,
-        currency: currency.to_string(),
-        notes: notes.map(|n| n.to_string()),
+        currency: args.currency,
+        is_category: args.category,
+        notes: args.notes,
     };
 
     // Show summary
@@ -590,6 +615,9 @@ pub async fn create_account(
         println!("Notes: {}", notes);
     }
     println!("Currency: {}", new_account.currency);
+    if new_account.is_category {
+        println!("Category: yes (groups accounts, takes no entries)");
+    }
 
     // Create the account
     match account_service.create_account(new_account).await {
@@ -929,6 +957,7 @@ pub async fn set_account_opening_balance(
                         purchase_date: None,
                         purchase_price: None,
                         currency: "EUR".to_string(),
+                        is_category: false,
                         notes: Some("Parent equity account".to_string()),
                     };
                     Some(account_service.create_account(equity_account).await?.id)
@@ -946,6 +975,7 @@ pub async fn set_account_opening_balance(
                 purchase_date: None,
                 purchase_price: None,
                 currency: "EUR".to_string(),
+                is_category: false,
                 notes: Some("Opening balances for accounts".to_string()),
             };
             account_service.create_account(opening_balance_new).await?
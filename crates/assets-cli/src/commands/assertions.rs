@@ -0,0 +1,232 @@
+use anyhow::Result;
+use assets_core::{
+    AccountService, BalanceAssertionCheck, BalanceAssertionService, Database, NewBalanceAssertion,
+};
+use chrono::NaiveDate;
+use clap::{Args, Subcommand};
+use comfy_table::{presets::UTF8_FULL, Table};
+use rust_decimal::Decimal;
+use uuid::Uuid;
+
+use crate::OutputFormat;
+
+#[derive(Subcommand)]
+pub enum AssertionCommands {
+    /// Record a known-good balance, e.g. from a bank statement
+    Add {
+        /// Account path (e.g. "Assets:Current Assets:BoursoBank")
+        #[arg(long)]
+        account: String,
+        /// Date the balance holds at, end of day included (YYYY-MM-DD format)
+        #[arg(long)]
+        date: String,
+        /// Expected balance, in ledger sign (negative when owed, e.g. on a credit card)
+        #[arg(long, allow_hyphen_values = true)]
+        balance: Decimal,
+    },
+    /// List recorded balance assertions
+    List {
+        /// Only assertions of this account
+        #[arg(long)]
+        account: Option<String>,
+        /// Output format
+        #[arg(long, value_enum, default_value = "table")]
+        format: OutputFormat,
+    },
+    /// Remove a balance assertion
+    Remove {
+        /// Assertion ID
+        id: String,
+    },
+}
+
+#[derive(Args)]
+pub struct CheckArgs {
+    /// Only check assertions of this account
+    #[arg(long)]
+    account: Option<String>,
+
+    /// Output format
+    #[arg(long, value_enum, default_value = "table")]
+    format: OutputFormat,
+}
+
+pub async fn handle_assertion_command(command: AssertionCommands) -> Result<()> {
+    let db = Database::from_env().await?;
+    let service = BalanceAssertionService::new(db.pool().clone());
+
+    match command {
+        AssertionCommands::Add {
+            account,
+            date,
+            balance,
+        } => {
+            let assertion_date = NaiveDate::parse_from_str(&date, "%Y-%m-%d")
+                .map_err(|_| anyhow::anyhow!("Invalid date '{}', expected YYYY-MM-DD", date))?;
+            let account_id = find_account(&db, &account).await?;
+            let assertion = service
+                .add_assertion(
+                    NewBalanceAssertion::builder()
+                        .account_id(account_id)
+                        .assertion_date(assertion_date)
+                        .expected_balance(balance)
+                        .build(),
+                )
+                .await?;
+            println!(
+                "✅ Asserted {} = {:.2} on {}",
+                account, assertion.expected_balance, assertion.assertion_date
+            );
+        }
+        AssertionCommands::List { account, format } => {
+            let account_id = match account {
+                Some(path) => Some(find_account(&db, &path).await?),
+                None => None,
+            };
+            let checks = service.check_assertions(account_id).await?;
+            print_assertions(&checks, format)?;
+        }
+        AssertionCommands::Remove { id } => {
+            let id = Uuid::parse_str(&id)
+                .map_err(|_| anyhow::anyhow!("Invalid assertion ID '{}'", id))?;
+            if service.remove_assertion(id).await? {
+                println!("✅ Balance assertion removed");
+            } else {
+                return Err(anyhow::anyhow!("No balance assertion with ID {}", id));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Evaluate every balance assertion; fails when any of them does not hold
+pub async fn check_balance_assertions(args: CheckArgs) -> Result<()> {
+    let db = Database::from_env().await?;
+    let account_id = match &args.account {
+        Some(path) => Some(find_account(&db, path).await?),
+        None => None,
+    };
+    let checks = BalanceAssertionService::new(db.pool().clone())
+        .check_assertions(account_id)
+        .await?;
+    let failures = checks.iter().filter(|check| !check.passed()).count();
+
+    match args.format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&checks)?),
+        OutputFormat::Csv => {
+            println!("date,account,expected,actual,difference,passed");
+            for check in &checks {
+                println!(
+                    "{},{},{:.2},{:.2},{:.2},{}",
+                    check.assertion.assertion_date,
+                    check.account_path,
+                    check.assertion.expected_balance,
+                    check.actual_balance,
+                    check.difference,
+                    check.passed()
+                );
+            }
+        }
+        OutputFormat::Table => {
+            println!("⚖️  Balance Assertions");
+            println!("=====================\n");
+
+            if checks.is_empty() {
+                println!("No balance assertions recorded.");
+                println!("💡 Add one with 'assets-cli assertions add --account <path> --date <date> --balance <amount>'");
+                return Ok(());
+            }
+
+            let mut table = Table::new();
+            table.load_preset(UTF8_FULL);
+            table.set_header(vec![
+                "Date",
+                "Account",
+                "Expected",
+                "Actual",
+                "Difference",
+                "",
+            ]);
+            for check in &checks {
+                table.add_row(vec![
+                    check.assertion.assertion_date.to_string(),
+                    check.account_path.clone(),
+                    format!("{:.2}", check.assertion.expected_balance),
+                    format!("{:.2}", check.actual_balance),
+                    format!("{:+.2}", check.difference),
+                    if check.passed() { "✅" } else { "❌" }.to_string(),
+                ]);
+            }
+            println!("{table}");
+
+            if failures == 0 {
+                println!("\n✅ All {} assertion(s) hold", checks.len());
+            }
+        }
+    }
+
+    if failures > 0 {
+        return Err(anyhow::anyhow!(
+            "{} of {} balance assertion(s) failed",
+            failures,
+            checks.len()
+        ));
+    }
+
+    Ok(())
+}
+
+fn print_assertions(checks: &[BalanceAssertionCheck], format: OutputFormat) -> Result<()> {
+    let assertions: Vec<_> = checks.iter().map(|check| &check.assertion).collect();
+    match format {
+        OutputFormat::Json => {
+            println!("{}", serde_json::to_string_pretty(&assertions)?);
+            return Ok(());
+        }
+        OutputFormat::Csv => {
+            println!("date,account,expected,source,id");
+            for check in checks {
+                println!(
+                    "{},{},{:.2},{},{}",
+                    check.assertion.assertion_date,
+                    check.account_path,
+                    check.assertion.expected_balance,
+                    check.assertion.source,
+                    check.assertion.id
+                );
+            }
+            return Ok(());
+        }
+        OutputFormat::Table => {}
+    }
+
+    if checks.is_empty() {
+        println!("No balance assertions recorded.");
+        return Ok(());
+    }
+
+    let mut table = Table::new();
+    table.load_preset(UTF8_FULL);
+    table.set_header(vec!["Date", "Account", "Expected", "Source", "ID"]);
+    for check in checks {
+        table.add_row(vec![
+            check.assertion.assertion_date.to_string(),
+            check.account_path.clone(),
+            format!("{:.2}", check.assertion.expected_balance),
+            check.assertion.source.clone(),
+            check.assertion.id.to_string(),
+        ]);
+    }
+    println!("{table}");
+
+    Ok(())
+}
+
+async fn find_account(db: &Database, account_path: &str) -> Result<Uuid> {
+    let account = AccountService::new(db.pool().clone())
+        .get_account_by_path(account_path)
+        .await
+        .map_err(|_| anyhow::anyhow!("Account '{}' not found", account_path))?;
+    Ok(account.id)
+}
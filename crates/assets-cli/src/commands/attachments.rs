@@ -0,0 +1,262 @@
+use anyhow::Result;
+use assets_core::{AccountService, Attachment, AttachmentService, AttachmentTarget, Database};
+use clap::{ArgGroup, Args, Subcommand};
+use comfy_table::{presets::UTF8_FULL, Table};
+use std::path::PathBuf;
+use std::process::Command;
+use uuid::Uuid;
+
+use crate::OutputFormat;
+
+#[derive(Subcommand)]
+pub enum AttachmentCommands {
+    /// Attach a receipt, invoice or statement to a transaction, an account or an import batch
+    Attach(AttachArgs),
+    /// List attachments, optionally of one transaction or account
+    List {
+        /// Only attachments of this transaction ID
+        #[arg(long, conflicts_with = "account")]
+        transaction: Option<String>,
+        /// Only attachments of this account (path or ID)
+        #[arg(long)]
+        account: Option<String>,
+        /// Output format
+        #[arg(long, value_enum, default_value = "table")]
+        format: OutputFormat,
+    },
+    /// Open an attachment with the default application
+    Open {
+        /// Attachment ID
+        id: String,
+        /// Print where the file is stored instead of opening it
+        #[arg(long)]
+        print_path: bool,
+    },
+    /// Remove an attachment
+    Detach {
+        /// Attachment ID
+        id: String,
+    },
+}
+
+#[derive(Args)]
+#[command(group(ArgGroup::new("target").required(true).args(["transaction", "account", "batch"])))]
+pub struct AttachArgs {
+    /// File to attach
+    file: PathBuf,
+    /// Transaction ID
+    #[arg(long)]
+    transaction: Option<String>,
+    /// Account path or ID
+    #[arg(long)]
+    account: Option<String>,
+    /// Import batch ID; the file is attached to every transaction of the batch
+    #[arg(long)]
+    batch: Option<String>,
+    /// Free-form notes
+    #[arg(long)]
+    notes: Option<String>,
+}
+
+pub async fn handle_attachment_command(command: AttachmentCommands) -> Result<()> {
+    let db = Database::from_env().await?;
+    let service = AttachmentService::from_env(db.pool().clone());
+
+    match command {
+        AttachmentCommands::Attach(args) => attach_file(&db, &service, args).await?,
+        AttachmentCommands::List {
+            transaction,
+            account,
+            format,
+        } => {
+            let attachments = match (transaction, account) {
+                (Some(id), _) => {
+                    service
+                        .list_for(AttachmentTarget::Transaction(parse_id(&id)?))
+                        .await?
+                }
+                (None, Some(account)) => {
+                    let account_id = resolve_account(&db, &account).await?;
+                    service
+                        .list_for(AttachmentTarget::Account(account_id))
+                        .await?
+                }
+                (None, None) => service.list_all().await?,
+            };
+            display_attachments(&attachments, format)?;
+        }
+        AttachmentCommands::Open { id, print_path } => {
+            let attachment = service.get(parse_id(&id)?).await?;
+            let stored = service.content_path(&attachment)?;
+            if print_path {
+                println!("{}", stored.display());
+                return Ok(());
+            }
+
+            // The store has no file extensions; open a copy under the original name
+            let dir = std::env::temp_dir().join("rusty-assets-attachments");
+            std::fs::create_dir_all(&dir)?;
+            let copy = dir.join(format!("{}-{}", attachment.id, attachment.file_name));
+            std::fs::copy(&stored, &copy)?;
+            open_with_default_application(&copy)?;
+            println!(
+                "📎 Opened {} ({})",
+                attachment.file_name, attachment.mime_type
+            );
+        }
+        AttachmentCommands::Detach { id } => {
+            let id = parse_id(&id)?;
+            match service.detach(id).await? {
+                Some(attachment) => println!(
+                    "✅ {} detached from {}",
+                    attachment.file_name,
+                    attachment.target()
+                ),
+                None => return Err(anyhow::anyhow!("No attachment {}", id)),
+            }
+        }
+    }
+
+    Ok(())
+}
+
+async fn attach_file(db: &Database, service: &AttachmentService, args: AttachArgs) -> Result<()> {
+    let notes = args.notes.as_deref();
+
+    if let Some(batch) = &args.batch {
+        let attachments = service
+            .attach_to_import_batch(&args.file, parse_id(batch)?, notes)
+            .await?;
+        println!(
+            "📎 {} attached to {} transaction(s) of import batch {}",
+            args.file.display(),
+            attachments.len(),
+            batch
+        );
+        return Ok(());
+    }
+
+    let target = match (&args.transaction, &args.account) {
+        (Some(id), _) => AttachmentTarget::Transaction(parse_id(id)?),
+        (None, Some(account)) => AttachmentTarget::Account(resolve_account(db, account).await?),
+        (None, None) => unreachable!("clap requires a target"),
+    };
+    let attachment = service.attach(&args.file, target, notes).await?;
+    println!(
+        "📎 {} ({}, {}) attached to {}",
+        attachment.file_name,
+        attachment.mime_type,
+        format_size(attachment.file_size),
+        target
+    );
+    println!("   ID: {}", attachment.id);
+
+    Ok(())
+}
+
+fn display_attachments(attachments: &[Attachment], format: OutputFormat) -> Result<()> {
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(attachments)?),
+        OutputFormat::Csv => {
+            println!("id,file_name,mime_type,file_size,transaction_id,account_id,content_hash,created_at");
+            for attachment in attachments {
+                println!(
+                    "{},\"{}\",{},{},{},{},{},{}",
+                    attachment.id,
+                    attachment.file_name.replace('"', "\"\""),
+                    attachment.mime_type,
+                    attachment.file_size,
+                    attachment
+                        .transaction_id
+                        .map(|id| id.to_string())
+                        .unwrap_or_default(),
+                    attachment
+                        .account_id
+                        .map(|id| id.to_string())
+                        .unwrap_or_default(),
+                    attachment.content_hash,
+                    attachment.created_at.format("%Y-%m-%d %H:%M:%S")
+                );
+            }
+        }
+        OutputFormat::Table => {
+            println!("📎 Attachments");
+            println!("==============\n");
+            if attachments.is_empty() {
+                println!("No attachments.");
+                println!(
+                    "💡 Add one with 'assets-cli attachments attach <file> --transaction <id>'"
+                );
+                return Ok(());
+            }
+            let mut table = Table::new();
+            table.load_preset(UTF8_FULL);
+            table.set_header(vec!["File", "Type", "Size", "Attached To", "Added", "ID"]);
+            for attachment in attachments {
+                table.add_row(vec![
+                    attachment.file_name.clone(),
+                    attachment.mime_type.clone(),
+                    format_size(attachment.file_size),
+                    attachment.target().to_string(),
+                    attachment.created_at.format("%Y-%m-%d").to_string(),
+                    attachment.id.to_string(),
+                ]);
+            }
+            println!("{table}");
+        }
+    }
+    Ok(())
+}
+
+fn open_with_default_application(path: &std::path::Path) -> Result<()> {
+    let status = if cfg!(target_os = "macos") {
+        Command::new("open").arg(path).status()
+    } else if cfg!(target_os = "windows") {
+        Command::new("cmd")
+            .args(["/C", "start", ""])
+            .arg(path)
+            .status()
+    } else {
+        Command::new("xdg-open").arg(path).status()
+    }
+    .map_err(|e| {
+        anyhow::anyhow!(
+            "Could not launch a viewer ({}); the file is at {}",
+            e,
+            path.display()
+        )
+    })?;
+
+    if !status.success() {
+        return Err(anyhow::anyhow!(
+            "The viewer exited with {}; the file is at {}",
+            status,
+            path.display()
+        ));
+    }
+    Ok(())
+}
+
+async fn resolve_account(db: &Database, account: &str) -> Result<Uuid> {
+    match Uuid::parse_str(account) {
+        Ok(id) => Ok(id),
+        Err(_) => Ok(AccountService::new(db.pool().clone())
+            .get_account_by_path(account)
+            .await?
+            .id),
+    }
+}
+
+fn format_size(bytes: i64) -> String {
+    if bytes < 1024 {
+        format!("{} B", bytes)
+    } else if bytes < 1024 * 1024 {
+        format!("{:.1} KB", bytes as f64 / 1024.0)
+    } else {
+        format!("{:.1} MB", bytes as f64 / (1024.0 * 1024.0))
+    }
+}
+
+fn parse_id(id: &str) -> Result<Uuid> {
+    Uuid::parse_str(id).map_err(|_| anyhow::anyhow!("Invalid ID '{}'", id))
+}
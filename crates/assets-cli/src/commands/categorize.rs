@@ -0,0 +1,295 @@
+use anyhow::Result;
+use assets_core::{
+    AccountService, CategorizationService, CategorySuggestion, Database, SuggestionSource,
+    UncategorizedEntry, UNCATEGORIZED_ACCOUNT_PATH,
+};
+use clap::{Args, Subcommand};
+use comfy_table::{presets::UTF8_FULL, Table};
+use std::collections::HashSet;
+use std::io::{self, Write};
+use uuid::Uuid;
+
+use crate::OutputFormat;
+
+#[derive(Args)]
+pub struct CategorizeArgs {
+    /// Apply saved categorization rules first, without prompting
+    #[arg(long)]
+    apply_rules: bool,
+
+    /// Number of suggested accounts shown per entry
+    #[arg(long, default_value_t = 5)]
+    suggestions: i64,
+}
+
+#[derive(Subcommand)]
+pub enum RuleCommands {
+    /// Save a rule: descriptions containing the pattern go to the account
+    Add {
+        /// Text to look for in descriptions, case-insensitive (e.g. "NAVIGO")
+        pattern: String,
+        /// Target account path (e.g. "Expenses:Transport")
+        #[arg(long)]
+        account: String,
+        /// Higher priority rules are tried first
+        #[arg(long, default_value_t = 0)]
+        priority: i32,
+    },
+    /// List rules in the order they are tried
+    List {
+        /// Output format
+        #[arg(long, value_enum, default_value = "table")]
+        format: OutputFormat,
+    },
+    /// Delete a rule
+    Delete {
+        /// Rule ID
+        id: String,
+    },
+    /// Categorize every uncategorized entry a rule matches
+    Apply,
+}
+
+pub async fn categorize_transactions(args: CategorizeArgs) -> Result<()> {
+    let db = Database::from_env().await?;
+    let service = CategorizationService::new(db.pool().clone());
+    let account_service = AccountService::new(db.pool().clone());
+
+    println!("🗂️  Categorize transactions");
+    println!("==========================\n");
+
+    if args.apply_rules {
+        let categorized = service.apply_rules().await?;
+        println!(
+            "✅ Rules categorized {} entr{}\n",
+            categorized,
+            plural(categorized)
+        );
+    }
+
+    let mut skipped: HashSet<Uuid> = HashSet::new();
+    loop {
+        let entries: Vec<UncategorizedEntry> = service
+            .uncategorized_entries()
+            .await?
+            .into_iter()
+            .filter(|entry| !skipped.contains(&entry.id))
+            .collect();
+        let Some(entry) = entries.first() else {
+            if skipped.is_empty() {
+                println!("✅ Nothing left in {}", UNCATEGORIZED_ACCOUNT_PATH);
+            } else {
+                println!(
+                    "👋 Done; {} entr{} skipped",
+                    skipped.len(),
+                    plural(skipped.len())
+                );
+            }
+            return Ok(());
+        };
+
+        let suggestions = service.suggest_accounts(entry, args.suggestions).await?;
+        display_entry(entry, entries.len(), &suggestions);
+
+        let input = prompt_input("Choose [1-9], an account path, [s]kip, [q]uit: ")?;
+        let account = match input.to_lowercase().as_str() {
+            "" => continue,
+            "q" => {
+                println!("👋 Stopped; categorized entries are saved.");
+                return Ok(());
+            }
+            "s" => {
+                skipped.insert(entry.id);
+                println!();
+                continue;
+            }
+            _ => match input.parse::<usize>() {
+                Ok(n) if (1..=suggestions.len()).contains(&n) => {
+                    let suggestion = &suggestions[n - 1];
+                    (suggestion.account_id, suggestion.account_path.clone())
+                }
+                Ok(_) => {
+                    println!("❌ No suggestion number {}\n", input);
+                    continue;
+                }
+                Err(_) => match account_service.get_account_by_path(&input).await {
+                    Ok(account) => (account.id, account.full_path.unwrap_or(input.clone())),
+                    Err(_) => {
+                        println!("❌ Account '{}' not found\n", input);
+                        continue;
+                    }
+                },
+            },
+        };
+        let (account_id, account_path) = account;
+
+        service.categorize_entry(entry.id, account_id).await?;
+        println!("✅ Moved to {}", account_path);
+
+        // Bulk-apply to the other entries with the same (or a shorter) description
+        let mut pattern = entry.description.trim().to_string();
+        if entries.len() > 1 {
+            let same = service
+                .uncategorized_entries_matching(&pattern)
+                .await?
+                .len();
+            let answer = prompt_input(&format!(
+                "Also move others? [y] the {} containing '{}', or type a shorter pattern, Enter to skip: ",
+                same, pattern
+            ))?;
+            if !answer.is_empty() && !answer.eq_ignore_ascii_case("n") {
+                if !answer.eq_ignore_ascii_case("y") {
+                    pattern = answer;
+                }
+                let moved = service.categorize_matching(&pattern, account_id).await?;
+                println!(
+                    "✅ Moved {} more entr{} containing '{}'",
+                    moved,
+                    plural(moved),
+                    pattern
+                );
+            }
+        }
+
+        let answer = prompt_input(&format!(
+            "Save a rule sending '{}' to {}? (y/N, or type another pattern): ",
+            pattern, account_path
+        ))?;
+        let rule_pattern = match answer.to_lowercase().as_str() {
+            "" | "n" => None,
+            "y" => Some(pattern),
+            _ => Some(answer),
+        };
+        if let Some(rule_pattern) = rule_pattern {
+            match service.create_rule(&rule_pattern, account_id, 0).await {
+                Ok(rule) => println!("💾 Rule saved for '{}'", rule.pattern),
+                Err(e) => println!("❌ {}", e),
+            }
+        }
+        println!();
+    }
+}
+
+pub async fn handle_rule_command(command: RuleCommands) -> Result<()> {
+    let db = Database::from_env().await?;
+    let service = CategorizationService::new(db.pool().clone());
+
+    match command {
+        RuleCommands::Add {
+            pattern,
+            account,
+            priority,
+        } => {
+            let account_id = AccountService::new(db.pool().clone())
+                .get_account_by_path(&account)
+                .await
+                .map_err(|_| anyhow::anyhow!("Account '{}' not found", account))?
+                .id;
+            let rule = service.create_rule(&pattern, account_id, priority).await?;
+            println!("✅ Rule saved: '{}' → {}", rule.pattern, rule.account_path);
+        }
+        RuleCommands::List { format } => {
+            let rules = service.list_rules().await?;
+            match format {
+                OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&rules)?),
+                OutputFormat::Csv => {
+                    println!("pattern,account,priority,id");
+                    for rule in &rules {
+                        println!(
+                            "\"{}\",{},{},{}",
+                            rule.pattern.replace('"', "\"\""),
+                            rule.account_path,
+                            rule.priority,
+                            rule.id
+                        );
+                    }
+                }
+                OutputFormat::Table => {
+                    if rules.is_empty() {
+                        println!("No categorization rules yet.");
+                        println!("💡 Save one with 'assets-cli transactions rules add <pattern> --account <path>'");
+                        return Ok(());
+                    }
+                    let mut table = Table::new();
+                    table.load_preset(UTF8_FULL);
+                    table.set_header(vec!["Pattern", "Account", "Priority", "ID"]);
+                    for rule in &rules {
+                        table.add_row(vec![
+                            rule.pattern.clone(),
+                            rule.account_path.clone(),
+                            rule.priority.to_string(),
+                            rule.id.to_string(),
+                        ]);
+                    }
+                    println!("{table}");
+                }
+            }
+        }
+        RuleCommands::Delete { id } => {
+            let id =
+                Uuid::parse_str(&id).map_err(|_| anyhow::anyhow!("Invalid rule ID '{}'", id))?;
+            if service.delete_rule(id).await? {
+                println!("✅ Rule deleted");
+            } else {
+                return Err(anyhow::anyhow!("No rule with ID {}", id));
+            }
+        }
+        RuleCommands::Apply => {
+            let categorized = service.apply_rules().await?;
+            println!(
+                "✅ Rules categorized {} entr{}",
+                categorized,
+                plural(categorized)
+            );
+        }
+    }
+
+    Ok(())
+}
+
+fn display_entry(entry: &UncategorizedEntry, remaining: usize, suggestions: &[CategorySuggestion]) {
+    println!(
+        "📄 {}  {}  {:.2}   ({} left)",
+        entry.transaction_date.format("%Y-%m-%d"),
+        entry.description,
+        -entry.amount,
+        remaining
+    );
+    println!("   {}", entry.counterpart_paths);
+
+    if suggestions.is_empty() {
+        println!("   No suggestions; type an account path.");
+        return;
+    }
+    for (index, suggestion) in suggestions.iter().enumerate() {
+        let why = match suggestion.source {
+            SuggestionSource::Rule => "rule".to_string(),
+            SuggestionSource::History => format!(
+                "{} similar, {:.0}% match",
+                suggestion.occurrences,
+                suggestion.similarity * 100.0
+            ),
+        };
+        println!("   [{}] {} ({})", index + 1, suggestion.account_path, why);
+    }
+}
+
+fn plural(count: usize) -> &'static str {
+    if count == 1 {
+        "y"
+    } else {
+        "ies"
+    }
+}
+
+fn prompt_input(prompt: &str) -> Result<String> {
+    print!("{}", prompt);
+    io::stdout().flush()?;
+    let mut input = String::new();
+    if io::stdin().read_line(&mut input)? == 0 {
+        return Err(anyhow::anyhow!(
+            "Input closed; categorized entries are saved"
+        ));
+    }
+    Ok(input.trim().to_string())
+}
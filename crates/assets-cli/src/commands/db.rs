@@ -1,5 +1,9 @@
 use anyhow::Result;
-use assets_core::Database;
+use assets_core::{BackupArchive, BackupService, ChainIssueKind, Database, IntegrityService};
+use comfy_table::{presets::UTF8_FULL, Table};
+use uuid::Uuid;
+
+use crate::OutputFormat;
 
 pub async fn init_database() -> Result<()> {
     println!("🗄️  Initializing Database");
@@ -124,3 +128,162 @@ pub async fn show_db_status() -> Result<()> {
 
     Ok(())
 }
+
+pub async fn export_database(file: &str, compress: bool) -> Result<()> {
+    println!("📦 Exporting Database");
+    println!("=====================\n");
+
+    let db = Database::from_env().await?;
+    let backup_service = BackupService::new(db.pool().clone());
+
+    let archive = backup_service.export_archive().await?;
+    BackupService::write_archive(&archive, file, compress)?;
+
+    println!("✅ Archive written to {}", file);
+    print_archive_contents(&archive);
+    println!("   • Checksum: {}", archive.checksum);
+
+    Ok(())
+}
+
+pub async fn import_database(file: &str) -> Result<()> {
+    println!("📥 Importing Database");
+    println!("=====================\n");
+
+    let archive = BackupService::read_archive(file)?;
+    println!("✅ Archive checksum verified");
+    println!(
+        "   Taken {} at schema version {}",
+        archive.created_at.format("%Y-%m-%d %H:%M:%S UTC"),
+        archive.schema_version
+    );
+
+    let db = Database::from_env().await?;
+    let backup_service = BackupService::new(db.pool().clone());
+
+    println!("🔄 Restoring...");
+    backup_service.restore_archive(&archive).await?;
+
+    println!("✅ Restore completed successfully!");
+    print_archive_contents(&archive);
+
+    Ok(())
+}
+
+/// Recompute the journal's hash chain; fails when anything does not match
+pub async fn verify_database(format: OutputFormat, reseal: bool) -> Result<()> {
+    let db = Database::from_env().await?;
+    let integrity_service = IntegrityService::new(db.pool().clone());
+    let verification = integrity_service.verify().await?;
+
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&verification)?),
+        OutputFormat::Csv => {
+            println!("kind,transaction_id,seq,recorded_hash,current_hash");
+            for issue in &verification.issues {
+                println!(
+                    "{},{},{},{},{}",
+                    issue.kind,
+                    issue.transaction_id,
+                    issue.seq.map(|seq| seq.to_string()).unwrap_or_default(),
+                    issue.expected_hash.as_deref().unwrap_or(""),
+                    issue.actual_hash.as_deref().unwrap_or("")
+                );
+            }
+        }
+        OutputFormat::Table => {
+            println!("🔏 Verifying Journal Integrity");
+            println!("==============================\n");
+            println!(
+                "   Checked {} link(s) against {} transaction(s)",
+                verification.links_checked, verification.transactions_checked
+            );
+            if let Some(head) = &verification.head_hash {
+                println!("   Chain head: {}", head);
+            }
+            println!();
+
+            if verification.is_intact() {
+                println!("✅ The journal matches its hash chain");
+                println!("💡 Keep the chain head somewhere safe to compare with later checks");
+            } else {
+                let mut table = Table::new();
+                table.load_preset(UTF8_FULL);
+                table.set_header(vec!["Issue", "Transaction", "Link", "Meaning"]);
+                for issue in &verification.issues {
+                    table.add_row(vec![
+                        issue.kind.to_string(),
+                        issue.transaction_id.to_string(),
+                        issue.seq.map(|seq| seq.to_string()).unwrap_or_default(),
+                        match issue.kind {
+                            ChainIssueKind::BrokenLink => "chain record altered after sealing",
+                            ChainIssueKind::Modified => "changed outside the application",
+                            ChainIssueKind::Unsealed => "created outside the application",
+                            ChainIssueKind::Deleted => "deleted outside the application",
+                        }
+                        .to_string(),
+                    ]);
+                }
+                println!("{table}");
+            }
+        }
+    }
+
+    if verification.is_intact() {
+        return Ok(());
+    }
+
+    if reseal {
+        // A broken link cannot be repaired, only transactions can be re-sealed
+        let transaction_ids: Vec<Uuid> = verification
+            .issues
+            .iter()
+            .filter(|issue| issue.kind != ChainIssueKind::BrokenLink)
+            .map(|issue| issue.transaction_id)
+            .collect();
+        integrity_service.reseal(&transaction_ids).await?;
+        println!(
+            "\n🔏 Accepted the current state of {} transaction(s)",
+            transaction_ids.len()
+        );
+        if verification
+            .issues
+            .iter()
+            .all(|issue| issue.kind != ChainIssueKind::BrokenLink)
+        {
+            return Ok(());
+        }
+    }
+
+    Err(anyhow::anyhow!(
+        "{} integrity issue(s) found",
+        verification.issues.len()
+    ))
+}
+
+fn print_archive_contents(archive: &BackupArchive) {
+    let data = &archive.data;
+    println!("\n📈 Contents:");
+    println!("   • Accounts: {}", data.accounts.len());
+    println!("   • Transactions: {}", data.transactions.len());
+    println!("   • Journal entries: {}", data.journal_entries.len());
+    println!("   • Prices: {}", data.price_history.len());
+    println!("   • Imported files: {}", data.imported_files.len());
+    println!("   • Duplicate matches: {}", data.transaction_matches.len());
+    println!("   • Tags: {}", data.tags.len());
+    println!(
+        "   • Recurring transactions: {}",
+        data.recurring_transactions.len()
+    );
+    println!(
+        "   • Categorization rules: {}",
+        data.categorization_rules.len()
+    );
+    println!("   • Inbox mappings: {}", data.import_mappings.len());
+    if !data.attachments.is_empty() {
+        println!(
+            "   • Attachments: {} (files are kept in the attachment store, back it up separately)",
+            data.attachments.len()
+        );
+    }
+}
@@ -0,0 +1,169 @@
+use anyhow::Result;
+use assets_core::{AccountService, AuditLogEntry, AuditService, Database};
+use clap::Args;
+use comfy_table::{presets::UTF8_FULL, Table};
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::OutputFormat;
+
+#[derive(Args)]
+pub struct HistoryArgs {
+    /// Show changes to one transaction and its journal entries
+    #[arg(long, conflicts_with = "account")]
+    transaction: Option<String>,
+
+    /// Show changes to one account (path or ID) and entries posted to it
+    #[arg(long)]
+    account: Option<String>,
+
+    /// Restrict recent changes to a table (transactions, journal_entries, accounts, price_history)
+    #[arg(long, conflicts_with_all = ["transaction", "account"])]
+    table: Option<String>,
+
+    /// Maximum number of recent changes to show
+    #[arg(long, default_value = "50")]
+    limit: u32,
+
+    /// Output format
+    #[arg(long, value_enum, default_value = "table")]
+    format: OutputFormat,
+}
+
+pub async fn show_history(args: HistoryArgs) -> Result<()> {
+    let db = Database::from_env().await?;
+    let audit_service = AuditService::new(db.pool().clone());
+
+    let (title, entries) = if let Some(id) = &args.transaction {
+        let transaction_id = Uuid::parse_str(id)?;
+        (
+            format!("Transaction {}", transaction_id),
+            audit_service
+                .history_for_transaction(transaction_id)
+                .await?,
+        )
+    } else if let Some(account) = &args.account {
+        let account_id = match Uuid::parse_str(account) {
+            Ok(id) => id,
+            Err(_) => {
+                AccountService::new(db.pool().clone())
+                    .get_account_by_path(account)
+                    .await?
+                    .id
+            }
+        };
+        (
+            format!("Account {}", account),
+            audit_service.history_for_account(account_id).await?,
+        )
+    } else {
+        (
+            "Recent changes".to_string(),
+            audit_service
+                .recent_changes(args.table.as_deref(), args.limit)
+                .await?,
+        )
+    };
+
+    match args.format {
+        OutputFormat::Table => display_history_table(&title, &entries),
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&entries)?),
+        OutputFormat::Csv => display_history_csv(&entries),
+    }
+
+    Ok(())
+}
+
+fn display_history_table(title: &str, entries: &[AuditLogEntry]) {
+    println!("📜 History: {}", title);
+    println!("{}\n", "=".repeat(11 + title.chars().count()));
+
+    if entries.is_empty() {
+        println!("No recorded changes.");
+        return;
+    }
+
+    let mut table = Table::new();
+    table.load_preset(UTF8_FULL);
+    table.set_header(vec![
+        "When",
+        "Who",
+        "Table",
+        "Operation",
+        "Record",
+        "Details",
+    ]);
+
+    for entry in entries {
+        table.add_row(vec![
+            entry.changed_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+            entry.changed_by.clone(),
+            entry.table_name.clone(),
+            entry.operation.clone(),
+            entry.record_id.to_string()[..8].to_string(),
+            describe_change(entry),
+        ]);
+    }
+
+    println!("{table}");
+    println!("\n📊 {} change(s)", entries.len());
+}
+
+fn display_history_csv(entries: &[AuditLogEntry]) {
+    println!("changed_at,changed_by,table,operation,record_id,details");
+    for entry in entries {
+        println!(
+            "{},{},{},{},{},\"{}\"",
+            entry.changed_at.format("%Y-%m-%d %H:%M:%S"),
+            entry.changed_by,
+            entry.table_name,
+            entry.operation,
+            entry.record_id,
+            describe_change(entry).replace('"', "\"\"")
+        );
+    }
+}
+
+/// One-line summary: changed fields for updates, key fields otherwise
+fn describe_change(entry: &AuditLogEntry) -> String {
+    if let (Some(old), Some(new)) = (&entry.old_data, &entry.new_data) {
+        return entry
+            .changed_fields()
+            .iter()
+            .filter(|field| field.as_str() != "updated_at")
+            .map(|field| {
+                format!(
+                    "{}: {} → {}",
+                    field,
+                    display_value(&old[field.as_str()]),
+                    display_value(&new[field.as_str()])
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("; ");
+    }
+
+    let Some(data) = entry.new_data.as_ref().or(entry.old_data.as_ref()) else {
+        return String::new();
+    };
+    let keys: &[&str] = match entry.table_name.as_str() {
+        "transactions" => &["transaction_date", "description"],
+        "journal_entries" => &["account_id", "amount", "memo"],
+        "accounts" => &["name", "account_type"],
+        "price_history" => &["symbol", "price_date", "price"],
+        _ => &[],
+    };
+    keys.iter()
+        .filter(|key| !data[**key].is_null())
+        .map(|key| format!("{}={}", key, display_value(&data[*key])))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn display_value(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Null => "∅".to_string(),
+        other => other.to_string(),
+    }
+}
@@ -1,8 +1,17 @@
 use anyhow::Result;
-use assets_core::importers::{BoursoBankImporter, QtPayslipImporter, SocietegeneraleImporter};
-use assets_core::{Database, DestinationAccount, ImportService, PayslipImportService};
+use assets_core::importers::{
+    AmountSign, BoursoBankImporter, GnuCashImporter, PdfStatementImporter, QtPayslipImporter,
+    SocietegeneraleImporter, SpreadsheetColumnMapping, SpreadsheetImporter, StatementBank,
+};
+use assets_core::{
+    Database, DestinationAccount, GnuCashMigrationService, ImportService, ImporterKind,
+    InboxOutcome, InboxReport, InboxService, InboxSnapshot, NewImportMapping, PayslipImportService,
+};
 use clap::{Args, Subcommand};
+use comfy_table::{presets::UTF8_FULL, Table};
 use rust_decimal::Decimal;
+use std::time::Duration;
+use uuid::Uuid;
 
 #[derive(Subcommand)]
 pub enum ImportCommands {
@@ -10,8 +19,113 @@ pub enum ImportCommands {
     Boursobank(BoursoBankArgs),
     /// Import Société Générale CSV transactions
     Sg(SgArgs),
+    /// Import a monthly PDF bank statement (BoursoBank or Société Générale)
+    PdfStatement(PdfStatementArgs),
+    /// Import an XLSX/XLS/ODS statement with configurable columns
+    Spreadsheet(SpreadsheetArgs),
     /// Import payslip data
     Payslip(PayslipArgs),
+    /// Migrate a complete GnuCash book (XML or SQLite)
+    Gnucash(GnuCashArgs),
+    /// Import every new file in an inbox folder once
+    Inbox(InboxArgs),
+    /// Keep scanning an inbox folder and import new files as they arrive
+    Watch(WatchArgs),
+    /// Manage the file-name mappings used by the inbox
+    Mappings {
+        #[command(subcommand)]
+        action: MappingCommands,
+    },
+}
+
+#[derive(Args)]
+pub struct InboxArgs {
+    /// Inbox directory to scan
+    dir: String,
+}
+
+#[derive(Args)]
+pub struct WatchArgs {
+    /// Inbox directory to scan
+    dir: String,
+    /// Seconds between two scans (files must stay unchanged for one interval)
+    #[arg(short, long, default_value = "60", value_parser = clap::value_parser!(u64).range(1..))]
+    interval: u64,
+}
+
+#[derive(Subcommand)]
+pub enum MappingCommands {
+    /// Route files matching a pattern to an importer
+    Add(Box<MappingArgs>),
+    /// List inbox mappings in the order they are tried
+    List,
+    /// Remove an inbox mapping
+    Remove {
+        /// Mapping ID
+        id: String,
+    },
+}
+
+#[derive(Args)]
+pub struct MappingArgs {
+    /// File name glob, e.g. "export-*.csv" (case-insensitive, supports * and ?)
+    #[arg(short, long)]
+    pattern: String,
+    /// Importer: boursobank, sg, qt_payslip, mathworks_payslip, spreadsheet, boursobank_pdf or sg_pdf
+    #[arg(short, long)]
+    importer: String,
+    /// Target account path for bank statement importers
+    #[arg(short, long)]
+    account: Option<String>,
+    /// Mappings with a higher priority are tried first
+    #[arg(long, default_value = "0")]
+    priority: i32,
+    #[arg(long = "fixed-income")]
+    fixed_gross_income: Option<String>,
+    #[arg(long = "variable-income")]
+    variable_gross_income: Option<String>,
+    #[arg(long = "bank-account")]
+    main_account: Option<String>,
+    #[arg(long = "social-contributions")]
+    social_contributions_expense: Option<String>,
+    #[arg(long = "income-taxes")]
+    revenue_taxes_expense: Option<String>,
+    #[arg(long = "meal-vouchers")]
+    meal_vouchers_account: Option<String>,
+    #[arg(long = "meal-vouchers-income")]
+    meal_vouchers_income: Option<String>,
+    #[arg(long = "additional-benefits-income")]
+    additional_benefits_income: Option<String>,
+    /// Sheet name for the spreadsheet importer (default: first sheet)
+    #[arg(long)]
+    sheet: Option<String>,
+    /// Row holding the column headers, as numbered in the spreadsheet
+    #[arg(long, default_value = "1")]
+    header_row: usize,
+    /// Header of the operation date column
+    #[arg(long)]
+    date_column: Option<String>,
+    /// Header of the value date column
+    #[arg(long)]
+    value_date_column: Option<String>,
+    /// Header of the label/description column
+    #[arg(long)]
+    label_column: Option<String>,
+    /// Header of the signed amount column
+    #[arg(long)]
+    amount_column: Option<String>,
+    /// Header of the debit (money out) column
+    #[arg(long, conflicts_with = "amount_column")]
+    debit_column: Option<String>,
+    /// Header of the credit (money in) column
+    #[arg(long, conflicts_with = "amount_column")]
+    credit_column: Option<String>,
+    /// Header of the category column
+    #[arg(long)]
+    category_column: Option<String>,
+    /// Positive amounts are money out (e.g. credit card exports)
+    #[arg(long)]
+    invert_sign: bool,
 }
 
 #[derive(Args)]
@@ -22,6 +136,9 @@ pub struct BoursoBankArgs {
     /// Target account path (e.g., "Assets:Current Assets:BoursoBank")
     #[arg(short, long)]
     account: String,
+    /// Create missing accounts, inferring their type from the path's root
+    #[arg(long)]
+    create_accounts: bool,
 }
 
 #[derive(Args)]
@@ -32,6 +149,75 @@ pub struct SgArgs {
     /// Target account path (e.g., "Assets:Current Assets:SG")
     #[arg(short, long)]
     account: String,
+    /// Create missing accounts, inferring their type from the path's root
+    #[arg(long)]
+    create_accounts: bool,
+}
+
+#[derive(Args)]
+pub struct PdfStatementArgs {
+    /// Path to the PDF statement to import
+    #[arg(short, long)]
+    file: String,
+    /// Target account path (e.g., "Assets:Current Assets:Livret A")
+    #[arg(short, long)]
+    account: String,
+    /// Statement layout: boursobank or sg
+    #[arg(short, long)]
+    bank: String,
+    /// Create missing accounts, inferring their type from the path's root
+    #[arg(long)]
+    create_accounts: bool,
+}
+
+#[derive(Args)]
+pub struct SpreadsheetArgs {
+    /// Path to the spreadsheet file to import
+    #[arg(short, long)]
+    file: String,
+    /// Target account path (e.g., "Assets:Current Assets:Broker")
+    #[arg(short, long)]
+    account: String,
+    /// Sheet name (default: first sheet)
+    #[arg(long)]
+    sheet: Option<String>,
+    /// Row holding the column headers, as numbered in the spreadsheet
+    #[arg(long, default_value = "1")]
+    header_row: usize,
+    /// Header of the operation date column
+    #[arg(long)]
+    date_column: String,
+    /// Header of the value date column
+    #[arg(long)]
+    value_date_column: Option<String>,
+    /// Header of the label/description column
+    #[arg(long)]
+    label_column: String,
+    /// Header of the signed amount column
+    #[arg(long, required_unless_present_any = ["debit_column", "credit_column"])]
+    amount_column: Option<String>,
+    /// Header of the debit (money out) column
+    #[arg(long, conflicts_with = "amount_column")]
+    debit_column: Option<String>,
+    /// Header of the credit (money in) column
+    #[arg(long, conflicts_with = "amount_column")]
+    credit_column: Option<String>,
+    /// Header of the category column
+    #[arg(long)]
+    category_column: Option<String>,
+    /// Positive amounts are money out (e.g. credit card exports)
+    #[arg(long)]
+    invert_sign: bool,
+    /// Create missing accounts, inferring their type from the path's root
+    #[arg(long)]
+    create_accounts: bool,
+}
+
+#[derive(Args)]
+pub struct GnuCashArgs {
+    /// Path to the GnuCash book (.gnucash, gzipped XML or SQLite)
+    #[arg(short, long)]
+    file: String,
 }
 
 #[derive(Args)]
@@ -57,13 +243,26 @@ pub struct PayslipArgs {
     /// Importer type (default: generic)
     #[arg(long, default_value = "generic")]
     importer: String,
+    /// Create missing accounts, inferring their type from the path's root
+    #[arg(long)]
+    create_accounts: bool,
 }
 
 pub async fn handle_import_command(command: ImportCommands) -> Result<()> {
     match command {
         ImportCommands::Boursobank(args) => import_boursobank(args).await,
         ImportCommands::Sg(args) => import_sg(args).await,
+        ImportCommands::PdfStatement(args) => import_pdf_statement(args).await,
+        ImportCommands::Spreadsheet(args) => import_spreadsheet(args).await,
         ImportCommands::Payslip(args) => import_payslip(args).await,
+        ImportCommands::Gnucash(args) => import_gnucash(args).await,
+        ImportCommands::Inbox(args) => import_inbox(args).await,
+        ImportCommands::Watch(args) => watch_inbox(args).await,
+        ImportCommands::Mappings { action } => match action {
+            MappingCommands::Add(args) => add_mapping(*args).await,
+            MappingCommands::List => list_mappings().await,
+            MappingCommands::Remove { id } => remove_mapping(&id).await,
+        },
     }
 }
 
@@ -72,7 +271,8 @@ async fn import_boursobank(args: BoursoBankArgs) -> Result<()> {
     println!("====================================\n");
 
     let db = Database::from_env().await?;
-    let import_service = ImportService::new(db.pool().clone());
+    let import_service =
+        ImportService::new(db.pool().clone()).ensure_accounts(args.create_accounts);
     let importer = BoursoBankImporter::default();
 
     let summary = import_service
@@ -94,7 +294,8 @@ async fn import_sg(args: SgArgs) -> Result<()> {
     println!("==========================================\n");
 
     let db = Database::from_env().await?;
-    let import_service = ImportService::new(db.pool().clone());
+    let import_service =
+        ImportService::new(db.pool().clone()).ensure_accounts(args.create_accounts);
     let importer = SocietegeneraleImporter::default();
 
     let summary = import_service
@@ -111,6 +312,120 @@ async fn import_sg(args: SgArgs) -> Result<()> {
     Ok(())
 }
 
+async fn import_pdf_statement(args: PdfStatementArgs) -> Result<()> {
+    println!("📄 Importing PDF Bank Statement");
+    println!("===============================\n");
+
+    let bank = match args.bank.to_lowercase().as_str() {
+        "boursobank" => StatementBank::BoursoBank,
+        "sg" | "societegenerale" => StatementBank::SocieteGenerale,
+        _ => {
+            return Err(anyhow::anyhow!(
+                "Unknown statement layout: {}. Available: boursobank, sg",
+                args.bank
+            ));
+        }
+    };
+    let importer = PdfStatementImporter::new(bank);
+
+    let db = Database::from_env().await?;
+    let import_service =
+        ImportService::new(db.pool().clone()).ensure_accounts(args.create_accounts);
+
+    let summary = import_service
+        .import_transactions(&importer, &args.file, &args.account)
+        .await?;
+
+    summary.print_summary();
+
+    if summary.created > 0 {
+        println!("\n✅ Import completed successfully!");
+        println!("💡 Tip: Run 'assets-cli reports balance-sheet' to see your updated balance");
+    }
+
+    Ok(())
+}
+
+async fn import_spreadsheet(args: SpreadsheetArgs) -> Result<()> {
+    println!("📊 Importing Spreadsheet Statement");
+    println!("==================================\n");
+
+    let mapping = SpreadsheetColumnMapping {
+        sheet: args.sheet,
+        header_row: args.header_row,
+        date_column: args.date_column,
+        value_date_column: args.value_date_column,
+        label_column: args.label_column,
+        amount_column: args.amount_column,
+        debit_column: args.debit_column,
+        credit_column: args.credit_column,
+        category_column: args.category_column,
+        sign: if args.invert_sign {
+            AmountSign::Inverted
+        } else {
+            AmountSign::AsIs
+        },
+    };
+    let importer = SpreadsheetImporter::new(mapping);
+
+    let db = Database::from_env().await?;
+    let import_service =
+        ImportService::new(db.pool().clone()).ensure_accounts(args.create_accounts);
+
+    let summary = import_service
+        .import_transactions(&importer, &args.file, &args.account)
+        .await?;
+
+    summary.print_summary();
+
+    if summary.created > 0 {
+        println!("\n✅ Import completed successfully!");
+        println!("💡 Tip: Run 'assets-cli reports balance-sheet' to see your updated balance");
+    }
+
+    Ok(())
+}
+
+async fn import_gnucash(args: GnuCashArgs) -> Result<()> {
+    println!("📚 Migrating GnuCash Book");
+    println!("=========================\n");
+
+    let importer = GnuCashImporter::new();
+    if !importer.can_handle_file(&args.file)? {
+        return Err(anyhow::anyhow!(
+            "'{}' is not a GnuCash book. Expected: {}",
+            args.file,
+            importer.format_description()
+        ));
+    }
+
+    let db = Database::from_env().await?;
+    let migration_service = GnuCashMigrationService::new(db.pool().clone());
+    let report = migration_service.migrate(&importer, &args.file).await?;
+
+    report.print_summary();
+
+    println!("\n✅ Migration finished!");
+    println!("   • Accounts created: {}", report.accounts_created);
+    println!(
+        "   • Transactions imported: {}",
+        report.transactions_imported
+    );
+    println!("   • Prices imported: {}", report.prices_imported);
+    if !report.unmapped.is_empty() {
+        println!(
+            "\n⚠️  {} item(s) could not be mapped:",
+            report.unmapped.len()
+        );
+        for item in &report.unmapped {
+            println!("  • {}", item);
+        }
+    }
+    println!("💡 Tip: Run 'assets-cli accounts tree' to review the migrated chart of accounts");
+
+    Ok(())
+}
+
 async fn import_payslip(args: PayslipArgs) -> Result<()> {
     println!("💰 Importing Payslip");
     println!("====================\n");
@@ -127,7 +442,8 @@ async fn import_payslip(args: PayslipArgs) -> Result<()> {
     };
 
     let db = Database::from_env().await?;
-    let payslip_import_service = PayslipImportService::new(db.pool().clone());
+    let payslip_import_service =
+        PayslipImportService::new(db.pool().clone()).ensure_accounts(args.create_accounts);
     let result = match args.importer.as_str() {
         "generic" => todo!(),
         "qt" => {
@@ -185,6 +501,13 @@ async fn import_payslip(args: PayslipArgs) -> Result<()> {
     );
     println!("• Transaction ID: {}", result.transaction_id);
 
+    if !result.accounts_created.is_empty() {
+        println!("\n➕ Accounts created:");
+        for path in &result.accounts_created {
+            println!("  • {}", path);
+        }
+    }
+
     if !result.warnings.is_empty() {
         println!("\n⚠️  Warnings:");
         for warning in &result.warnings {
@@ -198,3 +521,204 @@ async fn import_payslip(args: PayslipArgs) -> Result<()> {
 
     Ok(())
 }
+
+async fn import_inbox(args: InboxArgs) -> Result<()> {
+    println!("📥 Processing Import Inbox");
+    println!("==========================\n");
+
+    let db = Database::from_env().await?;
+    let inbox_service = InboxService::new(db.pool().clone());
+
+    let report = inbox_service.process_inbox(&args.dir).await?;
+    print_inbox_report(&report);
+
+    Ok(())
+}
+
+async fn watch_inbox(args: WatchArgs) -> Result<()> {
+    println!("👀 Watching Import Inbox");
+    println!("========================\n");
+    println!(
+        "📂 {} (every {}s, Ctrl+C to stop)\n",
+        args.dir, args.interval
+    );
+
+    let db = Database::from_env().await?;
+    let inbox_service = InboxService::new(db.pool().clone());
+    let mut snapshot = InboxSnapshot::default();
+
+    loop {
+        let report = inbox_service.poll_inbox(&args.dir, &mut snapshot).await?;
+        if !report.files.is_empty() {
+            print_inbox_report(&report);
+        }
+
+        tokio::select! {
+            _ = tokio::time::sleep(Duration::from_secs(args.interval)) => {}
+            _ = tokio::signal::ctrl_c() => {
+                println!("\n👋 Stopped watching {}", args.dir);
+                return Ok(());
+            }
+        }
+    }
+}
+
+fn print_inbox_report(report: &InboxReport) {
+    if report.files.is_empty() {
+        println!("📭 No new files in inbox");
+        return;
+    }
+
+    for file in &report.files {
+        let importer = file.importer.map(|kind| kind.as_str()).unwrap_or("-");
+        match &file.outcome {
+            InboxOutcome::Imported { created, skipped } => println!(
+                "✅ {} [{}]: {} created, {} skipped",
+                file.file_name, importer, created, skipped
+            ),
+            InboxOutcome::AlreadyImported => {
+                println!("⏭️  {} [{}]: already imported", file.file_name, importer)
+            }
+            InboxOutcome::Failed(reason) => {
+                println!("❌ {} [{}]: {}", file.file_name, importer, reason)
+            }
+        }
+    }
+
+    println!("\n📊 Inbox Summary:");
+    println!("   • Imported: {}", report.imported());
+    println!("   • Already imported: {}", report.already_imported());
+    println!("   • Failed: {}", report.failed());
+}
+
+async fn add_mapping(args: MappingArgs) -> Result<()> {
+    let importer: ImporterKind = args
+        .importer
+        .parse()
+        .map_err(|e: String| anyhow::anyhow!(e))?;
+
+    let payslip_destinations = if importer.is_payslip() {
+        let required = |value: Option<String>, flag: &str| {
+            value.ok_or_else(|| anyhow::anyhow!("--{} is required for payslip importers", flag))
+        };
+        let destinations = DestinationAccount {
+            fixed_gross: required(args.fixed_gross_income, "fixed-income")?,
+            variable_gross: required(args.variable_gross_income, "variable-income")?,
+            net_pay: required(args.main_account, "bank-account")?,
+            social_contributions: required(
+                args.social_contributions_expense,
+                "social-contributions",
+            )?,
+            revenue_taxes: required(args.revenue_taxes_expense, "income-taxes")?,
+            meal_vouchers: required(args.meal_vouchers_account, "meal-vouchers")?,
+            meal_vouchers_income: required(args.meal_vouchers_income, "meal-vouchers-income")?,
+            additional_benefits: required(
+                args.additional_benefits_income,
+                "additional-benefits-income",
+            )?,
+        };
+        Some(serde_json::to_value(destinations)?)
+    } else {
+        None
+    };
+
+    let spreadsheet_columns = if importer == ImporterKind::Spreadsheet {
+        let required = |value: Option<String>, flag: &str| {
+            value.ok_or_else(|| {
+                anyhow::anyhow!("--{} is required for the spreadsheet importer", flag)
+            })
+        };
+        let columns = SpreadsheetColumnMapping {
+            sheet: args.sheet,
+            header_row: args.header_row,
+            date_column: required(args.date_column, "date-column")?,
+            value_date_column: args.value_date_column,
+            label_column: required(args.label_column, "label-column")?,
+            amount_column: args.amount_column,
+            debit_column: args.debit_column,
+            credit_column: args.credit_column,
+            category_column: args.category_column,
+            sign: if args.invert_sign {
+                AmountSign::Inverted
+            } else {
+                AmountSign::AsIs
+            },
+        };
+        Some(serde_json::to_value(columns)?)
+    } else {
+        None
+    };
+
+    let db = Database::from_env().await?;
+    let inbox_service = InboxService::new(db.pool().clone());
+    let mapping = inbox_service
+        .add_mapping(NewImportMapping {
+            file_pattern: args.pattern,
+            importer,
+            target_account: args.account,
+            payslip_destinations,
+            spreadsheet_columns,
+            priority: args.priority,
+        })
+        .await?;
+
+    println!(
+        "✅ Files matching '{}' will be imported with {}",
+        mapping.file_pattern, mapping.importer
+    );
+    println!("   Mapping ID: {}", mapping.id);
+
+    Ok(())
+}
+
+async fn list_mappings() -> Result<()> {
+    println!("🗺️  Import Inbox Mappings");
+    println!("========================\n");
+
+    let db = Database::from_env().await?;
+    let inbox_service = InboxService::new(db.pool().clone());
+    let mappings = inbox_service.list_mappings().await?;
+
+    if mappings.is_empty() {
+        println!("No mappings defined.");
+        println!(
+            "💡 Tip: Add one with 'assets-cli import mappings add --pattern ... --importer ...'"
+        );
+        return Ok(());
+    }
+
+    let mut table = Table::new();
+    table.load_preset(UTF8_FULL);
+    table.set_header(vec!["Pattern", "Importer", "Target", "Priority", "ID"]);
+
+    for mapping in &mappings {
+        let target = match (&mapping.target_account, &mapping.payslip_destinations) {
+            (Some(account), _) => account.clone(),
+            (None, Some(_)) => "payslip destinations".to_string(),
+            (None, None) => "-".to_string(),
+        };
+        table.add_row(vec![
+            mapping.file_pattern.clone(),
+            mapping.importer.clone(),
+            target,
+            mapping.priority.to_string(),
+            mapping.id.to_string(),
+        ]);
+    }
+
+    println!("{}", table);
+
+    Ok(())
+}
+
+async fn remove_mapping(id: &str) -> Result<()> {
+    let mapping_id = Uuid::parse_str(id)?;
+
+    let db = Database::from_env().await?;
+    let inbox_service = InboxService::new(db.pool().clone());
+    inbox_service.remove_mapping(mapping_id).await?;
+
+    println!("🗑️  Mapping {} removed", mapping_id);
+
+    Ok(())
+}
@@ -1,11 +1,20 @@
 pub mod accounts;
+pub mod assertions;
+pub mod attachments;
+pub mod categorize;
 pub mod db;
 #[cfg(feature = "demo")]
 pub mod demo;
 pub mod duplicates;
+pub mod history;
 pub mod import;
+pub mod periods;
 pub mod prices;
+pub mod reconcile;
+pub mod recurring;
+pub mod refunds;
 pub mod reports;
+pub mod tags;
 pub mod transactions;
 pub mod users;
 
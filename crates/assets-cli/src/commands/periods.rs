@@ -0,0 +1,106 @@
+use anyhow::Result;
+use assets_core::{Database, PeriodService, RETAINED_EARNINGS_ACCOUNT_PATH};
+use chrono::NaiveDate;
+use clap::Args;
+use comfy_table::{presets::UTF8_FULL, Table};
+
+use crate::OutputFormat;
+
+#[derive(Args)]
+pub struct ClosePeriodArgs {
+    /// Last day of the period to close, included (YYYY-MM-DD format)
+    date: String,
+}
+
+pub async fn close_period(args: ClosePeriodArgs) -> Result<()> {
+    let period_end = NaiveDate::parse_from_str(&args.date, "%Y-%m-%d")
+        .map_err(|_| anyhow::anyhow!("Invalid date '{}'. Use YYYY-MM-DD", args.date))?;
+    let db = Database::from_env().await?;
+    let close = PeriodService::new(db.pool().clone())
+        .close_period(period_end)
+        .await?;
+
+    println!("🔒 Period closed through {}", close.period_end);
+    match close.closing_transaction_id {
+        Some(id) => println!(
+            "   Net income of {:.2} moved to {} (transaction {})",
+            close.net_income, RETAINED_EARNINGS_ACCOUNT_PATH, id
+        ),
+        None => println!("   Income and expense accounts were already at zero"),
+    }
+    println!(
+        "   Entries dated on or before {} are now locked",
+        close.period_end
+    );
+    println!("💡 Undo with 'assets-cli reopen-period'");
+
+    Ok(())
+}
+
+pub async fn reopen_period() -> Result<()> {
+    let db = Database::from_env().await?;
+    let service = PeriodService::new(db.pool().clone());
+
+    let Some(close) = service.reopen_latest().await? else {
+        return Err(anyhow::anyhow!("No closed period to reopen"));
+    };
+    println!(
+        "🔓 Period ending {} reopened; its closing entries were removed",
+        close.period_end
+    );
+    match service.lock_date().await? {
+        Some(lock_date) => println!("   Entries are still locked through {}", lock_date),
+        None => println!("   No period is locked anymore"),
+    }
+
+    Ok(())
+}
+
+pub async fn list_periods(format: OutputFormat) -> Result<()> {
+    let db = Database::from_env().await?;
+    let closes = PeriodService::new(db.pool().clone()).list_closes().await?;
+
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&closes)?),
+        OutputFormat::Csv => {
+            println!("period_end,net_income,closing_transaction_id,closed_at");
+            for close in &closes {
+                println!(
+                    "{},{:.2},{},{}",
+                    close.period_end,
+                    close.net_income,
+                    close
+                        .closing_transaction_id
+                        .map(|id| id.to_string())
+                        .unwrap_or_default(),
+                    close.closed_at.format("%Y-%m-%d %H:%M")
+                );
+            }
+        }
+        OutputFormat::Table => {
+            println!("🔒 Closed Periods");
+            println!("=================\n");
+            if closes.is_empty() {
+                println!("No period has been closed.");
+                println!("💡 Close one with 'assets-cli close-period <YYYY-MM-DD>'");
+                return Ok(());
+            }
+            let mut table = Table::new();
+            table.load_preset(UTF8_FULL);
+            table.set_header(vec!["Period End", "Net Income", "Closed At"]);
+            for close in &closes {
+                table.add_row(vec![
+                    close.period_end.to_string(),
+                    format!("{:.2}", close.net_income),
+                    close.closed_at.format("%Y-%m-%d %H:%M").to_string(),
+                ]);
+            }
+            println!("{table}");
+            if let Some(last) = closes.last() {
+                println!("\n🔒 Locked through {}", last.period_end);
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,477 @@
+use anyhow::Result;
+use assets_core::{
+    AccountService, BalanceAssertionService, Database, DiscrepancyEntry, DiscrepancyReport,
+    DiscrepancyService, EntryStatus, KnownBalance, ReconciliationEntry, ReconciliationService,
+    ReconciliationSummary,
+};
+use chrono::NaiveDate;
+use clap::{Args, Subcommand};
+use comfy_table::{presets::UTF8_FULL, Table};
+use rust_decimal::Decimal;
+use std::io::{self, Write};
+use uuid::Uuid;
+
+use crate::OutputFormat;
+
+#[derive(Subcommand)]
+pub enum ReconcileCommands {
+    /// Reconcile an account against a statement, ticking entries interactively
+    Start(StartReconciliationArgs),
+    /// List past reconciliations of an account
+    History {
+        /// Account path (e.g. "Assets:Current Assets:BoursoBank")
+        #[arg(long)]
+        account: String,
+        /// Output format
+        #[arg(long, value_enum, default_value = "table")]
+        format: OutputFormat,
+    },
+    /// Unlock the latest reconciliation of an account (its entries go back to cleared)
+    Undo {
+        /// Account path
+        #[arg(long)]
+        account: String,
+    },
+    /// Find the day where the ledger diverges from known bank balances
+    Discrepancy(DiscrepancyArgs),
+}
+
+#[derive(Args)]
+pub struct StartReconciliationArgs {
+    /// Account path (e.g. "Assets:Current Assets:BoursoBank")
+    #[arg(long)]
+    account: String,
+
+    /// Statement end date (YYYY-MM-DD format)
+    #[arg(long)]
+    date: String,
+
+    /// Statement ending balance, in ledger sign (negative when owed, e.g. on a credit card)
+    #[arg(long, allow_hyphen_values = true)]
+    balance: Decimal,
+}
+
+#[derive(Args)]
+pub struct DiscrepancyArgs {
+    /// Account path (e.g. "Assets:Current Assets:BoursoBank")
+    #[arg(long)]
+    account: String,
+
+    /// Known bank balance as DATE=AMOUNT, in ledger sign (repeatable, e.g. 2025-06-30=1234.56)
+    #[arg(long = "balance", allow_hyphen_values = true)]
+    balances: Vec<String>,
+
+    /// Also use the account's balance assertions, including those read from imported files
+    #[arg(long)]
+    assertions: bool,
+
+    /// Output format
+    #[arg(long, value_enum, default_value = "table")]
+    format: OutputFormat,
+}
+
+pub async fn handle_reconcile_command(command: ReconcileCommands) -> Result<()> {
+    match command {
+        ReconcileCommands::Start(args) => start_reconciliation(args).await,
+        ReconcileCommands::History { account, format } => {
+            reconciliation_history(&account, format).await
+        }
+        ReconcileCommands::Undo { account } => undo_reconciliation(&account).await,
+        ReconcileCommands::Discrepancy(args) => find_discrepancy(args).await,
+    }
+}
+
+async fn start_reconciliation(args: StartReconciliationArgs) -> Result<()> {
+    let statement_date = NaiveDate::parse_from_str(&args.date, "%Y-%m-%d")
+        .map_err(|_| anyhow::anyhow!("Invalid date format. Use YYYY-MM-DD (e.g., 2025-06-30)"))?;
+
+    let db = Database::from_env().await?;
+    let account_id = find_account(&db, &args.account).await?;
+    let service = ReconciliationService::new(db.pool().clone());
+
+    println!(
+        "🧾 Reconcile {} — statement of {}",
+        args.account, statement_date
+    );
+    println!("==========================================\n");
+    println!("Ticked entries are saved as you go; quit any time and start again later.\n");
+
+    loop {
+        let entries = service
+            .unreconciled_entries(account_id, statement_date)
+            .await?;
+        let summary = service
+            .summary(account_id, statement_date, args.balance)
+            .await?;
+        display_session(&entries, &summary);
+
+        let input =
+            prompt_input("Toggle entries (e.g. 1 3 5-7), [a]ll, [n]one, [f]inish, [q]uit: ")?;
+        match input.to_lowercase().as_str() {
+            "" => continue,
+            "q" => {
+                println!("👋 Progress saved. Nothing was locked.");
+                return Ok(());
+            }
+            "f" => {
+                if !summary.is_balanced() {
+                    println!(
+                        "❌ Difference is {:.2}; tick or untick entries until it is zero\n",
+                        summary.difference
+                    );
+                    continue;
+                }
+                let reconciliation = service
+                    .finish(account_id, statement_date, args.balance)
+                    .await?;
+                let locked = entries
+                    .iter()
+                    .filter(|e| e.status == EntryStatus::Cleared)
+                    .count();
+                println!(
+                    "✅ Reconciled {} entr{} up to {} (balance {:.2})",
+                    locked,
+                    if locked == 1 { "y" } else { "ies" },
+                    reconciliation.statement_date,
+                    reconciliation.statement_balance
+                );
+                return Ok(());
+            }
+            "a" | "n" => {
+                let ids: Vec<Uuid> = entries.iter().map(|e| e.id).collect();
+                if !ids.is_empty() {
+                    service
+                        .set_cleared(&ids, input.eq_ignore_ascii_case("a"))
+                        .await?;
+                }
+            }
+            selection => match parse_selection(selection, entries.len()) {
+                Ok(indices) => {
+                    // Toggle: tick unticked entries and untick ticked ones
+                    let (to_clear, to_unclear): (Vec<_>, Vec<_>) = indices
+                        .into_iter()
+                        .map(|i| &entries[i])
+                        .partition(|e| e.status == EntryStatus::Uncleared);
+                    let to_clear: Vec<Uuid> = to_clear.iter().map(|e| e.id).collect();
+                    let to_unclear: Vec<Uuid> = to_unclear.iter().map(|e| e.id).collect();
+                    if !to_clear.is_empty() {
+                        service.set_cleared(&to_clear, true).await?;
+                    }
+                    if !to_unclear.is_empty() {
+                        service.set_cleared(&to_unclear, false).await?;
+                    }
+                }
+                Err(e) => println!("❌ {}\n", e),
+            },
+        }
+    }
+}
+
+fn display_session(entries: &[ReconciliationEntry], summary: &ReconciliationSummary) {
+    if entries.is_empty() {
+        println!("No unreconciled entries up to {}.", summary.statement_date);
+    } else {
+        let mut table = Table::new();
+        table.load_preset(UTF8_FULL);
+        table.set_header(vec!["#", "✓", "Date", "Description", "Amount"]);
+        for (index, entry) in entries.iter().enumerate() {
+            table.add_row(vec![
+                (index + 1).to_string(),
+                if entry.status == EntryStatus::Cleared {
+                    "✓"
+                } else {
+                    ""
+                }
+                .to_string(),
+                entry.transaction_date.format("%Y-%m-%d").to_string(),
+                entry.description.clone(),
+                format!("{:.2}", entry.amount),
+            ]);
+        }
+        println!("{table}");
+    }
+
+    println!();
+    println!(
+        "   Previously reconciled: {:>12.2}",
+        summary.reconciled_balance
+    );
+    println!(
+        "   Cleared balance:       {:>12.2}",
+        summary.cleared_balance
+    );
+    println!(
+        "   Statement balance:     {:>12.2}",
+        summary.statement_balance
+    );
+    if summary.is_balanced() {
+        println!("   Difference:            {:>12.2} ✅", summary.difference);
+    } else {
+        println!("   Difference:            {:>12.2}", summary.difference);
+    }
+    println!();
+}
+
+/// Parse "1 3 5-7" (1-based, commas allowed) into 0-based indices
+fn parse_selection(input: &str, count: usize) -> Result<Vec<usize>> {
+    let mut indices = Vec::new();
+    for part in input
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|p| !p.is_empty())
+    {
+        let (start, end) = match part.split_once('-') {
+            Some((start, end)) => (start.trim(), end.trim()),
+            None => (part, part),
+        };
+        let start: usize = start
+            .parse()
+            .map_err(|_| anyhow::anyhow!("Invalid selection '{}'", part))?;
+        let end: usize = end
+            .parse()
+            .map_err(|_| anyhow::anyhow!("Invalid selection '{}'", part))?;
+        if start == 0 || end > count || start > end {
+            return Err(anyhow::anyhow!(
+                "Selection '{}' is outside 1-{}",
+                part,
+                count
+            ));
+        }
+        indices.extend((start - 1)..end);
+    }
+    indices.sort_unstable();
+    indices.dedup();
+    Ok(indices)
+}
+
+async fn reconciliation_history(account_path: &str, format: OutputFormat) -> Result<()> {
+    let db = Database::from_env().await?;
+    let account_id = find_account(&db, account_path).await?;
+    let reconciliations = ReconciliationService::new(db.pool().clone())
+        .list_reconciliations(account_id)
+        .await?;
+
+    match format {
+        OutputFormat::Json => {
+            println!("{}", serde_json::to_string_pretty(&reconciliations)?);
+            return Ok(());
+        }
+        OutputFormat::Csv => {
+            println!("statement_date,statement_balance,reconciled_at,id");
+            for reconciliation in &reconciliations {
+                println!(
+                    "{},{:.2},{},{}",
+                    reconciliation.statement_date,
+                    reconciliation.statement_balance,
+                    reconciliation.reconciled_at.to_rfc3339(),
+                    reconciliation.id
+                );
+            }
+            return Ok(());
+        }
+        OutputFormat::Table => {}
+    }
+
+    println!("🧾 Reconciliations of {}", account_path);
+    println!("==========================\n");
+
+    if reconciliations.is_empty() {
+        println!("No reconciliations yet.");
+        println!("💡 Start one with 'assets-cli reconcile start --account <path> --date <date> --balance <amount>'");
+        return Ok(());
+    }
+
+    let mut table = Table::new();
+    table.load_preset(UTF8_FULL);
+    table.set_header(vec!["Statement Date", "Balance", "Reconciled At"]);
+    for reconciliation in &reconciliations {
+        table.add_row(vec![
+            reconciliation.statement_date.to_string(),
+            format!("{:.2}", reconciliation.statement_balance),
+            reconciliation
+                .reconciled_at
+                .format("%Y-%m-%d %H:%M")
+                .to_string(),
+        ]);
+    }
+    println!("{table}");
+
+    Ok(())
+}
+
+async fn undo_reconciliation(account_path: &str) -> Result<()> {
+    let db = Database::from_env().await?;
+    let account_id = find_account(&db, account_path).await?;
+    let service = ReconciliationService::new(db.pool().clone());
+
+    match service.undo_last_reconciliation(account_id).await? {
+        Some(reconciliation) => {
+            println!(
+                "✅ Reconciliation of {} undone; its entries are cleared but no longer locked",
+                reconciliation.statement_date
+            );
+            Ok(())
+        }
+        None => Err(anyhow::anyhow!(
+            "Account '{}' has no reconciliation to undo",
+            account_path
+        )),
+    }
+}
+
+async fn find_discrepancy(args: DiscrepancyArgs) -> Result<()> {
+    let mut known_balances = args
+        .balances
+        .iter()
+        .map(|value| parse_known_balance(value))
+        .collect::<Result<Vec<_>>>()?;
+
+    let db = Database::from_env().await?;
+    let account_id = find_account(&db, &args.account).await?;
+    if args.assertions {
+        let assertions = BalanceAssertionService::new(db.pool().clone())
+            .list_assertions(Some(account_id))
+            .await?;
+        for assertion in assertions {
+            // Balances typed in take precedence over recorded ones on the same day
+            if !known_balances
+                .iter()
+                .any(|known| known.date == assertion.assertion_date)
+            {
+                known_balances.push(KnownBalance {
+                    date: assertion.assertion_date,
+                    balance: assertion.expected_balance,
+                });
+            }
+        }
+    }
+    if known_balances.is_empty() {
+        return Err(anyhow::anyhow!(
+            "Give known balances with --balance DATE=AMOUNT, or use --assertions"
+        ));
+    }
+
+    let report = DiscrepancyService::new(db.pool().clone())
+        .find_discrepancy(account_id, &known_balances)
+        .await?;
+
+    match args.format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+        OutputFormat::Csv => {
+            println!("date,description,amount,reasons,transaction_id");
+            for suspect in &report.suspects {
+                let reasons: Vec<String> = suspect.reasons.iter().map(|r| r.to_string()).collect();
+                println!(
+                    "{},\"{}\",{:.2},{},{}",
+                    suspect.entry.transaction_date.format("%Y-%m-%d"),
+                    suspect.entry.description.replace('"', "\"\""),
+                    suspect.entry.amount,
+                    reasons.join(";"),
+                    suspect.entry.transaction_id
+                );
+            }
+        }
+        OutputFormat::Table => print_discrepancy(&args.account, &report),
+    }
+
+    Ok(())
+}
+
+/// Parse "2025-06-30=1234.56" into a known balance
+fn parse_known_balance(value: &str) -> Result<KnownBalance> {
+    let (date, balance) = value
+        .split_once('=')
+        .ok_or_else(|| anyhow::anyhow!("Invalid balance '{}', expected DATE=AMOUNT", value))?;
+    let date = NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d")
+        .map_err(|_| anyhow::anyhow!("Invalid date in '{}', expected YYYY-MM-DD", value))?;
+    let balance = balance
+        .trim()
+        .parse::<Decimal>()
+        .map_err(|_| anyhow::anyhow!("Invalid amount in '{}'", value))?;
+    Ok(KnownBalance { date, balance })
+}
+
+fn print_discrepancy(account_path: &str, report: &DiscrepancyReport) {
+    println!("🔎 Discrepancy finder for {}", account_path);
+    println!("==========================\n");
+
+    if let Some(agreement) = &report.last_agreement {
+        println!(
+            "✅ Ledger agrees with the bank on {} ({:.2})",
+            agreement.date, agreement.expected
+        );
+    }
+    let Some(divergence) = &report.first_divergence else {
+        println!("✅ The ledger agrees with every known balance");
+        return;
+    };
+    println!(
+        "❌ Ledger diverges on {}: bank {:.2}, ledger {:.2} (difference {:+.2})",
+        divergence.date, divergence.expected, divergence.actual, divergence.difference
+    );
+
+    match report.diverging_day {
+        Some(day) => {
+            println!("\n📅 Most likely diverging day: {}", day);
+            print_discrepancy_entries(&report.day_entries);
+        }
+        None => {
+            println!("\nNo ledger entries between the two balances: a bank movement is missing.");
+            return;
+        }
+    }
+
+    println!("\n🕵️  Likely culprits");
+    if report.suspects.is_empty() {
+        println!("No suspicious entries; look for a bank movement missing from the ledger.");
+        return;
+    }
+    let mut table = Table::new();
+    table.load_preset(UTF8_FULL);
+    table.set_header(vec!["Date", "Description", "Amount", "Why", "Transaction"]);
+    for suspect in &report.suspects {
+        let reasons: Vec<String> = suspect.reasons.iter().map(|r| r.to_string()).collect();
+        table.add_row(vec![
+            suspect
+                .entry
+                .transaction_date
+                .format("%Y-%m-%d")
+                .to_string(),
+            suspect.entry.description.clone(),
+            format!("{:.2}", suspect.entry.amount),
+            reasons.join(", "),
+            suspect.entry.transaction_id.to_string(),
+        ]);
+    }
+    println!("{table}");
+}
+
+fn print_discrepancy_entries(entries: &[DiscrepancyEntry]) {
+    let mut table = Table::new();
+    table.load_preset(UTF8_FULL);
+    table.set_header(vec!["Description", "Amount", "Memo", "Transaction"]);
+    for entry in entries {
+        table.add_row(vec![
+            entry.description.clone(),
+            format!("{:.2}", entry.amount),
+            entry.memo.clone().unwrap_or_default(),
+            entry.transaction_id.to_string(),
+        ]);
+    }
+    println!("{table}");
+}
+
+async fn find_account(db: &Database, account_path: &str) -> Result<Uuid> {
+    let account = AccountService::new(db.pool().clone())
+        .get_account_by_path(account_path)
+        .await
+        .map_err(|_| anyhow::anyhow!("Account '{}' not found", account_path))?;
+    Ok(account.id)
+}
+
+fn prompt_input(prompt: &str) -> Result<String> {
+    print!("{}", prompt);
+    io::stdout().flush()?;
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+    Ok(input.trim().to_string())
+}
@@ -0,0 +1,263 @@
+use anyhow::Result;
+use assets_core::{
+    Database, NewRecurringTransaction, RecurrenceSchedule, RecurringService, RecurringTransaction,
+};
+use chrono::{Local, NaiveDate};
+use clap::{Args, Subcommand};
+use comfy_table::{presets::UTF8_FULL, Table};
+use rust_decimal::Decimal;
+
+use super::transactions::parse_entries_by_path;
+use crate::OutputFormat;
+
+#[derive(Subcommand)]
+pub enum RecurringCommands {
+    /// Add a recurring transaction template
+    Add(AddRecurringArgs),
+    /// List recurring transaction templates
+    List {
+        /// Output format
+        #[arg(long, value_enum, default_value = "table")]
+        format: OutputFormat,
+    },
+    /// Remove a recurring transaction template (posted transactions are kept)
+    Remove {
+        /// Template name
+        name: String,
+    },
+    /// Post (or preview) every instance due up to a date
+    Run {
+        /// Post instances due up to this date (YYYY-MM-DD, default: today)
+        #[arg(long)]
+        until: Option<String>,
+        /// Only show what would be posted
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+#[derive(Args)]
+pub struct AddRecurringArgs {
+    /// Unique template name (e.g. "rent")
+    #[arg(long)]
+    name: String,
+
+    /// Description of the generated transactions
+    #[arg(long)]
+    description: String,
+
+    /// Schedule: monthly:<day>, weekly:<weekday>, yearly:<MM-DD> or last-business-day
+    #[arg(long)]
+    schedule: String,
+
+    /// First date the schedule applies (YYYY-MM-DD format)
+    #[arg(long)]
+    start: String,
+
+    /// Last date the schedule applies (YYYY-MM-DD format)
+    #[arg(long)]
+    end: Option<String>,
+
+    /// Largest amount difference accepted when matching imported bank lines
+    #[arg(long, default_value = "0")]
+    tolerance: Decimal,
+
+    /// Reference copied to the generated transactions
+    #[arg(long)]
+    reference: Option<String>,
+
+    /// Entries as "Account:Path=amount[:memo]"; one amount may be omitted and is
+    /// computed to balance the template
+    #[arg(long = "entry", required = true, num_args = 1)]
+    entries: Vec<String>,
+}
+
+pub async fn handle_recurring_command(command: RecurringCommands) -> Result<()> {
+    match command {
+        RecurringCommands::Add(args) => add_recurring(args).await,
+        RecurringCommands::List { format } => list_recurring(format).await,
+        RecurringCommands::Remove { name } => remove_recurring(&name).await,
+        RecurringCommands::Run { until, dry_run } => run_recurring(until.as_deref(), dry_run).await,
+    }
+}
+
+async fn add_recurring(args: AddRecurringArgs) -> Result<()> {
+    println!("🔁 Add Recurring Transaction");
+    println!("============================\n");
+
+    let schedule: RecurrenceSchedule = args.schedule.parse().map_err(|e| anyhow::anyhow!("{e}"))?;
+    let new_recurring = NewRecurringTransaction {
+        name: args.name,
+        description: args.description,
+        reference: args.reference,
+        entries: parse_entries_by_path(&args.entries)?,
+        schedule,
+        start_date: parse_naive_date(&args.start)?,
+        end_date: args.end.as_deref().map(parse_naive_date).transpose()?,
+        amount_tolerance: args.tolerance,
+    };
+
+    let db = Database::from_env().await?;
+    let recurring_service = RecurringService::new(db.pool().clone());
+    let created = recurring_service.create_recurring(new_recurring).await?;
+
+    println!("✅ Recurring transaction '{}' created", created.name);
+    println!("   Schedule: {}", created.schedule);
+    println!("   Starts: {}", created.start_date);
+    if let Some(end_date) = created.end_date {
+        println!("   Ends: {}", end_date);
+    }
+    println!("\n💡 Run 'assets-cli recurring run --dry-run' to preview due transactions");
+
+    Ok(())
+}
+
+async fn list_recurring(format: OutputFormat) -> Result<()> {
+    let db = Database::from_env().await?;
+    let recurring_service = RecurringService::new(db.pool().clone());
+    let templates = recurring_service.list_recurring().await?;
+
+    match format {
+        OutputFormat::Json => {
+            println!("{}", serde_json::to_string_pretty(&templates)?);
+            return Ok(());
+        }
+        OutputFormat::Csv => {
+            println!("name,description,schedule,start_date,end_date,amount,tolerance,active");
+            for template in &templates {
+                println!(
+                    "{},\"{}\",{},{},{},{:.2},{},{}",
+                    template.name,
+                    template.description.replace('"', "\"\""),
+                    template.schedule,
+                    template.start_date,
+                    template.end_date.map(|d| d.to_string()).unwrap_or_default(),
+                    template_amount(template),
+                    template.amount_tolerance,
+                    template.is_active
+                );
+            }
+            return Ok(());
+        }
+        OutputFormat::Table => {}
+    }
+
+    println!("🔁 Recurring Transactions");
+    println!("=========================\n");
+
+    if templates.is_empty() {
+        println!("No recurring transactions defined.");
+        println!("💡 Add one with 'assets-cli recurring add'");
+        return Ok(());
+    }
+
+    let mut table = Table::new();
+    table.load_preset(UTF8_FULL);
+    table.set_header(vec![
+        "Name",
+        "Description",
+        "Schedule",
+        "Start",
+        "End",
+        "Amount",
+        "Tolerance",
+        "Active",
+    ]);
+    for template in &templates {
+        table.add_row(vec![
+            template.name.clone(),
+            template.description.clone(),
+            template.schedule.clone(),
+            template.start_date.to_string(),
+            template
+                .end_date
+                .map(|d| d.to_string())
+                .unwrap_or_else(|| "-".to_string()),
+            format!("{:.2}", template_amount(template)),
+            format!("{:.2}", template.amount_tolerance),
+            if template.is_active { "✓" } else { "✗" }.to_string(),
+        ]);
+    }
+    println!("{table}");
+
+    Ok(())
+}
+
+async fn remove_recurring(name: &str) -> Result<()> {
+    let db = Database::from_env().await?;
+    let recurring_service = RecurringService::new(db.pool().clone());
+
+    if recurring_service.remove_recurring(name).await? {
+        println!("✅ Removed recurring transaction '{}'", name);
+        Ok(())
+    } else {
+        Err(anyhow::anyhow!("No recurring transaction named '{}'", name))
+    }
+}
+
+async fn run_recurring(until: Option<&str>, dry_run: bool) -> Result<()> {
+    let as_of = match until {
+        Some(date) => parse_naive_date(date)?,
+        None => Local::now().date_naive(),
+    };
+
+    if dry_run {
+        println!("🔍 Recurring Transactions Due (dry run)");
+        println!("=======================================\n");
+    } else {
+        println!("🔁 Posting Recurring Transactions");
+        println!("================================\n");
+    }
+
+    let db = Database::from_env().await?;
+    let recurring_service = RecurringService::new(db.pool().clone());
+
+    let due = recurring_service.due_instances(as_of).await?;
+    if due.is_empty() {
+        println!("✅ Nothing due up to {}", as_of);
+        return Ok(());
+    }
+
+    let mut table = Table::new();
+    table.load_preset(UTF8_FULL);
+    table.set_header(vec!["Due Date", "Name", "Description", "Amount"]);
+    for instance in &due {
+        table.add_row(vec![
+            instance.due_date.to_string(),
+            instance.recurring.name.clone(),
+            instance.recurring.description.clone(),
+            format!("{:.2}", template_amount(&instance.recurring)),
+        ]);
+    }
+    println!("{table}");
+
+    if dry_run {
+        println!("\n📊 {} transaction(s) would be posted", due.len());
+        println!("💡 Run without --dry-run to post them");
+        return Ok(());
+    }
+
+    let posted = recurring_service.post_due_instances(as_of).await?;
+    println!("\n✅ Posted {} transaction(s)", posted.len());
+
+    Ok(())
+}
+
+/// Total debited by the template, i.e. the amount moved by each instance
+fn template_amount(template: &RecurringTransaction) -> Decimal {
+    template
+        .template_entries()
+        .map(|entries| {
+            entries
+                .iter()
+                .map(|e| e.amount)
+                .filter(|amount| *amount > Decimal::ZERO)
+                .sum()
+        })
+        .unwrap_or_default()
+}
+
+fn parse_naive_date(date_str: &str) -> Result<NaiveDate> {
+    NaiveDate::parse_from_str(date_str, "%Y-%m-%d")
+        .map_err(|_| anyhow::anyhow!("Invalid date format. Use YYYY-MM-DD (e.g., 2025-06-13)"))
+}
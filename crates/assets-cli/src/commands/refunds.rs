@@ -0,0 +1,249 @@
+use anyhow::Result;
+use assets_core::{Database, RefundDetection, RefundService, RefundSuggestion};
+use chrono::NaiveDate;
+use clap::Subcommand;
+use comfy_table::{presets::UTF8_FULL, Table};
+use std::io::{self, Write};
+use uuid::Uuid;
+
+use crate::OutputFormat;
+
+#[derive(Subcommand)]
+pub enum RefundCommands {
+    /// Walk unlinked refunds and pick the purchase each one belongs to
+    Suggest {
+        /// Maximum number of days between a purchase and its refund
+        #[arg(long, default_value_t = 60)]
+        window: i32,
+        /// Only refunds from this date (YYYY-MM-DD format)
+        #[arg(long)]
+        from: Option<String>,
+        /// Only refunds up to this date (YYYY-MM-DD format)
+        #[arg(long)]
+        to: Option<String>,
+        /// Link every refund to its best match without prompting
+        #[arg(long)]
+        auto_confirm: bool,
+    },
+    /// Link a refund to its purchase
+    Link {
+        /// Refund transaction ID
+        refund: String,
+        /// Purchase transaction ID
+        purchase: String,
+    },
+    /// Remove a refund link
+    Unlink {
+        /// Link ID or refund transaction ID
+        id: String,
+    },
+    /// List linked refunds
+    List {
+        /// Output format
+        #[arg(long, value_enum, default_value = "table")]
+        format: OutputFormat,
+    },
+}
+
+pub async fn handle_refund_command(command: RefundCommands) -> Result<()> {
+    let db = Database::from_env().await?;
+    let service = RefundService::new(db.pool().clone());
+
+    match command {
+        RefundCommands::Suggest {
+            window,
+            from,
+            to,
+            auto_confirm,
+        } => {
+            let detection = RefundDetection::builder()
+                .window_days(window)
+                .maybe_from_date(from.as_deref().map(parse_naive_date).transpose()?)
+                .maybe_to_date(to.as_deref().map(parse_naive_date).transpose()?)
+                .build();
+            suggest_refund_links(&service, &detection, auto_confirm).await?;
+        }
+        RefundCommands::Link { refund, purchase } => {
+            let link = service
+                .link(parse_id(&refund)?, parse_id(&purchase)?)
+                .await?;
+            println!(
+                "✅ Refund of {:.2} linked to '{}' ({})",
+                link.amount,
+                link.purchase_description,
+                link.purchase_date.format("%Y-%m-%d")
+            );
+        }
+        RefundCommands::Unlink { id } => {
+            let id = parse_id(&id)?;
+            match service.unlink(id).await? {
+                Some(link) => println!(
+                    "✅ Refund '{}' is no longer linked to '{}'",
+                    link.refund_description, link.purchase_description
+                ),
+                None => return Err(anyhow::anyhow!("No refund link for {}", id)),
+            }
+        }
+        RefundCommands::List { format } => {
+            let links = service.list_links().await?;
+            match format {
+                OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&links)?),
+                OutputFormat::Csv => {
+                    println!("refund_date,refund,purchase_date,purchase,account,amount,id");
+                    for link in &links {
+                        println!(
+                            "{},\"{}\",{},\"{}\",{},{:.2},{}",
+                            link.refund_date.format("%Y-%m-%d"),
+                            link.refund_description.replace('"', "\"\""),
+                            link.purchase_date.format("%Y-%m-%d"),
+                            link.purchase_description.replace('"', "\"\""),
+                            link.purchase_account_path,
+                            link.amount,
+                            link.id
+                        );
+                    }
+                }
+                OutputFormat::Table => {
+                    println!("↩️  Refunds");
+                    println!("==========\n");
+                    if links.is_empty() {
+                        println!("No linked refunds yet.");
+                        println!("💡 Find them with 'assets-cli transactions refunds suggest'");
+                        return Ok(());
+                    }
+                    let mut table = Table::new();
+                    table.load_preset(UTF8_FULL);
+                    table.set_header(vec![
+                        "Refunded",
+                        "Refund",
+                        "Purchased",
+                        "Purchase",
+                        "Account",
+                        "Amount",
+                        "ID",
+                    ]);
+                    for link in &links {
+                        table.add_row(vec![
+                            link.refund_date.format("%Y-%m-%d").to_string(),
+                            link.refund_description.clone(),
+                            link.purchase_date.format("%Y-%m-%d").to_string(),
+                            link.purchase_description.clone(),
+                            link.purchase_account_path.clone(),
+                            format!("{:.2}", link.amount),
+                            link.id.to_string(),
+                        ]);
+                    }
+                    println!("{table}");
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+async fn suggest_refund_links(
+    service: &RefundService,
+    detection: &RefundDetection,
+    auto_confirm: bool,
+) -> Result<()> {
+    println!("↩️  Refund linking");
+    println!("=================\n");
+
+    let suggestions = service.suggest(detection).await?;
+    if suggestions.is_empty() {
+        println!("✅ No unlinked refund matches a purchase.");
+        return Ok(());
+    }
+
+    let mut linked = 0;
+    for suggestion in &suggestions {
+        display_suggestion(suggestion);
+
+        let choice = if auto_confirm {
+            0
+        } else {
+            let answer = prompt_input(&format!(
+                "Link to which purchase? (1-{}, Enter to skip, q to quit): ",
+                suggestion.matches.len()
+            ))?;
+            match answer.as_str() {
+                "" => {
+                    println!();
+                    continue;
+                }
+                "q" => break,
+                _ => match answer.parse::<usize>() {
+                    Ok(n) if (1..=suggestion.matches.len()).contains(&n) => n - 1,
+                    _ => {
+                        println!("❌ No purchase [{}]; skipping\n", answer);
+                        continue;
+                    }
+                },
+            }
+        };
+
+        let purchase = &suggestion.matches[choice].purchase;
+        // An earlier link in this session may have used up the purchase
+        match service
+            .link(suggestion.refund.transaction_id, purchase.transaction_id)
+            .await
+        {
+            Ok(_) => {
+                linked += 1;
+                println!("✅ Linked to '{}'\n", purchase.description);
+            }
+            Err(e) => println!("❌ {}\n", e),
+        }
+    }
+
+    println!("↩️  Linked {} refund(s)", linked);
+    Ok(())
+}
+
+fn display_suggestion(suggestion: &RefundSuggestion) {
+    let refund = &suggestion.refund;
+    println!(
+        "📄 {}  {}  -{:.2}  ({})",
+        refund.transaction_date.format("%Y-%m-%d"),
+        refund.description,
+        refund.amount,
+        refund.account_path
+    );
+    for (index, refund_match) in suggestion.matches.iter().enumerate() {
+        let purchase = &refund_match.purchase;
+        println!(
+            "   [{}] {}  {}  {:.2}{}  ({}, {} day(s) earlier)",
+            index + 1,
+            purchase.transaction_date.format("%Y-%m-%d"),
+            purchase.description,
+            purchase.amount,
+            if purchase.refundable < purchase.amount {
+                format!(" ({:.2} left)", purchase.refundable)
+            } else {
+                String::new()
+            },
+            purchase.account_path,
+            refund_match.day_gap
+        );
+    }
+}
+
+fn prompt_input(prompt: &str) -> Result<String> {
+    print!("{}", prompt);
+    io::stdout().flush()?;
+    let mut input = String::new();
+    if io::stdin().read_line(&mut input)? == 0 {
+        return Err(anyhow::anyhow!("Input closed; linked refunds are saved"));
+    }
+    Ok(input.trim().to_lowercase())
+}
+
+fn parse_id(id: &str) -> Result<Uuid> {
+    Uuid::parse_str(id).map_err(|_| anyhow::anyhow!("Invalid ID '{}'", id))
+}
+
+fn parse_naive_date(date_str: &str) -> Result<NaiveDate> {
+    NaiveDate::parse_from_str(date_str, "%Y-%m-%d")
+        .map_err(|_| anyhow::anyhow!("Invalid date '{}'. Use YYYY-MM-DD", date_str))
+}
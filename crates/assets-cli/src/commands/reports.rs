@@ -2,7 +2,7 @@ use anyhow::Result;
 use assets_core::{Database, ReportService};
 use clap::Args;
 
-use crate::{DateRange, OutputFormat, SingleDate};
+use crate::{DateRange, KnownAsOf, OutputFormat, SingleDate};
 
 mod account_ledger;
 mod balance_sheet;
@@ -13,9 +13,11 @@ mod income_statement;
 pub async fn generate_balance_sheet(params: BalanceSheetParams) -> Result<()> {
     println!("{:?}", params);
     let db = Database::from_env().await?;
-    let report_service = ReportService::new(db.pool().clone());
+    let report_service =
+        ReportService::new(db.pool().clone()).known_as_of(params.known_as_of.get());
     let report_date = params.date.get_date();
     let balance_sheet_data = report_service.balance_sheet(report_date).await?;
+    print_known_as_of(&params.known_as_of, &params.format);
 
     match params.format {
         OutputFormat::Json => balance_sheet::print_balance_sheet_json(&balance_sheet_data)?,
@@ -31,13 +33,45 @@ pub async fn generate_balance_sheet(params: BalanceSheetParams) -> Result<()> {
 /// Generate income statement report
 pub async fn generate_income_statement(params: IncomeStatementParams) -> Result<()> {
     let db = Database::from_env().await?;
-    let report_service = ReportService::new(db.pool().clone());
+    let report_service =
+        ReportService::new(db.pool().clone()).known_as_of(params.known_as_of.get());
 
     let (start_date, end_date) = params.date_range.range();
+    print_known_as_of(&params.known_as_of, &params.format);
+
+    if params.by_tag {
+        let tagged_data = report_service
+            .income_statement_by_tag(start_date, end_date)
+            .await?;
+        match params.format {
+            OutputFormat::Json => {
+                income_statement::print_tagged_income_statement_json(&tagged_data)?
+            }
+            OutputFormat::Csv => income_statement::print_tagged_income_statement_csv(&tagged_data)?,
+            OutputFormat::Table => {
+                income_statement::print_tagged_income_statement_table(&tagged_data)?
+            }
+        }
+        return Ok(());
+    }
 
-    let income_statement_data = report_service
-        .income_statement(start_date, end_date) // user_uuid is already a Uuid
-        .await?;
+    let income_statement_data = match params.tag.as_deref() {
+        tag if params.net_refunds => {
+            report_service
+                .income_statement_net_of_refunds(start_date, end_date, tag)
+                .await?
+        }
+        Some(tag) => {
+            report_service
+                .income_statement_for_tag(start_date, end_date, tag)
+                .await?
+        }
+        None => {
+            report_service
+                .income_statement(start_date, end_date)
+                .await?
+        }
+    };
     match params.format {
         OutputFormat::Json => {
             income_statement::print_income_statement_json(&income_statement_data)?;
@@ -56,13 +90,15 @@ pub async fn generate_income_statement(params: IncomeStatementParams) -> Result<
 /// Generate cash flow statement
 pub async fn generate_cash_flow_statement(params: CashFlowParams) -> Result<()> {
     let db = Database::from_env().await?;
-    let report_service = ReportService::new(db.pool().clone());
+    let report_service =
+        ReportService::new(db.pool().clone()).known_as_of(params.known_as_of.get());
 
     let (start_date, end_date) = params.date_range.range();
 
     let cash_flow_data = report_service
         .cash_flow_statement(start_date, end_date)
         .await?;
+    print_known_as_of(&params.known_as_of, &params.format);
     match params.format {
         OutputFormat::Json => {
             cash_flow::print_cash_flow_json(&cash_flow_data, start_date, end_date)?
@@ -85,7 +121,8 @@ pub async fn generate_trial_balance(params: TrialBalanceParams) -> Result<()> {
 /// Generate account ledger report
 pub async fn generate_account_ledger(params: AccountLedgerParams) -> Result<()> {
     let db = Database::from_env().await?;
-    let report_service = ReportService::new(db.pool().clone());
+    let report_service =
+        ReportService::new(db.pool().clone()).known_as_of(params.known_as_of.get());
     // Find account by path
     let account_service = assets_core::AccountService::new(db.pool().clone());
     let account = account_service
@@ -96,9 +133,19 @@ pub async fn generate_account_ledger(params: AccountLedgerParams) -> Result<()>
     // Set default dates if not provided
     let (start_date, end_date) = params.date_range.range();
 
-    let ledger_data = report_service
-        .account_ledger(account.id, start_date, end_date)
-        .await?;
+    let ledger_data = match params.tag.as_deref() {
+        Some(tag) => {
+            report_service
+                .account_ledger_for_tag(account.id, start_date, end_date, tag)
+                .await?
+        }
+        None => {
+            report_service
+                .account_ledger(account.id, start_date, end_date)
+                .await?
+        }
+    };
+    print_known_as_of(&params.known_as_of, &params.format);
     match params.format {
         OutputFormat::Json => {
             account_ledger::print_account_ledger_json(&ledger_data, &account, start_date, end_date)?
@@ -118,6 +165,18 @@ pub async fn generate_account_ledger(params: AccountLedgerParams) -> Result<()>
     Ok(())
 }
 
+/// Note above a table report that it shows the ledger as recorded at a past moment
+fn print_known_as_of(known_as_of: &KnownAsOf, format: &OutputFormat) {
+    if let (Some(moment), OutputFormat::Table) = (known_as_of.get(), format) {
+        println!(
+            "🕰️  As recorded on {}\n",
+            moment
+                .with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M:%S %Z")
+        );
+    }
+}
+
 /// Generate net worth report over time
 pub async fn generate_net_worth_report(params: NetWorthParams) -> Result<()> {
     let (start_date, end_date) = params.date_range.range();
@@ -169,6 +228,8 @@ pub struct BalanceSheetParams {
     #[arg(long)]
     pub include_zero: bool,
 
+    #[command(flatten)]
+    pub known_as_of: KnownAsOf,
     /// Output format
     #[arg(long, value_enum, default_value_t = OutputFormat::Table)]
     pub format: OutputFormat,
@@ -179,6 +240,17 @@ pub struct BalanceSheetParams {
 pub struct IncomeStatementParams {
     #[command(flatten)]
     pub date_range: DateRange,
+    /// Only include entries carrying this tag
+    #[arg(long, conflicts_with = "by_tag")]
+    pub tag: Option<String>,
+    /// Group amounts by tag
+    #[arg(long)]
+    pub by_tag: bool,
+    /// Net linked refunds against the period of their purchase
+    #[arg(long, conflicts_with = "by_tag")]
+    pub net_refunds: bool,
+    #[command(flatten)]
+    pub known_as_of: KnownAsOf,
     /// Output format
     #[arg(long, value_enum, default_value_t = OutputFormat::Table)]
     pub format: OutputFormat,
@@ -190,6 +262,8 @@ pub struct CashFlowParams {
     #[command(flatten)]
     pub date_range: DateRange,
 
+    #[command(flatten)]
+    pub known_as_of: KnownAsOf,
     /// Output format
     #[arg(long, value_enum, default_value_t = OutputFormat::Table)]
     pub format: OutputFormat,
@@ -220,7 +294,12 @@ pub struct AccountLedgerParams {
     /// Show running balance
     #[arg(long)]
     pub show_balance: bool,
+    /// Only include entries carrying this tag
+    #[arg(long)]
+    pub tag: Option<String>,
 
+    #[command(flatten)]
+    pub known_as_of: KnownAsOf,
     /// Output format
     #[arg(long, value_enum, default_value_t = OutputFormat::Table)]
     pub format: OutputFormat,
@@ -1,5 +1,5 @@
 use anyhow::Result;
-use assets_core::models::IncomeStatementRow;
+use assets_core::models::{IncomeStatementRow, TaggedIncomeStatementRow};
 use comfy_table::{presets::UTF8_FULL, ContentArrangement, Table};
 use csv;
 use rust_decimal::Decimal;
@@ -70,6 +70,79 @@ pub(super) fn print_income_statement_csv(data: &[IncomeStatementRow]) -> Result<
     Ok(())
 }
 
+/// Format and print the income statement grouped by tag, with a subtotal per tag
+pub(super) fn print_tagged_income_statement_table(data: &[TaggedIncomeStatementRow]) -> Result<()> {
+    println!();
+    println!("┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓");
+    println!("┃                         📊 INCOME STATEMENT BY TAG                             ┃");
+    println!("┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛");
+    println!();
+
+    if data.is_empty() {
+        println!("No income statement data to display for the selected criteria.");
+        return Ok(());
+    }
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL)
+        .set_content_arrangement(ContentArrangement::Dynamic)
+        .set_header(vec!["Tag", "Category", "Account Path", "Total Amount"]);
+
+    // Rows arrive ordered by tag, untagged last
+    let mut index = 0;
+    while index < data.len() {
+        let tag = &data[index].tag_name;
+        let group: Vec<&TaggedIncomeStatementRow> = data[index..]
+            .iter()
+            .take_while(|row| &row.tag_name == tag)
+            .collect();
+        let tag_label = tag.as_deref().unwrap_or("(untagged)");
+
+        for row in &group {
+            table.add_row(vec![
+                tag_label,
+                row.category_name.as_deref().unwrap_or("N/A"),
+                &row.account_path,
+                &format_currency(row.total_amount),
+            ]);
+        }
+        let subtotal: Decimal = group.iter().map(|row| row.total_amount).sum();
+        table.add_row(vec![tag_label, "", "Subtotal", &format_currency(subtotal)]);
+
+        index += group.len();
+    }
+
+    println!("{table}");
+    println!("📝 Note: An entry with several tags is counted under each of them.");
+    println!("💡 Tip: Use --format=csv or --format=json for data export");
+
+    Ok(())
+}
+
+/// Print the income statement grouped by tag in JSON format
+pub(super) fn print_tagged_income_statement_json(data: &[TaggedIncomeStatementRow]) -> Result<()> {
+    let json_output = serde_json::to_string_pretty(data)?;
+    println!("{}", json_output);
+    Ok(())
+}
+
+/// Print the income statement grouped by tag in CSV format
+pub(super) fn print_tagged_income_statement_csv(data: &[TaggedIncomeStatementRow]) -> Result<()> {
+    let mut wtr = csv::Writer::from_writer(std::io::stdout());
+    wtr.write_record([
+        "Tag",
+        "Category",
+        "Account Name",
+        "Account Path",
+        "Total Amount",
+    ])?;
+    for row in data {
+        wtr.serialize(row)?;
+    }
+    wtr.flush()?;
+    Ok(())
+}
+
 // Helper function, could be moved to a shared utility module
 fn format_currency(amount: Decimal) -> String {
     if amount.is_sign_negative() {
@@ -0,0 +1,196 @@
+use anyhow::Result;
+use assets_core::{Database, TagService, TransactionService};
+use clap::Subcommand;
+use comfy_table::{presets::UTF8_FULL, Table};
+use uuid::Uuid;
+
+use crate::OutputFormat;
+
+#[derive(Subcommand)]
+pub enum TagCommands {
+    /// Create a tag
+    Create {
+        /// Tag name (e.g. "vacation-2025")
+        name: String,
+        /// What the tag is used for
+        #[arg(long)]
+        description: Option<String>,
+    },
+    /// List tags with their usage
+    List {
+        /// Output format
+        #[arg(long, value_enum, default_value = "table")]
+        format: OutputFormat,
+    },
+    /// Rename a tag
+    Rename {
+        /// Current tag name
+        name: String,
+        /// New tag name
+        new_name: String,
+    },
+    /// Delete a tag and detach it everywhere
+    Delete {
+        /// Tag name
+        name: String,
+    },
+    /// Attach a tag to a transaction, or to one of its journal entries
+    Attach {
+        /// Transaction ID
+        transaction_id: String,
+        /// Tag name
+        tag: String,
+        /// Journal entry ID to tag instead of the whole transaction
+        #[arg(long)]
+        entry: Option<String>,
+    },
+    /// Detach a tag from a transaction, or from one of its journal entries
+    Detach {
+        /// Transaction ID
+        transaction_id: String,
+        /// Tag name
+        tag: String,
+        /// Journal entry ID to untag instead of the whole transaction
+        #[arg(long)]
+        entry: Option<String>,
+    },
+}
+
+pub async fn handle_tag_command(command: TagCommands) -> Result<()> {
+    let db = Database::from_env().await?;
+    let tag_service = TagService::new(db.pool().clone());
+
+    match command {
+        TagCommands::Create { name, description } => {
+            let tag = tag_service
+                .create_tag(&name, description.as_deref())
+                .await?;
+            println!("✅ Tag '{}' created", tag.name);
+        }
+        TagCommands::List { format } => list_tags(&tag_service, format).await?,
+        TagCommands::Rename { name, new_name } => {
+            let tag = tag_service.rename_tag(&name, &new_name).await?;
+            println!("✅ Tag '{}' renamed to '{}'", name, tag.name);
+        }
+        TagCommands::Delete { name } => {
+            if tag_service.delete_tag(&name).await? {
+                println!("✅ Tag '{}' deleted", name);
+            } else {
+                return Err(anyhow::anyhow!("No tag named '{}'", name));
+            }
+        }
+        TagCommands::Attach {
+            transaction_id,
+            tag,
+            entry,
+        } => {
+            let transaction_id = Uuid::parse_str(&transaction_id)?;
+            match entry {
+                Some(entry) => {
+                    let entry_id = Uuid::parse_str(&entry)?;
+                    ensure_entry_in_transaction(&db, transaction_id, entry_id).await?;
+                    tag_service.tag_entry(entry_id, &tag).await?;
+                    println!("🏷️  Tagged entry {} with '{}'", entry_id, tag);
+                }
+                None => {
+                    tag_service.tag_transaction(transaction_id, &tag).await?;
+                    println!("🏷️  Tagged transaction {} with '{}'", transaction_id, tag);
+                }
+            }
+        }
+        TagCommands::Detach {
+            transaction_id,
+            tag,
+            entry,
+        } => {
+            let transaction_id = Uuid::parse_str(&transaction_id)?;
+            let removed = match entry {
+                Some(entry) => {
+                    let entry_id = Uuid::parse_str(&entry)?;
+                    ensure_entry_in_transaction(&db, transaction_id, entry_id).await?;
+                    tag_service.untag_entry(entry_id, &tag).await?
+                }
+                None => tag_service.untag_transaction(transaction_id, &tag).await?,
+            };
+            if removed {
+                println!("✅ Tag '{}' removed", tag);
+            } else {
+                println!("ℹ️  Tag '{}' was not attached", tag);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+async fn list_tags(tag_service: &TagService, format: OutputFormat) -> Result<()> {
+    let tags = tag_service.list_tags().await?;
+
+    match format {
+        OutputFormat::Json => {
+            println!("{}", serde_json::to_string_pretty(&tags)?);
+            return Ok(());
+        }
+        OutputFormat::Csv => {
+            println!("name,description,transactions,entries");
+            for tag in &tags {
+                println!(
+                    "{},\"{}\",{},{}",
+                    tag.name,
+                    tag.description
+                        .as_deref()
+                        .unwrap_or_default()
+                        .replace('"', "\"\""),
+                    tag.transaction_count,
+                    tag.entry_count
+                );
+            }
+            return Ok(());
+        }
+        OutputFormat::Table => {}
+    }
+
+    println!("🏷️  Tags");
+    println!("========\n");
+
+    if tags.is_empty() {
+        println!("No tags defined.");
+        println!("💡 Create one with 'assets-cli tags create <name>'");
+        return Ok(());
+    }
+
+    let mut table = Table::new();
+    table.load_preset(UTF8_FULL);
+    table.set_header(vec!["Name", "Description", "Transactions", "Entries"]);
+    for tag in &tags {
+        table.add_row(vec![
+            tag.name.clone(),
+            tag.description.clone().unwrap_or_else(|| "-".to_string()),
+            tag.transaction_count.to_string(),
+            tag.entry_count.to_string(),
+        ]);
+    }
+    println!("{table}");
+
+    Ok(())
+}
+
+/// Guard against tagging an entry through the wrong transaction ID
+async fn ensure_entry_in_transaction(
+    db: &Database,
+    transaction_id: Uuid,
+    entry_id: Uuid,
+) -> Result<()> {
+    let transaction = TransactionService::new(db.pool().clone())
+        .get_transaction(transaction_id)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("Transaction not found: {}", transaction_id))?;
+    if !transaction.entries.iter().any(|e| e.id == entry_id) {
+        return Err(anyhow::anyhow!(
+            "Entry {} does not belong to transaction {}",
+            entry_id,
+            transaction_id
+        ));
+    }
+    Ok(())
+}
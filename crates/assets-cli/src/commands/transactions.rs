@@ -1,23 +1,60 @@
 use anyhow::Result;
-use assets_core::{Database, TransactionService, TransactionWithEntriesAndAccounts};
-use chrono::{DateTime, NaiveDate, Utc};
+use assets_core::{
+    AccountService, AmountFilter, AttachmentService, AttachmentTarget, Database,
+    JournalEntryByPath, NewJournalEntry, NewTransactionByPath, TagService, TransactionFilter,
+    TransactionSearch, TransactionService, TransactionUpdates, TransactionWithEntriesAndAccounts,
+    TransferCandidate, TransferDetection, TransferService,
+};
+use chrono::{DateTime, Local, NaiveDate, Utc};
 use clap::{Args, Subcommand};
 use comfy_table::{presets::UTF8_FULL, Table};
+use futures::TryStreamExt;
 use rust_decimal::Decimal;
+use serde::Deserialize;
+use std::io::Read;
+use std::str::FromStr;
 use uuid::Uuid;
 
+use crate::commands::categorize::{
+    categorize_transactions, handle_rule_command, CategorizeArgs, RuleCommands,
+};
+use crate::commands::refunds::{handle_refund_command, RefundCommands};
 use crate::OutputFormat;
 
 #[derive(Subcommand)]
 pub enum TransactionCommands {
     /// List transactions with optional filtering
     List(ListTransactionsArgs),
+    /// Add a transaction from entries on the command line or a JSON/YAML file
+    Add(AddTransactionArgs),
+    /// Search transactions by fuzzy text, amount, account, source and dates
+    Search(SearchTransactionsArgs),
     /// Show detailed view of a specific transaction
     Show {
         /// Transaction ID to show
         id: String,
     },
-    /// Detect and interactively merge potential internal transfers
+    /// Edit a transaction in place (description, date, reference or entries)
+    Edit(EditTransactionArgs),
+    /// Split one journal entry into several entries with the same total
+    Split(SplitEntryArgs),
+    /// Cancel a transaction by posting its mirror image on a given date
+    Reverse {
+        /// Transaction ID to reverse
+        id: String,
+        /// Date of the reversal (YYYY-MM-DD format)
+        #[arg(long)]
+        date: String,
+        /// Description of the reversal (defaults to "Reversal of: <description>")
+        #[arg(long)]
+        description: Option<String>,
+    },
+    /// Void a transaction so that reports and balances ignore it
+    Void {
+        /// Transaction ID to void
+        id: String,
+    },
+    /// Detect internal transfers and interactively link their two sides
     MergeTransfers {
         /// Date range start (YYYY-MM-DD format)
         #[arg(long)]
@@ -25,13 +62,39 @@ pub enum TransactionCommands {
         /// Date range end (YYYY-MM-DD format)  
         #[arg(long)]
         to: Option<String>,
-        /// Automatically confirm all merges without prompting
+        /// Maximum number of days between the two sides of a transfer
+        #[arg(long, default_value_t = 3)]
+        tolerance: i32,
+        /// Link every unambiguous transfer without prompting
         #[arg(long)]
         auto_confirm: bool,
-        /// Allow merging transactions with same date/amount but different descriptions
+        /// Allow linking movements with the same amount but different descriptions
         #[arg(long)]
         allow_different_descriptions: bool,
     },
+    /// List linked internal transfers
+    Transfers {
+        /// Output format
+        #[arg(long, value_enum, default_value = "table")]
+        format: OutputFormat,
+    },
+    /// Unlink an internal transfer; its two sides go back to uncategorized
+    UnmergeTransfer {
+        /// Transfer link ID, or the ID of either linked transaction
+        id: String,
+    },
+    /// Link refunds to the purchases they give money back for
+    Refunds {
+        #[command(subcommand)]
+        action: RefundCommands,
+    },
+    /// Walk uncategorized entries oldest first and move them to their accounts
+    Categorize(CategorizeArgs),
+    /// Manage reusable categorization rules
+    Rules {
+        #[command(subcommand)]
+        action: RuleCommands,
+    },
 }
 
 #[derive(Args)]
@@ -48,7 +111,53 @@ pub struct ListTransactionsArgs {
     #[arg(long)]
     account: Option<String>,
 
-    /// Maximum number of transactions to show
+    /// Only show transactions carrying this tag (on the transaction or one of its entries)
+    #[arg(long)]
+    tag: Option<String>,
+
+    /// Number of transactions per page
+    #[arg(long, default_value = "50", value_parser = clap::value_parser!(u32).range(1..))]
+    limit: u32,
+
+    /// Page to show, newest transactions first
+    #[arg(long, default_value = "1", value_parser = clap::value_parser!(u32).range(1..))]
+    page: u32,
+
+    /// Show every matching transaction instead of one page
+    #[arg(long, conflicts_with = "page")]
+    all: bool,
+
+    /// Output format
+    #[arg(long, value_enum, default_value = "table")]
+    format: OutputFormat,
+}
+
+#[derive(Args)]
+pub struct SearchTransactionsArgs {
+    /// Text matched against descriptions, references and memos (typos tolerated)
+    query: Option<String>,
+
+    /// Amount condition on any entry: ">100", "<=20", "=42.50" or "100..200"
+    #[arg(long, allow_hyphen_values = true)]
+    amount: Option<String>,
+
+    /// Account path; sub-accounts are included
+    #[arg(long)]
+    account: Option<String>,
+
+    /// Import source (e.g. "BoursoBank")
+    #[arg(long)]
+    source: Option<String>,
+
+    /// Start date (YYYY-MM-DD format)
+    #[arg(long)]
+    from: Option<String>,
+
+    /// End date, included (YYYY-MM-DD format)
+    #[arg(long)]
+    to: Option<String>,
+
+    /// Maximum number of results
     #[arg(long, default_value = "50")]
     limit: u32,
 
@@ -57,16 +166,140 @@ pub struct ListTransactionsArgs {
     format: OutputFormat,
 }
 
+#[derive(Args)]
+pub struct AddTransactionArgs {
+    /// Transaction description (overrides the one from --file)
+    #[arg(long)]
+    description: Option<String>,
+
+    /// Transaction date (YYYY-MM-DD format, default: today; overrides --file)
+    #[arg(long)]
+    date: Option<String>,
+
+    /// Reference (e.g. invoice number; overrides --file)
+    #[arg(long)]
+    reference: Option<String>,
+
+    /// Entries as "Account:Path=amount[:memo]"; one amount may be omitted and
+    /// is computed to balance the transaction
+    #[arg(long = "entry", conflicts_with = "file")]
+    entries: Vec<String>,
+
+    /// Read the transaction from a JSON or YAML file, or from stdin with "-"
+    #[arg(long)]
+    file: Option<String>,
+
+    /// Create missing accounts, inferring their type from the path's root
+    #[arg(long)]
+    create_accounts: bool,
+}
+
+/// Transaction read by `transactions add --file`
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TransactionInput {
+    description: Option<String>,
+    date: Option<NaiveDate>,
+    reference: Option<String>,
+    #[serde(default)]
+    entries: Vec<EntryInput>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct EntryInput {
+    #[serde(alias = "account")]
+    account_path: String,
+    /// Left out on the balancing leg
+    amount: Option<Decimal>,
+    memo: Option<String>,
+}
+
+#[derive(Args)]
+pub struct EditTransactionArgs {
+    /// Transaction ID to edit
+    id: String,
+
+    /// New description
+    #[arg(long)]
+    description: Option<String>,
+
+    /// New date (YYYY-MM-DD format)
+    #[arg(long)]
+    date: Option<String>,
+
+    /// New reference
+    #[arg(long)]
+    reference: Option<String>,
+
+    /// Remove the reference
+    #[arg(long, conflicts_with = "reference")]
+    clear_reference: bool,
+
+    /// Replacement entries as "Account:Path=amount[:memo]"; one amount may be
+    /// omitted and is computed to balance the transaction
+    #[arg(long = "entry")]
+    entries: Vec<String>,
+}
+
+#[derive(Args)]
+pub struct SplitEntryArgs {
+    /// Transaction ID
+    id: String,
+
+    /// Entry to split: its ID or its account path
+    #[arg(long)]
+    entry: String,
+
+    /// Parts as "Account:Path=amount[:memo]"; one amount may be omitted and
+    /// receives the remainder
+    #[arg(long = "part", required = true, num_args = 1)]
+    parts: Vec<String>,
+}
+
+/// A parsed `Account:Path=amount[:memo]` argument
+#[derive(Debug, PartialEq)]
+struct EntrySpec {
+    account_path: String,
+    amount: Option<Decimal>,
+    memo: Option<String>,
+}
+
 pub async fn handle_transaction_command(command: TransactionCommands) -> Result<()> {
     match command {
         TransactionCommands::List(args) => list_transactions(args).await,
+        TransactionCommands::Add(args) => add_transaction(args).await,
+        TransactionCommands::Search(args) => search_transactions(args).await,
         TransactionCommands::Show { id } => show_transaction(&id).await,
+        TransactionCommands::Edit(args) => edit_transaction(args).await,
+        TransactionCommands::Split(args) => split_entry(args).await,
+        TransactionCommands::Reverse {
+            id,
+            date,
+            description,
+        } => reverse_transaction(&id, &date, description).await,
+        TransactionCommands::Void { id } => void_transaction(&id).await,
         TransactionCommands::MergeTransfers {
             from,
             to,
+            tolerance,
             auto_confirm,
             allow_different_descriptions,
-        } => merge_internal_transfers(from, to, auto_confirm, allow_different_descriptions).await,
+        } => {
+            merge_internal_transfers(
+                from,
+                to,
+                tolerance,
+                auto_confirm,
+                allow_different_descriptions,
+            )
+            .await
+        }
+        TransactionCommands::Transfers { format } => list_transfers(format).await,
+        TransactionCommands::UnmergeTransfer { id } => unmerge_transfer(&id).await,
+        TransactionCommands::Refunds { action } => handle_refund_command(action).await,
+        TransactionCommands::Categorize(args) => categorize_transactions(args).await,
+        TransactionCommands::Rules { action } => handle_rule_command(action).await,
     }
 }
 
@@ -89,15 +322,35 @@ async fn list_transactions(args: ListTransactionsArgs) -> Result<()> {
     } else {
         None
     };
-    // Get transactions with filters
-    let transactions = transaction_service
-        .get_transactions_with_filters_and_accounts(
-            from_date,
-            to_date,
-            args.account.as_deref(),
-            args.limit,
-        )
-        .await?;
+    let filter = TransactionFilter::builder()
+        .maybe_from_date(from_date)
+        .maybe_to_date(to_date)
+        .maybe_account_path(args.account.clone())
+        .maybe_tag(args.tag.clone())
+        .build();
+
+    let (transactions, next_page) = if args.all {
+        let mut stream = Box::pin(transaction_service.stream_transactions(filter));
+        // CSV rows are written as they arrive, so exports of any size stay cheap
+        if matches!(args.format, OutputFormat::Csv) {
+            println!("{}", CSV_HEADER);
+            let mut count = 0;
+            while let Some(tx) = stream.try_next().await? {
+                println!("{}", csv_row(&tx));
+                count += 1;
+            }
+            println!();
+            println!("📊 Summary: {} transactions found", count);
+            return Ok(());
+        }
+        (stream.try_collect().await?, None)
+    } else {
+        let page = transaction_service
+            .get_transactions_page_number(&filter, args.page, args.limit)
+            .await?;
+        let next_page = page.next_cursor.map(|_| args.page + 1);
+        (page.transactions, next_page)
+    };
 
     if transactions.is_empty() {
         println!("No transactions found with the specified filters.");
@@ -105,6 +358,7 @@ async fn list_transactions(args: ListTransactionsArgs) -> Result<()> {
         println!("💡 Try adjusting your filters or check:");
         println!("   - Date range with --from and --to");
         println!("   - Account filter with --account");
+        println!("   - Tag filter with --tag");
         println!("   - User filter with --user");
         return Ok(());
     }
@@ -116,10 +370,24 @@ async fn list_transactions(args: ListTransactionsArgs) -> Result<()> {
     }
 
     println!();
-    println!("📊 Summary: {} transactions found", transactions.len());
+    if args.all {
+        println!("📊 Summary: {} transactions found", transactions.len());
+    } else {
+        println!(
+            "📊 Summary: {} transactions on page {}",
+            transactions.len(),
+            args.page
+        );
+    }
     if let (Some(from), Some(to)) = (&args.from, &args.to) {
         println!("📅 Date range: {} to {}", from, to);
     }
+    if let Some(next_page) = next_page {
+        println!(
+            "📄 More transactions: --page {} for the next page, --all for everything",
+            next_page
+        );
+    }
 
     Ok(())
 }
@@ -138,6 +406,24 @@ async fn show_transaction(id_str: &str) -> Result<()> {
     {
         Some(transaction_with_entries) => {
             display_transaction_detail(&transaction_with_entries);
+
+            let tags = TagService::new(db.pool().clone())
+                .tags_for_transaction(transaction_id)
+                .await?;
+            if !tags.is_empty() {
+                let names: Vec<&str> = tags.iter().map(|t| t.name.as_str()).collect();
+                println!("🏷️  Tags: {}", names.join(", "));
+            }
+
+            let attachments = AttachmentService::from_env(db.pool().clone())
+                .list_for(AttachmentTarget::Transaction(transaction_id))
+                .await?;
+            for attachment in &attachments {
+                println!(
+                    "📎 {} ({}) — open with 'assets-cli attachments open {}'",
+                    attachment.file_name, attachment.mime_type, attachment.id
+                );
+            }
         }
         None => {
             println!("❌ Transaction not found: {}", id_str);
@@ -149,6 +435,482 @@ async fn show_transaction(id_str: &str) -> Result<()> {
     Ok(())
 }
 
+async fn search_transactions(args: SearchTransactionsArgs) -> Result<()> {
+    let amount = args
+        .amount
+        .as_deref()
+        .map(|filter| filter.parse::<AmountFilter>())
+        .transpose()
+        .map_err(|e| anyhow::anyhow!("{e}"))?;
+    let search = TransactionSearch::builder()
+        .maybe_query(args.query.clone())
+        .maybe_amount(amount)
+        .maybe_account_path(args.account)
+        .maybe_import_source(args.source)
+        .maybe_from_date(args.from.as_deref().map(parse_naive_date).transpose()?)
+        .maybe_to_date(args.to.as_deref().map(parse_naive_date).transpose()?)
+        .limit(args.limit)
+        .build();
+
+    let db = Database::from_env().await?;
+    let transaction_service = TransactionService::new(db.pool().clone());
+    let results = transaction_service.search(&search).await?;
+
+    match args.format {
+        OutputFormat::Json => {
+            println!("{}", serde_json::to_string_pretty(&results)?);
+            return Ok(());
+        }
+        OutputFormat::Csv => {
+            println!("Date,Description,Amount,Score,Reference,ID");
+            for result in &results {
+                let tx = &result.transaction;
+                println!(
+                    "{},{},{:.2},{:.2},{},{}",
+                    tx.transaction.transaction_date.format("%Y-%m-%d"),
+                    escape_csv(&tx.transaction.description),
+                    transaction_amount(tx),
+                    result.score,
+                    escape_csv(tx.transaction.reference.as_deref().unwrap_or("")),
+                    tx.transaction.id
+                );
+            }
+            return Ok(());
+        }
+        OutputFormat::Table => {}
+    }
+
+    println!("🔎 Transaction Search");
+    println!("=====================\n");
+
+    if results.is_empty() {
+        println!("No transactions match the search.");
+        println!();
+        println!(
+            "💡 Try a shorter query or loosen the --amount, --account, --source or date filters"
+        );
+        return Ok(());
+    }
+
+    let mut table = Table::new();
+    table.load_preset(UTF8_FULL);
+    table.set_header(vec![
+        "Date",
+        "Description",
+        "Amount",
+        "Reference",
+        "Score",
+        "ID",
+    ]);
+    for result in &results {
+        let tx = &result.transaction;
+        table.add_row(vec![
+            tx.transaction
+                .transaction_date
+                .format("%Y-%m-%d")
+                .to_string(),
+            if tx.transaction.is_voided {
+                format!(
+                    "[VOID] {}",
+                    truncate_string(&tx.transaction.description, 33)
+                )
+            } else {
+                truncate_string(&tx.transaction.description, 40)
+            },
+            format!("{:.2}", transaction_amount(tx)),
+            truncate_string(tx.transaction.reference.as_deref().unwrap_or("-"), 15),
+            if args.query.is_some() {
+                format!("{:.2}", result.score)
+            } else {
+                "-".to_string()
+            },
+            tx.transaction.id.to_string(),
+        ]);
+    }
+    println!("{table}");
+    println!();
+    println!("📊 {} transaction(s) found", results.len());
+
+    Ok(())
+}
+
+/// Amount moved by a transaction: half the sum of its absolute entries
+fn transaction_amount(transaction: &TransactionWithEntriesAndAccounts) -> Decimal {
+    transaction
+        .entries
+        .iter()
+        .map(|e| e.amount.abs())
+        .sum::<Decimal>()
+        / Decimal::from(2)
+}
+
+fn parse_naive_date(date_str: &str) -> Result<NaiveDate> {
+    NaiveDate::parse_from_str(date_str, "%Y-%m-%d")
+        .map_err(|_| anyhow::anyhow!("Invalid date format. Use YYYY-MM-DD (e.g., 2025-06-13)"))
+}
+
+async fn add_transaction(args: AddTransactionArgs) -> Result<()> {
+    println!("➕ Add Transaction");
+    println!("=================\n");
+
+    let input = match args.file.as_deref() {
+        Some(path) => read_transaction_input(path)?,
+        None => TransactionInput::default(),
+    };
+
+    let description = args
+        .description
+        .or(input.description)
+        .filter(|d| !d.trim().is_empty())
+        .ok_or_else(|| anyhow::anyhow!("A description is required (--description)"))?;
+    // Posted at noon like imported transactions, so the day reads the same in any timezone
+    let date = match args.date.as_deref() {
+        Some(date) => parse_date(date)?.date_naive(),
+        None => input.date.unwrap_or_else(|| Local::now().date_naive()),
+    }
+    .and_hms_opt(12, 0, 0)
+    .unwrap()
+    .and_utc();
+    let specs = if args.file.is_some() {
+        input
+            .entries
+            .into_iter()
+            .map(|entry| EntrySpec {
+                account_path: entry.account_path,
+                amount: entry.amount,
+                memo: entry.memo,
+            })
+            .collect()
+    } else {
+        args.entries
+            .iter()
+            .map(|spec| parse_entry_spec(spec))
+            .collect::<Result<Vec<_>>>()?
+    };
+    if specs.len() < 2 {
+        return Err(anyhow::anyhow!(
+            "A transaction needs at least two entries (--entry \"Account:Path=amount\")"
+        ));
+    }
+
+    let entries = balance_entry_specs(specs)?;
+    let total: Decimal = entries.iter().map(|e| e.amount).sum();
+    if total != Decimal::ZERO {
+        return Err(anyhow::anyhow!(
+            "Entries do not balance: they sum to {}. Fix an amount or omit one to balance it",
+            total
+        ));
+    }
+
+    let db = Database::from_env().await?;
+    let transaction_service = TransactionService::new(db.pool().clone());
+    let account_service = AccountService::new(db.pool().clone());
+
+    let new_transaction = NewTransactionByPath::builder()
+        .description(description)
+        .date(date)
+        .entries(entries)
+        .maybe_reference(args.reference.or(input.reference))
+        .ensure_accounts(args.create_accounts)
+        .build();
+    let created = transaction_service
+        .create_transaction_by_path(&account_service, new_transaction)
+        .await?;
+
+    println!("✅ Transaction created\n");
+    if let Some(transaction) = transaction_service
+        .get_transaction_with_accounts(created.transaction.id)
+        .await?
+    {
+        display_transaction_detail(&transaction);
+    }
+
+    Ok(())
+}
+
+/// Read a transaction from a JSON or YAML file ("-" reads stdin)
+fn read_transaction_input(path: &str) -> Result<TransactionInput> {
+    let content = if path == "-" {
+        let mut content = String::new();
+        std::io::stdin().read_to_string(&mut content)?;
+        content
+    } else {
+        std::fs::read_to_string(path)
+            .map_err(|e| anyhow::anyhow!("Cannot read '{}': {}", path, e))?
+    };
+
+    // YAML is a superset of JSON, so one parser handles both
+    serde_yml::from_str(&content)
+        .map_err(|e| anyhow::anyhow!("Invalid transaction file '{}': {}", path, e))
+}
+
+async fn edit_transaction(args: EditTransactionArgs) -> Result<()> {
+    let transaction_id = Uuid::parse_str(&args.id)?;
+
+    println!("✏️  Edit Transaction");
+    println!("===================\n");
+
+    let db = Database::from_env().await?;
+    let transaction_service = TransactionService::new(db.pool().clone());
+    let account_service = AccountService::new(db.pool().clone());
+
+    let entries = if args.entries.is_empty() {
+        None
+    } else {
+        let specs = args
+            .entries
+            .iter()
+            .map(|spec| parse_entry_spec(spec))
+            .collect::<Result<Vec<_>>>()?;
+        Some(resolve_entry_specs(&account_service, specs, Decimal::ZERO).await?)
+    };
+
+    let updates = TransactionUpdates {
+        description: args.description,
+        transaction_date: args.date.as_deref().map(parse_date).transpose()?,
+        reference: if args.clear_reference {
+            Some(None)
+        } else {
+            args.reference.map(Some)
+        },
+        entries,
+    };
+    if !updates.has_updates() {
+        println!(
+            "Nothing to change. Use --description, --date, --reference, --clear-reference or --entry."
+        );
+        return Ok(());
+    }
+
+    transaction_service
+        .update_transaction(transaction_id, updates)
+        .await?;
+
+    println!("✅ Transaction updated\n");
+    if let Some(transaction) = transaction_service
+        .get_transaction_with_accounts(transaction_id)
+        .await?
+    {
+        display_transaction_detail(&transaction);
+    }
+
+    Ok(())
+}
+
+async fn split_entry(args: SplitEntryArgs) -> Result<()> {
+    let transaction_id = Uuid::parse_str(&args.id)?;
+
+    println!("✂️  Split Journal Entry");
+    println!("======================\n");
+
+    let db = Database::from_env().await?;
+    let transaction_service = TransactionService::new(db.pool().clone());
+    let account_service = AccountService::new(db.pool().clone());
+
+    let transaction = transaction_service
+        .get_transaction_with_accounts(transaction_id)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("Transaction not found: {}", args.id))?;
+
+    // The entry can be designated by ID or, when unambiguous, by account path
+    let candidates: Vec<_> = match Uuid::parse_str(&args.entry) {
+        Ok(entry_id) => transaction
+            .entries
+            .iter()
+            .filter(|e| e.id == entry_id)
+            .collect(),
+        Err(_) => transaction
+            .entries
+            .iter()
+            .filter(|e| e.account_path == args.entry)
+            .collect(),
+    };
+    let entry = match candidates.as_slice() {
+        [entry] => *entry,
+        [] => {
+            return Err(anyhow::anyhow!(
+                "No entry '{}' in transaction {}",
+                args.entry,
+                transaction_id
+            ))
+        }
+        _ => {
+            return Err(anyhow::anyhow!(
+                "Several entries use '{}', pass the entry ID instead",
+                args.entry
+            ))
+        }
+    };
+
+    let specs = args
+        .parts
+        .iter()
+        .map(|spec| parse_entry_spec(spec))
+        .collect::<Result<Vec<_>>>()?;
+    let parts = resolve_entry_specs(&account_service, specs, entry.amount).await?;
+
+    transaction_service
+        .split_entry(transaction_id, entry.id, parts)
+        .await?;
+
+    println!(
+        "✅ Split {:.2} on {} into {} parts\n",
+        entry.amount,
+        entry.account_path,
+        args.parts.len()
+    );
+    if let Some(transaction) = transaction_service
+        .get_transaction_with_accounts(transaction_id)
+        .await?
+    {
+        display_transaction_detail(&transaction);
+    }
+
+    Ok(())
+}
+
+async fn reverse_transaction(id_str: &str, date: &str, description: Option<String>) -> Result<()> {
+    let transaction_id = Uuid::parse_str(id_str)?;
+
+    println!("↩️  Reverse Transaction");
+    println!("======================\n");
+
+    let db = Database::from_env().await?;
+    let transaction_service = TransactionService::new(db.pool().clone());
+
+    let reversal = transaction_service
+        .reverse_transaction(transaction_id, parse_date(date)?, description)
+        .await?;
+
+    println!("✅ Transaction {} reversed\n", transaction_id);
+    if let Some(transaction) = transaction_service
+        .get_transaction_with_accounts(reversal.transaction.id)
+        .await?
+    {
+        display_transaction_detail(&transaction);
+    }
+
+    Ok(())
+}
+
+async fn void_transaction(id_str: &str) -> Result<()> {
+    let transaction_id = Uuid::parse_str(id_str)?;
+
+    println!("🚫 Void Transaction");
+    println!("===================\n");
+
+    let db = Database::from_env().await?;
+    let transaction_service = TransactionService::new(db.pool().clone());
+
+    let voided = transaction_service.void_transaction(transaction_id).await?;
+
+    println!(
+        "✅ Voided \"{}\" dated {}",
+        voided.description,
+        voided.transaction_date.format("%Y-%m-%d")
+    );
+    println!("   Reports and balances no longer include this transaction.");
+
+    Ok(())
+}
+
+/// Parse entry arguments into path-based entries, filling the single elided amount so
+/// that the entries balance
+pub(crate) fn parse_entries_by_path(specs: &[String]) -> Result<Vec<JournalEntryByPath>> {
+    let specs = specs
+        .iter()
+        .map(|spec| parse_entry_spec(spec))
+        .collect::<Result<Vec<_>>>()?;
+    balance_entry_specs(specs)
+}
+
+/// Fill the single elided amount so that the entries sum to zero
+fn balance_entry_specs(specs: Vec<EntrySpec>) -> Result<Vec<JournalEntryByPath>> {
+    let elided = specs.iter().filter(|spec| spec.amount.is_none()).count();
+    if elided > 1 {
+        return Err(anyhow::anyhow!(
+            "Only one entry may omit its amount, {} do",
+            elided
+        ));
+    }
+    let remainder = -specs.iter().filter_map(|spec| spec.amount).sum::<Decimal>();
+
+    Ok(specs
+        .into_iter()
+        .map(|spec| JournalEntryByPath {
+            account_path: spec.account_path,
+            amount: spec.amount.unwrap_or(remainder),
+            memo: spec.memo,
+        })
+        .collect())
+}
+
+/// Parse "Account:Path=amount[:memo]" (the amount may be left empty: "Account:Path")
+fn parse_entry_spec(spec: &str) -> Result<EntrySpec> {
+    let (account_path, rest) = match spec.split_once('=') {
+        Some((path, rest)) => (path.trim(), Some(rest)),
+        None => (spec.trim(), None),
+    };
+    if account_path.is_empty() {
+        return Err(anyhow::anyhow!("Missing account path in entry '{}'", spec));
+    }
+
+    let (amount, memo) = match rest {
+        Some(rest) => {
+            let (amount, memo) = match rest.split_once(':') {
+                Some((amount, memo)) => (amount.trim(), Some(memo.trim().to_string())),
+                None => (rest.trim(), None),
+            };
+            let amount = if amount.is_empty() {
+                None
+            } else {
+                Some(Decimal::from_str(amount).map_err(|_| {
+                    anyhow::anyhow!("Invalid amount '{}' in entry '{}'", amount, spec)
+                })?)
+            };
+            (amount, memo.filter(|memo| !memo.is_empty()))
+        }
+        None => (None, None),
+    };
+
+    Ok(EntrySpec {
+        account_path: account_path.to_string(),
+        amount,
+        memo,
+    })
+}
+
+/// Resolve account paths and fill the single elided amount so entries sum to `total`
+async fn resolve_entry_specs(
+    account_service: &AccountService,
+    specs: Vec<EntrySpec>,
+    total: Decimal,
+) -> Result<Vec<NewJournalEntry>> {
+    let elided = specs.iter().filter(|spec| spec.amount.is_none()).count();
+    if elided > 1 {
+        return Err(anyhow::anyhow!(
+            "Only one entry may omit its amount, {} do",
+            elided
+        ));
+    }
+    let remainder = total - specs.iter().filter_map(|spec| spec.amount).sum::<Decimal>();
+
+    let mut entries = Vec::with_capacity(specs.len());
+    for spec in specs {
+        let account = account_service
+            .get_account_by_path(&spec.account_path)
+            .await
+            .map_err(|_| anyhow::anyhow!("Account not found: {}", spec.account_path))?;
+        entries.push(NewJournalEntry {
+            account_id: account.id,
+            amount: spec.amount.unwrap_or(remainder),
+            memo: spec.memo,
+        });
+    }
+
+    Ok(entries)
+}
+
 fn parse_date(date_str: &str) -> Result<DateTime<Utc>> {
     let naive_date = NaiveDate::parse_from_str(date_str, "%Y-%m-%d")
         .map_err(|_| anyhow::anyhow!("Invalid date format. Use YYYY-MM-DD (e.g., 2025-06-13)"))?;
@@ -179,7 +941,14 @@ fn display_transactions_table(transactions: &[TransactionWithEntriesAndAccounts]
                 .transaction_date
                 .format("%Y-%m-%d")
                 .to_string(),
-            truncate_string(&tx.transaction.description, 30),
+            if tx.transaction.is_voided {
+                format!(
+                    "[VOID] {}",
+                    truncate_string(&tx.transaction.description, 23)
+                )
+            } else {
+                truncate_string(&tx.transaction.description, 30)
+            },
             format!("{} entries", entry_count),
             format!("{:.2}", total_amount),
             truncate_string(reference, 15),
@@ -196,29 +965,35 @@ fn display_transactions_json(transactions: &[TransactionWithEntriesAndAccounts])
     Ok(())
 }
 
+const CSV_HEADER: &str = "Date,Description,Entries,Amount,Reference,ID";
+
 fn display_transactions_csv(transactions: &[TransactionWithEntriesAndAccounts]) -> Result<()> {
-    println!("Date,Description,Entries,Amount,Reference,ID");
+    println!("{}", CSV_HEADER);
 
     for tx in transactions {
-        let total_amount: Decimal =
-            tx.entries.iter().map(|e| e.amount.abs()).sum::<Decimal>() / Decimal::from(2);
-        let entry_count = tx.entries.len();
-        let reference = tx.transaction.reference.as_deref().unwrap_or("");
-
-        println!(
-            "{},{},{},{:.2},{},{}",
-            tx.transaction.transaction_date.format("%Y-%m-%d"),
-            escape_csv(&tx.transaction.description),
-            entry_count,
-            total_amount,
-            escape_csv(reference),
-            tx.transaction.id
-        );
+        println!("{}", csv_row(tx));
     }
 
     Ok(())
 }
 
+fn csv_row(tx: &TransactionWithEntriesAndAccounts) -> String {
+    let total_amount: Decimal =
+        tx.entries.iter().map(|e| e.amount.abs()).sum::<Decimal>() / Decimal::from(2);
+    let entry_count = tx.entries.len();
+    let reference = tx.transaction.reference.as_deref().unwrap_or("");
+
+    format!(
+        "{},{},{},{:.2},{},{}",
+        tx.transaction.transaction_date.format("%Y-%m-%d"),
+        escape_csv(&tx.transaction.description),
+        entry_count,
+        total_amount,
+        escape_csv(reference),
+        tx.transaction.id
+    )
+}
+
 fn display_transaction_detail(transaction_with_entries: &TransactionWithEntriesAndAccounts) {
     let tx = &transaction_with_entries.transaction;
 
@@ -230,6 +1005,15 @@ fn display_transaction_detail(transaction_with_entries: &TransactionWithEntriesA
         println!("   Reference: {}", reference);
     }
     println!("   Created at: {}", tx.created_at.format("%Y-%m-%d %H:%M"));
+    if tx.is_voided {
+        match tx.voided_at {
+            Some(voided_at) => println!("   Status: VOIDED on {}", voided_at.format("%Y-%m-%d")),
+            None => println!("   Status: VOIDED"),
+        }
+    }
+    if let Some(original) = tx.reversal_of_transaction_id {
+        println!("   Reverses: {}", original);
+    }
 
     println!();
     println!("📊 Journal Entries:");
@@ -297,6 +1081,7 @@ fn escape_csv(s: &str) -> String {
 async fn merge_internal_transfers(
     from: Option<String>,
     to: Option<String>,
+    tolerance: i32,
     auto_confirm: bool,
     allow_different_descriptions: bool,
 ) -> Result<()> {
@@ -304,66 +1089,69 @@ async fn merge_internal_transfers(
     println!("=============================\n");
 
     let db = Database::from_env().await?;
-    let transaction_service = TransactionService::new(db.pool().clone());
-
-    // Parse date filters
-    let from_date = if let Some(from_str) = &from {
-        Some(parse_date(from_str)?)
-    } else {
-        None
-    };
-
-    let to_date = if let Some(to_str) = &to {
-        Some(parse_date(to_str)?)
-    } else {
-        None
-    };
-
-    // Find potential internal transfers using SQL
-    let potential_transfers = find_potential_internal_transfers(
-        &db,
-        from_date.map(|d| d.date_naive()),
-        to_date.map(|d| d.date_naive()),
-        allow_different_descriptions,
-    )
-    .await?;
+    let transfer_service = TransferService::new(db.pool().clone());
+
+    let detection = TransferDetection::builder()
+        .tolerance_days(tolerance)
+        .maybe_from_date(from.as_deref().map(parse_naive_date).transpose()?)
+        .maybe_to_date(to.as_deref().map(parse_naive_date).transpose()?)
+        .same_description(!allow_different_descriptions)
+        .build();
+
+    if auto_confirm {
+        let links = transfer_service.auto_link(&detection).await?;
+        for link in &links {
+            println!(
+                "✅ Linked {} → {} ({:.2}) on {}",
+                link.outgoing_account_path,
+                link.incoming_account_path,
+                link.amount,
+                link.outgoing_date.format("%Y-%m-%d")
+            );
+        }
+        let left = transfer_service.find_candidates(&detection).await?.len();
+        println!("\n🔄 Linked {} transfer(s)", links.len());
+        if left > 0 {
+            println!(
+                "💡 {} ambiguous pair(s) left; run without --auto-confirm to review them",
+                left
+            );
+        }
+        return Ok(());
+    }
 
-    if potential_transfers.is_empty() {
+    let candidates = transfer_service.find_candidates(&detection).await?;
+    if candidates.is_empty() {
         println!("✅ No potential internal transfers found.");
         return Ok(());
     }
 
     println!(
-        "🔍 Found {} potential internal transfer groups:",
-        potential_transfers.len()
+        "🔍 Found {} potential internal transfer(s):",
+        candidates.len()
     );
     println!();
 
-    for (i, group) in potential_transfers.iter().enumerate() {
-        display_transfer_group(i + 1, group);
-
-        if !auto_confirm {
-            println!("Do you want to merge this transfer group? (y/N): ");
-            let mut input = String::new();
-            std::io::stdin().read_line(&mut input)?;
+    for (i, candidate) in candidates.iter().enumerate() {
+        display_transfer_candidate(i + 1, candidate);
 
-            if input.trim().to_lowercase() != "y" {
-                println!("❌ Skipping this group.\n");
-                continue;
-            }
+        println!("Do you want to link this transfer? (y/N): ");
+        let mut input = String::new();
+        std::io::stdin().read_line(&mut input)?;
+        if input.trim().to_lowercase() != "y" {
+            println!("❌ Skipping this transfer.\n");
+            continue;
         }
 
-        // Perform the merge
-        match merge_transfer_group(&transaction_service, group).await {
-            Ok(new_transaction_id) => {
-                println!(
-                    "✅ Successfully merged transfer group into transaction: {}",
-                    new_transaction_id
-                );
-            }
-            Err(e) => {
-                println!("❌ Failed to merge transfer group: {}", e);
-            }
+        match transfer_service
+            .link(
+                candidate.outgoing_transaction_id,
+                candidate.incoming_transaction_id,
+            )
+            .await
+        {
+            Ok(link) => println!("✅ Linked as transfer {}", link.id),
+            Err(e) => println!("❌ Failed to link transfer: {}", e),
         }
         println!();
     }
@@ -371,200 +1159,100 @@ async fn merge_internal_transfers(
     Ok(())
 }
 
-#[derive(Debug)]
-struct PotentialTransfer {
-    transaction_id: Uuid,
-    description: String,
-    amount: Decimal,
-    date: DateTime<Utc>,
-    non_equity_account: String,
-    non_equity_account_id: Uuid,
-}
-
-#[derive(Debug)]
-struct TransferGroup {
-    date: DateTime<Utc>,
-    amount: Decimal,
-    transfers: Vec<PotentialTransfer>,
+fn display_transfer_candidate(number: usize, candidate: &TransferCandidate) {
+    println!(
+        "📋 Transfer {}: €{:.2}{}",
+        number,
+        candidate.amount,
+        if candidate.ambiguous {
+            " ⚠️  other pairings possible"
+        } else {
+            ""
+        }
+    );
+    println!(
+        "  - {} {} ({})",
+        candidate.outgoing_date.format("%Y-%m-%d"),
+        truncate_string(&candidate.outgoing_description, 40),
+        truncate_string(&candidate.outgoing_account_path, 30)
+    );
+    println!(
+        "  + {} {} ({})",
+        candidate.incoming_date.format("%Y-%m-%d"),
+        truncate_string(&candidate.incoming_description, 40),
+        truncate_string(&candidate.incoming_account_path, 30)
+    );
+    println!();
 }
 
-async fn find_potential_internal_transfers(
-    db: &Database,
-    from_date: Option<NaiveDate>,
-    to_date: Option<NaiveDate>,
-    allow_different_descriptions: bool,
-) -> Result<Vec<TransferGroup>> {
-    // For now, let's create a simpler implementation without direct sqlx access
-    // We'll get transactions with entries and filter in Rust
-
-    let transaction_service = TransactionService::new(db.pool().clone());
-
-    // Get transactions in date range
-    let from_datetime = from_date.map(|d| d.and_hms_opt(0, 0, 0).unwrap().and_utc());
-    let to_datetime = to_date.map(|d| d.and_hms_opt(23, 59, 59).unwrap().and_utc());
-    let transactions = transaction_service
-        .get_transactions_with_filters_and_accounts(from_datetime, to_datetime, None, 1000)
-        .await?;
+async fn list_transfers(format: OutputFormat) -> Result<()> {
+    let db = Database::from_env().await?;
+    let links = TransferService::new(db.pool().clone()).list_links().await?;
 
-    let mut potential_transfers = Vec::new(); // Find transactions that involve Equity:Uncategorized
-    for tx in transactions {
-        let has_equity = tx
-            .entries
-            .iter()
-            .any(|e| e.account_path == "Equity:Uncategorized");
-        if !has_equity {
-            continue;
+    match format {
+        OutputFormat::Json => {
+            println!("{}", serde_json::to_string_pretty(&links)?);
+            return Ok(());
         }
-
-        // Find the non-equity account and amount
-        for entry in &tx.entries {
-            if entry.account_path != "Equity:Uncategorized" {
-                potential_transfers.push(PotentialTransfer {
-                    transaction_id: tx.transaction.id,
-                    description: tx.transaction.description.clone(),
-                    amount: entry.amount,
-                    date: tx.transaction.transaction_date,
-                    non_equity_account: entry.account_path.clone(),
-                    non_equity_account_id: entry.account_id,
-                });
+        OutputFormat::Csv => {
+            println!("date,from,to,amount,outgoing_transaction_id,incoming_transaction_id,id");
+            for link in &links {
+                println!(
+                    "{},{},{},{:.2},{},{},{}",
+                    link.outgoing_date.format("%Y-%m-%d"),
+                    escape_csv(&link.outgoing_account_path),
+                    escape_csv(&link.incoming_account_path),
+                    link.amount,
+                    link.outgoing_transaction_id,
+                    link.incoming_transaction_id,
+                    link.id
+                );
             }
+            return Ok(());
         }
+        OutputFormat::Table => {}
     }
-    // Group by date and description to find matching transaction pairs
-    let mut groups = std::collections::HashMap::new();
-    for transfer in potential_transfers {
-        let key = if allow_different_descriptions {
-            // Group by date and amount only (ignore description differences)
-            (
-                transfer.date.date_naive(),
-                "".to_string(),
-                transfer.amount.abs(),
-            )
-        } else {
-            // Group by date and description (exact match required)
-            (
-                transfer.date.date_naive(),
-                transfer.description.clone(),
-                Decimal::ZERO,
-            )
-        };
-        groups.entry(key).or_insert_with(Vec::new).push(transfer);
-    } // Filter groups with exactly 2 transfers (potential internal transfers)
-    let transfer_groups: Vec<TransferGroup> = groups
-        .into_iter()
-        .filter_map(|((_date, _description, _amount), transfers)| {
-            if transfers.len() == 2 {
-                // Check if one is positive and one is negative (opposite movements)
-                let positive_count = transfers
-                    .iter()
-                    .filter(|t| t.amount > Decimal::ZERO)
-                    .count();
-                let negative_count = transfers
-                    .iter()
-                    .filter(|t| t.amount < Decimal::ZERO)
-                    .count();
-
-                // Check if the amounts are opposite (one +X, one -X)
-                let amount1 = transfers[0].amount;
-                let amount2 = transfers[1].amount;
-
-                if positive_count == 1 && negative_count == 1 && amount1 == -amount2 {
-                    Some(TransferGroup {
-                        date: transfers[0].date,
-                        amount: amount1.abs(),
-                        transfers,
-                    })
-                } else {
-                    None
-                }
-            } else {
-                None
-            }
-        })
-        .collect();
-
-    Ok(transfer_groups)
-}
-
-fn display_transfer_group(group_num: usize, group: &TransferGroup) {
-    println!(
-        "📋 Group {}: {} - Amount: €{:.2}",
-        group_num,
-        group.date.format("%Y-%m-%d"),
-        group.amount
-    );
 
-    for (i, transfer) in group.transfers.iter().enumerate() {
-        let sign = if transfer.amount > Decimal::ZERO {
-            "+"
-        } else {
-            ""
-        };
-        println!(
-            "  {}. {} → {} ({}€{:.2})",
-            i + 1,
-            truncate_string(&transfer.description, 40),
-            truncate_string(&transfer.non_equity_account, 30),
-            sign,
-            transfer.amount
-        );
+    println!("🔄 Internal Transfers");
+    println!("====================\n");
+    if links.is_empty() {
+        println!("No linked transfers yet.");
+        println!("💡 Find them with 'assets-cli transactions merge-transfers'");
+        return Ok(());
     }
-    println!();
-}
 
-async fn merge_transfer_group(
-    transaction_service: &TransactionService,
-    group: &TransferGroup,
-) -> Result<Uuid> {
-    // Find the source (negative amount) and destination (positive amount) accounts
-    let source = group
-        .transfers
-        .iter()
-        .find(|t| t.amount < Decimal::ZERO)
-        .ok_or_else(|| anyhow::anyhow!("No source account found"))?;
-    let destination = group
-        .transfers
-        .iter()
-        .find(|t| t.amount > Decimal::ZERO)
-        .ok_or_else(|| anyhow::anyhow!("No destination account found"))?;
-
-    // Create the merged transaction description
-    let new_description = format!(
-        "Internal Transfer: {} → {}",
-        source
-            .non_equity_account
-            .split(':')
-            .next_back()
-            .unwrap_or(&source.non_equity_account),
-        destination
-            .non_equity_account
-            .split(':')
-            .next_back()
-            .unwrap_or(&destination.non_equity_account)
-    );
+    let mut table = Table::new();
+    table.load_preset(UTF8_FULL);
+    table.set_header(vec!["Date", "From", "To", "Amount", "ID"]);
+    for link in &links {
+        table.add_row(vec![
+            link.outgoing_date.format("%Y-%m-%d").to_string(),
+            link.outgoing_account_path.clone(),
+            link.incoming_account_path.clone(),
+            format!("{:.2}", link.amount),
+            link.id.to_string(),
+        ]);
+    }
+    println!("{table}");
 
-    // Create a new internal transfer transaction
-    let new_transaction = TransactionService::create_simple_transaction(
-        new_description,
-        destination.non_equity_account_id, // debit (destination receives money)
-        source.non_equity_account_id,      // credit (source sends money)
-        group.amount,
-        group.date,
-        None,
-    );
+    Ok(())
+}
 
-    // Create the transaction in the database
-    let created_transaction = transaction_service
-        .create_transaction(new_transaction)
-        .await?;
-    let new_transaction_id = created_transaction.transaction.id;
+async fn unmerge_transfer(id_str: &str) -> Result<()> {
+    let id = Uuid::parse_str(id_str).map_err(|_| anyhow::anyhow!("Invalid ID '{}'", id_str))?;
+    let db = Database::from_env().await?;
 
-    // Delete the original transactions
-    for transfer in &group.transfers {
-        transaction_service
-            .delete_transaction(transfer.transaction_id)
-            .await?;
+    match TransferService::new(db.pool().clone()).unlink(id).await? {
+        Some(link) => {
+            println!(
+                "✅ Transfer {} → {} ({:.2}) unlinked; both sides are uncategorized again",
+                link.outgoing_account_path, link.incoming_account_path, link.amount
+            );
+            Ok(())
+        }
+        None => Err(anyhow::anyhow!("No linked transfer for {}", id)),
     }
-
-    Ok(new_transaction_id)
 }
+
+#[cfg(test)]
+mod tests;
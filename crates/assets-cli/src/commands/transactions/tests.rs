@@ -0,0 +1,80 @@
+use super::*;
+
+fn spec(account_path: &str, amount: Option<i64>, memo: Option<&str>) -> EntrySpec {
+    EntrySpec {
+        account_path: account_path.to_string(),
+        amount: amount.map(Decimal::from),
+        memo: memo.map(str::to_string),
+    }
+}
+
+#[test]
+fn test_parse_entry_spec() {
+    let entry = parse_entry_spec("Expenses:Food:Groceries=42.50").unwrap();
+    assert_eq!(entry.account_path, "Expenses:Food:Groceries");
+    assert_eq!(entry.amount, Some(Decimal::new(4250, 2)));
+    assert_eq!(entry.memo, None);
+
+    // The memo is everything after the amount, colons included
+    let entry = parse_entry_spec(" Expenses:Food = -12 : lunch: team offsite ").unwrap();
+    assert_eq!(entry.account_path, "Expenses:Food");
+    assert_eq!(entry.amount, Some(Decimal::from(-12)));
+    assert_eq!(entry.memo.as_deref(), Some("lunch: team offsite"));
+
+    // The balancing leg leaves its amount out, with or without a memo
+    let entry = parse_entry_spec("Assets:Checking").unwrap();
+    assert_eq!(entry.account_path, "Assets:Checking");
+    assert_eq!(entry.amount, None);
+    let entry = parse_entry_spec("Assets:Checking=:card").unwrap();
+    assert_eq!(entry.amount, None);
+    assert_eq!(entry.memo.as_deref(), Some("card"));
+    let entry = parse_entry_spec("Assets:Checking=10:").unwrap();
+    assert_eq!(entry.memo, None);
+}
+
+#[test]
+fn test_parse_entry_spec_rejects_malformed_specs() {
+    for malformed in [
+        "",
+        "=10",
+        "  =10:memo",
+        "Expenses:Food=ten",
+        "Expenses:Food=1,5",
+    ] {
+        assert!(
+            parse_entry_spec(malformed).is_err(),
+            "'{}' should be rejected",
+            malformed
+        );
+    }
+}
+
+#[test]
+fn test_balance_entry_specs() {
+    let entries = balance_entry_specs(vec![
+        spec("Expenses:Food", Some(30), Some("market")),
+        spec("Expenses:Household", Some(12), None),
+        spec("Assets:Checking", None, None),
+    ])
+    .unwrap();
+    assert_eq!(entries[2].account_path, "Assets:Checking");
+    assert_eq!(entries[2].amount, Decimal::from(-42));
+    assert_eq!(entries[0].memo.as_deref(), Some("market"));
+
+    // Without an elided amount the entries are kept as given, balanced or not
+    let entries = balance_entry_specs(vec![
+        spec("Expenses:Food", Some(30), None),
+        spec("Assets:Checking", Some(-20), None),
+    ])
+    .unwrap();
+    assert_eq!(
+        entries.iter().map(|e| e.amount).sum::<Decimal>(),
+        Decimal::from(10)
+    );
+
+    assert!(balance_entry_specs(vec![
+        spec("Expenses:Food", None, None),
+        spec("Assets:Checking", None, None),
+    ])
+    .is_err());
+}
@@ -1,4 +1,4 @@
-use chrono::{Datelike, NaiveDate};
+use chrono::{DateTime, Datelike, Local, NaiveDate, NaiveDateTime, TimeZone, Utc};
 use clap::Args;
 
 // Note: the unwrap in date arithmetic functions should be safe as we are working with valid dates
@@ -71,6 +71,51 @@ impl DateRange {
     }
 }
 
+/// Moment a report looks back to, seeing the ledger as it was recorded then
+#[derive(Args, Clone, Debug)]
+pub struct KnownAsOf {
+    /// Report the ledger as recorded at this moment: YYYY-MM-DD (end of that day),
+    /// YYYY-MM-DD HH:MM[:SS] in local time, or RFC 3339
+    #[arg(long, value_parser = parse_known_as_of)]
+    known_as_of: Option<DateTime<Utc>>,
+}
+
+impl KnownAsOf {
+    pub fn get(&self) -> Option<DateTime<Utc>> {
+        self.known_as_of
+    }
+}
+
+fn parse_known_as_of(value: &str) -> Result<DateTime<Utc>, String> {
+    if let Ok(moment) = DateTime::parse_from_rfc3339(value) {
+        return Ok(moment.with_timezone(&Utc));
+    }
+    let local = [
+        "%Y-%m-%d %H:%M:%S",
+        "%Y-%m-%d %H:%M",
+        "%Y-%m-%dT%H:%M:%S",
+        "%Y-%m-%dT%H:%M",
+    ]
+    .iter()
+    .find_map(|format| NaiveDateTime::parse_from_str(value, format).ok())
+    .or_else(|| {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d")
+            .ok()
+            .map(|date| date.and_hms_micro_opt(23, 59, 59, 999_999).unwrap())
+    })
+    .ok_or_else(|| {
+        format!(
+            "invalid moment '{}'; use YYYY-MM-DD, YYYY-MM-DD HH:MM[:SS] or RFC 3339",
+            value
+        )
+    })?;
+    Local
+        .from_local_datetime(&local)
+        .earliest()
+        .map(|moment| moment.with_timezone(&Utc))
+        .ok_or_else(|| format!("'{}' does not exist in the local time zone", value))
+}
+
 fn today() -> NaiveDate {
     chrono::Utc::now().naive_utc().date()
 }
@@ -5,7 +5,10 @@ use rust_decimal::Decimal;
 mod commands;
 #[cfg(feature = "demo")]
 use commands::demo::*;
-use commands::{accounts::*, db::*, duplicates::*, import::*, prices, reports::*, transactions::*};
+use commands::{
+    accounts::*, assertions::*, attachments::*, db::*, duplicates::*, history::*, import::*,
+    periods::*, prices, reconcile::*, recurring::*, reports::*, tags::*, transactions::*,
+};
 pub mod date_utils;
 pub use date_utils::*;
 mod utils;
@@ -60,6 +63,45 @@ enum Commands {
         #[command(subcommand)]
         action: DuplicateCommands,
     },
+    /// Reconcile accounts against bank statements
+    Reconcile {
+        #[command(subcommand)]
+        action: ReconcileCommands,
+    },
+    /// Known-good account balances checked against the ledger
+    Assertions {
+        #[command(subcommand)]
+        action: AssertionCommands,
+    },
+    /// Check every balance assertion against the ledger
+    Check(CheckArgs),
+    /// Move income and expenses into retained earnings and lock everything up to a date
+    ClosePeriod(ClosePeriodArgs),
+    /// Reopen the latest closed period
+    ReopenPeriod,
+    /// List closed periods
+    Periods {
+        /// Output format
+        #[arg(long, value_enum, default_value = "table")]
+        format: OutputFormat,
+    },
+    /// Recurring transaction templates and scheduler
+    Recurring {
+        #[command(subcommand)]
+        action: RecurringCommands,
+    },
+    /// Tag management and tagging of transactions
+    Tags {
+        #[command(subcommand)]
+        action: TagCommands,
+    },
+    /// Receipts, invoices and statements attached to transactions and accounts
+    Attachments {
+        #[command(subcommand)]
+        action: AttachmentCommands,
+    },
+    /// Browse the audit log of changes per transaction or account
+    History(HistoryArgs),
     Completion {
         #[arg(value_enum)]
         shell: clap_complete::Shell,
@@ -78,6 +120,28 @@ enum DbCommands {
     Init,
     /// Show database status and connection info
     Status,
+    /// Export all ledger data to a versioned archive
+    Export {
+        /// Output file path
+        file: String,
+        /// Gzip-compress the archive
+        #[arg(long)]
+        compress: bool,
+    },
+    /// Restore ledger data from an archive into an empty database
+    Import {
+        /// Archive file path (plain or gzipped JSON)
+        file: String,
+    },
+    /// Recompute the journal's hash chain and report transactions changed outside the application
+    Verify {
+        /// Output format
+        #[arg(long, value_enum, default_value = "table")]
+        format: OutputFormat,
+        /// Accept the current state of flagged transactions into the chain
+        #[arg(long)]
+        reseal: bool,
+    },
 }
 
 #[derive(Subcommand)]
@@ -91,29 +155,7 @@ enum AccountCommands {
         id: Option<String>,
     },
     /// Create a new account interactively
-    Create {
-        /// Account name
-        #[arg(long)]
-        name: Option<String>,
-        /// Account type (Asset, Liability, Equity, Income, Expense)
-        #[arg(long)]
-        account_type: Option<String>,
-        /// Account subtype
-        #[arg(long)]
-        subtype: Option<String>,
-        /// Parent account path (e.g., "Assets:Current Assets")
-        #[arg(long)]
-        parent: Option<String>,
-        /// Stock/ETF symbol (for investment accounts)
-        #[arg(long)]
-        symbol: Option<String>,
-        /// Currency code (default: EUR)
-        #[arg(long, default_value = "EUR")]
-        currency: String,
-        /// Account notes
-        #[arg(long)]
-        notes: Option<String>,
-    },
+    Create(CreateAccountArgs),
     /// Show chart of accounts as a tree
     Tree,
     /// Show account ownership details
@@ -223,30 +265,14 @@ async fn main() -> Result<()> {
         Commands::Db { action } => match action {
             DbCommands::Init => init_database().await?,
             DbCommands::Status => show_db_status().await?,
+            DbCommands::Export { file, compress } => export_database(&file, compress).await?,
+            DbCommands::Import { file } => import_database(&file).await?,
+            DbCommands::Verify { format, reseal } => verify_database(format, reseal).await?,
         },
         Commands::Accounts { action } => match action {
             AccountCommands::List => list_accounts().await?,
             AccountCommands::Balance { id } => show_account_balance(id.as_deref()).await?,
-            AccountCommands::Create {
-                name,
-                account_type,
-                subtype,
-                parent,
-                symbol,
-                currency,
-                notes,
-            } => {
-                create_account(
-                    name.as_deref(),
-                    account_type.as_deref(),
-                    subtype.as_deref(),
-                    parent.as_deref(),
-                    symbol.as_deref(),
-                    &currency,
-                    notes.as_deref(),
-                )
-                .await?
-            }
+            AccountCommands::Create(args) => create_account(args).await?,
             AccountCommands::Tree => show_accounts_tree().await?,
             AccountCommands::Ownership { account_id } => {
                 show_account_ownership(&account_id).await?
@@ -296,6 +322,16 @@ async fn main() -> Result<()> {
         Commands::Transactions { action } => handle_transaction_command(action).await?,
         Commands::Import { action } => handle_import_command(action).await?,
         Commands::Duplicates { action } => handle_duplicate_command(action).await?,
+        Commands::Reconcile { action } => handle_reconcile_command(action).await?,
+        Commands::Assertions { action } => handle_assertion_command(action).await?,
+        Commands::Check(args) => check_balance_assertions(args).await?,
+        Commands::ClosePeriod(args) => close_period(args).await?,
+        Commands::ReopenPeriod => reopen_period().await?,
+        Commands::Periods { format } => list_periods(format).await?,
+        Commands::Recurring { action } => handle_recurring_command(action).await?,
+        Commands::Tags { action } => handle_tag_command(action).await?,
+        Commands::Attachments { action } => handle_attachment_command(action).await?,
+        Commands::History(args) => show_history(args).await?,
         Commands::Completion { shell } => {
             clap_complete::generate(
                 shell,
@@ -0,0 +1,17 @@
+-- Mappings used by the import inbox to route dropped files to an importer
+CREATE TABLE import_mappings (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    file_pattern VARCHAR(255) NOT NULL, -- Glob on the file name, e.g. 'export-bourso-*.csv'
+    importer VARCHAR(50) NOT NULL,
+    target_account VARCHAR(500), -- Account path for bank statement importers
+    payslip_destinations JSONB, -- Destination accounts for payslip importers
+    priority INTEGER NOT NULL DEFAULT 0, -- Higher priority mappings are tried first
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    CONSTRAINT uq_import_mapping_pattern UNIQUE (file_pattern),
+    CONSTRAINT chk_import_mapping_importer CHECK (
+        importer IN ('boursobank', 'sg', 'qt_payslip', 'mathworks_payslip')
+    ),
+    CONSTRAINT chk_import_mapping_destination CHECK (
+        target_account IS NOT NULL OR payslip_destinations IS NOT NULL
+    )
+);
@@ -0,0 +1,7 @@
+DROP TRIGGER IF EXISTS trg_audit_price_history ON price_history;
+DROP TRIGGER IF EXISTS trg_audit_accounts ON accounts;
+DROP TRIGGER IF EXISTS trg_audit_journal_entries ON journal_entries;
+DROP TRIGGER IF EXISTS trg_audit_transactions ON transactions;
+DROP TABLE IF EXISTS audit_log;
+DROP FUNCTION IF EXISTS fn_audit_log_append_only();
+DROP FUNCTION IF EXISTS fn_audit_row_change();
@@ -0,0 +1,96 @@
+-- Append-only audit trail of every ledger mutation
+CREATE TABLE audit_log (
+    id BIGSERIAL PRIMARY KEY,
+    changed_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    changed_by TEXT NOT NULL,
+    db_transaction_id BIGINT NOT NULL DEFAULT txid_current(), -- Groups rows changed by one operation
+    table_name VARCHAR(50) NOT NULL,
+    operation VARCHAR(10) NOT NULL CHECK (operation IN ('INSERT', 'UPDATE', 'DELETE')),
+    record_id UUID NOT NULL,
+    transaction_id UUID, -- Ledger transaction the row belongs to, if any
+    account_id UUID, -- Account the row belongs to, if any
+    old_data JSONB,
+    new_data JSONB
+);
+
+CREATE INDEX idx_audit_log_transaction ON audit_log(transaction_id) WHERE transaction_id IS NOT NULL;
+CREATE INDEX idx_audit_log_account ON audit_log(account_id) WHERE account_id IS NOT NULL;
+CREATE INDEX idx_audit_log_changed_at ON audit_log(changed_at);
+
+-- Record one row change; the actor comes from the rusty_assets.actor setting when present
+CREATE OR REPLACE FUNCTION fn_audit_row_change()
+RETURNS TRIGGER AS $$
+DECLARE
+    old_row JSONB;
+    new_row JSONB;
+    current_row JSONB;
+BEGIN
+    IF TG_OP <> 'INSERT' THEN
+        old_row := to_jsonb(OLD);
+    END IF;
+    IF TG_OP <> 'DELETE' THEN
+        new_row := to_jsonb(NEW);
+    END IF;
+
+    -- Skip no-op updates, including accounts.full_path being recomputed by its own trigger
+    IF TG_OP = 'UPDATE' AND (old_row - 'full_path') = (new_row - 'full_path') THEN
+        RETURN NULL;
+    END IF;
+
+    current_row := COALESCE(new_row, old_row);
+
+    INSERT INTO audit_log (
+        changed_by, table_name, operation, record_id, transaction_id, account_id, old_data, new_data
+    )
+    VALUES (
+        COALESCE(NULLIF(current_setting('rusty_assets.actor', true), ''), session_user),
+        TG_TABLE_NAME,
+        TG_OP,
+        (current_row->>'id')::uuid,
+        CASE TG_TABLE_NAME
+            WHEN 'transactions' THEN (current_row->>'id')::uuid
+            WHEN 'journal_entries' THEN (current_row->>'transaction_id')::uuid
+        END,
+        CASE TG_TABLE_NAME
+            WHEN 'accounts' THEN (current_row->>'id')::uuid
+            WHEN 'journal_entries' THEN (current_row->>'account_id')::uuid
+        END,
+        old_row,
+        new_row
+    );
+
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trg_audit_transactions
+    AFTER INSERT OR UPDATE OR DELETE ON transactions
+    FOR EACH ROW EXECUTE FUNCTION fn_audit_row_change();
+
+CREATE TRIGGER trg_audit_journal_entries
+    AFTER INSERT OR UPDATE OR DELETE ON journal_entries
+    FOR EACH ROW EXECUTE FUNCTION fn_audit_row_change();
+
+CREATE TRIGGER trg_audit_accounts
+    AFTER INSERT OR UPDATE OR DELETE ON accounts
+    FOR EACH ROW EXECUTE FUNCTION fn_audit_row_change();
+
+CREATE TRIGGER trg_audit_price_history
+    AFTER INSERT OR UPDATE OR DELETE ON price_history
+    FOR EACH ROW EXECUTE FUNCTION fn_audit_row_change();
+
+-- The audit trail itself can only grow
+CREATE OR REPLACE FUNCTION fn_audit_log_append_only()
+RETURNS TRIGGER AS $$
+BEGIN
+    RAISE EXCEPTION 'audit_log is append-only (% is not allowed)', TG_OP;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trg_audit_log_append_only
+    BEFORE UPDATE OR DELETE ON audit_log
+    FOR EACH ROW EXECUTE FUNCTION fn_audit_log_append_only();
+
+CREATE TRIGGER trg_audit_log_no_truncate
+    BEFORE TRUNCATE ON audit_log
+    FOR EACH STATEMENT EXECUTE FUNCTION fn_audit_log_append_only();
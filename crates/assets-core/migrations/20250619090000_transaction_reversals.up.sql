@@ -0,0 +1,270 @@
+-- Voiding and reversal instead of hard deletes
+ALTER TABLE transactions
+    ADD COLUMN is_voided BOOLEAN NOT NULL DEFAULT FALSE,
+    ADD COLUMN voided_at TIMESTAMP WITH TIME ZONE,
+    ADD COLUMN reversal_of_transaction_id UUID REFERENCES transactions(id);
+
+-- A transaction can be reversed at most once
+CREATE UNIQUE INDEX idx_transactions_reversal_of ON transactions(reversal_of_transaction_id)
+WHERE reversal_of_transaction_id IS NOT NULL;
+CREATE INDEX idx_transactions_is_voided ON transactions(is_voided) WHERE is_voided;
+
+-- Voided transactions no longer count towards balances
+CREATE OR REPLACE VIEW account_daily_balance_changes AS
+SELECT j.account_id,
+    t.transaction_date::date AS balance_day,
+    -- Cast to date to group by day
+    SUM(j.amount) AS net_change_on_day
+FROM journal_entries j
+    JOIN transactions t ON j.transaction_id = t.id
+WHERE NOT t.is_voided
+GROUP BY j.account_id,
+    t.transaction_date::date;
+
+CREATE OR REPLACE FUNCTION balance_sheet_data(report_date DATE) RETURNS TABLE (
+        account_type TEXT,
+        name TEXT,
+        balance DECIMAL(20, 2),
+        full_path TEXT,
+        level INTEGER
+    ) AS $$ BEGIN RETURN QUERY
+SELECT a.account_type::TEXT,
+    a.name::TEXT,
+    COALESCE(SUM(je.amount), 0) as balance,
+    a.full_path::TEXT,
+    -- Calculate level from the number of colons in full_path
+    (
+        LENGTH(a.full_path) - LENGTH(REPLACE(a.full_path, ':', ''))
+    ) as level
+FROM accounts a
+    LEFT JOIN journal_entries je ON a.id = je.account_id
+    LEFT JOIN transactions t ON je.transaction_id = t.id
+WHERE a.account_type IN ('asset', 'liability', 'equity')
+    AND a.is_active = true
+    AND (
+        t.transaction_date IS NULL
+        OR t.transaction_date <= report_date
+    )
+    AND (
+        t.id IS NULL
+        OR NOT t.is_voided
+    )
+GROUP BY a.id,
+    a.name,
+    a.account_type,
+    a.full_path
+HAVING COALESCE(SUM(je.amount), 0) != 0
+ORDER BY a.account_type,
+    a.full_path;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE FUNCTION fn_income_statement(
+    p_start_date DATE,
+    p_end_date DATE
+)
+RETURNS TABLE (
+    category_name TEXT,
+    account_name TEXT,
+    account_path TEXT,
+    total_amount DECIMAL(19, 4)
+)
+AS $$
+BEGIN
+    RETURN QUERY
+    SELECT
+        COALESCE(parent_acc.name, 'Uncategorized')::TEXT AS category_name,
+        a.name::TEXT AS account_name,
+        COALESCE(a.full_path, a.name)::TEXT AS account_path,
+        COALESCE(
+            SUM(
+                CASE
+                    WHEN a.account_type = 'income' THEN (je.amount * -1.0)
+                    WHEN a.account_type = 'expense' THEN je.amount
+                    ELSE 0.0
+                END
+            ), 0.0
+        )::DECIMAL(19, 4) AS total_amount
+    FROM
+        accounts a
+    INNER JOIN
+        journal_entries je ON je.account_id = a.id
+    INNER JOIN
+        transactions t ON t.id = je.transaction_id
+    LEFT JOIN
+        accounts parent_acc ON a.parent_id = parent_acc.id
+    WHERE
+        a.account_type IN ('income', 'expense')
+        AND a.is_active = true
+        AND t.transaction_date >= p_start_date
+        AND t.transaction_date <= p_end_date
+        AND NOT t.is_voided
+    GROUP BY
+        parent_acc.name, a.id, a.name, a.account_type, a.full_path
+    HAVING
+        ABS(COALESCE(
+            SUM(
+                CASE
+                    WHEN a.account_type = 'income' THEN je.amount
+                    WHEN a.account_type = 'expense' THEN je.amount
+                    ELSE 0.0
+                END
+            ), 0.0
+        )) > 0.01;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE FUNCTION fn_account_ledger(
+    p_account_id UUID,
+    p_start_date DATE,
+    p_end_date DATE
+)
+RETURNS TABLE (
+    transaction_date DATE,
+    transaction_id UUID,
+    description TEXT,
+    reference TEXT,
+    memo TEXT,
+    debit_amount DECIMAL(19, 4),
+    credit_amount DECIMAL(19, 4),
+    running_balance DECIMAL(19, 4)
+)
+AS $$
+DECLARE
+    opening_balance DECIMAL(19, 4) := 0.0;
+BEGIN
+    -- Calculate opening balance (all transactions before start date)
+    SELECT COALESCE(SUM(je.amount), 0.0) INTO opening_balance
+    FROM journal_entries je
+    INNER JOIN transactions t ON t.id = je.transaction_id
+    WHERE je.account_id = p_account_id
+      AND t.transaction_date < p_start_date
+      AND NOT t.is_voided;
+
+    -- Return the ledger entries with running balance
+    RETURN QUERY
+    SELECT
+        t.transaction_date::DATE as transaction_date,
+        t.id as transaction_id,
+        t.description::TEXT as description,
+        COALESCE(t.reference, '')::TEXT as reference,
+        COALESCE(je.memo, '')::TEXT as memo,
+        CASE 
+            WHEN je.amount > 0 THEN je.amount
+            ELSE 0.0
+        END as debit_amount,
+        CASE 
+            WHEN je.amount < 0 THEN ABS(je.amount)
+            ELSE 0.0
+        END as credit_amount,
+        (opening_balance + SUM(je.amount) OVER (ORDER BY t.transaction_date, t.created_at, je.created_at))::DECIMAL(19, 4) as running_balance
+    FROM 
+        journal_entries je
+    INNER JOIN 
+        transactions t ON t.id = je.transaction_id
+    WHERE 
+        je.account_id = p_account_id
+        AND t.transaction_date >= p_start_date
+        AND t.transaction_date <= p_end_date
+        AND NOT t.is_voided
+    ORDER BY 
+        t.transaction_date, t.created_at, je.created_at;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE FUNCTION fn_cash_flow_statement(
+    p_start_date DATE,
+    p_end_date DATE
+)
+RETURNS TABLE (
+    activity_type TEXT,
+    category_name TEXT,
+    account_name TEXT,
+    account_path TEXT,
+    cash_flow DECIMAL(19, 4)
+)
+AS $$
+BEGIN
+    RETURN QUERY
+    SELECT
+        CASE
+            -- Operating Activities: Daily life cash flows (income and routine expenses)
+            WHEN a.account_type = 'income' THEN 'Operating'
+            WHEN a.account_type = 'expense' AND a.account_subtype IN (
+                'food', 'housing', 'transportation', 'communication', 'utilities', 
+                'healthcare', 'personal', 'entertainment', 'fees', 'taxes'
+            ) THEN 'Operating'
+            
+            -- Investing Activities: Investment and savings-related cash flows
+            WHEN a.account_type = 'asset' AND a.account_subtype IN (
+                'stocks', 'etf', 'bonds', 'mutual_fund', 'crypto', 'investment_account'
+            ) THEN 'Investing'
+            WHEN a.account_type = 'asset' AND a.account_subtype = 'savings' THEN 'Investing'
+            WHEN a.account_type = 'expense' AND a.account_subtype = 'investment' THEN 'Investing'
+            
+            -- Financing Activities: Debt and equity-related cash flows
+            WHEN a.account_type = 'liability' THEN 'Financing'
+            WHEN a.account_type = 'equity' THEN 'Financing'
+            WHEN a.account_type = 'asset' AND a.account_subtype IN ('loan', 'mortgage') THEN 'Financing'
+            
+            -- Default to Operating for uncategorized items
+            ELSE 'Operating'
+        END::TEXT AS activity_type,
+        
+        COALESCE(parent_acc.name, 'Uncategorized')::TEXT AS category_name,
+        a.name::TEXT AS account_name,
+        COALESCE(a.full_path, a.name)::TEXT AS account_path,
+        
+        -- Calculate cash flow (positive = cash inflow, negative = cash outflow)
+        COALESCE(
+            SUM(
+                CASE
+                    -- For income accounts: positive amounts are cash inflows
+                    WHEN a.account_type = 'income' THEN (je.amount * -1.0)
+                    -- For asset accounts: positive amounts are cash outflows (money leaving to buy assets)
+                    WHEN a.account_type = 'asset' THEN (je.amount * -1.0)
+                    -- For expense accounts: positive amounts are cash outflows  
+                    WHEN a.account_type = 'expense' THEN (je.amount * -1.0)
+                    -- For liability accounts: positive amounts are cash inflows (borrowing)
+                    WHEN a.account_type = 'liability' THEN je.amount
+                    -- For equity accounts: positive amounts are cash inflows
+                    WHEN a.account_type = 'equity' THEN je.amount
+                    ELSE 0.0
+                END
+            ), 0.0
+        )::DECIMAL(19, 4) AS cash_flow
+    FROM
+        accounts a
+    INNER JOIN
+        journal_entries je ON je.account_id = a.id
+    INNER JOIN
+        transactions t ON t.id = je.transaction_id
+    LEFT JOIN
+        accounts parent_acc ON a.parent_id = parent_acc.id
+    WHERE
+        a.is_active = true
+        AND t.transaction_date >= p_start_date
+        AND t.transaction_date <= p_end_date
+        AND NOT t.is_voided
+        -- Exclude opening balance transactions to focus on actual cash flows
+        AND COALESCE(t.reference, '') != 'OPENING'
+    GROUP BY
+        a.account_type, a.account_subtype, parent_acc.name, a.id, a.name, a.full_path
+    HAVING
+        -- Only include accounts with meaningful cash flows
+        ABS(COALESCE(
+            SUM(
+                CASE
+                    WHEN a.account_type = 'income' THEN (je.amount * -1.0)
+                    WHEN a.account_type = 'asset' THEN (je.amount * -1.0)
+                    WHEN a.account_type = 'expense' THEN (je.amount * -1.0)
+                    WHEN a.account_type = 'liability' THEN je.amount
+                    WHEN a.account_type = 'equity' THEN je.amount
+                    ELSE 0.0
+                END
+            ), 0.0
+        )) > 0.01
+    ORDER BY
+        activity_type, category_name, a.name;
+END;
+$$ LANGUAGE plpgsql;
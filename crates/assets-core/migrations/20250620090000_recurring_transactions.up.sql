@@ -0,0 +1,29 @@
+-- Templates for transactions that repeat on a schedule (rent, salary, subscriptions)
+CREATE TABLE recurring_transactions (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    name VARCHAR(255) NOT NULL,
+    description VARCHAR(500) NOT NULL,
+    reference VARCHAR(100),
+    entries JSONB NOT NULL, -- [{"account_path": ..., "amount": ..., "memo": ...}]
+    schedule VARCHAR(50) NOT NULL, -- 'monthly:5', 'weekly:mon', 'yearly:12-25', 'last-business-day'
+    start_date DATE NOT NULL,
+    end_date DATE,
+    amount_tolerance DECIMAL(19, 4) NOT NULL DEFAULT 0, -- Accepted difference when matching imported lines
+    is_active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    CONSTRAINT uq_recurring_transaction_name UNIQUE (name),
+    CONSTRAINT chk_recurring_dates CHECK (end_date IS NULL OR end_date >= start_date),
+    CONSTRAINT chk_recurring_tolerance CHECK (amount_tolerance >= 0)
+);
+
+-- One row per due date already handled, which makes generation idempotent
+CREATE TABLE recurring_transaction_instances (
+    recurring_transaction_id UUID NOT NULL REFERENCES recurring_transactions(id) ON DELETE CASCADE,
+    due_date DATE NOT NULL,
+    transaction_id UUID REFERENCES transactions(id) ON DELETE SET NULL, -- NULL once the posted transaction is deleted
+    matched_at TIMESTAMP WITH TIME ZONE, -- Set when an imported bank line was matched to the instance
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (recurring_transaction_id, due_date)
+);
+
+CREATE INDEX idx_recurring_instances_transaction ON recurring_transaction_instances(transaction_id);
@@ -0,0 +1,121 @@
+DROP FUNCTION IF EXISTS fn_income_statement_by_tag(DATE, DATE);
+DROP FUNCTION IF EXISTS fn_income_statement(DATE, DATE, TEXT);
+DROP FUNCTION IF EXISTS fn_account_ledger(UUID, DATE, DATE, TEXT);
+
+CREATE FUNCTION fn_income_statement(
+    p_start_date DATE,
+    p_end_date DATE
+)
+RETURNS TABLE (
+    category_name TEXT,
+    account_name TEXT,
+    account_path TEXT,
+    total_amount DECIMAL(19, 4)
+)
+AS $$
+BEGIN
+    RETURN QUERY
+    SELECT
+        COALESCE(parent_acc.name, 'Uncategorized')::TEXT AS category_name,
+        a.name::TEXT AS account_name,
+        COALESCE(a.full_path, a.name)::TEXT AS account_path,
+        COALESCE(
+            SUM(
+                CASE
+                    WHEN a.account_type = 'income' THEN (je.amount * -1.0)
+                    WHEN a.account_type = 'expense' THEN je.amount
+                    ELSE 0.0
+                END
+            ), 0.0
+        )::DECIMAL(19, 4) AS total_amount
+    FROM
+        accounts a
+    INNER JOIN
+        journal_entries je ON je.account_id = a.id
+    INNER JOIN
+        transactions t ON t.id = je.transaction_id
+    LEFT JOIN
+        accounts parent_acc ON a.parent_id = parent_acc.id
+    WHERE
+        a.account_type IN ('income', 'expense')
+        AND a.is_active = true
+        AND t.transaction_date >= p_start_date
+        AND t.transaction_date <= p_end_date
+        AND NOT t.is_voided
+    GROUP BY
+        parent_acc.name, a.id, a.name, a.account_type, a.full_path
+    HAVING
+        ABS(COALESCE(
+            SUM(
+                CASE
+                    WHEN a.account_type = 'income' THEN je.amount
+                    WHEN a.account_type = 'expense' THEN je.amount
+                    ELSE 0.0
+                END
+            ), 0.0
+        )) > 0.01;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE FUNCTION fn_account_ledger(
+    p_account_id UUID,
+    p_start_date DATE,
+    p_end_date DATE
+)
+RETURNS TABLE (
+    transaction_date DATE,
+    transaction_id UUID,
+    description TEXT,
+    reference TEXT,
+    memo TEXT,
+    debit_amount DECIMAL(19, 4),
+    credit_amount DECIMAL(19, 4),
+    running_balance DECIMAL(19, 4)
+)
+AS $$
+DECLARE
+    opening_balance DECIMAL(19, 4) := 0.0;
+BEGIN
+    -- Calculate opening balance (all transactions before start date)
+    SELECT COALESCE(SUM(je.amount), 0.0) INTO opening_balance
+    FROM journal_entries je
+    INNER JOIN transactions t ON t.id = je.transaction_id
+    WHERE je.account_id = p_account_id
+      AND t.transaction_date < p_start_date
+      AND NOT t.is_voided;
+
+    -- Return the ledger entries with running balance
+    RETURN QUERY
+    SELECT
+        t.transaction_date::DATE as transaction_date,
+        t.id as transaction_id,
+        t.description::TEXT as description,
+        COALESCE(t.reference, '')::TEXT as reference,
+        COALESCE(je.memo, '')::TEXT as memo,
+        CASE 
+            WHEN je.amount > 0 THEN je.amount
+            ELSE 0.0
+        END as debit_amount,
+        CASE 
+            WHEN je.amount < 0 THEN ABS(je.amount)
+            ELSE 0.0
+        END as credit_amount,
+        (opening_balance + SUM(je.amount) OVER (ORDER BY t.transaction_date, t.created_at, je.created_at))::DECIMAL(19, 4) as running_balance
+    FROM 
+        journal_entries je
+    INNER JOIN 
+        transactions t ON t.id = je.transaction_id
+    WHERE 
+        je.account_id = p_account_id
+        AND t.transaction_date >= p_start_date
+        AND t.transaction_date <= p_end_date
+        AND NOT t.is_voided
+    ORDER BY 
+        t.transaction_date, t.created_at, je.created_at;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP VIEW IF EXISTS v_journal_entry_tags;
+DROP TABLE IF EXISTS journal_entry_tags;
+DROP TABLE IF EXISTS transaction_tags;
+DROP TABLE IF EXISTS tags;
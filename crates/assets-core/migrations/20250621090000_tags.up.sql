@@ -0,0 +1,236 @@
+-- Cross-cutting labels ("vacation 2025", "reimbursable") on transactions and entries
+CREATE TABLE tags (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    name VARCHAR(100) NOT NULL,
+    description TEXT,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE UNIQUE INDEX idx_tags_name ON tags(LOWER(name));
+
+CREATE TABLE transaction_tags (
+    transaction_id UUID NOT NULL REFERENCES transactions(id) ON DELETE CASCADE,
+    tag_id UUID NOT NULL REFERENCES tags(id) ON DELETE CASCADE,
+    PRIMARY KEY (transaction_id, tag_id)
+);
+
+CREATE TABLE journal_entry_tags (
+    journal_entry_id UUID NOT NULL REFERENCES journal_entries(id) ON DELETE CASCADE,
+    tag_id UUID NOT NULL REFERENCES tags(id) ON DELETE CASCADE,
+    PRIMARY KEY (journal_entry_id, tag_id)
+);
+
+CREATE INDEX idx_transaction_tags_tag ON transaction_tags(tag_id);
+CREATE INDEX idx_journal_entry_tags_tag ON journal_entry_tags(tag_id);
+
+-- Tags of each journal entry, including those inherited from its transaction
+CREATE VIEW v_journal_entry_tags AS
+SELECT je.id AS journal_entry_id,
+    tg.id AS tag_id,
+    tg.name AS tag_name
+FROM journal_entries je
+    JOIN transaction_tags tt ON tt.transaction_id = je.transaction_id
+    JOIN tags tg ON tg.id = tt.tag_id
+UNION
+SELECT jet.journal_entry_id,
+    tg.id AS tag_id,
+    tg.name AS tag_name
+FROM journal_entry_tags jet
+    JOIN tags tg ON tg.id = jet.tag_id;
+
+-- Reports take an optional tag filter
+DROP FUNCTION IF EXISTS fn_income_statement(DATE, DATE);
+DROP FUNCTION IF EXISTS fn_account_ledger(UUID, DATE, DATE);
+
+CREATE FUNCTION fn_income_statement(
+    p_start_date DATE,
+    p_end_date DATE,
+    p_tag TEXT DEFAULT NULL
+)
+RETURNS TABLE (
+    category_name TEXT,
+    account_name TEXT,
+    account_path TEXT,
+    total_amount DECIMAL(19, 4)
+)
+AS $$
+BEGIN
+    RETURN QUERY
+    SELECT
+        COALESCE(parent_acc.name, 'Uncategorized')::TEXT AS category_name,
+        a.name::TEXT AS account_name,
+        COALESCE(a.full_path, a.name)::TEXT AS account_path,
+        COALESCE(
+            SUM(
+                CASE
+                    WHEN a.account_type = 'income' THEN (je.amount * -1.0)
+                    WHEN a.account_type = 'expense' THEN je.amount
+                    ELSE 0.0
+                END
+            ), 0.0
+        )::DECIMAL(19, 4) AS total_amount
+    FROM
+        accounts a
+    INNER JOIN
+        journal_entries je ON je.account_id = a.id
+    INNER JOIN
+        transactions t ON t.id = je.transaction_id
+    LEFT JOIN
+        accounts parent_acc ON a.parent_id = parent_acc.id
+    WHERE
+        a.account_type IN ('income', 'expense')
+        AND a.is_active = true
+        AND t.transaction_date >= p_start_date
+        AND t.transaction_date <= p_end_date
+        AND NOT t.is_voided
+        AND (
+            p_tag IS NULL
+            OR EXISTS (
+                SELECT 1 FROM v_journal_entry_tags jt
+                WHERE jt.journal_entry_id = je.id
+                  AND LOWER(jt.tag_name) = LOWER(p_tag)
+            )
+        )
+    GROUP BY
+        parent_acc.name, a.id, a.name, a.account_type, a.full_path
+    HAVING
+        ABS(COALESCE(
+            SUM(
+                CASE
+                    WHEN a.account_type = 'income' THEN je.amount
+                    WHEN a.account_type = 'expense' THEN je.amount
+                    ELSE 0.0
+                END
+            ), 0.0
+        )) > 0.01;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE FUNCTION fn_account_ledger(
+    p_account_id UUID,
+    p_start_date DATE,
+    p_end_date DATE,
+    p_tag TEXT DEFAULT NULL
+)
+RETURNS TABLE (
+    transaction_date DATE,
+    transaction_id UUID,
+    description TEXT,
+    reference TEXT,
+    memo TEXT,
+    debit_amount DECIMAL(19, 4),
+    credit_amount DECIMAL(19, 4),
+    running_balance DECIMAL(19, 4)
+)
+AS $$
+DECLARE
+    opening_balance DECIMAL(19, 4) := 0.0;
+BEGIN
+    -- Calculate opening balance (all transactions before start date)
+    SELECT COALESCE(SUM(je.amount), 0.0) INTO opening_balance
+    FROM journal_entries je
+    INNER JOIN transactions t ON t.id = je.transaction_id
+    WHERE je.account_id = p_account_id
+      AND t.transaction_date < p_start_date
+      AND NOT t.is_voided
+      AND (
+          p_tag IS NULL
+          OR EXISTS (
+              SELECT 1 FROM v_journal_entry_tags jt
+              WHERE jt.journal_entry_id = je.id
+                AND LOWER(jt.tag_name) = LOWER(p_tag)
+          )
+      );
+
+    -- Return the ledger entries with running balance
+    RETURN QUERY
+    SELECT
+        t.transaction_date::DATE as transaction_date,
+        t.id as transaction_id,
+        t.description::TEXT as description,
+        COALESCE(t.reference, '')::TEXT as reference,
+        COALESCE(je.memo, '')::TEXT as memo,
+        CASE 
+            WHEN je.amount > 0 THEN je.amount
+            ELSE 0.0
+        END as debit_amount,
+        CASE 
+            WHEN je.amount < 0 THEN ABS(je.amount)
+            ELSE 0.0
+        END as credit_amount,
+        (opening_balance + SUM(je.amount) OVER (ORDER BY t.transaction_date, t.created_at, je.created_at))::DECIMAL(19, 4) as running_balance
+    FROM 
+        journal_entries je
+    INNER JOIN 
+        transactions t ON t.id = je.transaction_id
+    WHERE 
+        je.account_id = p_account_id
+        AND t.transaction_date >= p_start_date
+        AND t.transaction_date <= p_end_date
+        AND NOT t.is_voided
+        AND (
+            p_tag IS NULL
+            OR EXISTS (
+                SELECT 1 FROM v_journal_entry_tags jt
+                WHERE jt.journal_entry_id = je.id
+                  AND LOWER(jt.tag_name) = LOWER(p_tag)
+            )
+        )
+    ORDER BY 
+        t.transaction_date, t.created_at, je.created_at;
+END;
+$$ LANGUAGE plpgsql;
+
+-- Income statement split by tag; entries carry their own tags and their transaction's
+CREATE FUNCTION fn_income_statement_by_tag(
+    p_start_date DATE,
+    p_end_date DATE
+)
+RETURNS TABLE (
+    tag_name TEXT,
+    category_name TEXT,
+    account_name TEXT,
+    account_path TEXT,
+    total_amount DECIMAL(19, 4)
+)
+AS $$
+BEGIN
+    RETURN QUERY
+    SELECT
+        jt.tag_name::TEXT AS tag_name,
+        COALESCE(parent_acc.name, 'Uncategorized')::TEXT AS category_name,
+        a.name::TEXT AS account_name,
+        COALESCE(a.full_path, a.name)::TEXT AS account_path,
+        COALESCE(
+            SUM(
+                CASE
+                    WHEN a.account_type = 'income' THEN (je.amount * -1.0)
+                    WHEN a.account_type = 'expense' THEN je.amount
+                    ELSE 0.0
+                END
+            ), 0.0
+        )::DECIMAL(19, 4) AS total_amount
+    FROM
+        accounts a
+    INNER JOIN
+        journal_entries je ON je.account_id = a.id
+    INNER JOIN
+        transactions t ON t.id = je.transaction_id
+    LEFT JOIN
+        v_journal_entry_tags jt ON jt.journal_entry_id = je.id
+    LEFT JOIN
+        accounts parent_acc ON a.parent_id = parent_acc.id
+    WHERE
+        a.account_type IN ('income', 'expense')
+        AND a.is_active = true
+        AND t.transaction_date >= p_start_date
+        AND t.transaction_date <= p_end_date
+        AND NOT t.is_voided
+    GROUP BY
+        jt.tag_name, parent_acc.name, a.id, a.name, a.account_type, a.full_path
+    HAVING
+        ABS(SUM(je.amount)) > 0.01
+    ORDER BY
+        jt.tag_name NULLS LAST, a.full_path;
+END;
+$$ LANGUAGE plpgsql;
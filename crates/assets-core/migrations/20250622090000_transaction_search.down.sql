@@ -0,0 +1,3 @@
+DROP INDEX IF EXISTS idx_journal_entries_memo_trgm;
+DROP INDEX IF EXISTS idx_transactions_reference_trgm;
+DROP INDEX IF EXISTS idx_transactions_description_trgm;
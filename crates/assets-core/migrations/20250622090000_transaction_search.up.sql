@@ -0,0 +1,7 @@
+-- Trigram indexes backing fuzzy transaction search (pg_trgm is enabled in the setup migration)
+CREATE INDEX IF NOT EXISTS idx_transactions_description_trgm
+    ON transactions USING GIN (description gin_trgm_ops);
+CREATE INDEX IF NOT EXISTS idx_transactions_reference_trgm
+    ON transactions USING GIN (reference gin_trgm_ops);
+CREATE INDEX IF NOT EXISTS idx_journal_entries_memo_trgm
+    ON journal_entries USING GIN (memo gin_trgm_ops);
@@ -0,0 +1,12 @@
+DROP TRIGGER IF EXISTS trg_protect_reconciled_transactions ON transactions;
+DROP FUNCTION IF EXISTS fn_protect_reconciled_transactions();
+DROP TRIGGER IF EXISTS trg_protect_reconciled_entries ON journal_entries;
+DROP FUNCTION IF EXISTS fn_protect_reconciled_entries();
+
+DROP INDEX IF EXISTS idx_journal_entries_account_status;
+ALTER TABLE journal_entries
+    DROP CONSTRAINT IF EXISTS chk_journal_entries_reconciliation,
+    DROP COLUMN IF EXISTS reconciliation_id,
+    DROP COLUMN IF EXISTS status;
+
+DROP TABLE IF EXISTS reconciliations;
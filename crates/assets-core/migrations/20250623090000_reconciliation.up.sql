@@ -0,0 +1,66 @@
+-- Statement reconciliations: entries are ticked as cleared, then locked as reconciled
+CREATE TABLE reconciliations (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    account_id UUID NOT NULL REFERENCES accounts(id) ON DELETE CASCADE,
+    statement_date DATE NOT NULL,
+    statement_balance DECIMAL(19, 4) NOT NULL,
+    reconciled_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_reconciliations_account ON reconciliations(account_id, statement_date);
+
+ALTER TABLE journal_entries
+    ADD COLUMN status VARCHAR(12) NOT NULL DEFAULT 'uncleared'
+        CHECK (status IN ('uncleared', 'cleared', 'reconciled')),
+    ADD COLUMN reconciliation_id UUID REFERENCES reconciliations(id),
+    ADD CONSTRAINT chk_journal_entries_reconciliation
+        CHECK ((status = 'reconciled') = (reconciliation_id IS NOT NULL));
+
+CREATE INDEX idx_journal_entries_account_status ON journal_entries(account_id, status);
+
+-- Reconciled entries keep their amount, account and transaction; only their status may change
+CREATE OR REPLACE FUNCTION fn_protect_reconciled_entries()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF OLD.status <> 'reconciled' THEN
+        RETURN COALESCE(NEW, OLD);
+    END IF;
+
+    IF TG_OP = 'DELETE' THEN
+        RAISE EXCEPTION 'Journal entry % is reconciled and cannot be deleted', OLD.id;
+    END IF;
+
+    IF NEW.amount IS DISTINCT FROM OLD.amount
+        OR NEW.account_id IS DISTINCT FROM OLD.account_id
+        OR NEW.transaction_id IS DISTINCT FROM OLD.transaction_id THEN
+        RAISE EXCEPTION 'Journal entry % is reconciled and cannot be modified', OLD.id;
+    END IF;
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trg_protect_reconciled_entries
+    BEFORE UPDATE OR DELETE ON journal_entries
+    FOR EACH ROW EXECUTE FUNCTION fn_protect_reconciled_entries();
+
+-- Moving or voiding a transaction would change reconciled balances
+CREATE OR REPLACE FUNCTION fn_protect_reconciled_transactions()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF (NEW.transaction_date IS DISTINCT FROM OLD.transaction_date
+        OR NEW.is_voided IS DISTINCT FROM OLD.is_voided)
+        AND EXISTS (
+            SELECT 1 FROM journal_entries
+            WHERE transaction_id = OLD.id AND status = 'reconciled'
+        ) THEN
+        RAISE EXCEPTION 'Transaction % has reconciled entries and cannot be moved or voided', OLD.id;
+    END IF;
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trg_protect_reconciled_transactions
+    BEFORE UPDATE ON transactions
+    FOR EACH ROW EXECUTE FUNCTION fn_protect_reconciled_transactions();
@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS idx_balance_assertions_date;
+DROP TABLE IF EXISTS balance_assertions;
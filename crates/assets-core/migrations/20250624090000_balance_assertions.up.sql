@@ -0,0 +1,15 @@
+-- Known-good account balances checked against the ledger, like Beancount `balance` directives
+CREATE TABLE balance_assertions (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    account_id UUID NOT NULL REFERENCES accounts(id) ON DELETE CASCADE,
+    -- The balance holds at the end of this day, all its transactions included
+    assertion_date DATE NOT NULL,
+    expected_balance DECIMAL(19, 4) NOT NULL,
+    -- 'manual' or the import source that read it from a statement
+    source VARCHAR(100) NOT NULL DEFAULT 'manual',
+    import_batch_id UUID,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    UNIQUE (account_id, assertion_date)
+);
+
+CREATE INDEX idx_balance_assertions_date ON balance_assertions(assertion_date);
@@ -0,0 +1,12 @@
+-- Reusable categorization rules: descriptions containing the pattern go to the account
+CREATE TABLE categorization_rules (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    -- Matched case-insensitively as a substring of the transaction description
+    pattern VARCHAR(255) NOT NULL CHECK (LENGTH(TRIM(pattern)) > 0),
+    account_id UUID NOT NULL REFERENCES accounts(id) ON DELETE CASCADE,
+    -- Higher priority wins; among equal priorities the longest pattern wins
+    priority INTEGER NOT NULL DEFAULT 0,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE UNIQUE INDEX idx_categorization_rules_pattern ON categorization_rules(LOWER(pattern));
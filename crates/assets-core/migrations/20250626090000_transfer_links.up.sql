@@ -0,0 +1,13 @@
+-- Internal transfers: an outgoing and an incoming imported line linked as one movement.
+-- Their uncategorized legs are moved to a clearing account instead of deleting anything.
+CREATE TABLE transfer_links (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    outgoing_transaction_id UUID NOT NULL UNIQUE REFERENCES transactions(id),
+    incoming_transaction_id UUID NOT NULL UNIQUE REFERENCES transactions(id),
+    -- The legs that were moved from the uncategorized account
+    outgoing_entry_id UUID NOT NULL REFERENCES journal_entries(id),
+    incoming_entry_id UUID NOT NULL REFERENCES journal_entries(id),
+    amount DECIMAL(19, 4) NOT NULL CHECK (amount > 0),
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    CONSTRAINT chk_transfer_links_different CHECK (outgoing_transaction_id <> incoming_transaction_id)
+);
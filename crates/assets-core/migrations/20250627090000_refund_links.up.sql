@@ -0,0 +1,101 @@
+-- Refunds linked to the purchase they give money back for.
+-- A purchase may be refunded in several parts; each refund settles a single purchase.
+CREATE TABLE refund_links (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    refund_transaction_id UUID NOT NULL UNIQUE REFERENCES transactions(id) ON DELETE CASCADE,
+    purchase_transaction_id UUID NOT NULL REFERENCES transactions(id) ON DELETE CASCADE,
+    -- The spending legs (expense or uncategorized) on each side
+    refund_entry_id UUID NOT NULL REFERENCES journal_entries(id) ON DELETE CASCADE,
+    purchase_entry_id UUID NOT NULL REFERENCES journal_entries(id) ON DELETE CASCADE,
+    amount DECIMAL(19, 4) NOT NULL CHECK (amount > 0),
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    CONSTRAINT chk_refund_links_different CHECK (refund_transaction_id <> purchase_transaction_id)
+);
+
+CREATE INDEX idx_refund_links_purchase_entry ON refund_links(purchase_entry_id);
+
+-- The income statement can net linked refunds against the purchase's period:
+-- the refund leg is dropped from its own period and the purchase's account is
+-- credited the refunded amount on the purchase date instead.
+DROP FUNCTION IF EXISTS fn_income_statement(DATE, DATE, TEXT);
+
+CREATE FUNCTION fn_income_statement(
+    p_start_date DATE,
+    p_end_date DATE,
+    p_tag TEXT DEFAULT NULL,
+    p_net_refunds BOOLEAN DEFAULT false
+)
+RETURNS TABLE (
+    category_name TEXT,
+    account_name TEXT,
+    account_path TEXT,
+    total_amount DECIMAL(19, 4)
+)
+AS $$
+BEGIN
+    RETURN QUERY
+    WITH active_refunds AS (
+        SELECT rl.refund_entry_id, rl.purchase_entry_id, rl.amount
+        FROM refund_links rl
+        JOIN transactions rt ON rt.id = rl.refund_transaction_id
+        JOIN transactions pt ON pt.id = rl.purchase_transaction_id
+        WHERE p_net_refunds AND NOT rt.is_voided AND NOT pt.is_voided
+    ),
+    lines AS (
+        SELECT je.id AS entry_id, je.account_id, je.amount, t.transaction_date
+        FROM journal_entries je
+        JOIN transactions t ON t.id = je.transaction_id
+        WHERE NOT t.is_voided
+          AND je.id NOT IN (SELECT ar.refund_entry_id FROM active_refunds ar)
+        UNION ALL
+        SELECT pe.id, pe.account_id, -ar.amount, pt.transaction_date
+        FROM active_refunds ar
+        JOIN journal_entries pe ON pe.id = ar.purchase_entry_id
+        JOIN transactions pt ON pt.id = pe.transaction_id
+    )
+    SELECT
+        COALESCE(parent_acc.name, 'Uncategorized')::TEXT AS category_name,
+        a.name::TEXT AS account_name,
+        COALESCE(a.full_path, a.name)::TEXT AS account_path,
+        COALESCE(
+            SUM(
+                CASE
+                    WHEN a.account_type = 'income' THEN (l.amount * -1.0)
+                    WHEN a.account_type = 'expense' THEN l.amount
+                    ELSE 0.0
+                END
+            ), 0.0
+        )::DECIMAL(19, 4) AS total_amount
+    FROM
+        accounts a
+    INNER JOIN
+        lines l ON l.account_id = a.id
+    LEFT JOIN
+        accounts parent_acc ON a.parent_id = parent_acc.id
+    WHERE
+        a.account_type IN ('income', 'expense')
+        AND a.is_active = true
+        AND l.transaction_date >= p_start_date
+        AND l.transaction_date <= p_end_date
+        AND (
+            p_tag IS NULL
+            OR EXISTS (
+                SELECT 1 FROM v_journal_entry_tags jt
+                WHERE jt.journal_entry_id = l.entry_id
+                  AND LOWER(jt.tag_name) = LOWER(p_tag)
+            )
+        )
+    GROUP BY
+        parent_acc.name, a.id, a.name, a.account_type, a.full_path
+    HAVING
+        ABS(COALESCE(
+            SUM(
+                CASE
+                    WHEN a.account_type = 'income' THEN l.amount
+                    WHEN a.account_type = 'expense' THEN l.amount
+                    ELSE 0.0
+                END
+            ), 0.0
+        )) > 0.01;
+END;
+$$ LANGUAGE plpgsql;
@@ -0,0 +1,7 @@
+DROP TRIGGER IF EXISTS trg_protect_locked_transactions ON transactions;
+DROP FUNCTION IF EXISTS fn_protect_locked_transactions();
+DROP TRIGGER IF EXISTS trg_protect_locked_entries ON journal_entries;
+DROP FUNCTION IF EXISTS fn_protect_locked_entries();
+DROP FUNCTION IF EXISTS fn_lock_date();
+
+DROP TABLE IF EXISTS period_closes;
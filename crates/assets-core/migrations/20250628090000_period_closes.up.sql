@@ -0,0 +1,87 @@
+-- Closed periods: income and expense balances moved to retained earnings, then locked
+CREATE TABLE period_closes (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    period_end DATE NOT NULL UNIQUE,
+    -- NULL when there was nothing to close
+    closing_transaction_id UUID REFERENCES transactions(id),
+    -- Income minus expenses moved to retained earnings
+    net_income DECIMAL(19, 4) NOT NULL DEFAULT 0,
+    closed_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+-- Everything dated on or before the lock date is frozen; NULL when nothing is closed
+CREATE OR REPLACE FUNCTION fn_lock_date()
+RETURNS DATE AS $$
+    SELECT MAX(period_end) FROM period_closes;
+$$ LANGUAGE sql STABLE;
+
+-- Entries of locked transactions cannot be added, removed or changed; their status still can
+CREATE OR REPLACE FUNCTION fn_protect_locked_entries()
+RETURNS TRIGGER AS $$
+DECLARE
+    v_lock_date DATE := fn_lock_date();
+BEGIN
+    IF v_lock_date IS NULL THEN
+        RETURN COALESCE(NEW, OLD);
+    END IF;
+
+    IF TG_OP = 'UPDATE'
+        AND NEW.amount IS NOT DISTINCT FROM OLD.amount
+        AND NEW.account_id IS NOT DISTINCT FROM OLD.account_id
+        AND NEW.transaction_id IS NOT DISTINCT FROM OLD.transaction_id THEN
+        RETURN NEW;
+    END IF;
+
+    IF TG_OP IN ('UPDATE', 'DELETE') AND EXISTS (
+        SELECT 1 FROM transactions
+        WHERE id = OLD.transaction_id AND transaction_date::date <= v_lock_date
+    ) THEN
+        RAISE EXCEPTION 'Journal entry % is in the period closed through % and cannot be changed', OLD.id, v_lock_date;
+    END IF;
+
+    IF TG_OP IN ('INSERT', 'UPDATE') AND EXISTS (
+        SELECT 1 FROM transactions
+        WHERE id = NEW.transaction_id AND transaction_date::date <= v_lock_date
+    ) THEN
+        RAISE EXCEPTION 'Cannot add entries to a transaction in the period closed through %', v_lock_date;
+    END IF;
+
+    RETURN COALESCE(NEW, OLD);
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trg_protect_locked_entries
+    BEFORE INSERT OR UPDATE OR DELETE ON journal_entries
+    FOR EACH ROW EXECUTE FUNCTION fn_protect_locked_entries();
+
+-- Transactions cannot be created, moved, voided or deleted on either side of the lock
+CREATE OR REPLACE FUNCTION fn_protect_locked_transactions()
+RETURNS TRIGGER AS $$
+DECLARE
+    v_lock_date DATE := fn_lock_date();
+BEGIN
+    IF v_lock_date IS NULL THEN
+        RETURN COALESCE(NEW, OLD);
+    END IF;
+
+    IF TG_OP = 'UPDATE'
+        AND NEW.transaction_date IS NOT DISTINCT FROM OLD.transaction_date
+        AND NEW.is_voided IS NOT DISTINCT FROM OLD.is_voided THEN
+        RETURN NEW;
+    END IF;
+
+    IF TG_OP IN ('UPDATE', 'DELETE') AND OLD.transaction_date::date <= v_lock_date THEN
+        RAISE EXCEPTION 'Transaction % is in the period closed through % and cannot be changed', OLD.id, v_lock_date;
+    END IF;
+
+    IF TG_OP IN ('INSERT', 'UPDATE') AND NEW.transaction_date::date <= v_lock_date THEN
+        RAISE EXCEPTION 'Transaction date % is in the period closed through %', NEW.transaction_date::date, v_lock_date;
+    END IF;
+
+    RETURN COALESCE(NEW, OLD);
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trg_protect_locked_transactions
+    BEFORE INSERT OR UPDATE OR DELETE ON transactions
+    FOR EACH ROW EXECUTE FUNCTION fn_protect_locked_transactions();
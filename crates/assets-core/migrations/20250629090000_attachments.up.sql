@@ -0,0 +1,22 @@
+-- Receipts, invoices and statements attached to transactions or accounts.
+-- File content lives in a content-addressed store on disk, keyed by its SHA-256;
+-- each row links one stored file to one transaction or account.
+CREATE TABLE attachments (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    content_hash VARCHAR(64) NOT NULL, -- SHA-256 of the file content
+    file_name VARCHAR(255) NOT NULL, -- Original file name
+    mime_type VARCHAR(100) NOT NULL,
+    file_size BIGINT NOT NULL,
+    transaction_id UUID REFERENCES transactions(id) ON DELETE CASCADE,
+    account_id UUID REFERENCES accounts(id) ON DELETE CASCADE,
+    notes TEXT,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    CONSTRAINT chk_attachments_one_target CHECK (num_nonnulls(transaction_id, account_id) = 1)
+);
+
+-- Attaching the same file twice to the same target is a no-op
+CREATE UNIQUE INDEX idx_attachments_transaction ON attachments(transaction_id, content_hash)
+WHERE transaction_id IS NOT NULL;
+CREATE UNIQUE INDEX idx_attachments_account ON attachments(account_id, content_hash)
+WHERE account_id IS NOT NULL;
+CREATE INDEX idx_attachments_content_hash ON attachments(content_hash);
@@ -0,0 +1,7 @@
+DROP TRIGGER IF EXISTS trg_ledger_chain_no_truncate ON ledger_chain;
+DROP TRIGGER IF EXISTS trg_ledger_chain_append_only ON ledger_chain;
+DROP FUNCTION IF EXISTS fn_ledger_chain_append_only();
+DROP FUNCTION IF EXISTS fn_transaction_content_hash(UUID);
+DROP FUNCTION IF EXISTS fn_transaction_canonical(UUID);
+
+DROP TABLE IF EXISTS ledger_chain;
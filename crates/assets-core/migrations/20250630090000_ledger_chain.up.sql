@@ -0,0 +1,86 @@
+-- Tamper-evident hash chain over the journal.
+-- Whenever the services create, change or delete a transaction they append a link
+-- holding the SHA-256 of the transaction's canonical serialization and a hash over
+-- the previous link. A row edited outside the services no longer matches its latest
+-- link, and an edited or removed link breaks the chain from there on.
+CREATE TABLE ledger_chain (
+    seq BIGSERIAL PRIMARY KEY,
+    -- No foreign key: links outlive the transactions they describe
+    transaction_id UUID NOT NULL,
+    -- NULL once the transaction has been deleted
+    content_hash VARCHAR(64),
+    previous_hash VARCHAR(64) NOT NULL,
+    -- SHA-256 of 'previous_hash:transaction_id:content_hash' ('-' for a deletion)
+    chain_hash VARCHAR(64) NOT NULL,
+    sealed_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_ledger_chain_transaction ON ledger_chain(transaction_id, seq);
+
+-- What a transaction's hash covers: its date, texts, voided flag and entries.
+-- Workflow metadata (clearing status, duplicate flags, import provenance) is left out.
+CREATE OR REPLACE FUNCTION fn_transaction_canonical(p_transaction_id UUID)
+RETURNS TEXT AS $$
+    SELECT jsonb_build_object(
+        'id', t.id,
+        'date', to_char(t.transaction_date AT TIME ZONE 'UTC', 'YYYY-MM-DD"T"HH24:MI:SS.US"Z"'),
+        'description', t.description,
+        'reference', t.reference,
+        'voided', t.is_voided,
+        'entries', COALESCE((
+            SELECT jsonb_agg(
+                jsonb_build_object(
+                    'id', je.id,
+                    'account_id', je.account_id,
+                    'amount', je.amount,
+                    'memo', je.memo
+                )
+                ORDER BY je.id
+            )
+            FROM journal_entries je
+            WHERE je.transaction_id = t.id
+        ), '[]'::jsonb)
+    )::text
+    FROM transactions t
+    WHERE t.id = p_transaction_id;
+$$ LANGUAGE sql STABLE;
+
+-- NULL when the transaction does not exist
+CREATE OR REPLACE FUNCTION fn_transaction_content_hash(p_transaction_id UUID)
+RETURNS VARCHAR(64) AS $$
+    SELECT encode(sha256(convert_to(fn_transaction_canonical(p_transaction_id), 'UTF8')), 'hex');
+$$ LANGUAGE sql STABLE;
+
+-- The chain can only grow
+CREATE OR REPLACE FUNCTION fn_ledger_chain_append_only()
+RETURNS TRIGGER AS $$
+BEGIN
+    RAISE EXCEPTION 'ledger_chain is append-only (% is not allowed)', TG_OP;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trg_ledger_chain_append_only
+    BEFORE UPDATE OR DELETE ON ledger_chain
+    FOR EACH ROW EXECUTE FUNCTION fn_ledger_chain_append_only();
+
+CREATE TRIGGER trg_ledger_chain_no_truncate
+    BEFORE TRUNCATE ON ledger_chain
+    FOR EACH STATEMENT EXECUTE FUNCTION fn_ledger_chain_append_only();
+
+-- Seal the existing journal in creation order
+DO $$
+DECLARE
+    v_transaction RECORD;
+    v_previous VARCHAR(64) := repeat('0', 64);
+    v_content VARCHAR(64);
+    v_chain VARCHAR(64);
+BEGIN
+    FOR v_transaction IN SELECT id FROM transactions ORDER BY created_at, id LOOP
+        v_content := fn_transaction_content_hash(v_transaction.id);
+        v_chain := encode(sha256(convert_to(
+            v_previous || ':' || v_transaction.id::text || ':' || v_content, 'UTF8')), 'hex');
+        INSERT INTO ledger_chain (transaction_id, content_hash, previous_hash, chain_hash)
+        VALUES (v_transaction.id, v_content, v_previous, v_chain);
+        v_previous := v_chain;
+    END LOOP;
+END $$;
@@ -0,0 +1,353 @@
+DROP FUNCTION IF EXISTS balance_sheet_data(DATE, TIMESTAMP WITH TIME ZONE);
+DROP FUNCTION IF EXISTS fn_income_statement(DATE, DATE, TEXT, BOOLEAN, TIMESTAMP WITH TIME ZONE);
+DROP FUNCTION IF EXISTS fn_income_statement_by_tag(DATE, DATE, TIMESTAMP WITH TIME ZONE);
+DROP FUNCTION IF EXISTS fn_account_ledger(UUID, DATE, DATE, TEXT, TIMESTAMP WITH TIME ZONE);
+DROP FUNCTION IF EXISTS fn_cash_flow_statement(DATE, DATE, TIMESTAMP WITH TIME ZONE);
+
+DROP FUNCTION IF EXISTS fn_journal_entries_as_of(TIMESTAMP WITH TIME ZONE);
+DROP FUNCTION IF EXISTS fn_transactions_as_of(TIMESTAMP WITH TIME ZONE);
+DROP INDEX IF EXISTS idx_audit_log_record;
+
+CREATE FUNCTION balance_sheet_data(report_date DATE) RETURNS TABLE (
+        account_type TEXT,
+        name TEXT,
+        balance DECIMAL(20, 2),
+        full_path TEXT,
+        level INTEGER
+    ) AS $$ BEGIN RETURN QUERY
+SELECT a.account_type::TEXT,
+    a.name::TEXT,
+    COALESCE(SUM(je.amount), 0) as balance,
+    a.full_path::TEXT,
+    -- Calculate level from the number of colons in full_path
+    (
+        LENGTH(a.full_path) - LENGTH(REPLACE(a.full_path, ':', ''))
+    ) as level
+FROM accounts a
+    LEFT JOIN journal_entries je ON a.id = je.account_id
+    LEFT JOIN transactions t ON je.transaction_id = t.id
+WHERE a.account_type IN ('asset', 'liability', 'equity')
+    AND a.is_active = true
+    AND (
+        t.transaction_date IS NULL
+        OR t.transaction_date <= report_date
+    )
+    AND (
+        t.id IS NULL
+        OR NOT t.is_voided
+    )
+GROUP BY a.id,
+    a.name,
+    a.account_type,
+    a.full_path
+HAVING COALESCE(SUM(je.amount), 0) != 0
+ORDER BY a.account_type,
+    a.full_path;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE FUNCTION fn_income_statement(
+    p_start_date DATE,
+    p_end_date DATE,
+    p_tag TEXT DEFAULT NULL,
+    p_net_refunds BOOLEAN DEFAULT false
+)
+RETURNS TABLE (
+    category_name TEXT,
+    account_name TEXT,
+    account_path TEXT,
+    total_amount DECIMAL(19, 4)
+)
+AS $$
+BEGIN
+    RETURN QUERY
+    WITH active_refunds AS (
+        SELECT rl.refund_entry_id, rl.purchase_entry_id, rl.amount
+        FROM refund_links rl
+        JOIN transactions rt ON rt.id = rl.refund_transaction_id
+        JOIN transactions pt ON pt.id = rl.purchase_transaction_id
+        WHERE p_net_refunds AND NOT rt.is_voided AND NOT pt.is_voided
+    ),
+    lines AS (
+        SELECT je.id AS entry_id, je.account_id, je.amount, t.transaction_date
+        FROM journal_entries je
+        JOIN transactions t ON t.id = je.transaction_id
+        WHERE NOT t.is_voided
+          AND je.id NOT IN (SELECT ar.refund_entry_id FROM active_refunds ar)
+        UNION ALL
+        SELECT pe.id, pe.account_id, -ar.amount, pt.transaction_date
+        FROM active_refunds ar
+        JOIN journal_entries pe ON pe.id = ar.purchase_entry_id
+        JOIN transactions pt ON pt.id = pe.transaction_id
+    )
+    SELECT
+        COALESCE(parent_acc.name, 'Uncategorized')::TEXT AS category_name,
+        a.name::TEXT AS account_name,
+        COALESCE(a.full_path, a.name)::TEXT AS account_path,
+        COALESCE(
+            SUM(
+                CASE
+                    WHEN a.account_type = 'income' THEN (l.amount * -1.0)
+                    WHEN a.account_type = 'expense' THEN l.amount
+                    ELSE 0.0
+                END
+            ), 0.0
+        )::DECIMAL(19, 4) AS total_amount
+    FROM
+        accounts a
+    INNER JOIN
+        lines l ON l.account_id = a.id
+    LEFT JOIN
+        accounts parent_acc ON a.parent_id = parent_acc.id
+    WHERE
+        a.account_type IN ('income', 'expense')
+        AND a.is_active = true
+        AND l.transaction_date >= p_start_date
+        AND l.transaction_date <= p_end_date
+        AND (
+            p_tag IS NULL
+            OR EXISTS (
+                SELECT 1 FROM v_journal_entry_tags jt
+                WHERE jt.journal_entry_id = l.entry_id
+                  AND LOWER(jt.tag_name) = LOWER(p_tag)
+            )
+        )
+    GROUP BY
+        parent_acc.name, a.id, a.name, a.account_type, a.full_path
+    HAVING
+        ABS(COALESCE(
+            SUM(
+                CASE
+                    WHEN a.account_type = 'income' THEN l.amount
+                    WHEN a.account_type = 'expense' THEN l.amount
+                    ELSE 0.0
+                END
+            ), 0.0
+        )) > 0.01;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE FUNCTION fn_income_statement_by_tag(
+    p_start_date DATE,
+    p_end_date DATE
+)
+RETURNS TABLE (
+    tag_name TEXT,
+    category_name TEXT,
+    account_name TEXT,
+    account_path TEXT,
+    total_amount DECIMAL(19, 4)
+)
+AS $$
+BEGIN
+    RETURN QUERY
+    SELECT
+        jt.tag_name::TEXT AS tag_name,
+        COALESCE(parent_acc.name, 'Uncategorized')::TEXT AS category_name,
+        a.name::TEXT AS account_name,
+        COALESCE(a.full_path, a.name)::TEXT AS account_path,
+        COALESCE(
+            SUM(
+                CASE
+                    WHEN a.account_type = 'income' THEN (je.amount * -1.0)
+                    WHEN a.account_type = 'expense' THEN je.amount
+                    ELSE 0.0
+                END
+            ), 0.0
+        )::DECIMAL(19, 4) AS total_amount
+    FROM
+        accounts a
+    INNER JOIN
+        journal_entries je ON je.account_id = a.id
+    INNER JOIN
+        transactions t ON t.id = je.transaction_id
+    LEFT JOIN
+        v_journal_entry_tags jt ON jt.journal_entry_id = je.id
+    LEFT JOIN
+        accounts parent_acc ON a.parent_id = parent_acc.id
+    WHERE
+        a.account_type IN ('income', 'expense')
+        AND a.is_active = true
+        AND t.transaction_date >= p_start_date
+        AND t.transaction_date <= p_end_date
+        AND NOT t.is_voided
+    GROUP BY
+        jt.tag_name, parent_acc.name, a.id, a.name, a.account_type, a.full_path
+    HAVING
+        ABS(SUM(je.amount)) > 0.01
+    ORDER BY
+        jt.tag_name NULLS LAST, a.full_path;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE FUNCTION fn_account_ledger(
+    p_account_id UUID,
+    p_start_date DATE,
+    p_end_date DATE,
+    p_tag TEXT DEFAULT NULL
+)
+RETURNS TABLE (
+    transaction_date DATE,
+    transaction_id UUID,
+    description TEXT,
+    reference TEXT,
+    memo TEXT,
+    debit_amount DECIMAL(19, 4),
+    credit_amount DECIMAL(19, 4),
+    running_balance DECIMAL(19, 4)
+)
+AS $$
+DECLARE
+    opening_balance DECIMAL(19, 4) := 0.0;
+BEGIN
+    -- Calculate opening balance (all transactions before start date)
+    SELECT COALESCE(SUM(je.amount), 0.0) INTO opening_balance
+    FROM journal_entries je
+    INNER JOIN transactions t ON t.id = je.transaction_id
+    WHERE je.account_id = p_account_id
+      AND t.transaction_date < p_start_date
+      AND NOT t.is_voided
+      AND (
+          p_tag IS NULL
+          OR EXISTS (
+              SELECT 1 FROM v_journal_entry_tags jt
+              WHERE jt.journal_entry_id = je.id
+                AND LOWER(jt.tag_name) = LOWER(p_tag)
+          )
+      );
+
+    -- Return the ledger entries with running balance
+    RETURN QUERY
+    SELECT
+        t.transaction_date::DATE as transaction_date,
+        t.id as transaction_id,
+        t.description::TEXT as description,
+        COALESCE(t.reference, '')::TEXT as reference,
+        COALESCE(je.memo, '')::TEXT as memo,
+        CASE 
+            WHEN je.amount > 0 THEN je.amount
+            ELSE 0.0
+        END as debit_amount,
+        CASE 
+            WHEN je.amount < 0 THEN ABS(je.amount)
+            ELSE 0.0
+        END as credit_amount,
+        (opening_balance + SUM(je.amount) OVER (ORDER BY t.transaction_date, t.created_at, je.created_at))::DECIMAL(19, 4) as running_balance
+    FROM 
+        journal_entries je
+    INNER JOIN 
+        transactions t ON t.id = je.transaction_id
+    WHERE 
+        je.account_id = p_account_id
+        AND t.transaction_date >= p_start_date
+        AND t.transaction_date <= p_end_date
+        AND NOT t.is_voided
+        AND (
+            p_tag IS NULL
+            OR EXISTS (
+                SELECT 1 FROM v_journal_entry_tags jt
+                WHERE jt.journal_entry_id = je.id
+                  AND LOWER(jt.tag_name) = LOWER(p_tag)
+            )
+        )
+    ORDER BY 
+        t.transaction_date, t.created_at, je.created_at;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE FUNCTION fn_cash_flow_statement(
+    p_start_date DATE,
+    p_end_date DATE
+)
+RETURNS TABLE (
+    activity_type TEXT,
+    category_name TEXT,
+    account_name TEXT,
+    account_path TEXT,
+    cash_flow DECIMAL(19, 4)
+)
+AS $$
+BEGIN
+    RETURN QUERY
+    SELECT
+        CASE
+            -- Operating Activities: Daily life cash flows (income and routine expenses)
+            WHEN a.account_type = 'income' THEN 'Operating'
+            WHEN a.account_type = 'expense' AND a.account_subtype IN (
+                'food', 'housing', 'transportation', 'communication', 'utilities', 
+                'healthcare', 'personal', 'entertainment', 'fees', 'taxes'
+            ) THEN 'Operating'
+            
+            -- Investing Activities: Investment and savings-related cash flows
+            WHEN a.account_type = 'asset' AND a.account_subtype IN (
+                'stocks', 'etf', 'bonds', 'mutual_fund', 'crypto', 'investment_account'
+            ) THEN 'Investing'
+            WHEN a.account_type = 'asset' AND a.account_subtype = 'savings' THEN 'Investing'
+            WHEN a.account_type = 'expense' AND a.account_subtype = 'investment' THEN 'Investing'
+            
+            -- Financing Activities: Debt and equity-related cash flows
+            WHEN a.account_type = 'liability' THEN 'Financing'
+            WHEN a.account_type = 'equity' THEN 'Financing'
+            WHEN a.account_type = 'asset' AND a.account_subtype IN ('loan', 'mortgage') THEN 'Financing'
+            
+            -- Default to Operating for uncategorized items
+            ELSE 'Operating'
+        END::TEXT AS activity_type,
+        
+        COALESCE(parent_acc.name, 'Uncategorized')::TEXT AS category_name,
+        a.name::TEXT AS account_name,
+        COALESCE(a.full_path, a.name)::TEXT AS account_path,
+        
+        -- Calculate cash flow (positive = cash inflow, negative = cash outflow)
+        COALESCE(
+            SUM(
+                CASE
+                    -- For income accounts: positive amounts are cash inflows
+                    WHEN a.account_type = 'income' THEN (je.amount * -1.0)
+                    -- For asset accounts: positive amounts are cash outflows (money leaving to buy assets)
+                    WHEN a.account_type = 'asset' THEN (je.amount * -1.0)
+                    -- For expense accounts: positive amounts are cash outflows  
+                    WHEN a.account_type = 'expense' THEN (je.amount * -1.0)
+                    -- For liability accounts: positive amounts are cash inflows (borrowing)
+                    WHEN a.account_type = 'liability' THEN je.amount
+                    -- For equity accounts: positive amounts are cash inflows
+                    WHEN a.account_type = 'equity' THEN je.amount
+                    ELSE 0.0
+                END
+            ), 0.0
+        )::DECIMAL(19, 4) AS cash_flow
+    FROM
+        accounts a
+    INNER JOIN
+        journal_entries je ON je.account_id = a.id
+    INNER JOIN
+        transactions t ON t.id = je.transaction_id
+    LEFT JOIN
+        accounts parent_acc ON a.parent_id = parent_acc.id
+    WHERE
+        a.is_active = true
+        AND t.transaction_date >= p_start_date
+        AND t.transaction_date <= p_end_date
+        AND NOT t.is_voided
+        -- Exclude opening balance transactions to focus on actual cash flows
+        AND COALESCE(t.reference, '') != 'OPENING'
+    GROUP BY
+        a.account_type, a.account_subtype, parent_acc.name, a.id, a.name, a.full_path
+    HAVING
+        -- Only include accounts with meaningful cash flows
+        ABS(COALESCE(
+            SUM(
+                CASE
+                    WHEN a.account_type = 'income' THEN (je.amount * -1.0)
+                    WHEN a.account_type = 'asset' THEN (je.amount * -1.0)
+                    WHEN a.account_type = 'expense' THEN (je.amount * -1.0)
+                    WHEN a.account_type = 'liability' THEN je.amount
+                    WHEN a.account_type = 'equity' THEN je.amount
+                    ELSE 0.0
+                END
+            ), 0.0
+        )) > 0.01
+    ORDER BY
+        activity_type, category_name, a.name;
+END;
+$$ LANGUAGE plpgsql;
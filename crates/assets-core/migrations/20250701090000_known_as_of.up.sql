@@ -0,0 +1,448 @@
+-- Bitemporal reporting: every report can be run "as recorded on" a past moment.
+-- A row counts as known at that moment when it was created by then; rows changed or
+-- deleted since are rolled back to the image the audit log kept before their first
+-- later change. Accounts, tags and refund links are read as they are now.
+CREATE INDEX idx_audit_log_record ON audit_log(record_id, changed_at);
+
+-- Each branch is gated on p_known_as_of alone, so once the functions are inlined the
+-- gates are evaluated once: current reports (p_known_as_of NULL) keep index access on
+-- the plain tables, and the audit log is only read for "as recorded on" reports.
+-- Images logged before a column was added lack it; those columns take their default.
+
+-- Transactions as they were recorded at p_known_as_of; all current ones when NULL
+CREATE FUNCTION fn_transactions_as_of(p_known_as_of TIMESTAMP WITH TIME ZONE)
+RETURNS SETOF transactions
+AS $$
+    SELECT t.*
+    FROM transactions t
+    WHERE p_known_as_of IS NULL
+    UNION ALL
+    -- Unchanged since p_known_as_of
+    SELECT t.*
+    FROM transactions t
+    WHERE p_known_as_of IS NOT NULL
+      AND t.created_at <= p_known_as_of
+      AND NOT EXISTS (
+          SELECT 1 FROM audit_log al
+          WHERE al.record_id = t.id
+            AND al.table_name = 'transactions'
+            AND al.changed_at > p_known_as_of
+      )
+    UNION ALL
+    -- Changed since: the image before the first later change, or the inserted one
+    -- for rows restored with their original created_at
+    SELECT
+        r.id, r.description, r.reference, r.transaction_date, r.import_source,
+        r.import_batch_id, r.external_reference, r.is_duplicate,
+        r.merged_into_transaction_id, r.created_at,
+        COALESCE(r.is_voided, FALSE), r.voided_at, r.reversal_of_transaction_id
+    FROM (
+        SELECT DISTINCT ON (al.record_id)
+            CASE WHEN al.operation = 'INSERT' THEN al.new_data ELSE al.old_data END AS image
+        FROM audit_log al
+        WHERE p_known_as_of IS NOT NULL
+          AND al.table_name = 'transactions'
+          AND al.changed_at > p_known_as_of
+        ORDER BY al.record_id, al.id
+    ) first_change
+    CROSS JOIN LATERAL jsonb_populate_record(NULL::transactions, first_change.image) r
+    WHERE r.created_at <= p_known_as_of;
+$$ LANGUAGE sql STABLE;
+
+-- Journal entries as they were recorded at p_known_as_of; all current ones when NULL
+CREATE FUNCTION fn_journal_entries_as_of(p_known_as_of TIMESTAMP WITH TIME ZONE)
+RETURNS SETOF journal_entries
+AS $$
+    SELECT je.*
+    FROM journal_entries je
+    WHERE p_known_as_of IS NULL
+    UNION ALL
+    SELECT je.*
+    FROM journal_entries je
+    WHERE p_known_as_of IS NOT NULL
+      AND je.created_at <= p_known_as_of
+      AND NOT EXISTS (
+          SELECT 1 FROM audit_log al
+          WHERE al.record_id = je.id
+            AND al.table_name = 'journal_entries'
+            AND al.changed_at > p_known_as_of
+      )
+    UNION ALL
+    SELECT
+        r.id, r.transaction_id, r.account_id, r.amount, r.memo, r.created_at,
+        COALESCE(r.status, 'uncleared'), r.reconciliation_id
+    FROM (
+        SELECT DISTINCT ON (al.record_id)
+            CASE WHEN al.operation = 'INSERT' THEN al.new_data ELSE al.old_data END AS image
+        FROM audit_log al
+        WHERE p_known_as_of IS NOT NULL
+          AND al.table_name = 'journal_entries'
+          AND al.changed_at > p_known_as_of
+        ORDER BY al.record_id, al.id
+    ) first_change
+    CROSS JOIN LATERAL jsonb_populate_record(NULL::journal_entries, first_change.image) r
+    WHERE r.created_at <= p_known_as_of;
+$$ LANGUAGE sql STABLE;
+
+-- Reports take an optional "known as of" moment and read through the functions above
+DROP FUNCTION IF EXISTS balance_sheet_data(DATE);
+DROP FUNCTION IF EXISTS fn_income_statement(DATE, DATE, TEXT, BOOLEAN);
+DROP FUNCTION IF EXISTS fn_income_statement_by_tag(DATE, DATE);
+DROP FUNCTION IF EXISTS fn_account_ledger(UUID, DATE, DATE, TEXT);
+DROP FUNCTION IF EXISTS fn_cash_flow_statement(DATE, DATE);
+
+CREATE FUNCTION balance_sheet_data(
+        report_date DATE,
+        p_known_as_of TIMESTAMP WITH TIME ZONE DEFAULT NULL
+    ) RETURNS TABLE (
+        account_type TEXT,
+        name TEXT,
+        balance DECIMAL(20, 2),
+        full_path TEXT,
+        level INTEGER
+    ) AS $$ BEGIN RETURN QUERY
+SELECT a.account_type::TEXT,
+    a.name::TEXT,
+    COALESCE(SUM(je.amount), 0) as balance,
+    a.full_path::TEXT,
+    -- Calculate level from the number of colons in full_path
+    (
+        LENGTH(a.full_path) - LENGTH(REPLACE(a.full_path, ':', ''))
+    ) as level
+FROM accounts a
+    LEFT JOIN fn_journal_entries_as_of(p_known_as_of) je ON a.id = je.account_id
+    LEFT JOIN fn_transactions_as_of(p_known_as_of) t ON je.transaction_id = t.id
+WHERE a.account_type IN ('asset', 'liability', 'equity')
+    AND a.is_active = true
+    AND (
+        t.transaction_date IS NULL
+        OR t.transaction_date <= report_date
+    )
+    AND (
+        t.id IS NULL
+        OR NOT t.is_voided
+    )
+GROUP BY a.id,
+    a.name,
+    a.account_type,
+    a.full_path
+HAVING COALESCE(SUM(je.amount), 0) != 0
+ORDER BY a.account_type,
+    a.full_path;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE FUNCTION fn_income_statement(
+    p_start_date DATE,
+    p_end_date DATE,
+    p_tag TEXT DEFAULT NULL,
+    p_net_refunds BOOLEAN DEFAULT false,
+    p_known_as_of TIMESTAMP WITH TIME ZONE DEFAULT NULL
+)
+RETURNS TABLE (
+    category_name TEXT,
+    account_name TEXT,
+    account_path TEXT,
+    total_amount DECIMAL(19, 4)
+)
+AS $$
+BEGIN
+    RETURN QUERY
+    WITH active_refunds AS (
+        SELECT rl.refund_entry_id, rl.purchase_entry_id, rl.amount
+        FROM refund_links rl
+        JOIN fn_transactions_as_of(p_known_as_of) rt ON rt.id = rl.refund_transaction_id
+        JOIN fn_transactions_as_of(p_known_as_of) pt ON pt.id = rl.purchase_transaction_id
+        WHERE p_net_refunds AND NOT rt.is_voided AND NOT pt.is_voided
+    ),
+    lines AS (
+        SELECT je.id AS entry_id, je.account_id, je.amount, t.transaction_date
+        FROM fn_journal_entries_as_of(p_known_as_of) je
+        JOIN fn_transactions_as_of(p_known_as_of) t ON t.id = je.transaction_id
+        WHERE NOT t.is_voided
+          -- Period closes only move balances to retained earnings
+          AND t.id NOT IN (SELECT pc.closing_transaction_id FROM period_closes pc WHERE pc.closing_transaction_id IS NOT NULL)
+          AND je.id NOT IN (SELECT ar.refund_entry_id FROM active_refunds ar)
+        UNION ALL
+        SELECT pe.id, pe.account_id, -ar.amount, pt.transaction_date
+        FROM active_refunds ar
+        JOIN fn_journal_entries_as_of(p_known_as_of) pe ON pe.id = ar.purchase_entry_id
+        JOIN fn_transactions_as_of(p_known_as_of) pt ON pt.id = pe.transaction_id
+    )
+    SELECT
+        COALESCE(parent_acc.name, 'Uncategorized')::TEXT AS category_name,
+        a.name::TEXT AS account_name,
+        COALESCE(a.full_path, a.name)::TEXT AS account_path,
+        COALESCE(
+            SUM(
+                CASE
+                    WHEN a.account_type = 'income' THEN (l.amount * -1.0)
+                    WHEN a.account_type = 'expense' THEN l.amount
+                    ELSE 0.0
+                END
+            ), 0.0
+        )::DECIMAL(19, 4) AS total_amount
+    FROM
+        accounts a
+    INNER JOIN
+        lines l ON l.account_id = a.id
+    LEFT JOIN
+        accounts parent_acc ON a.parent_id = parent_acc.id
+    WHERE
+        a.account_type IN ('income', 'expense')
+        AND a.is_active = true
+        AND l.transaction_date >= p_start_date
+        AND l.transaction_date <= p_end_date
+        AND (
+            p_tag IS NULL
+            OR EXISTS (
+                SELECT 1 FROM v_journal_entry_tags jt
+                WHERE jt.journal_entry_id = l.entry_id
+                  AND LOWER(jt.tag_name) = LOWER(p_tag)
+            )
+        )
+    GROUP BY
+        parent_acc.name, a.id, a.name, a.account_type, a.full_path
+    HAVING
+        ABS(COALESCE(
+            SUM(
+                CASE
+                    WHEN a.account_type = 'income' THEN l.amount
+                    WHEN a.account_type = 'expense' THEN l.amount
+                    ELSE 0.0
+                END
+            ), 0.0
+        )) > 0.01;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE FUNCTION fn_income_statement_by_tag(
+    p_start_date DATE,
+    p_end_date DATE,
+    p_known_as_of TIMESTAMP WITH TIME ZONE DEFAULT NULL
+)
+RETURNS TABLE (
+    tag_name TEXT,
+    category_name TEXT,
+    account_name TEXT,
+    account_path TEXT,
+    total_amount DECIMAL(19, 4)
+)
+AS $$
+BEGIN
+    RETURN QUERY
+    SELECT
+        jt.tag_name::TEXT AS tag_name,
+        COALESCE(parent_acc.name, 'Uncategorized')::TEXT AS category_name,
+        a.name::TEXT AS account_name,
+        COALESCE(a.full_path, a.name)::TEXT AS account_path,
+        COALESCE(
+            SUM(
+                CASE
+                    WHEN a.account_type = 'income' THEN (je.amount * -1.0)
+                    WHEN a.account_type = 'expense' THEN je.amount
+                    ELSE 0.0
+                END
+            ), 0.0
+        )::DECIMAL(19, 4) AS total_amount
+    FROM
+        accounts a
+    INNER JOIN
+        fn_journal_entries_as_of(p_known_as_of) je ON je.account_id = a.id
+    INNER JOIN
+        fn_transactions_as_of(p_known_as_of) t ON t.id = je.transaction_id
+    LEFT JOIN
+        v_journal_entry_tags jt ON jt.journal_entry_id = je.id
+    LEFT JOIN
+        accounts parent_acc ON a.parent_id = parent_acc.id
+    WHERE
+        a.account_type IN ('income', 'expense')
+        AND a.is_active = true
+        AND t.transaction_date >= p_start_date
+        AND t.transaction_date <= p_end_date
+        AND NOT t.is_voided
+        AND t.id NOT IN (SELECT pc.closing_transaction_id FROM period_closes pc WHERE pc.closing_transaction_id IS NOT NULL)
+    GROUP BY
+        jt.tag_name, parent_acc.name, a.id, a.name, a.account_type, a.full_path
+    HAVING
+        ABS(SUM(je.amount)) > 0.01
+    ORDER BY
+        jt.tag_name NULLS LAST, a.full_path;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE FUNCTION fn_account_ledger(
+    p_account_id UUID,
+    p_start_date DATE,
+    p_end_date DATE,
+    p_tag TEXT DEFAULT NULL,
+    p_known_as_of TIMESTAMP WITH TIME ZONE DEFAULT NULL
+)
+RETURNS TABLE (
+    transaction_date DATE,
+    transaction_id UUID,
+    description TEXT,
+    reference TEXT,
+    memo TEXT,
+    debit_amount DECIMAL(19, 4),
+    credit_amount DECIMAL(19, 4),
+    running_balance DECIMAL(19, 4)
+)
+AS $$
+DECLARE
+    opening_balance DECIMAL(19, 4) := 0.0;
+BEGIN
+    -- Calculate opening balance (all transactions before start date)
+    SELECT COALESCE(SUM(je.amount), 0.0) INTO opening_balance
+    FROM fn_journal_entries_as_of(p_known_as_of) je
+    INNER JOIN fn_transactions_as_of(p_known_as_of) t ON t.id = je.transaction_id
+    WHERE je.account_id = p_account_id
+      AND t.transaction_date < p_start_date
+      AND NOT t.is_voided
+      AND (
+          p_tag IS NULL
+          OR EXISTS (
+              SELECT 1 FROM v_journal_entry_tags jt
+              WHERE jt.journal_entry_id = je.id
+                AND LOWER(jt.tag_name) = LOWER(p_tag)
+          )
+      );
+
+    -- Return the ledger entries with running balance
+    RETURN QUERY
+    SELECT
+        t.transaction_date::DATE as transaction_date,
+        t.id as transaction_id,
+        t.description::TEXT as description,
+        COALESCE(t.reference, '')::TEXT as reference,
+        COALESCE(je.memo, '')::TEXT as memo,
+        CASE 
+            WHEN je.amount > 0 THEN je.amount
+            ELSE 0.0
+        END as debit_amount,
+        CASE 
+            WHEN je.amount < 0 THEN ABS(je.amount)
+            ELSE 0.0
+        END as credit_amount,
+        (opening_balance + SUM(je.amount) OVER (ORDER BY t.transaction_date, t.created_at, je.created_at))::DECIMAL(19, 4) as running_balance
+    FROM 
+        fn_journal_entries_as_of(p_known_as_of) je
+    INNER JOIN 
+        fn_transactions_as_of(p_known_as_of) t ON t.id = je.transaction_id
+    WHERE 
+        je.account_id = p_account_id
+        AND t.transaction_date >= p_start_date
+        AND t.transaction_date <= p_end_date
+        AND NOT t.is_voided
+        AND (
+            p_tag IS NULL
+            OR EXISTS (
+                SELECT 1 FROM v_journal_entry_tags jt
+                WHERE jt.journal_entry_id = je.id
+                  AND LOWER(jt.tag_name) = LOWER(p_tag)
+            )
+        )
+    ORDER BY 
+        t.transaction_date, t.created_at, je.created_at;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE FUNCTION fn_cash_flow_statement(
+    p_start_date DATE,
+    p_end_date DATE,
+    p_known_as_of TIMESTAMP WITH TIME ZONE DEFAULT NULL
+)
+RETURNS TABLE (
+    activity_type TEXT,
+    category_name TEXT,
+    account_name TEXT,
+    account_path TEXT,
+    cash_flow DECIMAL(19, 4)
+)
+AS $$
+BEGIN
+    RETURN QUERY
+    SELECT
+        CASE
+            -- Operating Activities: Daily life cash flows (income and routine expenses)
+            WHEN a.account_type = 'income' THEN 'Operating'
+            WHEN a.account_type = 'expense' AND a.account_subtype IN (
+                'food', 'housing', 'transportation', 'communication', 'utilities', 
+                'healthcare', 'personal', 'entertainment', 'fees', 'taxes'
+            ) THEN 'Operating'
+            
+            -- Investing Activities: Investment and savings-related cash flows
+            WHEN a.account_type = 'asset' AND a.account_subtype IN (
+                'stocks', 'etf', 'bonds', 'mutual_fund', 'crypto', 'investment_account'
+            ) THEN 'Investing'
+            WHEN a.account_type = 'asset' AND a.account_subtype = 'savings' THEN 'Investing'
+            WHEN a.account_type = 'expense' AND a.account_subtype = 'investment' THEN 'Investing'
+            
+            -- Financing Activities: Debt and equity-related cash flows
+            WHEN a.account_type = 'liability' THEN 'Financing'
+            WHEN a.account_type = 'equity' THEN 'Financing'
+            WHEN a.account_type = 'asset' AND a.account_subtype IN ('loan', 'mortgage') THEN 'Financing'
+            
+            -- Default to Operating for uncategorized items
+            ELSE 'Operating'
+        END::TEXT AS activity_type,
+        
+        COALESCE(parent_acc.name, 'Uncategorized')::TEXT AS category_name,
+        a.name::TEXT AS account_name,
+        COALESCE(a.full_path, a.name)::TEXT AS account_path,
+        
+        -- Calculate cash flow (positive = cash inflow, negative = cash outflow)
+        COALESCE(
+            SUM(
+                CASE
+                    -- For income accounts: positive amounts are cash inflows
+                    WHEN a.account_type = 'income' THEN (je.amount * -1.0)
+                    -- For asset accounts: positive amounts are cash outflows (money leaving to buy assets)
+                    WHEN a.account_type = 'asset' THEN (je.amount * -1.0)
+                    -- For expense accounts: positive amounts are cash outflows  
+                    WHEN a.account_type = 'expense' THEN (je.amount * -1.0)
+                    -- For liability accounts: positive amounts are cash inflows (borrowing)
+                    WHEN a.account_type = 'liability' THEN je.amount
+                    -- For equity accounts: positive amounts are cash inflows
+                    WHEN a.account_type = 'equity' THEN je.amount
+                    ELSE 0.0
+                END
+            ), 0.0
+        )::DECIMAL(19, 4) AS cash_flow
+    FROM
+        accounts a
+    INNER JOIN
+        fn_journal_entries_as_of(p_known_as_of) je ON je.account_id = a.id
+    INNER JOIN
+        fn_transactions_as_of(p_known_as_of) t ON t.id = je.transaction_id
+    LEFT JOIN
+        accounts parent_acc ON a.parent_id = parent_acc.id
+    WHERE
+        a.is_active = true
+        AND t.transaction_date >= p_start_date
+        AND t.transaction_date <= p_end_date
+        AND NOT t.is_voided
+        -- Exclude opening balance transactions to focus on actual cash flows
+        AND COALESCE(t.reference, '') != 'OPENING'
+        -- Period closes are bookkeeping, not cash
+        AND t.id NOT IN (SELECT pc.closing_transaction_id FROM period_closes pc WHERE pc.closing_transaction_id IS NOT NULL)
+    GROUP BY
+        a.account_type, a.account_subtype, parent_acc.name, a.id, a.name, a.full_path
+    HAVING
+        -- Only include accounts with meaningful cash flows
+        ABS(COALESCE(
+            SUM(
+                CASE
+                    WHEN a.account_type = 'income' THEN (je.amount * -1.0)
+                    WHEN a.account_type = 'asset' THEN (je.amount * -1.0)
+                    WHEN a.account_type = 'expense' THEN (je.amount * -1.0)
+                    WHEN a.account_type = 'liability' THEN je.amount
+                    WHEN a.account_type = 'equity' THEN je.amount
+                    ELSE 0.0
+                END
+            ), 0.0
+        )) > 0.01
+    ORDER BY
+        activity_type, category_name, a.name;
+END;
+$$ LANGUAGE plpgsql;
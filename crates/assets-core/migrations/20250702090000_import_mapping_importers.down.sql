@@ -0,0 +1,9 @@
+DELETE FROM import_mappings WHERE importer IN ('spreadsheet', 'boursobank_pdf', 'sg_pdf');
+
+ALTER TABLE import_mappings DROP CONSTRAINT IF EXISTS chk_import_mapping_spreadsheet_columns;
+ALTER TABLE import_mappings DROP CONSTRAINT IF EXISTS chk_import_mapping_importer;
+ALTER TABLE import_mappings ADD CONSTRAINT chk_import_mapping_importer CHECK (
+    importer IN ('boursobank', 'sg', 'qt_payslip', 'mathworks_payslip')
+);
+
+ALTER TABLE import_mappings DROP COLUMN IF EXISTS spreadsheet_columns;
@@ -0,0 +1,15 @@
+-- Let inbox mappings route files to the spreadsheet and PDF statement importers
+ALTER TABLE import_mappings
+    ADD COLUMN spreadsheet_columns JSONB; -- Column mapping for the spreadsheet importer
+
+ALTER TABLE import_mappings DROP CONSTRAINT chk_import_mapping_importer;
+ALTER TABLE import_mappings ADD CONSTRAINT chk_import_mapping_importer CHECK (
+    importer IN (
+        'boursobank', 'sg', 'qt_payslip', 'mathworks_payslip',
+        'spreadsheet', 'boursobank_pdf', 'sg_pdf'
+    )
+);
+
+ALTER TABLE import_mappings ADD CONSTRAINT chk_import_mapping_spreadsheet_columns CHECK (
+    importer <> 'spreadsheet' OR spreadsheet_columns IS NOT NULL
+);
@@ -8,9 +8,25 @@ pub struct Database {
 
 impl Database {
     /// Create a new database connection from a connection string
+    ///
+    /// Every connection is tagged with the current actor so that the audit log
+    /// records who made each change.
     pub async fn new(database_url: &str) -> Result<Self> {
+        let actor = current_actor();
         let pool = PgPoolOptions::new()
             .max_connections(10)
+            .after_connect(move |conn, _meta| {
+                let actor = actor.clone();
+                Box::pin(async move {
+                    if let Some(actor) = actor {
+                        sqlx::query("SELECT set_config('rusty_assets.actor', $1, false)")
+                            .bind(actor)
+                            .execute(conn)
+                            .await?;
+                    }
+                    Ok(())
+                })
+            })
             .connect(database_url)
             .await?;
 
@@ -35,5 +51,13 @@ impl Database {
     }
 }
 
+/// Who is making changes: `RUSTY_ASSETS_ACTOR`, else the OS user
+fn current_actor() -> Option<String> {
+    std::env::var("RUSTY_ASSETS_ACTOR")
+        .or_else(|_| std::env::var("USER"))
+        .ok()
+        .filter(|actor| !actor.is_empty())
+}
+
 #[cfg(test)]
 mod tests;
@@ -24,6 +24,9 @@ pub enum CoreError {
     #[error("Account validation failed: {0}")]
     AccountValidation(#[from] crate::validation::ValidationError),
 
+    #[error("Transaction validation failed: {0}")]
+    TransactionValidation(crate::validation::ValidationError),
+
     #[error("Not found: {0}")]
     NotFound(String),
 
@@ -1,7 +1,7 @@
 use super::traits::{ImportedTransaction, TransactionImporter};
 use crate::error::{CoreError, Result};
 use async_trait::async_trait;
-use chrono::NaiveDate;
+use chrono::{Local, NaiveDate};
 use csv::ReaderBuilder;
 use rust_decimal::Decimal;
 use std::collections::HashMap;
@@ -28,6 +28,7 @@ impl TransactionImporter for BoursoBankImporter {
             transactions.push(record.into_imported_transaction()?);
         }
 
+        attach_export_balance(&mut transactions, Local::now().date_naive());
         Ok(transactions)
     }
 
@@ -71,6 +72,36 @@ impl TransactionImporter for BoursoBankImporter {
     }
 }
 
+/// Record the `accountbalance` column as the statement closing balance
+///
+/// The column is the account balance at export time, repeated on every row, and the
+/// file does not say when it was exported. It is only a closing balance for the rows
+/// when the export runs up to `today`, in which case it is dated `today`.
+fn attach_export_balance(transactions: &mut [ImportedTransaction], today: NaiveDate) {
+    let Some(latest) = transactions.iter().map(|tx| tx.date_op).max() else {
+        return;
+    };
+    if latest != today {
+        return;
+    }
+
+    for tx in transactions {
+        let Some(raw) = tx.raw_data.get("account_balance") else {
+            continue;
+        };
+        let balance_str = raw
+            .trim_matches('"')
+            .replace(',', ".")
+            .replace([' ', '\u{a0}'], "");
+        if let Ok(balance) = Decimal::from_str(&balance_str) {
+            tx.raw_data
+                .insert("statement_closing_balance".to_string(), balance.to_string());
+            tx.raw_data
+                .insert("statement_closing_date".to_string(), today.to_string());
+        }
+    }
+}
+
 #[derive(Debug, serde::Deserialize)]
 struct BoursoBankCsvRecord {
     #[serde(rename = "dateOp")]
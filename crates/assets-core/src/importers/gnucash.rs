@@ -0,0 +1,428 @@
+use crate::error::{CoreError, Result};
+use crate::models::{AccountSubtype, AccountType};
+use chrono::{DateTime, NaiveDate, NaiveDateTime};
+use flate2::read::GzDecoder;
+use quick_xml::Reader;
+use quick_xml::events::Event;
+use rust_decimal::Decimal;
+use sqlx::sqlite::SqliteConnectOptions;
+use sqlx::{ConnectOptions, Row};
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read};
+use std::str::FromStr;
+
+const GZIP_MAGIC: &[u8] = &[0x1f, 0x8b];
+const SQLITE_MAGIC: &[u8] = b"SQLite format 3\0";
+
+/// A commodity reference as stored by GnuCash (e.g. `ISO4217:EUR` or `NASDAQ:AAPL`)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GnuCashCommodity {
+    pub namespace: String,
+    pub id: String,
+}
+
+impl GnuCashCommodity {
+    /// Currencies live in the ISO4217 namespace (older books use CURRENCY)
+    pub fn is_currency(&self) -> bool {
+        self.namespace == "ISO4217" || self.namespace == "CURRENCY"
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct GnuCashAccount {
+    pub guid: String,
+    pub name: String,
+    pub account_type: String, // GnuCash type: BANK, CASH, STOCK, EXPENSE, ROOT...
+    pub commodity: Option<GnuCashCommodity>,
+    pub parent_guid: Option<String>,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct GnuCashSplit {
+    pub guid: String,
+    pub account_guid: String,
+    pub memo: Option<String>,
+    pub value: Decimal,    // In the transaction currency
+    pub quantity: Decimal, // In the account commodity
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct GnuCashTransaction {
+    pub guid: String,
+    pub currency: Option<GnuCashCommodity>,
+    pub num: Option<String>,
+    pub date_posted: Option<NaiveDate>,
+    pub description: String,
+    pub splits: Vec<GnuCashSplit>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct GnuCashPrice {
+    pub commodity: GnuCashCommodity,
+    pub currency: GnuCashCommodity,
+    pub date: Option<NaiveDate>,
+    pub value: Decimal,
+    pub source: Option<String>,
+}
+
+/// Everything we migrate out of a GnuCash book
+#[derive(Debug, Clone, Default)]
+pub struct GnuCashBook {
+    pub accounts: Vec<GnuCashAccount>,
+    pub transactions: Vec<GnuCashTransaction>,
+    pub prices: Vec<GnuCashPrice>,
+}
+
+impl GnuCashBook {
+    pub fn find_account(&self, guid: &str) -> Option<&GnuCashAccount> {
+        self.accounts.iter().find(|a| a.guid == guid)
+    }
+}
+
+/// GnuCash book reader supporting the XML (plain or gzipped) and SQLite backends
+#[derive(Default)]
+pub struct GnuCashImporter {}
+
+impl GnuCashImporter {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    pub fn format_description(&self) -> &'static str {
+        "GnuCash book (gzipped XML, plain XML or SQLite)"
+    }
+
+    /// Check the file header for one of the supported GnuCash backends
+    pub fn can_handle_file(&self, file_path: &str) -> Result<bool> {
+        let mut header = [0u8; 16];
+        let mut file = std::fs::File::open(file_path)?;
+        let read = file.read(&mut header)?;
+        let header = &header[..read];
+
+        Ok(header.starts_with(GZIP_MAGIC)
+            || header.starts_with(SQLITE_MAGIC)
+            || header.starts_with(b"<?xml"))
+    }
+
+    /// Read the whole book from disk
+    pub async fn import_from_file(&self, file_path: &str) -> Result<GnuCashBook> {
+        let mut header = [0u8; 16];
+        let read = std::fs::File::open(file_path)?.read(&mut header)?;
+        let header = &header[..read];
+
+        if header.starts_with(SQLITE_MAGIC) {
+            read_sqlite_book(file_path).await
+        } else if header.starts_with(GZIP_MAGIC) {
+            let file = std::fs::File::open(file_path)?;
+            parse_xml_book(BufReader::new(GzDecoder::new(file)))
+        } else {
+            let file = std::fs::File::open(file_path)?;
+            parse_xml_book(BufReader::new(file))
+        }
+    }
+}
+
+/// Map a GnuCash account type to our account type and a sensible default subtype.
+/// Returns `None` for types we cannot represent (ROOT, TRADING).
+pub fn map_account_type(gnucash_type: &str) -> Option<(AccountType, AccountSubtype)> {
+    let mapped = match gnucash_type {
+        "BANK" => (AccountType::Asset, AccountSubtype::Checking),
+        "CASH" => (AccountType::Asset, AccountSubtype::Cash),
+        "ASSET" | "RECEIVABLE" => (AccountType::Asset, AccountSubtype::OtherAsset),
+        "STOCK" => (AccountType::Asset, AccountSubtype::Stocks),
+        "MUTUAL" => (AccountType::Asset, AccountSubtype::MutualFund),
+        "CURRENCY" => (AccountType::Asset, AccountSubtype::Cash),
+        "CREDIT" => (AccountType::Liability, AccountSubtype::CreditCard),
+        "LIABILITY" | "PAYABLE" => (AccountType::Liability, AccountSubtype::OtherLiability),
+        "EQUITY" => (AccountType::Equity, AccountSubtype::OwnerEquity),
+        "INCOME" => (AccountType::Income, AccountSubtype::OtherIncome),
+        "EXPENSE" => (AccountType::Expense, AccountSubtype::OtherExpense),
+        _ => return None,
+    };
+    Some(mapped)
+}
+
+/// Parse a GnuCash rational number ("12345/100")
+pub fn parse_gnucash_amount(value: &str) -> Result<Decimal> {
+    let (num, denom) = value.trim().split_once('/').unwrap_or((value.trim(), "1"));
+    let num = Decimal::from_str(num)
+        .map_err(|e| CoreError::ImportError(format!("Invalid amount '{}': {}", value, e)))?;
+    let denom = Decimal::from_str(denom)
+        .map_err(|e| CoreError::ImportError(format!("Invalid amount '{}': {}", value, e)))?;
+    if denom.is_zero() {
+        return Err(CoreError::ImportError(format!(
+            "Invalid amount '{}': zero denominator",
+            value
+        )));
+    }
+    Ok(num / denom)
+}
+
+/// Parse the timestamps used by both backends into the calendar date they were posted on:
+/// XML uses "2025-01-31 10:59:00 +0100", SQLite "2025-01-31 10:59:00" or "20250131105900"
+///
+/// XML timestamps carry the book's offset, and the date is read in that offset so that a
+/// posting just after local midnight keeps its day.
+pub fn parse_gnucash_timestamp(value: &str) -> Result<NaiveDate> {
+    let value = value.trim();
+    if let Ok(dt) = DateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S %z") {
+        return Ok(dt.naive_local().date());
+    }
+    NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S")
+        .or_else(|_| NaiveDateTime::parse_from_str(value, "%Y%m%d%H%M%S"))
+        .map(|dt| dt.date())
+        .map_err(|e| CoreError::ImportError(format!("Invalid date '{}': {}", value, e)))
+}
+
+/// Parse a GnuCash XML book from any reader
+pub fn parse_xml_book<R: BufRead>(reader: R) -> Result<GnuCashBook> {
+    let mut reader = Reader::from_reader(reader);
+    reader.config_mut().trim_text(true);
+
+    let mut book = GnuCashBook::default();
+    let mut stack: Vec<String> = Vec::new();
+    let mut buf = Vec::new();
+
+    let mut account: Option<GnuCashAccount> = None;
+    let mut transaction: Option<GnuCashTransaction> = None;
+    let mut split: Option<GnuCashSplit> = None;
+    let mut price: Option<GnuCashPrice> = None;
+
+    loop {
+        let event = reader
+            .read_event_into(&mut buf)
+            .map_err(|e| CoreError::ImportError(format!("Invalid GnuCash XML: {}", e)))?;
+
+        match event {
+            Event::Start(e) => {
+                let name = String::from_utf8_lossy(e.name().as_ref()).to_string();
+                match name.as_str() {
+                    "gnc:account" => account = Some(GnuCashAccount::default()),
+                    "gnc:transaction" => transaction = Some(GnuCashTransaction::default()),
+                    "trn:split" => split = Some(GnuCashSplit::default()),
+                    "price" => price = Some(GnuCashPrice::default()),
+                    _ => {}
+                }
+                stack.push(name);
+            }
+            Event::End(_) => {
+                let name = stack.pop().unwrap_or_default();
+                match name.as_str() {
+                    "gnc:account" => book.accounts.extend(account.take()),
+                    "gnc:transaction" => book.transactions.extend(transaction.take()),
+                    "trn:split" => {
+                        if let (Some(tx), Some(s)) = (transaction.as_mut(), split.take()) {
+                            tx.splits.push(s);
+                        }
+                    }
+                    "price" => book.prices.extend(price.take()),
+                    _ => {}
+                }
+            }
+            Event::Text(t) => {
+                // Slots hold arbitrary nested key/values we don't migrate
+                if stack.iter().any(|s| s.ends_with(":slots")) {
+                    continue;
+                }
+                let text = t
+                    .unescape()
+                    .map_err(|e| CoreError::ImportError(format!("Invalid GnuCash XML: {}", e)))?
+                    .to_string();
+                let current = stack.last().map(String::as_str).unwrap_or_default();
+                let parent = stack
+                    .len()
+                    .checked_sub(2)
+                    .map(|i| stack[i].as_str())
+                    .unwrap_or_default();
+
+                if let Some(s) = split.as_mut() {
+                    match current {
+                        "split:id" => s.guid = text,
+                        "split:account" => s.account_guid = text,
+                        "split:memo" => s.memo = Some(text),
+                        "split:value" => s.value = parse_gnucash_amount(&text)?,
+                        "split:quantity" => s.quantity = parse_gnucash_amount(&text)?,
+                        _ => {}
+                    }
+                } else if let Some(tx) = transaction.as_mut() {
+                    match (parent, current) {
+                        (_, "trn:id") => tx.guid = text,
+                        (_, "trn:num") => tx.num = Some(text),
+                        (_, "trn:description") => tx.description = text,
+                        ("trn:date-posted", "ts:date") => {
+                            tx.date_posted = Some(parse_gnucash_timestamp(&text)?)
+                        }
+                        ("trn:currency", "cmdty:space") => {
+                            tx.currency.get_or_insert_with(Default::default).namespace = text
+                        }
+                        ("trn:currency", "cmdty:id") => {
+                            tx.currency.get_or_insert_with(Default::default).id = text
+                        }
+                        _ => {}
+                    }
+                } else if let Some(acc) = account.as_mut() {
+                    match (parent, current) {
+                        (_, "act:id") => acc.guid = text,
+                        (_, "act:name") => acc.name = text,
+                        (_, "act:type") => acc.account_type = text,
+                        (_, "act:parent") => acc.parent_guid = Some(text),
+                        (_, "act:description") => acc.description = Some(text),
+                        ("act:commodity", "cmdty:space") => {
+                            acc.commodity.get_or_insert_with(Default::default).namespace = text
+                        }
+                        ("act:commodity", "cmdty:id") => {
+                            acc.commodity.get_or_insert_with(Default::default).id = text
+                        }
+                        _ => {}
+                    }
+                } else if let Some(p) = price.as_mut() {
+                    match (parent, current) {
+                        ("price:commodity", "cmdty:space") => p.commodity.namespace = text,
+                        ("price:commodity", "cmdty:id") => p.commodity.id = text,
+                        ("price:currency", "cmdty:space") => p.currency.namespace = text,
+                        ("price:currency", "cmdty:id") => p.currency.id = text,
+                        ("price:time", "ts:date") => p.date = Some(parse_gnucash_timestamp(&text)?),
+                        (_, "price:source") => p.source = Some(text),
+                        (_, "price:value") => p.value = parse_gnucash_amount(&text)?,
+                        _ => {}
+                    }
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    Ok(book)
+}
+
+/// Read a GnuCash book stored with the SQLite backend
+async fn read_sqlite_book(file_path: &str) -> Result<GnuCashBook> {
+    let mut conn = SqliteConnectOptions::new()
+        .filename(file_path)
+        .read_only(true)
+        .connect()
+        .await?;
+
+    let commodities: Vec<(String, GnuCashCommodity)> =
+        sqlx::query("SELECT guid, namespace, mnemonic FROM commodities")
+            .fetch_all(&mut conn)
+            .await?
+            .into_iter()
+            .map(|row| {
+                (
+                    row.get("guid"),
+                    GnuCashCommodity {
+                        namespace: row.get("namespace"),
+                        id: row.get("mnemonic"),
+                    },
+                )
+            })
+            .collect();
+    let commodity = |guid: Option<String>| -> Option<GnuCashCommodity> {
+        let guid = guid?;
+        commodities
+            .iter()
+            .find(|(g, _)| *g == guid)
+            .map(|(_, c)| c.clone())
+    };
+
+    let mut book = GnuCashBook::default();
+
+    for row in sqlx::query(
+        "SELECT guid, name, account_type, commodity_guid, parent_guid, description FROM accounts",
+    )
+    .fetch_all(&mut conn)
+    .await?
+    {
+        book.accounts.push(GnuCashAccount {
+            guid: row.get("guid"),
+            name: row.get("name"),
+            account_type: row.get("account_type"),
+            commodity: commodity(row.get("commodity_guid")),
+            parent_guid: row.get("parent_guid"),
+            description: row.get("description"),
+        });
+    }
+
+    for row in sqlx::query(
+        "SELECT guid, currency_guid, num, post_date, description FROM transactions ORDER BY post_date",
+    )
+    .fetch_all(&mut conn)
+    .await?
+    {
+        let post_date: Option<String> = row.get("post_date");
+        let num: Option<String> = row.get("num");
+        book.transactions.push(GnuCashTransaction {
+            guid: row.get("guid"),
+            currency: commodity(row.get("currency_guid")),
+            num: num.filter(|n| !n.is_empty()),
+            date_posted: post_date
+                .as_deref()
+                .map(parse_gnucash_timestamp)
+                .transpose()?,
+            description: row
+                .get::<Option<String>, _>("description")
+                .unwrap_or_default(),
+            splits: Vec::new(),
+        });
+    }
+    let tx_index: HashMap<String, usize> = book
+        .transactions
+        .iter()
+        .enumerate()
+        .map(|(i, tx)| (tx.guid.clone(), i))
+        .collect();
+
+    for row in sqlx::query(
+        "SELECT guid, tx_guid, account_guid, memo, value_num, value_denom, quantity_num, quantity_denom FROM splits",
+    )
+    .fetch_all(&mut conn)
+    .await?
+    {
+        let tx_guid: String = row.get("tx_guid");
+        let memo: Option<String> = row.get("memo");
+        let split = GnuCashSplit {
+            guid: row.get("guid"),
+            account_guid: row.get("account_guid"),
+            memo: memo.filter(|m| !m.is_empty()),
+            value: rational(row.get("value_num"), row.get("value_denom"))?,
+            quantity: rational(row.get("quantity_num"), row.get("quantity_denom"))?,
+        };
+        if let Some(&i) = tx_index.get(&tx_guid) {
+            book.transactions[i].splits.push(split);
+        }
+    }
+
+    for row in sqlx::query(
+        "SELECT commodity_guid, currency_guid, date, source, value_num, value_denom FROM prices",
+    )
+    .fetch_all(&mut conn)
+    .await?
+    {
+        let date: Option<String> = row.get("date");
+        book.prices.push(GnuCashPrice {
+            commodity: commodity(row.get("commodity_guid")).unwrap_or_default(),
+            currency: commodity(row.get("currency_guid")).unwrap_or_default(),
+            date: date.as_deref().map(parse_gnucash_timestamp).transpose()?,
+            value: rational(row.get("value_num"), row.get("value_denom"))?,
+            source: row.get("source"),
+        });
+    }
+
+    Ok(book)
+}
+
+fn rational(num: i64, denom: i64) -> Result<Decimal> {
+    if denom == 0 {
+        return Err(CoreError::ImportError(
+            "Invalid amount: zero denominator".to_string(),
+        ));
+    }
+    Ok(Decimal::from(num) / Decimal::from(denom))
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,130 @@
+use super::*;
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+
+const SAMPLE_BOOK: &str = r#"<?xml version="1.0" encoding="utf-8" ?>
+<gnc-v2>
+<gnc:book version="2.0.0">
+<gnc:pricedb version="1">
+  <price>
+    <price:commodity><cmdty:space>NASDAQ</cmdty:space><cmdty:id>AAPL</cmdty:id></price:commodity>
+    <price:currency><cmdty:space>ISO4217</cmdty:space><cmdty:id>EUR</cmdty:id></price:currency>
+    <price:time><ts:date>2025-03-14 10:59:00 +0000</ts:date></price:time>
+    <price:source>user:price</price:source>
+    <price:value>19525/100</price:value>
+  </price>
+</gnc:pricedb>
+<gnc:account version="2.0.0">
+  <act:name>Root Account</act:name>
+  <act:id type="guid">root</act:id>
+  <act:type>ROOT</act:type>
+</gnc:account>
+<gnc:account version="2.0.0">
+  <act:name>Checking &amp; Co</act:name>
+  <act:id type="guid">checking</act:id>
+  <act:type>BANK</act:type>
+  <act:commodity><cmdty:space>ISO4217</cmdty:space><cmdty:id>EUR</cmdty:id></act:commodity>
+  <act:slots><slot><slot:key>placeholder</slot:key><slot:value type="string">false</slot:value></slot></act:slots>
+  <act:parent type="guid">root</act:parent>
+</gnc:account>
+<gnc:transaction version="2.0.0">
+  <trn:id type="guid">tx1</trn:id>
+  <trn:currency><cmdty:space>ISO4217</cmdty:space><cmdty:id>EUR</cmdty:id></trn:currency>
+  <trn:num>42</trn:num>
+  <trn:date-posted><ts:date>2025-03-15 10:59:00 +0100</ts:date></trn:date-posted>
+  <trn:date-entered><ts:date>2025-04-01 08:00:00 +0000</ts:date></trn:date-entered>
+  <trn:description>Groceries</trn:description>
+  <trn:splits>
+    <trn:split>
+      <split:id type="guid">s1</split:id>
+      <split:memo>weekly</split:memo>
+      <split:value>-4550/100</split:value>
+      <split:quantity>-4550/100</split:quantity>
+      <split:account type="guid">checking</split:account>
+    </trn:split>
+    <trn:split>
+      <split:id type="guid">s2</split:id>
+      <split:value>4550/100</split:value>
+      <split:quantity>4550/100</split:quantity>
+      <split:account type="guid">groceries</split:account>
+    </trn:split>
+  </trn:splits>
+</gnc:transaction>
+</gnc:book>
+</gnc-v2>
+"#;
+
+#[test]
+fn test_parse_xml_book() {
+    let book = parse_xml_book(SAMPLE_BOOK.as_bytes()).unwrap();
+
+    assert_eq!(book.accounts.len(), 2);
+    let checking = book.find_account("checking").unwrap();
+    assert_eq!(checking.name, "Checking & Co");
+    assert_eq!(checking.account_type, "BANK");
+    assert_eq!(checking.parent_guid.as_deref(), Some("root"));
+    assert!(checking.commodity.as_ref().unwrap().is_currency());
+
+    assert_eq!(book.transactions.len(), 1);
+    let tx = &book.transactions[0];
+    assert_eq!(tx.description, "Groceries");
+    assert_eq!(tx.num.as_deref(), Some("42"));
+    assert_eq!(
+        tx.date_posted.unwrap(),
+        NaiveDate::from_ymd_opt(2025, 3, 15).unwrap()
+    );
+    assert_eq!(tx.splits.len(), 2);
+    assert_eq!(tx.splits[0].value, Decimal::new(-4550, 2));
+    assert_eq!(tx.splits[0].memo.as_deref(), Some("weekly"));
+    assert_eq!(tx.splits[1].account_guid, "groceries");
+
+    assert_eq!(book.prices.len(), 1);
+    let price = &book.prices[0];
+    assert_eq!(price.commodity.id, "AAPL");
+    assert_eq!(price.currency.id, "EUR");
+    assert_eq!(price.value, Decimal::new(19525, 2));
+    assert_eq!(price.date, NaiveDate::from_ymd_opt(2025, 3, 14));
+}
+
+#[test]
+fn test_parse_gnucash_amount() {
+    assert_eq!(
+        parse_gnucash_amount("12345/100").unwrap(),
+        Decimal::new(12345, 2)
+    );
+    assert_eq!(parse_gnucash_amount("-3/1").unwrap(), Decimal::from(-3));
+    assert_eq!(parse_gnucash_amount("7").unwrap(), Decimal::from(7));
+    assert!(parse_gnucash_amount("1/0").is_err());
+}
+
+#[test]
+fn test_parse_gnucash_timestamp() {
+    let expected = NaiveDate::from_ymd_opt(2025, 1, 31).unwrap();
+    for value in [
+        "2025-01-31 10:59:00 +0000",
+        "2025-01-31 00:30:00 +0100",
+        "2025-01-31 23:30:00 -0500",
+        "2025-01-31 10:59:00",
+        "20250131105900",
+    ] {
+        assert_eq!(parse_gnucash_timestamp(value).unwrap(), expected);
+    }
+}
+
+#[test]
+fn test_map_account_type() {
+    assert_eq!(
+        map_account_type("BANK"),
+        Some((AccountType::Asset, AccountSubtype::Checking))
+    );
+    assert_eq!(
+        map_account_type("CREDIT"),
+        Some((AccountType::Liability, AccountSubtype::CreditCard))
+    );
+    assert_eq!(
+        map_account_type("EXPENSE"),
+        Some((AccountType::Expense, AccountSubtype::OtherExpense))
+    );
+    assert_eq!(map_account_type("ROOT"), None);
+    assert_eq!(map_account_type("TRADING"), None);
+}
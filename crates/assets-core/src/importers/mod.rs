@@ -1,13 +1,19 @@
 pub mod boursobank;
+pub mod gnucash;
 pub mod mathworks_payslip;
 pub mod payslip_traits;
+pub mod pdf_statement;
 pub mod qt_payslip;
 pub mod societegenerale;
+pub mod spreadsheet;
 pub mod traits;
 
 pub use boursobank::BoursoBankImporter;
+pub use gnucash::{GnuCashBook, GnuCashImporter};
 pub use mathworks_payslip::MathWorksPayslipImporter;
 pub use payslip_traits::{ImportedPayslip, PayslipImporter};
+pub use pdf_statement::{PdfStatement, PdfStatementImporter, StatementBank, StatementLine};
 pub use qt_payslip::QtPayslipImporter;
 pub use societegenerale::SocietegeneraleImporter;
+pub use spreadsheet::{AmountSign, SpreadsheetColumnMapping, SpreadsheetImporter};
 pub use traits::{ImportedTransaction, TransactionImporter};
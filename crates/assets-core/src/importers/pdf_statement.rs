@@ -0,0 +1,398 @@
+use super::traits::{ImportedTransaction, TransactionImporter};
+use crate::error::{CoreError, Result};
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use log::{debug, info};
+use regex::Regex;
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::LazyLock;
+
+/// French amount as printed on statements: "1 234,56", "1.234,56", "-12,00"
+static AMOUNT_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"-?\d{1,3}(?:[ .\u{a0}\u{202f}]\d{3})*,\d{2}").unwrap());
+
+static DATE_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\d{2}/\d{2}/\d{4}").unwrap());
+
+/// 02/01/2025  CARTE 30/12/24 LECLERC  02/01/2025   45,50
+static BOURSOBANK_OPERATION_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"^\s*(?P<op>\d{2}/\d{2}/\d{4})\s+(?P<label>.+?)\s+(?P<val>\d{2}/\d{2}/\d{4})\s+(?P<amount>\S.*?)\s*$",
+    )
+    .unwrap()
+});
+
+/// 02/01/2025  02/01/2025  CARTE X1234 LECLERC   45,50
+static SOCIETE_GENERALE_OPERATION_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"^\s*(?P<op>\d{2}/\d{2}/\d{4})\s+(?P<val>\d{2}/\d{2}/\d{4})\s+(?P<label>.+?)\s+(?P<amount>-?\d[\d .\u{a0}\u{202f}]*,\d{2})\s*$",
+    )
+    .unwrap()
+});
+
+/// Banks whose monthly PDF statement layout is supported
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatementBank {
+    /// Columns: Date opération | Libellé | Valeur | Débit | Crédit
+    BoursoBank,
+    /// Columns: Date | Valeur | Nature de l'opération | Débit | Crédit
+    SocieteGenerale,
+}
+
+/// A single operation line read from the statement
+#[derive(Debug, Clone, PartialEq)]
+pub struct StatementLine {
+    pub date_op: NaiveDate,
+    pub date_val: NaiveDate,
+    pub label: String,
+    /// Positive for credits, negative for debits
+    pub amount: Decimal,
+}
+
+/// Everything extracted from one statement
+#[derive(Debug, Clone)]
+pub struct PdfStatement {
+    pub opening_balance: Decimal,
+    pub closing_balance: Decimal,
+    pub closing_date: Option<NaiveDate>,
+    pub lines: Vec<StatementLine>,
+}
+
+impl PdfStatement {
+    /// Sum of all operation lines
+    pub fn total(&self) -> Decimal {
+        self.lines.iter().map(|line| line.amount).sum()
+    }
+
+    /// Check that operations explain the move from opening to closing balance
+    pub fn verify(&self) -> Result<()> {
+        let expected = self.closing_balance - self.opening_balance;
+        let actual = self.total();
+        if expected != actual {
+            return Err(CoreError::ImportError(format!(
+                "Statement does not reconcile: balances move by {} ({} -> {}) but the {} extracted operations sum to {} (difference {})",
+                expected,
+                self.opening_balance,
+                self.closing_balance,
+                self.lines.len(),
+                actual,
+                expected - actual
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Monthly PDF bank statement importer
+pub struct PdfStatementImporter {
+    bank: StatementBank,
+}
+
+#[async_trait]
+impl TransactionImporter for PdfStatementImporter {
+    async fn import_from_file(&self, file_path: &str) -> Result<Vec<ImportedTransaction>> {
+        info!("📄 Importing PDF statement...");
+
+        let text = extract_text_from_pdf(file_path)?;
+        let statement = self.parse_statement(&text)?;
+        statement.verify()?;
+
+        info!(
+            "✅ {} operations, balance {} -> {}",
+            statement.lines.len(),
+            statement.opening_balance,
+            statement.closing_balance
+        );
+
+        Ok(self.to_imported_transactions(statement))
+    }
+
+    fn format_description(&self) -> &'static str {
+        match self.bank {
+            StatementBank::BoursoBank => "BoursoBank PDF statement",
+            StatementBank::SocieteGenerale => "Société Générale PDF statement",
+        }
+    }
+
+    fn can_handle_file(&self, file_path: &str) -> Result<bool> {
+        if !file_path.to_lowercase().ends_with(".pdf") {
+            return Ok(false);
+        }
+
+        let Ok(text) = extract_text_from_pdf(file_path) else {
+            return Ok(false);
+        };
+        let text = text.to_uppercase();
+        let is_bank = match self.bank {
+            StatementBank::BoursoBank => text.contains("BOURSOBANK") || text.contains("BOURSORAMA"),
+            StatementBank::SocieteGenerale => {
+                text.contains("SOCIÉTÉ GÉNÉRALE") || text.contains("SOCIETE GENERALE")
+            }
+        };
+
+        Ok(is_bank && text.contains("SOLDE"))
+    }
+}
+
+impl PdfStatementImporter {
+    pub fn new(bank: StatementBank) -> Self {
+        Self { bank }
+    }
+
+    pub fn boursobank() -> Self {
+        Self::new(StatementBank::BoursoBank)
+    }
+
+    pub fn societe_generale() -> Self {
+        Self::new(StatementBank::SocieteGenerale)
+    }
+
+    pub fn bank(&self) -> StatementBank {
+        self.bank
+    }
+
+    /// Parse the text extracted from a statement
+    ///
+    /// Debit and credit amounts are told apart by their horizontal position relative
+    /// to the "Débit"/"Crédit" column headers, so the text must keep its layout spacing.
+    pub fn parse_statement(&self, text: &str) -> Result<PdfStatement> {
+        let operation_regex = match self.bank {
+            StatementBank::BoursoBank => &*BOURSOBANK_OPERATION_REGEX,
+            StatementBank::SocieteGenerale => &*SOCIETE_GENERALE_OPERATION_REGEX,
+        };
+
+        let mut columns: Option<(usize, usize)> = None;
+        let mut opening_balance = None;
+        let mut closing_balance = None;
+        let mut closing_date = None;
+        let mut lines: Vec<StatementLine> = Vec::new();
+        // Labels wrapping on several lines continue until a blank line
+        let mut continuing = false;
+
+        for raw_line in text.lines() {
+            let line = raw_line.trim_end();
+            let upper = line.to_uppercase();
+
+            if line.trim().is_empty() {
+                continuing = false;
+                continue;
+            }
+
+            if let Some(found) = debit_credit_columns(line) {
+                columns = Some(found);
+                continuing = false;
+                continue;
+            }
+
+            if let Some(kind) = balance_kind(&upper) {
+                continuing = false;
+                let Some(found) = AMOUNT_REGEX.find_iter(line).last() else {
+                    continue;
+                };
+                let mut balance = parse_french_amount(found.as_str())?;
+                if is_debit_balance(&upper, line, found.end(), columns) {
+                    balance = -balance.abs();
+                }
+                match kind {
+                    BalanceKind::Opening if opening_balance.is_none() => {
+                        opening_balance = Some(balance)
+                    }
+                    BalanceKind::Closing => {
+                        closing_balance = Some(balance);
+                        closing_date = find_date(line);
+                    }
+                    BalanceKind::Opening => {}
+                }
+                continue;
+            }
+
+            if let Some(captures) = operation_regex.captures(line) {
+                let amount_text = captures.name("amount").unwrap().as_str();
+                let Some(found) = AMOUNT_REGEX.find_iter(amount_text).last() else {
+                    continuing = false;
+                    continue;
+                };
+                let amount_end = captures.name("amount").unwrap().start() + found.end();
+
+                let (debit_end, credit_end) = columns.ok_or_else(|| {
+                    CoreError::ImportError(
+                        "Operation found before the Débit/Crédit column headers".to_string(),
+                    )
+                })?;
+                let amount = parse_french_amount(found.as_str())?.abs();
+                let position = char_position(line, amount_end);
+                let is_credit = position.abs_diff(credit_end) < position.abs_diff(debit_end);
+
+                lines.push(StatementLine {
+                    date_op: parse_date(&captures["op"])?,
+                    date_val: parse_date(&captures["val"])?,
+                    label: collapse_spaces(&captures["label"]),
+                    amount: if is_credit { amount } else { -amount },
+                });
+                continuing = true;
+                continue;
+            }
+
+            if continuing && !is_noise(&upper) {
+                if let Some(last) = lines.last_mut() {
+                    last.label = format!("{} {}", last.label, collapse_spaces(line));
+                }
+            } else {
+                continuing = false;
+            }
+        }
+
+        let statement = PdfStatement {
+            opening_balance: opening_balance.ok_or_else(|| {
+                CoreError::ImportError("Could not find the opening balance".to_string())
+            })?,
+            closing_balance: closing_balance.ok_or_else(|| {
+                CoreError::ImportError("Could not find the closing balance".to_string())
+            })?,
+            closing_date,
+            lines,
+        };
+
+        debug!("Parsed statement: {:#?}", statement);
+        Ok(statement)
+    }
+
+    fn to_imported_transactions(&self, statement: PdfStatement) -> Vec<ImportedTransaction> {
+        let account_label = self.format_description().to_string();
+        statement
+            .lines
+            .into_iter()
+            .map(|line| {
+                let mut raw_data = HashMap::new();
+                raw_data.insert(
+                    "statement_opening_balance".to_string(),
+                    statement.opening_balance.to_string(),
+                );
+                raw_data.insert(
+                    "statement_closing_balance".to_string(),
+                    statement.closing_balance.to_string(),
+                );
+                if let Some(date) = statement.closing_date {
+                    raw_data.insert("statement_closing_date".to_string(), date.to_string());
+                }
+
+                ImportedTransaction {
+                    date_op: line.date_op,
+                    date_val: line.date_val,
+                    description: line.label,
+                    amount: line.amount,
+                    category: None,
+                    category_parent: None,
+                    supplier: None,
+                    account_number: String::new(),
+                    account_label: account_label.clone(),
+                    raw_data,
+                }
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum BalanceKind {
+    Opening,
+    Closing,
+}
+
+fn balance_kind(upper: &str) -> Option<BalanceKind> {
+    if upper.contains("NOUVEAU SOLDE") || upper.contains("SOLDE FINAL") {
+        Some(BalanceKind::Closing)
+    } else if upper.contains("SOLDE PRÉCÉDENT")
+        || upper.contains("SOLDE PRECEDENT")
+        || upper.contains("SOLDE AU")
+        || upper.contains("ANCIEN SOLDE")
+    {
+        Some(BalanceKind::Opening)
+    } else {
+        None
+    }
+}
+
+/// Balances are negative when flagged as such or printed in the debit column
+fn is_debit_balance(
+    upper: &str,
+    line: &str,
+    amount_end: usize,
+    columns: Option<(usize, usize)>,
+) -> bool {
+    if upper.contains("DÉBITEUR") || upper.contains("DEBITEUR") {
+        return true;
+    }
+    if upper.contains("CRÉDITEUR") || upper.contains("CREDITEUR") {
+        return false;
+    }
+    columns.is_some_and(|(debit_end, credit_end)| {
+        let position = char_position(line, amount_end);
+        position.abs_diff(debit_end) < position.abs_diff(credit_end)
+    })
+}
+
+/// End positions (in characters) of the "Débit" and "Crédit" headers
+fn debit_credit_columns(line: &str) -> Option<(usize, usize)> {
+    let upper = line.to_uppercase();
+    let find_end = |words: &[&str]| {
+        words
+            .iter()
+            .find_map(|word| upper.find(word).map(|start| start + word.len()))
+    };
+    let debit = find_end(&["DÉBIT", "DEBIT"])?;
+    let credit = find_end(&["CRÉDIT", "CREDIT"])?;
+
+    // A header line has nothing but column titles, never an amount
+    if AMOUNT_REGEX.is_match(line) {
+        return None;
+    }
+
+    Some((char_position(&upper, debit), char_position(&upper, credit)))
+}
+
+fn is_noise(upper: &str) -> bool {
+    upper.contains("TOTAL") || upper.contains("PAGE") || upper.contains("SOLDE")
+}
+
+fn char_position(line: &str, byte_index: usize) -> usize {
+    line[..byte_index].chars().count()
+}
+
+fn find_date(line: &str) -> Option<NaiveDate> {
+    DATE_REGEX
+        .find(line)
+        .and_then(|found| parse_date(found.as_str()).ok())
+}
+
+fn parse_date(text: &str) -> Result<NaiveDate> {
+    NaiveDate::parse_from_str(text, "%d/%m/%Y")
+        .map_err(|e| CoreError::ImportError(format!("Invalid date '{}': {}", text, e)))
+}
+
+fn collapse_spaces(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Parse French decimal format (handles spaces, dots and commas)
+pub fn parse_french_amount(text: &str) -> Result<Decimal> {
+    let cleaned: String = text
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != '.' && *c != '\u{202f}')
+        .map(|c| if c == ',' { '.' } else { c })
+        .collect();
+
+    Decimal::from_str(&cleaned)
+        .map_err(|_| CoreError::ImportError(format!("Failed to parse amount: {}", text)))
+}
+
+/// Extract text from PDF
+fn extract_text_from_pdf(file_path: &str) -> Result<String> {
+    let bytes = std::fs::read(file_path)?;
+    let out = pdf_extract::extract_text_from_mem(&bytes)?;
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,139 @@
+use super::*;
+
+/// Lay out a statement row the way pdf-extract renders columns
+fn row(left: &str, debit: &str, credit: &str) -> String {
+    format!("{:<70}{:>12}{:>14}", left, debit, credit)
+}
+
+fn boursobank_text() -> String {
+    [
+        "BoursoBank - Relevé de compte".to_string(),
+        String::new(),
+        row(
+            "Date opération  Libellé                              Valeur",
+            "Débit",
+            "Crédit",
+        ),
+        row("SOLDE AU : 31/12/2024", "", "1 000,00"),
+        row(
+            "02/01/2025  CARTE 30/12/24 LECLERC CB*1234    02/01/2025",
+            "45,50",
+            "",
+        ),
+        row(
+            "05/01/2025  VIR SEPA EMPLOYEUR                05/01/2025",
+            "",
+            "2 500,00",
+        ),
+        "             SALAIRE JANVIER".to_string(),
+        String::new(),
+        row(
+            "10/01/2025  PRLV SEPA EDF                     10/01/2025",
+            "1 054,50",
+            "",
+        ),
+        row("Nouveau solde en EUR :", "", "2 400,00"),
+        "Page 1/1".to_string(),
+    ]
+    .join("\n")
+}
+
+#[test]
+fn test_parse_boursobank_statement() {
+    let importer = PdfStatementImporter::boursobank();
+    let statement = importer.parse_statement(&boursobank_text()).unwrap();
+
+    assert_eq!(statement.opening_balance, Decimal::new(100000, 2));
+    assert_eq!(statement.closing_balance, Decimal::new(240000, 2));
+    assert_eq!(statement.lines.len(), 3);
+
+    assert_eq!(
+        statement.lines[0],
+        StatementLine {
+            date_op: NaiveDate::from_ymd_opt(2025, 1, 2).unwrap(),
+            date_val: NaiveDate::from_ymd_opt(2025, 1, 2).unwrap(),
+            label: "CARTE 30/12/24 LECLERC CB*1234".to_string(),
+            amount: Decimal::new(-4550, 2),
+        }
+    );
+    assert_eq!(statement.lines[1].amount, Decimal::new(250000, 2));
+    assert_eq!(
+        statement.lines[1].label,
+        "VIR SEPA EMPLOYEUR SALAIRE JANVIER"
+    );
+    assert_eq!(statement.lines[2].amount, Decimal::new(-105450, 2));
+
+    statement.verify().unwrap();
+}
+
+#[test]
+fn test_parse_societe_generale_statement() {
+    let text = [
+        "SOCIETE GENERALE".to_string(),
+        row(
+            "Date        Valeur      Nature de l'opération",
+            "Débit",
+            "Crédit",
+        ),
+        row("SOLDE PRÉCÉDENT AU 31/01/2025", "150,00", ""),
+        row(
+            "03/02/2025  03/02/2025  CARTE X1234 BOULANGERIE",
+            "4,20",
+            "",
+        ),
+        row("04/02/2025  04/02/2025  VIR RECU DE M DUPONT", "", "300,00"),
+        row("TOTAUX DES MOUVEMENTS", "4,20", "300,00"),
+        row("NOUVEAU SOLDE AU 28/02/2025", "", "145,80"),
+    ]
+    .join("\n");
+
+    let importer = PdfStatementImporter::societe_generale();
+    let statement = importer.parse_statement(&text).unwrap();
+
+    // The opening balance sits in the debit column: the account was overdrawn
+    assert_eq!(statement.opening_balance, Decimal::new(-15000, 2));
+    assert_eq!(statement.closing_balance, Decimal::new(14580, 2));
+    assert_eq!(statement.closing_date, NaiveDate::from_ymd_opt(2025, 2, 28));
+    assert_eq!(statement.lines.len(), 2);
+    assert_eq!(statement.lines[0].label, "CARTE X1234 BOULANGERIE");
+    assert_eq!(statement.lines[0].amount, Decimal::new(-420, 2));
+    assert_eq!(statement.lines[1].amount, Decimal::new(30000, 2));
+
+    statement.verify().unwrap();
+}
+
+#[test]
+fn test_verify_detects_missing_operation() {
+    let text = boursobank_text().replace(
+        &row(
+            "10/01/2025  PRLV SEPA EDF                     10/01/2025",
+            "1 054,50",
+            "",
+        ),
+        "",
+    );
+
+    let statement = PdfStatementImporter::boursobank()
+        .parse_statement(&text)
+        .unwrap();
+
+    let error = statement.verify().unwrap_err();
+    assert!(error.to_string().contains("difference -1054.50"));
+}
+
+#[test]
+fn test_parse_french_amount() {
+    assert_eq!(
+        parse_french_amount("1 234,56").unwrap(),
+        Decimal::new(123456, 2)
+    );
+    assert_eq!(
+        parse_french_amount("1.234,56").unwrap(),
+        Decimal::new(123456, 2)
+    );
+    assert_eq!(
+        parse_french_amount("-12,00").unwrap(),
+        Decimal::new(-1200, 2)
+    );
+    assert!(parse_french_amount("abc").is_err());
+}
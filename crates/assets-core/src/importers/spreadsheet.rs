@@ -0,0 +1,410 @@
+use super::traits::{ImportedTransaction, TransactionImporter};
+use crate::error::{CoreError, Result};
+use async_trait::async_trait;
+use bon::Builder;
+use calamine::{Data, ExcelDateTime, ExcelDateTimeType, Range, Reader, open_workbook_auto};
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use std::str::FromStr;
+
+const SPREADSHEET_EXTENSIONS: &[&str] = &["xlsx", "xlsm", "xlsb", "xls", "ods"];
+
+/// Date formats tried when a date column holds text instead of a date cell
+const TEXT_DATE_FORMATS: &[&str] = &["%d/%m/%Y", "%Y-%m-%d", "%d-%m-%Y", "%d.%m.%Y", "%d/%m/%y"];
+
+/// How the amount column relates to money entering the account
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AmountSign {
+    /// Positive amounts are money in, negative amounts money out (bank accounts)
+    #[default]
+    AsIs,
+    /// Positive amounts are money out (most credit card exports)
+    Inverted,
+}
+
+/// Which sheet, header row and columns hold the statement
+///
+/// Columns are referenced by their header text (case-insensitive). Either
+/// `amount_column` or at least one of `debit_column`/`credit_column` must be set.
+#[derive(Debug, Clone, Builder, Serialize, Deserialize)]
+pub struct SpreadsheetColumnMapping {
+    /// Sheet name, defaults to the first sheet
+    #[builder(into)]
+    pub sheet: Option<String>,
+    /// Row holding the column headers, 1-based as displayed by spreadsheet tools
+    #[builder(default = 1)]
+    #[serde(default = "default_header_row")]
+    pub header_row: usize,
+    #[builder(into)]
+    pub date_column: String,
+    #[builder(into)]
+    pub value_date_column: Option<String>,
+    #[builder(into)]
+    pub label_column: String,
+    #[builder(into)]
+    pub amount_column: Option<String>,
+    /// Money out, as a positive or negative number
+    #[builder(into)]
+    pub debit_column: Option<String>,
+    /// Money in, as a positive number
+    #[builder(into)]
+    pub credit_column: Option<String>,
+    #[builder(into)]
+    pub category_column: Option<String>,
+    #[builder(default)]
+    #[serde(default)]
+    pub sign: AmountSign,
+}
+
+fn default_header_row() -> usize {
+    1
+}
+
+/// Column positions resolved against the header row
+#[derive(Debug)]
+struct ResolvedColumns {
+    date: usize,
+    value_date: Option<usize>,
+    label: usize,
+    amount: Option<usize>,
+    debit: Option<usize>,
+    credit: Option<usize>,
+    category: Option<usize>,
+}
+
+pub struct SpreadsheetImporter {
+    mapping: SpreadsheetColumnMapping,
+}
+
+impl SpreadsheetImporter {
+    pub fn new(mapping: SpreadsheetColumnMapping) -> Self {
+        Self { mapping }
+    }
+
+    pub fn mapping(&self) -> &SpreadsheetColumnMapping {
+        &self.mapping
+    }
+
+    /// Load the configured sheet and its name
+    fn read_sheet(&self, file_path: &str) -> Result<(String, Range<Data>)> {
+        let mut workbook = open_workbook_auto(file_path)
+            .map_err(|e| CoreError::ImportError(format!("Failed to open spreadsheet: {}", e)))?;
+
+        let sheet_name =
+            match &self.mapping.sheet {
+                Some(name) => name.clone(),
+                None => workbook.sheet_names().first().cloned().ok_or_else(|| {
+                    CoreError::ImportError("Spreadsheet has no sheets".to_string())
+                })?,
+            };
+
+        let range = workbook.worksheet_range(&sheet_name).map_err(|e| {
+            CoreError::ImportError(format!("Failed to read sheet '{}': {}", sheet_name, e))
+        })?;
+
+        Ok((sheet_name, range))
+    }
+
+    /// Convert the rows below the header into imported transactions
+    pub fn parse_range(
+        &self,
+        sheet_name: &str,
+        range: &Range<Data>,
+    ) -> Result<Vec<ImportedTransaction>> {
+        if self.mapping.header_row == 0 {
+            return Err(CoreError::InvalidInput(
+                "Header row is 1-based, 0 is not a valid row".to_string(),
+            ));
+        }
+
+        let first_row = range.start().map(|(row, _)| row as usize).unwrap_or(0);
+        let header_index = (self.mapping.header_row - 1)
+            .checked_sub(first_row)
+            .ok_or_else(|| {
+                CoreError::ImportError(format!(
+                    "Header row {} is above the data of sheet '{}'",
+                    self.mapping.header_row, sheet_name
+                ))
+            })?;
+
+        let mut rows = range.rows().skip(header_index);
+        let headers: Vec<String> = rows
+            .next()
+            .ok_or_else(|| {
+                CoreError::ImportError(format!(
+                    "Sheet '{}' has no row {}",
+                    sheet_name, self.mapping.header_row
+                ))
+            })?
+            .iter()
+            .map(cell_to_string)
+            .collect();
+
+        let columns = self.resolve_columns(&headers)?;
+
+        let mut transactions = Vec::new();
+        for (offset, row) in rows.enumerate() {
+            let row_number = self.mapping.header_row + offset + 1;
+            if let Some(mut transaction) = self
+                .parse_row(row, &columns, &headers, sheet_name)
+                .map_err(|e| CoreError::ImportError(format!("Row {}: {}", row_number, e)))?
+            {
+                transaction
+                    .raw_data
+                    .insert("row".to_string(), row_number.to_string());
+                transactions.push(transaction);
+            }
+        }
+
+        Ok(transactions)
+    }
+
+    fn resolve_columns(&self, headers: &[String]) -> Result<ResolvedColumns> {
+        let find = |name: &str| -> Result<usize> {
+            headers
+                .iter()
+                .position(|header| header.trim().eq_ignore_ascii_case(name.trim()))
+                .ok_or_else(|| {
+                    CoreError::ImportError(format!(
+                        "Column '{}' not found in header row (found: {})",
+                        name,
+                        headers.join(", ")
+                    ))
+                })
+        };
+        let find_optional = |name: &Option<String>| name.as_deref().map(find).transpose();
+
+        let columns = ResolvedColumns {
+            date: find(&self.mapping.date_column)?,
+            value_date: find_optional(&self.mapping.value_date_column)?,
+            label: find(&self.mapping.label_column)?,
+            amount: find_optional(&self.mapping.amount_column)?,
+            debit: find_optional(&self.mapping.debit_column)?,
+            credit: find_optional(&self.mapping.credit_column)?,
+            category: find_optional(&self.mapping.category_column)?,
+        };
+
+        if columns.amount.is_none() && columns.debit.is_none() && columns.credit.is_none() {
+            return Err(CoreError::InvalidInput(
+                "An amount column or debit/credit columns are required".to_string(),
+            ));
+        }
+
+        Ok(columns)
+    }
+
+    fn parse_row(
+        &self,
+        row: &[Data],
+        columns: &ResolvedColumns,
+        headers: &[String],
+        sheet_name: &str,
+    ) -> Result<Option<ImportedTransaction>> {
+        let cell = |index: usize| row.get(index).unwrap_or(&Data::Empty);
+
+        // Blank separators and footers without a date are not transactions
+        let Some(date_op) = cell_to_date(cell(columns.date))? else {
+            return Ok(None);
+        };
+        let date_val = match columns.value_date {
+            Some(index) => cell_to_date(cell(index))?.unwrap_or(date_op),
+            None => date_op,
+        };
+
+        let amount = match columns.amount {
+            Some(index) => cell_to_decimal(cell(index))?,
+            None => {
+                let debit = columns
+                    .debit
+                    .map(|index| cell_to_decimal(cell(index)))
+                    .transpose()?
+                    .flatten();
+                let credit = columns
+                    .credit
+                    .map(|index| cell_to_decimal(cell(index)))
+                    .transpose()?
+                    .flatten();
+                match (debit, credit) {
+                    (None, None) => None,
+                    (debit, credit) => {
+                        Some(credit.unwrap_or_default().abs() - debit.unwrap_or_default().abs())
+                    }
+                }
+            }
+        };
+        let Some(amount) = amount else {
+            return Err(CoreError::ImportError(format!(
+                "Missing amount for operation dated {}",
+                date_op
+            )));
+        };
+        let amount = match self.mapping.sign {
+            AmountSign::AsIs => amount,
+            AmountSign::Inverted => -amount,
+        };
+
+        let description = cell_to_string(cell(columns.label)).trim().to_string();
+        let category = columns
+            .category
+            .map(|index| cell_to_string(cell(index)).trim().to_string())
+            .filter(|category| !category.is_empty());
+
+        let mut raw_data = HashMap::new();
+        raw_data.insert("sheet".to_string(), sheet_name.to_string());
+        for (index, header) in headers.iter().enumerate() {
+            if !header.is_empty() {
+                raw_data.insert(header.clone(), cell_to_string(cell(index)));
+            }
+        }
+
+        Ok(Some(ImportedTransaction {
+            date_op,
+            date_val,
+            description,
+            amount,
+            category,
+            category_parent: None,
+            supplier: None,
+            account_number: String::new(),
+            account_label: sheet_name.to_string(),
+            raw_data,
+        }))
+    }
+}
+
+#[async_trait]
+impl TransactionImporter for SpreadsheetImporter {
+    async fn import_from_file(&self, file_path: &str) -> Result<Vec<ImportedTransaction>> {
+        let (sheet_name, range) = self.read_sheet(file_path)?;
+        self.parse_range(&sheet_name, &range)
+    }
+
+    fn format_description(&self) -> &'static str {
+        "Spreadsheet statement (XLSX/XLS/ODS) with configurable sheet, header row and columns"
+    }
+
+    /// Check the extension, then that the configured columns exist in the header row
+    fn can_handle_file(&self, file_path: &str) -> Result<bool> {
+        let has_spreadsheet_extension = Path::new(file_path)
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| {
+                SPREADSHEET_EXTENSIONS
+                    .iter()
+                    .any(|known| known.eq_ignore_ascii_case(ext))
+            });
+        if !has_spreadsheet_extension {
+            return Ok(false);
+        }
+
+        let Ok((_, range)) = self.read_sheet(file_path) else {
+            return Ok(false);
+        };
+        let first_row = range.start().map(|(row, _)| row as usize).unwrap_or(0);
+        let headers: Option<Vec<String>> = (self.mapping.header_row.max(1) - 1)
+            .checked_sub(first_row)
+            .and_then(|index| range.rows().nth(index))
+            .map(|row| row.iter().map(cell_to_string).collect());
+
+        Ok(headers.is_some_and(|headers| self.resolve_columns(&headers).is_ok()))
+    }
+}
+
+/// Text content of a cell, numbers rendered as displayed
+pub fn cell_to_string(cell: &Data) -> String {
+    match cell {
+        Data::String(s) => s.clone(),
+        Data::Empty => String::new(),
+        other => other.to_string(),
+    }
+}
+
+/// Read a date from a real date cell, a raw Excel serial number or French-formatted text
+pub fn cell_to_date(cell: &Data) -> Result<Option<NaiveDate>> {
+    match cell {
+        Data::Empty => Ok(None),
+        Data::DateTime(datetime) => datetime
+            .as_datetime()
+            .map(|dt| Some(dt.date()))
+            .ok_or_else(|| CoreError::ImportError(format!("Invalid date cell {}", datetime))),
+        Data::DateTimeIso(iso) => {
+            NaiveDate::parse_from_str(iso.get(..10).unwrap_or(iso), "%Y-%m-%d")
+                .map(Some)
+                .map_err(|e| CoreError::ImportError(format!("Invalid date '{}': {}", iso, e)))
+        }
+        // Unformatted date cells come through as the serial day number
+        Data::Float(serial) => excel_serial_to_date(*serial),
+        Data::Int(serial) => excel_serial_to_date(*serial as f64),
+        Data::String(text) => {
+            let text = text.trim();
+            if text.is_empty() {
+                return Ok(None);
+            }
+            TEXT_DATE_FORMATS
+                .iter()
+                .find_map(|format| NaiveDate::parse_from_str(text, format).ok())
+                .map(Some)
+                .ok_or_else(|| CoreError::ImportError(format!("Invalid date '{}'", text)))
+        }
+        other => Err(CoreError::ImportError(format!(
+            "Unexpected date cell '{}'",
+            other
+        ))),
+    }
+}
+
+fn excel_serial_to_date(serial: f64) -> Result<Option<NaiveDate>> {
+    ExcelDateTime::new(serial, ExcelDateTimeType::DateTime, false)
+        .as_datetime()
+        .map(|dt| Some(dt.date()))
+        .ok_or_else(|| CoreError::ImportError(format!("Invalid date serial {}", serial)))
+}
+
+/// Read an amount from a numeric cell or French-formatted text ("-1 234,56 €")
+pub fn cell_to_decimal(cell: &Data) -> Result<Option<Decimal>> {
+    match cell {
+        Data::Empty => Ok(None),
+        // Cells hold binary floats; statements are in cents
+        Data::Float(value) => Decimal::try_from(*value)
+            .map(|amount| Some(amount.round_dp(2)))
+            .map_err(|e| CoreError::ImportError(format!("Invalid amount {}: {}", value, e))),
+        Data::Int(value) => Ok(Some(Decimal::from(*value))),
+        Data::String(text) => parse_text_amount(text),
+        other => Err(CoreError::ImportError(format!(
+            "Unexpected amount cell '{}'",
+            other
+        ))),
+    }
+}
+
+fn parse_text_amount(text: &str) -> Result<Option<Decimal>> {
+    let mut cleaned: String = text
+        .chars()
+        .filter(|c| !c.is_whitespace() && !matches!(c, '€' | '+' | '\u{202f}'))
+        .collect();
+    if cleaned.is_empty() {
+        return Ok(None);
+    }
+
+    // Accounting style negatives: (12,50)
+    let negative = cleaned.starts_with('(') && cleaned.ends_with(')');
+    if negative {
+        cleaned = cleaned[1..cleaned.len() - 1].to_string();
+    }
+
+    if cleaned.contains(',') {
+        cleaned = cleaned.replace('.', "").replace(',', ".");
+    }
+
+    let amount = Decimal::from_str(&cleaned)
+        .map_err(|e| CoreError::ImportError(format!("Invalid amount '{}': {}", text, e)))?;
+
+    Ok(Some(if negative { -amount } else { amount }))
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,165 @@
+use super::*;
+use calamine::Range;
+
+/// Build a sheet from rows of cells, starting at the given 0-based row
+fn sheet(first_row: u32, rows: Vec<Vec<Data>>) -> Range<Data> {
+    let width = rows.iter().map(|row| row.len()).max().unwrap_or(1) as u32;
+    let mut range = Range::new(
+        (first_row, 0),
+        (first_row + rows.len() as u32 - 1, width - 1),
+    );
+    for (r, row) in rows.into_iter().enumerate() {
+        for (c, value) in row.into_iter().enumerate() {
+            range.set_value((first_row + r as u32, c as u32), value);
+        }
+    }
+    range
+}
+
+fn text(value: &str) -> Data {
+    Data::String(value.to_string())
+}
+
+fn date_cell(date: NaiveDate) -> Data {
+    let epoch = NaiveDate::from_ymd_opt(1899, 12, 30).unwrap();
+    Data::DateTime(ExcelDateTime::new(
+        (date - epoch).num_days() as f64,
+        ExcelDateTimeType::DateTime,
+        false,
+    ))
+}
+
+#[test]
+fn test_parse_range_with_date_and_numeric_cells() {
+    let jan_5 = NaiveDate::from_ymd_opt(2025, 1, 5).unwrap();
+    let range = sheet(
+        0,
+        vec![
+            vec![text("Relevé de compte")],
+            vec![],
+            vec![
+                text("Date"),
+                text("Libellé"),
+                text("Montant"),
+                text("Catégorie"),
+            ],
+            vec![
+                date_cell(jan_5),
+                text("CARTE LECLERC"),
+                Data::Float(-45.5),
+                text("Courses"),
+            ],
+            vec![
+                text("06/01/2025"),
+                text("VIR SALAIRE"),
+                text("2 500,00 €"),
+                Data::Empty,
+            ],
+            vec![Data::Empty, text("Solde"), Data::Float(2454.5), Data::Empty],
+        ],
+    );
+    let importer = SpreadsheetImporter::new(
+        SpreadsheetColumnMapping::builder()
+            .header_row(3)
+            .date_column("date")
+            .label_column("Libellé")
+            .amount_column("Montant")
+            .category_column("Catégorie")
+            .build(),
+    );
+
+    let transactions = importer.parse_range("Compte", &range).unwrap();
+
+    assert_eq!(transactions.len(), 2);
+    assert_eq!(transactions[0].date_op, jan_5);
+    assert_eq!(transactions[0].amount, Decimal::new(-4550, 2));
+    assert_eq!(transactions[0].category.as_deref(), Some("Courses"));
+    assert_eq!(
+        transactions[0].raw_data.get("row").map(String::as_str),
+        Some("4")
+    );
+    assert_eq!(
+        transactions[1].date_op,
+        NaiveDate::from_ymd_opt(2025, 1, 6).unwrap()
+    );
+    assert_eq!(transactions[1].amount, Decimal::new(250000, 2));
+    assert_eq!(transactions[1].category, None);
+}
+
+#[test]
+fn test_parse_range_with_debit_credit_columns_and_inverted_sign() {
+    let range = sheet(
+        1,
+        vec![
+            vec![text("Date"), text("Label"), text("Debit"), text("Credit")],
+            vec![
+                Data::Float(45658.0),
+                text("Purchase"),
+                Data::Float(12.3),
+                Data::Empty,
+            ],
+            vec![
+                Data::Float(45659.0),
+                text("Refund"),
+                Data::Empty,
+                Data::Int(5),
+            ],
+        ],
+    );
+    let importer = SpreadsheetImporter::new(
+        SpreadsheetColumnMapping::builder()
+            .header_row(2)
+            .date_column("Date")
+            .label_column("Label")
+            .debit_column("Debit")
+            .credit_column("Credit")
+            .sign(AmountSign::Inverted)
+            .build(),
+    );
+
+    let transactions = importer.parse_range("Card", &range).unwrap();
+
+    assert_eq!(
+        transactions[0].date_op,
+        NaiveDate::from_ymd_opt(2025, 1, 1).unwrap()
+    );
+    assert_eq!(transactions[0].amount, Decimal::new(1230, 2));
+    assert_eq!(transactions[1].amount, Decimal::from(-5));
+}
+
+#[test]
+fn test_parse_range_reports_missing_column() {
+    let range = sheet(0, vec![vec![text("Date"), text("Label")]]);
+    let importer = SpreadsheetImporter::new(
+        SpreadsheetColumnMapping::builder()
+            .date_column("Date")
+            .label_column("Label")
+            .amount_column("Amount")
+            .build(),
+    );
+
+    let error = importer.parse_range("Sheet1", &range).unwrap_err();
+    assert!(error.to_string().contains("Column 'Amount' not found"));
+}
+
+#[test]
+fn test_cell_to_decimal() {
+    assert_eq!(
+        cell_to_decimal(&text("-1 234,56 €")).unwrap(),
+        Some(Decimal::new(-123456, 2))
+    );
+    assert_eq!(
+        cell_to_decimal(&text("1.234,56")).unwrap(),
+        Some(Decimal::new(123456, 2))
+    );
+    assert_eq!(
+        cell_to_decimal(&text("(12,50)")).unwrap(),
+        Some(Decimal::new(-1250, 2))
+    );
+    assert_eq!(
+        cell_to_decimal(&Data::Float(0.1 + 0.2)).unwrap(),
+        Some(Decimal::new(30, 2))
+    );
+    assert_eq!(cell_to_decimal(&text("  ")).unwrap(), None);
+    assert!(cell_to_decimal(&text("abc")).is_err());
+}
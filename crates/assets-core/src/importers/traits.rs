@@ -30,3 +30,19 @@ pub struct ImportedTransaction {
     pub account_label: String,
     pub raw_data: HashMap<String, String>,
 }
+
+impl ImportedTransaction {
+    /// Closing balance of the statement this line came from, with its date
+    ///
+    /// Importers that know it store it in `raw_data` under `statement_closing_balance`
+    /// and `statement_closing_date`.
+    pub fn statement_closing_balance(&self) -> Option<(NaiveDate, Decimal)> {
+        let balance = self
+            .raw_data
+            .get("statement_closing_balance")?
+            .parse()
+            .ok()?;
+        let date = self.raw_data.get("statement_closing_date")?.parse().ok()?;
+        Some((date, balance))
+    }
+}
@@ -27,6 +27,8 @@ pub struct Account {
 
     // General
     pub currency: String,
+    #[serde(default)]
+    pub is_category: bool, // Groups other accounts and does not take entries itself
     pub is_active: bool,
     pub notes: Option<String>,
     pub created_at: DateTime<Utc>,
@@ -53,6 +55,9 @@ pub struct NewAccount {
     pub purchase_price: Option<Decimal>, // General fields
     #[builder(into, default = "EUR")]
     pub currency: String,
+    #[builder(default)]
+    #[serde(default)]
+    pub is_category: bool,
     pub notes: Option<String>,
 }
 
@@ -77,6 +82,9 @@ pub struct NewAccountByPath {
     pub purchase_price: Option<Decimal>,
 
     // General fields
+    #[builder(default)]
+    #[serde(default)]
+    pub is_category: bool,
     pub notes: Option<String>,
 }
 
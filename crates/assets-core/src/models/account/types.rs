@@ -66,6 +66,30 @@ pub enum AccountSubtype {
 }
 
 impl AccountType {
+    /// Infer the type from the root segment of an account path ("Expenses:Food" → Expense)
+    pub fn from_path_root(path: &str) -> Option<Self> {
+        let root = path.split(':').next()?.trim().to_lowercase();
+        match root.as_str() {
+            "assets" | "asset" => Some(AccountType::Asset),
+            "liabilities" | "liability" => Some(AccountType::Liability),
+            "equity" => Some(AccountType::Equity),
+            "income" | "revenue" | "revenues" => Some(AccountType::Income),
+            "expenses" | "expense" => Some(AccountType::Expense),
+            _ => None,
+        }
+    }
+
+    /// Subtype given to accounts created without a more specific one
+    pub fn default_subtype(&self) -> AccountSubtype {
+        match self {
+            AccountType::Asset => AccountSubtype::OtherAsset,
+            AccountType::Liability => AccountSubtype::OtherLiability,
+            AccountType::Equity => AccountSubtype::OwnerEquity,
+            AccountType::Income => AccountSubtype::OtherIncome,
+            AccountType::Expense => AccountSubtype::OtherExpense,
+        }
+    }
+
     /// Returns true if this account type increases with debits
     pub fn increases_with_debit(&self) -> bool {
         matches!(self, AccountType::Asset | AccountType::Expense)
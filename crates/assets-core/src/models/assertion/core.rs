@@ -0,0 +1,51 @@
+use bon::Builder;
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Source recorded for assertions entered by hand
+pub const MANUAL_ASSERTION_SOURCE: &str = "manual";
+
+/// A known-good balance of an account at the end of a day
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct BalanceAssertion {
+    pub id: Uuid,
+    pub account_id: Uuid,
+    pub assertion_date: NaiveDate,
+    /// Expected balance in ledger sign (negative when owed)
+    pub expected_balance: Decimal,
+    /// `manual`, or the import source that read it from a statement
+    pub source: String,
+    pub import_batch_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// New balance assertion data for creation
+#[derive(Debug, Clone, Serialize, Deserialize, Builder)]
+pub struct NewBalanceAssertion {
+    pub account_id: Uuid,
+    pub assertion_date: NaiveDate,
+    pub expected_balance: Decimal,
+    #[builder(into, default = MANUAL_ASSERTION_SOURCE)]
+    pub source: String,
+    pub import_batch_id: Option<Uuid>,
+}
+
+/// An assertion evaluated against the ledger
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct BalanceAssertionCheck {
+    #[sqlx(flatten)]
+    pub assertion: BalanceAssertion,
+    pub account_path: String,
+    /// Balance of the account at the end of the assertion date, voided transactions excluded
+    pub actual_balance: Decimal,
+    /// `actual_balance - expected_balance`
+    pub difference: Decimal,
+}
+
+impl BalanceAssertionCheck {
+    pub fn passed(&self) -> bool {
+        self.difference == Decimal::ZERO
+    }
+}
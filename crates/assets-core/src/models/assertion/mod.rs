@@ -0,0 +1,10 @@
+//! Balance assertion models and types
+//!
+//! This module contains all types related to checking known-good balances against the ledger:
+//! - Recorded assertions and their input (BalanceAssertion, NewBalanceAssertion)
+//! - Outcome of evaluating an assertion (BalanceAssertionCheck)
+
+pub mod core;
+
+// Re-export all public types for easier importing
+pub use core::*;
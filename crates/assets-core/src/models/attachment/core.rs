@@ -0,0 +1,50 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use uuid::Uuid;
+
+/// A stored file linked to a transaction or an account
+///
+/// The content lives in the attachment store under its `content_hash`; several
+/// attachments share one stored file when the same document is linked more than once.
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct Attachment {
+    pub id: Uuid,
+    /// SHA-256 of the file content
+    pub content_hash: String,
+    /// Name of the file when it was attached
+    pub file_name: String,
+    pub mime_type: String,
+    pub file_size: i64,
+    pub transaction_id: Option<Uuid>,
+    pub account_id: Option<Uuid>,
+    pub notes: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Attachment {
+    /// What the attachment is linked to
+    pub fn target(&self) -> AttachmentTarget {
+        match (self.transaction_id, self.account_id) {
+            (Some(transaction_id), _) => AttachmentTarget::Transaction(transaction_id),
+            (None, Some(account_id)) => AttachmentTarget::Account(account_id),
+            (None, None) => unreachable!("attachments always have exactly one target"),
+        }
+    }
+}
+
+/// The record an attachment belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AttachmentTarget {
+    Transaction(Uuid),
+    Account(Uuid),
+}
+
+impl fmt::Display for AttachmentTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AttachmentTarget::Transaction(id) => write!(f, "transaction {}", id),
+            AttachmentTarget::Account(id) => write!(f, "account {}", id),
+        }
+    }
+}
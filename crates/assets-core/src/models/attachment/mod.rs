@@ -0,0 +1,10 @@
+//! Attachment-related models and types
+//!
+//! This module contains all types related to supporting documents:
+//! - Receipts, invoices and statements linked to a transaction or an account (Attachment)
+//! - What an attachment is linked to (AttachmentTarget)
+
+pub mod core;
+
+// Re-export all public types for easier importing
+pub use core::*;
@@ -0,0 +1,59 @@
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A journal entry still posted to the uncategorized account
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct UncategorizedEntry {
+    pub id: Uuid,
+    pub transaction_id: Uuid,
+    pub transaction_date: DateTime<Utc>,
+    pub description: String,
+    /// Amount of the uncategorized leg; the bank side carries the opposite sign
+    pub amount: Decimal,
+    /// Accounts of the other legs, usually the bank account the line was imported into
+    pub counterpart_paths: String,
+}
+
+/// Where a suggested account comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SuggestionSource {
+    /// A saved categorization rule matches the description
+    Rule,
+    /// Past transactions with a similar description used this account
+    History,
+}
+
+/// A candidate target account for an uncategorized entry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CategorySuggestion {
+    pub account_id: Uuid,
+    pub account_path: String,
+    pub source: SuggestionSource,
+    /// Number of similar past transactions that used the account (zero for rules)
+    pub occurrences: i64,
+    /// Best trigram similarity to a past description (1 for rules)
+    pub similarity: f64,
+}
+
+/// Descriptions containing `pattern` are categorized to the rule's account
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct CategorizationRule {
+    pub id: Uuid,
+    pub pattern: String,
+    pub account_id: Uuid,
+    pub account_path: String,
+    pub priority: i32,
+    pub created_at: DateTime<Utc>,
+}
+
+impl CategorizationRule {
+    /// Case-insensitive substring match, as done in the database
+    pub fn matches(&self, description: &str) -> bool {
+        description
+            .to_lowercase()
+            .contains(&self.pattern.to_lowercase())
+    }
+}
@@ -0,0 +1,10 @@
+//! Categorization models and types
+//!
+//! This module contains all types related to triaging uncategorized imported entries:
+//! - Entries still waiting in the uncategorized account (UncategorizedEntry)
+//! - Suggested target accounts and reusable rules (CategorySuggestion, CategorizationRule)
+
+pub mod core;
+
+// Re-export all public types for easier importing
+pub use core::*;
@@ -0,0 +1,91 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A balance reported by the bank at the end of a day, in ledger sign
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KnownBalance {
+    pub date: NaiveDate,
+    pub balance: Decimal,
+}
+
+/// A known bank balance compared to the ledger on the same day
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BalanceCheckpoint {
+    pub date: NaiveDate,
+    pub expected: Decimal,
+    pub actual: Decimal,
+    /// `actual - expected`
+    pub difference: Decimal,
+}
+
+/// A journal entry of the account being investigated
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct DiscrepancyEntry {
+    pub id: Uuid,
+    pub transaction_id: Uuid,
+    pub transaction_date: DateTime<Utc>,
+    pub description: String,
+    pub amount: Decimal,
+    pub memo: Option<String>,
+    /// Flagged by duplicate detection, or another entry of the account has the same
+    /// amount within a few days
+    pub possible_duplicate: bool,
+    /// The other side of the transaction is still in `Equity:Uncategorized`
+    pub uncategorized: bool,
+}
+
+/// Why an entry may explain the discrepancy
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SuspectReason {
+    /// The entry amount equals the difference: it may not belong in the account
+    MatchesDifference,
+    /// Twice the entry amount equals the difference: its sign may be wrong
+    MisSigned,
+    /// The entry may be a duplicate
+    Duplicate,
+    /// The entry was never categorized
+    Uncategorized,
+}
+
+impl std::fmt::Display for SuspectReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SuspectReason::MatchesDifference => f.write_str("matches difference"),
+            SuspectReason::MisSigned => f.write_str("mis-signed"),
+            SuspectReason::Duplicate => f.write_str("duplicate"),
+            SuspectReason::Uncategorized => f.write_str("uncategorized"),
+        }
+    }
+}
+
+/// An entry of the divergence window with the reasons it looks wrong
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiscrepancySuspect {
+    pub entry: DiscrepancyEntry,
+    pub reasons: Vec<SuspectReason>,
+}
+
+/// Where the ledger stops agreeing with the bank, and the likely culprits
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiscrepancyReport {
+    pub account_id: Uuid,
+    /// Latest known balance the ledger agrees with before the divergence
+    pub last_agreement: Option<BalanceCheckpoint>,
+    /// First known balance the ledger disagrees with; `None` when all agree
+    pub first_divergence: Option<BalanceCheckpoint>,
+    /// Day within the divergence window where the ledger most likely went wrong
+    pub diverging_day: Option<NaiveDate>,
+    /// Entries of the account on the diverging day
+    pub day_entries: Vec<DiscrepancyEntry>,
+    /// Suspicious entries of the whole divergence window, in date order
+    pub suspects: Vec<DiscrepancySuspect>,
+}
+
+impl DiscrepancyReport {
+    pub fn is_consistent(&self) -> bool {
+        self.first_divergence.is_none()
+    }
+}
@@ -0,0 +1,10 @@
+//! Discrepancy finder models and types
+//!
+//! This module contains all types related to locating where the ledger diverges from the bank:
+//! - Known bank balances and how the ledger compares to them (KnownBalance, BalanceCheckpoint)
+//! - Entries around the divergence and why they look suspicious (DiscrepancyEntry, SuspectReason)
+
+pub mod core;
+
+// Re-export all public types for easier importing
+pub use core::*;
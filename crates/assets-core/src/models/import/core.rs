@@ -1,8 +1,9 @@
 use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 // File import tracking models
-#[derive(Debug, sqlx::FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
 pub struct ImportedFile {
     pub id: Uuid,
     pub file_path: String,
@@ -27,3 +28,121 @@ pub struct NewImportedFile {
     pub transaction_count: i32,
     pub notes: Option<String>,
 }
+
+/// Importers the inbox knows how to dispatch a file to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImporterKind {
+    BoursoBank,
+    SocieteGenerale,
+    QtPayslip,
+    MathWorksPayslip,
+    Spreadsheet,
+    BoursoBankPdf,
+    SocieteGeneralePdf,
+}
+
+impl ImporterKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ImporterKind::BoursoBank => "boursobank",
+            ImporterKind::SocieteGenerale => "sg",
+            ImporterKind::QtPayslip => "qt_payslip",
+            ImporterKind::MathWorksPayslip => "mathworks_payslip",
+            ImporterKind::Spreadsheet => "spreadsheet",
+            ImporterKind::BoursoBankPdf => "boursobank_pdf",
+            ImporterKind::SocieteGeneralePdf => "sg_pdf",
+        }
+    }
+
+    /// Payslip importers need destination accounts instead of a single target account
+    pub fn is_payslip(&self) -> bool {
+        matches!(
+            self,
+            ImporterKind::QtPayslip | ImporterKind::MathWorksPayslip
+        )
+    }
+}
+
+impl std::fmt::Display for ImporterKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for ImporterKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "boursobank" => Ok(ImporterKind::BoursoBank),
+            "sg" | "societegenerale" => Ok(ImporterKind::SocieteGenerale),
+            "qt_payslip" | "qt" => Ok(ImporterKind::QtPayslip),
+            "mathworks_payslip" | "mathworks" => Ok(ImporterKind::MathWorksPayslip),
+            "spreadsheet" => Ok(ImporterKind::Spreadsheet),
+            "boursobank_pdf" => Ok(ImporterKind::BoursoBankPdf),
+            "sg_pdf" | "societegenerale_pdf" => Ok(ImporterKind::SocieteGeneralePdf),
+            _ => Err(format!(
+                "Unknown importer '{}'. Expected one of: boursobank, sg, qt_payslip, mathworks_payslip, spreadsheet, boursobank_pdf, sg_pdf",
+                s
+            )),
+        }
+    }
+}
+
+/// Routes files dropped in the import inbox to an importer and account(s)
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct ImportMapping {
+    pub id: Uuid,
+    pub file_pattern: String,
+    pub importer: String,
+    pub target_account: Option<String>,
+    pub payslip_destinations: Option<serde_json::Value>,
+    /// `SpreadsheetColumnMapping` used by the spreadsheet importer
+    pub spreadsheet_columns: Option<serde_json::Value>,
+    pub priority: i32,
+    pub created_at: DateTime<Utc>,
+}
+
+impl ImportMapping {
+    /// Case-insensitive glob match (`*` and `?`) against a file name
+    pub fn matches(&self, file_name: &str) -> bool {
+        glob_match(&self.file_pattern.to_lowercase(), &file_name.to_lowercase())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct NewImportMapping {
+    pub file_pattern: String,
+    pub importer: ImporterKind,
+    pub target_account: Option<String>,
+    pub payslip_destinations: Option<serde_json::Value>,
+    pub spreadsheet_columns: Option<serde_json::Value>,
+    pub priority: i32,
+}
+
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, t));
+            p += 1;
+        } else if let Some((star_p, star_t)) = backtrack {
+            // Let the last star swallow one more character
+            p = star_p + 1;
+            t = star_t + 1;
+            backtrack = Some((star_p, star_t + 1));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|c| *c == '*')
+}
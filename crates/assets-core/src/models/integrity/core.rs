@@ -0,0 +1,71 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use uuid::Uuid;
+
+/// One link of the hash chain, appended each time a transaction is written
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct ChainLink {
+    pub seq: i64,
+    pub transaction_id: Uuid,
+    /// SHA-256 of the transaction's canonical serialization; `None` once deleted
+    pub content_hash: Option<String>,
+    pub previous_hash: String,
+    pub chain_hash: String,
+    pub sealed_at: DateTime<Utc>,
+}
+
+/// What kind of tampering a chain check found
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ChainIssueKind {
+    /// A link was edited, removed or inserted: its hash no longer follows from the previous one
+    BrokenLink,
+    /// The transaction or its entries changed since they were last sealed
+    Modified,
+    /// The transaction exists but was never sealed, e.g. inserted by hand
+    Unsealed,
+    /// The transaction disappeared without its deletion being sealed
+    Deleted,
+}
+
+impl fmt::Display for ChainIssueKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            ChainIssueKind::BrokenLink => "broken link",
+            ChainIssueKind::Modified => "modified",
+            ChainIssueKind::Unsealed => "unsealed",
+            ChainIssueKind::Deleted => "deleted",
+        };
+        write!(f, "{}", label)
+    }
+}
+
+/// A transaction or link that does not match the chain
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChainIssue {
+    pub kind: ChainIssueKind,
+    pub transaction_id: Uuid,
+    /// Link where the problem shows (the broken link, or the transaction's latest link)
+    pub seq: Option<i64>,
+    /// Hash recorded in the chain
+    pub expected_hash: Option<String>,
+    /// Hash recomputed from the database
+    pub actual_hash: Option<String>,
+}
+
+/// Result of recomputing the chain against the journal
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChainVerification {
+    pub links_checked: usize,
+    pub transactions_checked: usize,
+    /// Hash of the latest link; noting it down elsewhere lets a later check
+    /// also catch a chain rebuilt from scratch
+    pub head_hash: Option<String>,
+    pub issues: Vec<ChainIssue>,
+}
+
+impl ChainVerification {
+    pub fn is_intact(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
@@ -0,0 +1,10 @@
+//! Integrity-related models and types
+//!
+//! This module contains all types related to the tamper-evident journal:
+//! - Links of the hash chain over transactions (ChainLink)
+//! - Results of recomputing the chain (ChainVerification, ChainIssue, ChainIssueKind)
+
+pub mod core;
+
+// Re-export all public types for easier importing
+pub use core::*;
@@ -2,10 +2,19 @@
 //!
 //! This module contains all data models organized by domain:
 //! - `account`: Account management, types, and enhanced views
+//! - `assertion`: Known-good balances checked against the ledger
+//! - `attachment`: Receipts, invoices and statements linked to transactions and accounts
 //! - `transaction`: Transaction processing, journal entries, and builders
 //! - `pricing`: Asset pricing and market data
 //! - `reports`: Financial reporting structures
 //! - `import`: Data import tracking and management
+//! - `integrity`: Tamper-evident hash chain over the journal
+//! - `categorization`: Triage of uncategorized entries and reusable rules
+//! - `discrepancy`: Locating where the ledger diverges from bank balances
+//! - `period`: Closed periods and the lock date
+//! - `reconciliation`: Statement reconciliation and entry clearing status
+//! - `recurring`: Scheduled transaction templates
+//! - `tag`: Cross-cutting transaction labels
 //!
 //! ## Design Principles
 //!
@@ -15,9 +24,18 @@
 //! - **Documentation**: Each module is well-documented with examples
 
 pub mod account;
+pub mod assertion;
+pub mod attachment;
+pub mod categorization;
+pub mod discrepancy;
 pub mod import;
+pub mod integrity;
+pub mod period;
 pub mod pricing;
+pub mod reconciliation;
+pub mod recurring;
 pub mod reports;
+pub mod tag;
 pub mod transaction;
 pub mod user;
 
@@ -29,17 +47,59 @@ pub use account::{
     Account, AccountSubtype, AccountType, AccountWithMarketValue, NewAccount, NewAccountByPath,
 };
 
+// Balance assertion types
+pub use assertion::{
+    BalanceAssertion, BalanceAssertionCheck, MANUAL_ASSERTION_SOURCE, NewBalanceAssertion,
+};
+
+// Attachment types
+pub use attachment::{Attachment, AttachmentTarget};
+
 // Transaction types
 pub use transaction::{
-    JournalEntry, JournalEntryByPath, JournalEntryWithAccount, NewJournalEntry, NewTransaction,
-    NewTransactionByPath, Transaction, TransactionWithEntries, TransactionWithEntriesAndAccounts,
+    AmountFilter, JournalEntry, JournalEntryByPath, JournalEntryWithAccount, NewJournalEntry,
+    NewTransaction, NewTransactionByPath, RefundDetection, RefundLink, RefundMatch,
+    RefundSuggestion, SpendingLine, Transaction, TransactionCursor, TransactionFilter,
+    TransactionPage, TransactionSearch, TransactionSearchResult, TransactionWithEntries,
+    TransactionWithEntriesAndAccounts, TransferCandidate, TransferDetection, TransferLink,
+};
+
+// Journal integrity types
+pub use integrity::{ChainIssue, ChainIssueKind, ChainLink, ChainVerification};
+
+// Period closing types
+pub use period::PeriodClose;
+
+// Categorization types
+pub use categorization::{
+    CategorizationRule, CategorySuggestion, SuggestionSource, UncategorizedEntry,
+};
+
+// Discrepancy finder types
+pub use discrepancy::{
+    BalanceCheckpoint, DiscrepancyEntry, DiscrepancyReport, DiscrepancySuspect, KnownBalance,
+    SuspectReason,
 };
 
 // Pricing types
 pub use pricing::{NewPriceHistory, PriceHistory};
 
+// Reconciliation types
+pub use reconciliation::{
+    EntryStatus, JournalEntryStatus, Reconciliation, ReconciliationEntry, ReconciliationSummary,
+};
+
+// Recurring transaction types
+pub use recurring::{
+    DueRecurringInstance, NewRecurringTransaction, RecurrenceSchedule, RecurringInstance,
+    RecurringTransaction,
+};
+
 // Report types
-pub use reports::{AccountLedgerRow, CashFlowRow, IncomeStatementRow};
+pub use reports::{AccountLedgerRow, CashFlowRow, IncomeStatementRow, TaggedIncomeStatementRow};
+
+// Tag types
+pub use tag::{JournalEntryTag, Tag, TagUsage, TransactionTag};
 
 // Import types
-pub use import::{ImportedFile, NewImportedFile};
+pub use import::{ImportMapping, ImportedFile, ImporterKind, NewImportMapping, NewImportedFile};
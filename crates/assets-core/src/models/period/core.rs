@@ -0,0 +1,18 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A closed period; everything dated on or before the latest `period_end` is locked
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct PeriodClose {
+    pub id: Uuid,
+    /// Last day of the period, included
+    pub period_end: NaiveDate,
+    /// Transaction moving income and expense balances to retained earnings;
+    /// `None` when they were already zero
+    pub closing_transaction_id: Option<Uuid>,
+    /// Income minus expenses moved to retained earnings
+    pub net_income: Decimal,
+    pub closed_at: DateTime<Utc>,
+}
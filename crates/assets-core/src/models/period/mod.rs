@@ -0,0 +1,9 @@
+//! Period closing models and types
+//!
+//! This module contains all types related to closing finished periods:
+//! - Recorded closes and the lock date they imply (PeriodClose)
+
+pub mod core;
+
+// Re-export all public types for easier importing
+pub use core::*;
@@ -0,0 +1,78 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Where a journal entry stands against the bank statements
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "varchar", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum EntryStatus {
+    /// Not seen on a statement yet
+    Uncleared,
+    /// Ticked during a reconciliation in progress
+    Cleared,
+    /// Locked by a completed reconciliation; amount, account and date are frozen
+    Reconciled,
+}
+
+impl std::fmt::Display for EntryStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EntryStatus::Uncleared => f.write_str("uncleared"),
+            EntryStatus::Cleared => f.write_str("cleared"),
+            EntryStatus::Reconciled => f.write_str("reconciled"),
+        }
+    }
+}
+
+/// A completed reconciliation of an account against a statement
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct Reconciliation {
+    pub id: Uuid,
+    pub account_id: Uuid,
+    pub statement_date: NaiveDate,
+    /// Ending balance of the statement, in ledger sign (negative when owed)
+    pub statement_balance: Decimal,
+    pub reconciled_at: DateTime<Utc>,
+}
+
+/// A journal entry of the account being reconciled
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct ReconciliationEntry {
+    pub id: Uuid,
+    pub transaction_id: Uuid,
+    pub transaction_date: DateTime<Utc>,
+    pub description: String,
+    pub amount: Decimal,
+    pub memo: Option<String>,
+    pub status: EntryStatus,
+}
+
+/// Progress of a reconciliation against a statement
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReconciliationSummary {
+    pub account_id: Uuid,
+    pub statement_date: NaiveDate,
+    pub statement_balance: Decimal,
+    /// Balance locked by previous reconciliations
+    pub reconciled_balance: Decimal,
+    /// Reconciled balance plus entries cleared up to the statement date
+    pub cleared_balance: Decimal,
+    /// `statement_balance - cleared_balance`; the reconciliation can finish at zero
+    pub difference: Decimal,
+}
+
+impl ReconciliationSummary {
+    pub fn is_balanced(&self) -> bool {
+        self.difference == Decimal::ZERO
+    }
+}
+
+/// Status of one journal entry, kept in backups alongside the entries themselves
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct JournalEntryStatus {
+    pub journal_entry_id: Uuid,
+    pub status: EntryStatus,
+    pub reconciliation_id: Option<Uuid>,
+}
@@ -0,0 +1,10 @@
+//! Statement reconciliation models and types
+//!
+//! This module contains all types related to matching the ledger against bank statements:
+//! - Journal entry clearing status (EntryStatus)
+//! - Completed reconciliations and in-progress summaries (Reconciliation, ReconciliationSummary)
+
+pub mod core;
+
+// Re-export all public types for easier importing
+pub use core::*;
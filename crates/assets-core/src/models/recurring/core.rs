@@ -0,0 +1,175 @@
+use crate::models::JournalEntryByPath;
+use chrono::{DateTime, Datelike, Days, NaiveDate, Utc, Weekday};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// When a recurring transaction falls due
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecurrenceSchedule {
+    /// Day N of every month, moved to the last day in shorter months
+    Monthly { day: u32 },
+    /// Every week on the same weekday
+    Weekly { weekday: Weekday },
+    /// Once a year, moved to the last day of the month if the day does not exist
+    Yearly { month: u32, day: u32 },
+    /// Last Monday to Friday of every month
+    LastBusinessDay,
+}
+
+impl RecurrenceSchedule {
+    /// Whether the schedule falls due on `date`
+    pub fn occurs_on(&self, date: NaiveDate) -> bool {
+        match *self {
+            RecurrenceSchedule::Monthly { day } => date.day() == day.min(last_day_of_month(date)),
+            RecurrenceSchedule::Weekly { weekday } => date.weekday() == weekday,
+            RecurrenceSchedule::Yearly { month, day } => {
+                date.month() == month && date.day() == day.min(last_day_of_month(date))
+            }
+            RecurrenceSchedule::LastBusinessDay => {
+                let mut last = date.with_day(last_day_of_month(date)).unwrap();
+                while matches!(last.weekday(), Weekday::Sat | Weekday::Sun) {
+                    last = last - Days::new(1);
+                }
+                date == last
+            }
+        }
+    }
+
+    /// All due dates between `from` and `to`, both included
+    pub fn occurrences(&self, from: NaiveDate, to: NaiveDate) -> Vec<NaiveDate> {
+        from.iter_days()
+            .take_while(|date| *date <= to)
+            .filter(|date| self.occurs_on(*date))
+            .collect()
+    }
+}
+
+fn last_day_of_month(date: NaiveDate) -> u32 {
+    let (year, month) = if date.month() == 12 {
+        (date.year() + 1, 1)
+    } else {
+        (date.year(), date.month() + 1)
+    };
+    NaiveDate::from_ymd_opt(year, month, 1)
+        .and_then(|first| first.pred_opt())
+        .map(|last| last.day())
+        .unwrap_or(28)
+}
+
+impl std::fmt::Display for RecurrenceSchedule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RecurrenceSchedule::Monthly { day } => write!(f, "monthly:{}", day),
+            RecurrenceSchedule::Weekly { weekday } => {
+                write!(f, "weekly:{}", weekday.to_string().to_lowercase())
+            }
+            RecurrenceSchedule::Yearly { month, day } => {
+                write!(f, "yearly:{:02}-{:02}", month, day)
+            }
+            RecurrenceSchedule::LastBusinessDay => f.write_str("last-business-day"),
+        }
+    }
+}
+
+impl std::str::FromStr for RecurrenceSchedule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim().to_lowercase();
+        let invalid = || {
+            format!(
+                "Invalid schedule '{}'. Expected monthly:<day>, weekly:<weekday>, yearly:<MM-DD> or last-business-day",
+                s
+            )
+        };
+
+        if s == "last-business-day" {
+            return Ok(RecurrenceSchedule::LastBusinessDay);
+        }
+        let (kind, value) = s.split_once(':').ok_or_else(invalid)?;
+        match kind {
+            "monthly" => {
+                let day: u32 = value.parse().map_err(|_| invalid())?;
+                if !(1..=31).contains(&day) {
+                    return Err(invalid());
+                }
+                Ok(RecurrenceSchedule::Monthly { day })
+            }
+            "weekly" => {
+                let weekday: Weekday = value.parse().map_err(|_| invalid())?;
+                Ok(RecurrenceSchedule::Weekly { weekday })
+            }
+            "yearly" => {
+                let (month, day) = value.split_once('-').ok_or_else(invalid)?;
+                let month: u32 = month.parse().map_err(|_| invalid())?;
+                let day: u32 = day.parse().map_err(|_| invalid())?;
+                // 2024 is a leap year, so 02-29 is accepted
+                if NaiveDate::from_ymd_opt(2024, month, day).is_none() {
+                    return Err(invalid());
+                }
+                Ok(RecurrenceSchedule::Yearly { month, day })
+            }
+            _ => Err(invalid()),
+        }
+    }
+}
+
+/// A transaction template posted on a schedule
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct RecurringTransaction {
+    pub id: Uuid,
+    pub name: String,
+    pub description: String,
+    pub reference: Option<String>,
+    /// Serialized `Vec<JournalEntryByPath>`
+    pub entries: serde_json::Value,
+    pub schedule: String,
+    pub start_date: NaiveDate,
+    pub end_date: Option<NaiveDate>,
+    /// Largest difference accepted when matching an imported bank line
+    pub amount_tolerance: Decimal,
+    pub is_active: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+impl RecurringTransaction {
+    pub fn parsed_schedule(&self) -> Result<RecurrenceSchedule, String> {
+        self.schedule.parse()
+    }
+
+    pub fn template_entries(&self) -> Result<Vec<JournalEntryByPath>, serde_json::Error> {
+        serde_json::from_value(self.entries.clone())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct NewRecurringTransaction {
+    pub name: String,
+    pub description: String,
+    pub reference: Option<String>,
+    pub entries: Vec<JournalEntryByPath>,
+    pub schedule: RecurrenceSchedule,
+    pub start_date: NaiveDate,
+    pub end_date: Option<NaiveDate>,
+    pub amount_tolerance: Decimal,
+}
+
+/// A due date of a recurring transaction that has already been handled
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct RecurringInstance {
+    pub recurring_transaction_id: Uuid,
+    pub due_date: NaiveDate,
+    /// The posted transaction, unless it was deleted since
+    pub transaction_id: Option<Uuid>,
+    /// Set when an imported bank line was matched to the instance
+    pub matched_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A due date of a recurring transaction that has not been posted yet
+#[derive(Debug, Clone)]
+pub struct DueRecurringInstance {
+    pub recurring: RecurringTransaction,
+    pub due_date: NaiveDate,
+}
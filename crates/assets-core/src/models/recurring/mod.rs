@@ -0,0 +1,10 @@
+//! Recurring transaction models and types
+//!
+//! This module contains all types related to scheduled transactions:
+//! - Transaction templates (RecurringTransaction, NewRecurringTransaction)
+//! - Schedules and due date computation (RecurrenceSchedule)
+
+pub mod core;
+
+// Re-export all public types for easier importing
+pub use core::*;
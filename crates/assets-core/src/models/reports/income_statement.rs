@@ -9,3 +9,13 @@ pub struct IncomeStatementRow {
     pub account_path: String, // Added account_path for full account path display
     pub total_amount: Decimal,
 }
+
+/// An income statement row within one tag's group; `tag_name` is `None` for untagged entries
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct TaggedIncomeStatementRow {
+    pub tag_name: Option<String>,
+    pub category_name: Option<String>,
+    pub account_name: String,
+    pub account_path: String,
+    pub total_amount: Decimal,
+}
@@ -0,0 +1,37 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A cross-cutting label such as "vacation 2025" or "reimbursable"
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct Tag {
+    pub id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A tag with the number of transactions and journal entries carrying it
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct TagUsage {
+    pub id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub transaction_count: i64,
+    pub entry_count: i64,
+}
+
+/// A tag attached to a whole transaction
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct TransactionTag {
+    pub transaction_id: Uuid,
+    pub tag_id: Uuid,
+}
+
+/// A tag attached to a single journal entry
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct JournalEntryTag {
+    pub journal_entry_id: Uuid,
+    pub tag_id: Uuid,
+}
@@ -0,0 +1,9 @@
+//! Tag-related models and types
+//!
+//! This module contains all types related to tagging:
+//! - Tags attached to transactions and journal entries (Tag, TransactionTag, JournalEntryTag)
+
+pub mod core;
+
+// Re-export all public types for easier importing
+pub use core::*;
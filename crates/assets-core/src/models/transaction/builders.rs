@@ -35,6 +35,11 @@ pub struct NewTransactionByPath {
     pub reference: Option<String>,
 
     pub memo: Option<String>,
+
+    /// Create accounts whose paths don't exist yet instead of failing
+    #[builder(default)]
+    #[serde(default)]
+    pub ensure_accounts: bool,
 }
 
 impl NewTransaction {
@@ -89,6 +94,7 @@ impl NewTransactionByPath {
             ],
             reference: None,
             memo: None,
+            ensure_accounts: false,
         }
     }
 
@@ -18,6 +18,11 @@ pub struct Transaction {
     // Duplicate tracking fields
     pub is_duplicate: Option<bool>,
     pub merged_into_transaction_id: Option<Uuid>,
+    // Voiding and reversal fields
+    #[serde(default)]
+    pub is_voided: bool,
+    pub voided_at: Option<DateTime<Utc>>,
+    pub reversal_of_transaction_id: Option<Uuid>,
 }
 
 /// Journal entries - the actual debits and credits that make up a transaction
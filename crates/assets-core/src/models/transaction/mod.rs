@@ -5,12 +5,24 @@
 //! - Transaction builders and helpers (NewTransaction, NewTransactionByPath)
 //! - Enhanced transaction types with account information
 //! - Path-based transaction creation utilities
+//! - Search criteria and results
+//! - Listing filters and keyset pagination
+//! - Internal transfer detection and links
+//! - Refunds linked to their original purchases
 
 pub mod builders;
 pub mod core;
 pub mod enhanced;
+pub mod page;
+pub mod refund;
+pub mod search;
+pub mod transfer;
 
 // Re-export all public types for easier importing
 pub use builders::*;
 pub use core::*;
 pub use enhanced::*;
+pub use page::*;
+pub use refund::*;
+pub use search::*;
+pub use transfer::*;
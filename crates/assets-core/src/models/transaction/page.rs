@@ -0,0 +1,37 @@
+use super::enhanced::TransactionWithEntriesAndAccounts;
+use bon::Builder;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Filters of [`TransactionService::get_transactions_page`](crate::TransactionService::get_transactions_page)
+/// and [`TransactionService::stream_transactions`](crate::TransactionService::stream_transactions)
+#[derive(Debug, Clone, Default, Builder)]
+pub struct TransactionFilter {
+    pub from_date: Option<DateTime<Utc>>,
+    /// Included
+    pub to_date: Option<DateTime<Utc>>,
+    /// Account path prefix; sub-accounts are included
+    #[builder(into)]
+    pub account_path: Option<String>,
+    /// Tag on the transaction or one of its entries
+    #[builder(into)]
+    pub tag: Option<String>,
+}
+
+/// Position after the last transaction of a page, newest first
+///
+/// Pages are ordered by `(transaction_date, id)`, which is unique, so resuming from
+/// a cursor never skips or repeats a transaction even when new ones are inserted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransactionCursor {
+    pub transaction_date: DateTime<Utc>,
+    pub id: Uuid,
+}
+
+/// One page of transactions and the cursor to the next one, `None` on the last page
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransactionPage {
+    pub transactions: Vec<TransactionWithEntriesAndAccounts>,
+    pub next_cursor: Option<TransactionCursor>,
+}
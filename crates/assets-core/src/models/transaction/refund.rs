@@ -0,0 +1,71 @@
+use bon::Builder;
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Criteria of [`RefundService::suggest`](crate::RefundService::suggest)
+#[derive(Debug, Clone, Builder)]
+pub struct RefundDetection {
+    /// Maximum number of days between a purchase and its refund
+    #[builder(default = 60)]
+    pub window_days: i32,
+    /// Refunds dated from this day
+    pub from_date: Option<NaiveDate>,
+    /// Included
+    pub to_date: Option<NaiveDate>,
+}
+
+impl Default for RefundDetection {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+/// A spending leg (expense or uncategorized) of a purchase or a refund
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct SpendingLine {
+    pub transaction_id: Uuid,
+    pub entry_id: Uuid,
+    pub transaction_date: DateTime<Utc>,
+    pub description: String,
+    pub account_path: String,
+    /// Always positive, whether money was spent or given back
+    pub amount: Decimal,
+    /// What is left to refund on a purchase; the amount itself for a refund
+    pub refundable: Decimal,
+}
+
+/// A purchase a refund may belong to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RefundMatch {
+    pub purchase: SpendingLine,
+    /// Days between the purchase and the refund
+    pub day_gap: i32,
+    /// The refund gives back everything that is left on the purchase
+    pub full_refund: bool,
+}
+
+/// An unlinked refund and its possible purchases, most likely first
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RefundSuggestion {
+    pub refund: SpendingLine,
+    pub matches: Vec<RefundMatch>,
+}
+
+/// A refund linked to the purchase it gives money back for
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct RefundLink {
+    pub id: Uuid,
+    pub refund_transaction_id: Uuid,
+    pub purchase_transaction_id: Uuid,
+    pub refund_entry_id: Uuid,
+    pub purchase_entry_id: Uuid,
+    pub amount: Decimal,
+    pub created_at: DateTime<Utc>,
+    pub refund_date: DateTime<Utc>,
+    pub refund_description: String,
+    pub purchase_date: DateTime<Utc>,
+    pub purchase_description: String,
+    pub purchase_account_path: String,
+}
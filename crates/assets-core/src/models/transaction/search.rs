@@ -0,0 +1,83 @@
+use super::enhanced::TransactionWithEntriesAndAccounts;
+use bon::Builder;
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// Amount condition of a transaction search, e.g. `>100`, `<=20`, `=42.50` or `100..200`
+///
+/// A transaction matches when one of its entries matches in absolute value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AmountFilter {
+    GreaterThan(Decimal),
+    GreaterOrEqual(Decimal),
+    LessThan(Decimal),
+    LessOrEqual(Decimal),
+    Equal(Decimal),
+    /// Both bounds included
+    Between(Decimal, Decimal),
+}
+
+impl FromStr for AmountFilter {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let invalid = || {
+            format!(
+                "Invalid amount filter '{}'. Expected >N, >=N, <N, <=N, =N, N or N..M",
+                s
+            )
+        };
+        let parse = |value: &str| Decimal::from_str(value.trim()).map_err(|_| invalid());
+
+        if let Some((low, high)) = s.split_once("..") {
+            let (low, high) = (parse(low)?, parse(high)?);
+            if low > high {
+                return Err(invalid());
+            }
+            return Ok(AmountFilter::Between(low, high));
+        }
+        // Two-character operators first so ">=" is not read as ">"
+        if let Some(value) = s.strip_prefix(">=") {
+            Ok(AmountFilter::GreaterOrEqual(parse(value)?))
+        } else if let Some(value) = s.strip_prefix("<=") {
+            Ok(AmountFilter::LessOrEqual(parse(value)?))
+        } else if let Some(value) = s.strip_prefix('>') {
+            Ok(AmountFilter::GreaterThan(parse(value)?))
+        } else if let Some(value) = s.strip_prefix('<') {
+            Ok(AmountFilter::LessThan(parse(value)?))
+        } else if let Some(value) = s.strip_prefix('=') {
+            Ok(AmountFilter::Equal(parse(value)?))
+        } else {
+            Ok(AmountFilter::Equal(parse(s)?))
+        }
+    }
+}
+
+/// Criteria of [`TransactionService::search`](crate::TransactionService::search); all are optional
+#[derive(Debug, Clone, Builder)]
+pub struct TransactionSearch {
+    /// Free text matched fuzzily against descriptions, references and entry memos
+    #[builder(into)]
+    pub query: Option<String>,
+    pub amount: Option<AmountFilter>,
+    /// Account path; sub-accounts are included
+    #[builder(into)]
+    pub account_path: Option<String>,
+    #[builder(into)]
+    pub import_source: Option<String>,
+    pub from_date: Option<NaiveDate>,
+    /// Included
+    pub to_date: Option<NaiveDate>,
+    #[builder(default = 50)]
+    pub limit: u32,
+}
+
+/// A transaction found by a search, with its relevance between 0 and 1
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransactionSearchResult {
+    pub transaction: TransactionWithEntriesAndAccounts,
+    pub score: f64,
+}
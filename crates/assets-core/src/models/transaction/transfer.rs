@@ -0,0 +1,61 @@
+use bon::Builder;
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Criteria of [`TransferService::find_candidates`](crate::TransferService::find_candidates)
+#[derive(Debug, Clone, Builder)]
+pub struct TransferDetection {
+    /// Maximum number of days between the outgoing and the incoming line
+    #[builder(default = 3)]
+    pub tolerance_days: i32,
+    pub from_date: Option<NaiveDate>,
+    /// Included
+    pub to_date: Option<NaiveDate>,
+    /// Only pair lines with the same description (case-insensitive)
+    #[builder(default)]
+    pub same_description: bool,
+    /// Only pairs where at least one line comes from this import batch
+    pub import_batch_id: Option<Uuid>,
+}
+
+impl Default for TransferDetection {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+/// An outgoing and an incoming uncategorized line that look like one internal transfer
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct TransferCandidate {
+    pub outgoing_transaction_id: Uuid,
+    pub outgoing_account_path: String,
+    pub outgoing_date: DateTime<Utc>,
+    pub outgoing_description: String,
+    pub incoming_transaction_id: Uuid,
+    pub incoming_account_path: String,
+    pub incoming_date: DateTime<Utc>,
+    pub incoming_description: String,
+    pub amount: Decimal,
+    /// Days between the two lines
+    pub day_gap: i32,
+    /// Either line could also pair with another one
+    #[sqlx(skip)]
+    pub ambiguous: bool,
+}
+
+/// Two transactions linked as one internal transfer
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct TransferLink {
+    pub id: Uuid,
+    pub outgoing_transaction_id: Uuid,
+    pub incoming_transaction_id: Uuid,
+    pub outgoing_entry_id: Uuid,
+    pub incoming_entry_id: Uuid,
+    pub amount: Decimal,
+    pub created_at: DateTime<Utc>,
+    pub outgoing_account_path: String,
+    pub incoming_account_path: String,
+    pub outgoing_date: DateTime<Utc>,
+}
@@ -5,7 +5,7 @@ use crate::{AccountSubtype, NewAccountByPath};
 use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
-use sqlx::PgPool;
+use sqlx::{PgConnection, PgPool};
 use uuid::Uuid;
 
 /// Struct for updating existing accounts - all fields are optional
@@ -55,7 +55,7 @@ impl AccountService {
                 id, name, full_path,
                 account_type, account_subtype,
                 parent_id, symbol, quantity, average_cost, address, 
-                purchase_date, purchase_price, currency, is_active, 
+                purchase_date, purchase_price, currency, is_category, is_active, 
                 notes, created_at, updated_at
             FROM accounts 
             WHERE is_active = true 
@@ -75,7 +75,7 @@ impl AccountService {
                 id, name, full_path,
                 account_type, account_subtype,
                 parent_id, symbol, quantity, average_cost, address, 
-                purchase_date, purchase_price, currency, is_active, 
+                purchase_date, purchase_price, currency, is_category, is_active, 
                 notes, created_at, updated_at
             FROM accounts 
             WHERE account_type = $1 AND is_active = true 
@@ -96,7 +96,7 @@ impl AccountService {
                 id, name, full_path,
                 account_type, account_subtype,
                 parent_id, symbol, quantity, average_cost, address, 
-                purchase_date, purchase_price, currency, is_active, 
+                purchase_date, purchase_price, currency, is_category, is_active, 
                 notes, created_at, updated_at
             FROM accounts 
             WHERE id = $1
@@ -111,21 +111,33 @@ impl AccountService {
 
     /// Create a new account
     pub async fn create_account(&self, new_account: NewAccount) -> Result<Account> {
+        let mut conn = self.pool.acquire().await?;
+        self.create_account_in(&mut conn, new_account).await
+    }
+
+    /// Create a new account on `conn`, e.g. inside a caller's database transaction
+    pub(crate) async fn create_account_in(
+        &self,
+        conn: &mut PgConnection,
+        new_account: NewAccount,
+    ) -> Result<Account> {
         // Validate the account before creation
-        self.validator.validate_new_account(&new_account).await?;
+        self.validator
+            .validate_new_account_in(&mut *conn, &new_account)
+            .await?;
 
         let account = sqlx::query_as::<_, Account>(
             r#"
             INSERT INTO accounts (
                 name, account_type, account_subtype, parent_id,
                 symbol, quantity, average_cost, address, purchase_date, 
-                purchase_price, currency, notes
+                purchase_price, currency, is_category, notes
             )
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
             RETURNING 
                 id, name, full_path, account_type, account_subtype, parent_id,
                 symbol, quantity, average_cost, address, purchase_date,
-                purchase_price, currency, is_active, notes, created_at, updated_at
+                purchase_price, currency, is_category, is_active, notes, created_at, updated_at
             "#,
         )
         .bind(&new_account.name)
@@ -139,8 +151,9 @@ impl AccountService {
         .bind(new_account.purchase_date)
         .bind(new_account.purchase_price)
         .bind(&new_account.currency)
+        .bind(new_account.is_category)
         .bind(&new_account.notes)
-        .fetch_one(&self.pool)
+        .fetch_one(conn)
         .await?;
 
         Ok(account)
@@ -148,6 +161,20 @@ impl AccountService {
 
     /// Create an account by path, auto-creating missing parent accounts
     pub async fn create_account_by_path(&self, account: NewAccountByPath) -> Result<Account> {
+        let mut tx = self.pool.begin().await?;
+        let created = self.create_account_by_path_in(&mut tx, account).await?;
+        tx.commit().await?;
+
+        Ok(created)
+    }
+
+    /// [`Self::create_account_by_path`] on `conn`, so the hierarchy is created atomically
+    /// with the caller's own writes
+    pub(crate) async fn create_account_by_path_in(
+        &self,
+        conn: &mut PgConnection,
+        account: NewAccountByPath,
+    ) -> Result<Account> {
         // Parse the path into components
         let path_parts: Vec<&str> = account.full_path.split(':').collect();
 
@@ -166,7 +193,10 @@ impl AccountService {
             current_path.push_str(part);
 
             // Check if this level already exists
-            if let Ok(existing_account) = self.get_account_by_path(&current_path).await {
+            if let Some(existing_account) = self
+                .get_account_by_path_optional_in(&mut *conn, &current_path)
+                .await?
+            {
                 current_parent_id = Some(existing_account.id);
                 continue;
             }
@@ -185,10 +215,11 @@ impl AccountService {
                     address: account.address,
                     purchase_date: account.purchase_date,
                     purchase_price: account.purchase_price,
+                    is_category: account.is_category,
                     notes: account.notes,
                 };
 
-                return self.create_account(new_account).await;
+                return self.create_account_in(conn, new_account).await;
             } else {
                 // Create intermediate account as Category
                 let intermediate_account = NewAccount {
@@ -203,10 +234,13 @@ impl AccountService {
                     address: None,
                     purchase_date: None,
                     purchase_price: None,
+                    is_category: false,
                     notes: None,
                 };
 
-                let created_account = self.create_account(intermediate_account).await?;
+                let created_account = self
+                    .create_account_in(&mut *conn, intermediate_account)
+                    .await?;
                 current_parent_id = Some(created_account.id);
             }
         }
@@ -214,6 +248,74 @@ impl AccountService {
         unreachable!("Should have returned in the loop")
     }
 
+    /// Get the account at `path`, creating it and any missing parents first
+    ///
+    /// The type is inferred from the root segment and the new leaf gets that
+    /// type's default subtype and its nearest existing ancestor's currency.
+    /// Returns the account with the paths of the accounts created, parents first.
+    pub async fn ensure_account_by_path(&self, path: &str) -> Result<(Account, Vec<String>)> {
+        let mut tx = self.pool.begin().await?;
+        let ensured = self.ensure_account_by_path_in(&mut tx, path).await?;
+        tx.commit().await?;
+
+        Ok(ensured)
+    }
+
+    /// [`Self::ensure_account_by_path`] on `conn`, so the accounts created are rolled
+    /// back with the caller's database transaction
+    pub(crate) async fn ensure_account_by_path_in(
+        &self,
+        conn: &mut PgConnection,
+        path: &str,
+    ) -> Result<(Account, Vec<String>)> {
+        if let Some(account) = self
+            .get_account_by_path_optional_in(&mut *conn, path)
+            .await?
+        {
+            return Ok((account, Vec::new()));
+        }
+
+        let account_type = AccountType::from_path_root(path).ok_or_else(|| {
+            crate::CoreError::InvalidInput(format!(
+                "Cannot infer the account type of '{}': it should start with Assets, Liabilities, Equity, Income or Expenses",
+                path
+            ))
+        })?;
+
+        let parts: Vec<&str> = path.split(':').collect();
+        let mut created = Vec::new();
+        let mut currency = None;
+        for depth in (1..parts.len()).rev() {
+            let prefix = parts[..depth].join(":");
+            match self
+                .get_account_by_path_optional_in(&mut *conn, &prefix)
+                .await?
+            {
+                Some(ancestor) => {
+                    currency = Some(ancestor.currency);
+                    break;
+                }
+                None => created.push(prefix),
+            }
+        }
+        created.reverse();
+        created.push(path.to_string());
+
+        let account = self
+            .create_account_by_path_in(
+                conn,
+                NewAccountByPath::builder()
+                    .full_path(path)
+                    .account_type(account_type)
+                    .account_subtype(account_type.default_subtype())
+                    .maybe_currency(currency)
+                    .build(),
+            )
+            .await?;
+
+        Ok((account, created))
+    }
+
     /// Get account by full path (e.g., "Assets:Current Assets:Checking")
     pub async fn get_account_by_path(&self, path: &str) -> Result<Account> {
         let account = sqlx::query_as::<_, Account>(
@@ -222,7 +324,7 @@ impl AccountService {
                 id, name, full_path,
                 account_type, account_subtype,
                 parent_id, symbol, quantity, average_cost, address, 
-                purchase_date, purchase_price, currency, is_active, 
+                purchase_date, purchase_price, currency, is_category, is_active, 
                 notes, created_at, updated_at
             FROM accounts 
             WHERE full_path = $1 AND is_active = true
@@ -276,7 +378,7 @@ impl AccountService {
             WHERE id = $1
             RETURNING id, name, full_path, account_type, account_subtype, parent_id,
                       symbol, quantity, average_cost, address, purchase_date,
-                      purchase_price, currency, is_active, notes, created_at, updated_at
+                      purchase_price, currency, is_category, is_active, notes, created_at, updated_at
             "#,
         )
         .bind(account_id)
@@ -323,7 +425,7 @@ impl AccountService {
             WHERE id = $1
             RETURNING id, name, full_path, account_type, account_subtype, parent_id,
                       symbol, quantity, average_cost, address, purchase_date,
-                      purchase_price, currency, is_active, notes, created_at, updated_at
+                      purchase_price, currency, is_category, is_active, notes, created_at, updated_at
             "#,
         )
         .bind(account_id)
@@ -335,25 +437,63 @@ impl AccountService {
 
     /// Make get_account_by_path return Option for consistency
     pub async fn get_account_by_path_optional(&self, path: &str) -> Result<Option<Account>> {
+        let mut conn = self.pool.acquire().await?;
+        self.get_account_by_path_optional_in(&mut conn, path).await
+    }
+
+    pub(crate) async fn get_account_by_path_optional_in(
+        &self,
+        conn: &mut PgConnection,
+        path: &str,
+    ) -> Result<Option<Account>> {
         let account = sqlx::query_as::<_, Account>(
             r#"
             SELECT 
                 id, name, full_path,
                 account_type, account_subtype,
                 parent_id, symbol, quantity, average_cost, address, 
-                purchase_date, purchase_price, currency, is_active, 
+                purchase_date, purchase_price, currency, is_category, is_active, 
                 notes, created_at, updated_at
             FROM accounts 
             WHERE full_path = $1 AND is_active = true
             "#,
         )
         .bind(path)
-        .fetch_optional(&self.pool)
+        .fetch_optional(conn)
         .await?;
 
         Ok(account)
     }
 }
 
+/// Order tree nodes so that every parent comes before its children
+///
+/// A parent missing from `nodes` does not hold its children back; on a cycle the
+/// remaining nodes are appended as they are.
+pub(crate) fn sorted_parents_first<'a, T, K: PartialEq>(
+    nodes: &'a [T],
+    key: impl Fn(&'a T) -> K,
+    parent_key: impl Fn(&'a T) -> Option<K>,
+) -> Vec<&'a T> {
+    let mut sorted: Vec<&'a T> = Vec::with_capacity(nodes.len());
+    let mut remaining: Vec<&'a T> = nodes.iter().collect();
+
+    while !remaining.is_empty() {
+        let (ready, pending): (Vec<_>, Vec<_>) = remaining.into_iter().partition(|node| {
+            parent_key(node).is_none_or(|parent| {
+                !nodes.iter().any(|n| key(n) == parent) || sorted.iter().any(|s| key(s) == parent)
+            })
+        });
+        if ready.is_empty() {
+            sorted.extend(pending);
+            break;
+        }
+        sorted.extend(ready);
+        remaining = pending;
+    }
+
+    sorted
+}
+
 #[cfg(test)]
 mod tests;
@@ -122,6 +122,7 @@ async fn test_create_investment_account() {
         address: None,
         purchase_date: None,  // Real estate fields should be None for stocks
         purchase_price: None, // Real estate fields should be None for stocks
+        is_category: false,
         notes: Some("Technology stock".to_string()),
     };
 
@@ -154,6 +155,7 @@ async fn test_create_account_by_path_simple() {
         address: None,
         purchase_date: None,
         purchase_price: None,
+        is_category: false,
         notes: None,
     };
 
@@ -188,6 +190,7 @@ async fn test_create_account_by_path_with_hierarchy() {
         address: None,
         purchase_date: None,
         purchase_price: None,
+        is_category: false,
         notes: None,
     };
 
@@ -232,6 +235,7 @@ async fn test_empty_account_path() {
         address: None,
         purchase_date: None,
         purchase_price: None,
+        is_category: false,
         notes: None,
     };
 
@@ -282,6 +286,7 @@ async fn test_update_account_investment_fields() {
         address: None,
         purchase_date: None,
         purchase_price: None,
+        is_category: false,
         notes: None,
     };
 
@@ -443,6 +448,7 @@ async fn test_get_account_by_path_optional() {
         address: None,
         purchase_date: None,
         purchase_price: None,
+        is_category: false,
         notes: None,
     };
 
@@ -457,6 +463,64 @@ async fn test_get_account_by_path_optional() {
     assert_eq!(found_account.id, created_account.id);
 }
 
+#[tokio::test]
+async fn test_ensure_account_by_path_creates_missing_hierarchy() {
+    let (pool, _container) = setup_test_db().await;
+    let service = AccountService::new(pool);
+
+    let usd_parent = NewAccountByPath::builder()
+        .full_path("Expenses:Travel")
+        .account_type(AccountType::Expense)
+        .account_subtype(AccountSubtype::Category)
+        .currency("USD")
+        .build();
+    service.create_account_by_path(usd_parent).await.unwrap();
+
+    // Only the missing levels are created; the leaf takes the type's default subtype
+    let (account, created) = service
+        .ensure_account_by_path("Expenses:Travel:Trains:Night Trains")
+        .await
+        .unwrap();
+    assert_eq!(
+        created,
+        vec![
+            "Expenses:Travel:Trains".to_string(),
+            "Expenses:Travel:Trains:Night Trains".to_string()
+        ]
+    );
+    assert_eq!(account.account_type, AccountType::Expense);
+    assert_eq!(account.account_subtype, AccountSubtype::OtherExpense);
+    assert_eq!(account.currency, "USD");
+
+    // Existing accounts are returned as is
+    let (again, created) = service
+        .ensure_account_by_path("Expenses:Travel:Trains:Night Trains")
+        .await
+        .unwrap();
+    assert_eq!(again.id, account.id);
+    assert!(created.is_empty());
+
+    let (liability, created) = service
+        .ensure_account_by_path("Liabilities:Credit Cards:Visa")
+        .await
+        .unwrap();
+    assert_eq!(created.len(), 3);
+    assert_eq!(liability.account_type, AccountType::Liability);
+    assert_eq!(liability.account_subtype, AccountSubtype::OtherLiability);
+    assert_eq!(liability.currency, "EUR");
+
+    // Without a known root there is no type to infer
+    let result = service.ensure_account_by_path("Misc:Stuff").await;
+    assert!(matches!(result, Err(crate::CoreError::InvalidInput(_))));
+    assert!(
+        service
+            .get_account_by_path_optional("Misc")
+            .await
+            .unwrap()
+            .is_none()
+    );
+}
+
 #[tokio::test]
 async fn test_account_updates_has_updates() {
     // Test empty updates
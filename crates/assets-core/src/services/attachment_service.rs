@@ -0,0 +1,356 @@
+use crate::error::{CoreError, Result};
+use crate::models::{Attachment, AttachmentTarget};
+use crate::services::FileImportService;
+use sqlx::PgPool;
+use std::fs;
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+/// Environment variable overriding where attached files are stored
+pub const ATTACHMENTS_DIR_ENV: &str = "RUSTY_ASSETS_ATTACHMENTS_DIR";
+
+/// MIME type used when the file extension is not recognized
+pub const DEFAULT_MIME_TYPE: &str = "application/octet-stream";
+
+/// Receipts, invoices and statements linked to transactions and accounts
+///
+/// Files are copied into a content-addressed store: each one is saved once under
+/// its SHA-256 (`<storage_dir>/<first two hex digits>/<hash>`), however many
+/// transactions or accounts it is attached to. The database only keeps the hash,
+/// the original name and the MIME type.
+pub struct AttachmentService {
+    pool: PgPool,
+    storage_dir: PathBuf,
+}
+
+impl AttachmentService {
+    pub fn new(pool: PgPool, storage_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            pool,
+            storage_dir: storage_dir.into(),
+        }
+    }
+
+    /// Store files in `RUSTY_ASSETS_ATTACHMENTS_DIR`, else `~/.rusty-assets/attachments`
+    pub fn from_env(pool: PgPool) -> Self {
+        Self::new(pool, default_storage_dir())
+    }
+
+    /// Root of the content-addressed store
+    pub fn storage_dir(&self) -> &Path {
+        &self.storage_dir
+    }
+
+    /// Copy a file into the store and link it to a transaction or an account
+    ///
+    /// Attaching the same content to the same target twice returns the existing attachment.
+    pub async fn attach<P: AsRef<Path>>(
+        &self,
+        file_path: P,
+        target: AttachmentTarget,
+        notes: Option<&str>,
+    ) -> Result<Attachment> {
+        self.ensure_target_exists(target).await?;
+        let stored = self.store_file(file_path.as_ref())?;
+        self.insert_attachment(&stored, target, notes).await
+    }
+
+    /// Attach an imported statement file to every transaction of its import batch
+    pub async fn attach_to_import_batch<P: AsRef<Path>>(
+        &self,
+        file_path: P,
+        import_batch_id: Uuid,
+        notes: Option<&str>,
+    ) -> Result<Vec<Attachment>> {
+        let transaction_ids: Vec<Uuid> = sqlx::query_scalar(
+            "SELECT id FROM transactions WHERE import_batch_id = $1 ORDER BY transaction_date, id",
+        )
+        .bind(import_batch_id)
+        .fetch_all(&self.pool)
+        .await?;
+        if transaction_ids.is_empty() {
+            return Err(CoreError::NotFound(format!(
+                "Transactions of import batch {}",
+                import_batch_id
+            )));
+        }
+
+        let stored = self.store_file(file_path.as_ref())?;
+        let mut attachments = Vec::with_capacity(transaction_ids.len());
+        for transaction_id in transaction_ids {
+            attachments.push(
+                self.insert_attachment(
+                    &stored,
+                    AttachmentTarget::Transaction(transaction_id),
+                    notes,
+                )
+                .await?,
+            );
+        }
+        Ok(attachments)
+    }
+
+    /// Attachments of one transaction or account, oldest first
+    pub async fn list_for(&self, target: AttachmentTarget) -> Result<Vec<Attachment>> {
+        let (transaction_id, account_id) = target_columns(target);
+        let attachments = sqlx::query_as::<_, Attachment>(
+            r#"
+            SELECT id, content_hash, file_name, mime_type, file_size, transaction_id, account_id, notes, created_at
+            FROM attachments
+            WHERE transaction_id IS NOT DISTINCT FROM $1 AND account_id IS NOT DISTINCT FROM $2
+            ORDER BY created_at, id
+            "#,
+        )
+        .bind(transaction_id)
+        .bind(account_id)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(attachments)
+    }
+
+    /// Every attachment, oldest first
+    pub async fn list_all(&self) -> Result<Vec<Attachment>> {
+        let attachments = sqlx::query_as::<_, Attachment>(
+            r#"
+            SELECT id, content_hash, file_name, mime_type, file_size, transaction_id, account_id, notes, created_at
+            FROM attachments
+            ORDER BY created_at, id
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(attachments)
+    }
+
+    pub async fn get(&self, id: Uuid) -> Result<Attachment> {
+        sqlx::query_as::<_, Attachment>(
+            r#"
+            SELECT id, content_hash, file_name, mime_type, file_size, transaction_id, account_id, notes, created_at
+            FROM attachments
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| CoreError::NotFound(format!("Attachment {}", id)))
+    }
+
+    /// Location of an attachment's content in the store
+    ///
+    /// Fails when the file is missing, e.g. after restoring a backup without its store.
+    pub fn content_path(&self, attachment: &Attachment) -> Result<PathBuf> {
+        let path = self.blob_path(&attachment.content_hash);
+        if !path.is_file() {
+            return Err(CoreError::NotFound(format!(
+                "Stored file for attachment {} ({}) at {}",
+                attachment.id,
+                attachment.file_name,
+                path.display()
+            )));
+        }
+        Ok(path)
+    }
+
+    /// Remove an attachment; its stored file goes too once nothing references it
+    ///
+    /// Returns the removed attachment, or `None` when there was none with this ID.
+    pub async fn detach(&self, id: Uuid) -> Result<Option<Attachment>> {
+        let Some(attachment) = sqlx::query_as::<_, Attachment>(
+            r#"
+            DELETE FROM attachments
+            WHERE id = $1
+            RETURNING id, content_hash, file_name, mime_type, file_size, transaction_id, account_id, notes, created_at
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?
+        else {
+            return Ok(None);
+        };
+
+        let still_used: bool =
+            sqlx::query_scalar("SELECT EXISTS (SELECT 1 FROM attachments WHERE content_hash = $1)")
+                .bind(&attachment.content_hash)
+                .fetch_one(&self.pool)
+                .await?;
+        if !still_used {
+            let path = self.blob_path(&attachment.content_hash);
+            if path.is_file() {
+                fs::remove_file(path)?;
+            }
+        }
+
+        Ok(Some(attachment))
+    }
+
+    async fn ensure_target_exists(&self, target: AttachmentTarget) -> Result<()> {
+        match target {
+            AttachmentTarget::Transaction(id) => {
+                let exists: bool =
+                    sqlx::query_scalar("SELECT EXISTS (SELECT 1 FROM transactions WHERE id = $1)")
+                        .bind(id)
+                        .fetch_one(&self.pool)
+                        .await?;
+                if !exists {
+                    return Err(CoreError::NotFound(format!("Transaction {}", id)));
+                }
+            }
+            AttachmentTarget::Account(id) => {
+                let exists: bool =
+                    sqlx::query_scalar("SELECT EXISTS (SELECT 1 FROM accounts WHERE id = $1)")
+                        .bind(id)
+                        .fetch_one(&self.pool)
+                        .await?;
+                if !exists {
+                    return Err(CoreError::AccountNotFound(id.to_string()));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    async fn insert_attachment(
+        &self,
+        stored: &StoredFile,
+        target: AttachmentTarget,
+        notes: Option<&str>,
+    ) -> Result<Attachment> {
+        let (transaction_id, account_id) = target_columns(target);
+
+        let existing = sqlx::query_as::<_, Attachment>(
+            r#"
+            SELECT id, content_hash, file_name, mime_type, file_size, transaction_id, account_id, notes, created_at
+            FROM attachments
+            WHERE content_hash = $1
+              AND transaction_id IS NOT DISTINCT FROM $2
+              AND account_id IS NOT DISTINCT FROM $3
+            "#,
+        )
+        .bind(&stored.content_hash)
+        .bind(transaction_id)
+        .bind(account_id)
+        .fetch_optional(&self.pool)
+        .await?;
+        if let Some(existing) = existing {
+            return Ok(existing);
+        }
+
+        let attachment = sqlx::query_as::<_, Attachment>(
+            r#"
+            INSERT INTO attachments (content_hash, file_name, mime_type, file_size, transaction_id, account_id, notes)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            RETURNING id, content_hash, file_name, mime_type, file_size, transaction_id, account_id, notes, created_at
+            "#,
+        )
+        .bind(&stored.content_hash)
+        .bind(&stored.file_name)
+        .bind(&stored.mime_type)
+        .bind(stored.file_size)
+        .bind(transaction_id)
+        .bind(account_id)
+        .bind(notes)
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(attachment)
+    }
+
+    /// Copy a file into the store unless its content is already there
+    fn store_file(&self, file_path: &Path) -> Result<StoredFile> {
+        if !file_path.is_file() {
+            return Err(CoreError::InvalidInput(format!(
+                "{} is not a file",
+                file_path.display()
+            )));
+        }
+        let content_hash = FileImportService::calculate_file_hash(file_path)?;
+        let file_size = FileImportService::get_file_size(file_path)?;
+        let file_name = file_path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or("unknown")
+            .to_string();
+
+        let blob_path = self.blob_path(&content_hash);
+        if !blob_path.is_file() {
+            let dir = blob_path.parent().expect("blob paths have a parent");
+            fs::create_dir_all(dir)?;
+            // Copy under a temporary name first so an interrupted copy never looks stored
+            let partial = dir.join(format!("{}.partial-{}", content_hash, Uuid::new_v4()));
+            fs::copy(file_path, &partial)?;
+            fs::rename(&partial, &blob_path)?;
+        }
+
+        Ok(StoredFile {
+            mime_type: mime_type_for(file_path).to_string(),
+            content_hash,
+            file_name,
+            file_size,
+        })
+    }
+
+    fn blob_path(&self, content_hash: &str) -> PathBuf {
+        self.storage_dir
+            .join(&content_hash[..2.min(content_hash.len())])
+            .join(content_hash)
+    }
+}
+
+struct StoredFile {
+    content_hash: String,
+    file_name: String,
+    mime_type: String,
+    file_size: i64,
+}
+
+fn target_columns(target: AttachmentTarget) -> (Option<Uuid>, Option<Uuid>) {
+    match target {
+        AttachmentTarget::Transaction(id) => (Some(id), None),
+        AttachmentTarget::Account(id) => (None, Some(id)),
+    }
+}
+
+/// `RUSTY_ASSETS_ATTACHMENTS_DIR`, else `~/.rusty-assets/attachments`
+fn default_storage_dir() -> PathBuf {
+    if let Ok(dir) = std::env::var(ATTACHMENTS_DIR_ENV) {
+        if !dir.is_empty() {
+            return PathBuf::from(dir);
+        }
+    }
+    std::env::var("HOME")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| PathBuf::from("."))
+        .join(".rusty-assets")
+        .join("attachments")
+}
+
+/// MIME type guessed from the file extension
+pub fn mime_type_for(path: &Path) -> &'static str {
+    let extension = path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .map(|extension| extension.to_ascii_lowercase());
+    match extension.as_deref() {
+        Some("pdf") => "application/pdf",
+        Some("png") => "image/png",
+        Some("jpg" | "jpeg") => "image/jpeg",
+        Some("gif") => "image/gif",
+        Some("webp") => "image/webp",
+        Some("heic") => "image/heic",
+        Some("tif" | "tiff") => "image/tiff",
+        Some("txt") => "text/plain",
+        Some("csv") => "text/csv",
+        Some("html" | "htm") => "text/html",
+        Some("xml") => "application/xml",
+        Some("json") => "application/json",
+        Some("ofx" | "qfx") => "application/x-ofx",
+        Some("xls") => "application/vnd.ms-excel",
+        Some("xlsx") => "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+        Some("eml") => "message/rfc822",
+        _ => DEFAULT_MIME_TYPE,
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,171 @@
+use super::*;
+use crate::models::{AccountSubtype, AccountType, NewAccountByPath};
+use crate::services::{AccountService, TransactionService};
+use crate::tests::utils::*;
+use chrono::{TimeZone, Utc};
+use rust_decimal::Decimal;
+
+async fn create_accounts(pool: &PgPool) -> (Uuid, Uuid) {
+    let account_service = AccountService::new(pool.clone());
+    let mut ids = Vec::new();
+    for (path, account_type, subtype) in [
+        (
+            "Assets:Checking",
+            AccountType::Asset,
+            AccountSubtype::Checking,
+        ),
+        (
+            "Expenses:Groceries",
+            AccountType::Expense,
+            AccountSubtype::Category,
+        ),
+    ] {
+        let account = account_service
+            .create_account_by_path(
+                NewAccountByPath::builder()
+                    .full_path(path)
+                    .account_type(account_type)
+                    .account_subtype(subtype)
+                    .build(),
+            )
+            .await
+            .unwrap();
+        ids.push(account.id);
+    }
+    (ids[0], ids[1])
+}
+
+async fn post(pool: &PgPool, checking: Uuid, groceries: Uuid, batch: Option<Uuid>) -> Uuid {
+    let mut transaction = TransactionService::create_simple_transaction(
+        "Groceries".to_string(),
+        groceries,
+        checking,
+        Decimal::from(42),
+        Utc.with_ymd_and_hms(2025, 3, 1, 12, 0, 0).unwrap(),
+        None,
+    );
+    transaction.import_batch_id = batch;
+    TransactionService::new(pool.clone())
+        .create_transaction(transaction)
+        .await
+        .unwrap()
+        .transaction
+        .id
+}
+
+#[test]
+fn test_mime_type_from_extension() {
+    assert_eq!(mime_type_for(Path::new("receipt.PDF")), "application/pdf");
+    assert_eq!(mime_type_for(Path::new("photo.jpeg")), "image/jpeg");
+    assert_eq!(mime_type_for(Path::new("export.csv")), "text/csv");
+    assert_eq!(mime_type_for(Path::new("README")), DEFAULT_MIME_TYPE);
+}
+
+#[tokio::test]
+async fn test_attachments_share_stored_content() {
+    let (pool, _container) = setup_test_db().await;
+    let (checking, groceries) = create_accounts(&pool).await;
+    let first = post(&pool, checking, groceries, None).await;
+    let second = post(&pool, checking, groceries, None).await;
+
+    let store = tempfile::tempdir().unwrap();
+    let service = AttachmentService::new(pool.clone(), store.path());
+    let inbox = tempfile::tempdir().unwrap();
+    let receipt = inbox.path().join("receipt.pdf");
+    fs::write(&receipt, b"%PDF receipt").unwrap();
+
+    let attached = service
+        .attach(&receipt, AttachmentTarget::Transaction(first), Some("Till"))
+        .await
+        .unwrap();
+    assert_eq!(attached.file_name, "receipt.pdf");
+    assert_eq!(attached.mime_type, "application/pdf");
+    assert_eq!(attached.file_size, 12);
+    assert_eq!(attached.target(), AttachmentTarget::Transaction(first));
+
+    // Same content on the same transaction: the existing attachment comes back
+    let again = service
+        .attach(&receipt, AttachmentTarget::Transaction(first), None)
+        .await
+        .unwrap();
+    assert_eq!(again.id, attached.id);
+
+    // Same content elsewhere: a new link to the same stored file
+    let statement = service
+        .attach(&receipt, AttachmentTarget::Account(checking), None)
+        .await
+        .unwrap();
+    assert_eq!(statement.content_hash, attached.content_hash);
+    let stored = service.content_path(&attached).unwrap();
+    assert_eq!(fs::read(&stored).unwrap(), b"%PDF receipt");
+    assert_eq!(service.list_all().await.unwrap().len(), 2);
+    assert!(
+        service
+            .list_for(AttachmentTarget::Transaction(second))
+            .await
+            .unwrap()
+            .is_empty()
+    );
+
+    assert!(matches!(
+        service
+            .attach(
+                &receipt,
+                AttachmentTarget::Transaction(Uuid::new_v4()),
+                None
+            )
+            .await,
+        Err(CoreError::NotFound(_))
+    ));
+
+    // The stored file stays while another attachment still uses it
+    service.detach(attached.id).await.unwrap().unwrap();
+    assert!(stored.is_file());
+    service.detach(statement.id).await.unwrap().unwrap();
+    assert!(!stored.exists());
+    assert!(service.detach(statement.id).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_attach_statement_to_import_batch() {
+    let (pool, _container) = setup_test_db().await;
+    let (checking, groceries) = create_accounts(&pool).await;
+    let batch = Uuid::new_v4();
+    let first = post(&pool, checking, groceries, Some(batch)).await;
+    let second = post(&pool, checking, groceries, Some(batch)).await;
+    let other = post(&pool, checking, groceries, None).await;
+
+    let store = tempfile::tempdir().unwrap();
+    let service = AttachmentService::new(pool.clone(), store.path());
+    let inbox = tempfile::tempdir().unwrap();
+    let statement = inbox.path().join("statement-2025-03.csv");
+    fs::write(&statement, "date,amount\n").unwrap();
+
+    let attachments = service
+        .attach_to_import_batch(&statement, batch, None)
+        .await
+        .unwrap();
+    let mut linked: Vec<_> = attachments
+        .iter()
+        .map(|attachment| attachment.transaction_id.unwrap())
+        .collect();
+    linked.sort();
+    let mut expected = vec![first, second];
+    expected.sort();
+    assert_eq!(linked, expected);
+    assert_eq!(attachments[0].mime_type, "text/csv");
+    assert!(
+        service
+            .list_for(AttachmentTarget::Transaction(other))
+            .await
+            .unwrap()
+            .is_empty()
+    );
+
+    assert!(matches!(
+        service
+            .attach_to_import_batch(&statement, Uuid::new_v4(), None)
+            .await,
+        Err(CoreError::NotFound(_))
+    ));
+}
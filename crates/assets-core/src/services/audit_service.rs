@@ -0,0 +1,113 @@
+use crate::error::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// One row change recorded by the audit triggers
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct AuditLogEntry {
+    pub id: i64,
+    pub changed_at: DateTime<Utc>,
+    pub changed_by: String,
+    /// Rows sharing this ID were changed by the same database transaction
+    pub db_transaction_id: i64,
+    pub table_name: String,
+    pub operation: String,
+    pub record_id: Uuid,
+    pub transaction_id: Option<Uuid>,
+    pub account_id: Option<Uuid>,
+    pub old_data: Option<serde_json::Value>,
+    pub new_data: Option<serde_json::Value>,
+}
+
+impl AuditLogEntry {
+    /// Names of the columns whose value differs between old and new data
+    pub fn changed_fields(&self) -> Vec<String> {
+        match (&self.old_data, &self.new_data) {
+            (Some(serde_json::Value::Object(old)), Some(serde_json::Value::Object(new))) => new
+                .iter()
+                .filter(|(key, value)| old.get(*key) != Some(*value))
+                .map(|(key, _)| key.clone())
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// Read access to the append-only audit log
+pub struct AuditService {
+    pool: PgPool,
+}
+
+impl AuditService {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// All changes to a transaction and its journal entries, oldest first
+    pub async fn history_for_transaction(
+        &self,
+        transaction_id: Uuid,
+    ) -> Result<Vec<AuditLogEntry>> {
+        let entries = sqlx::query_as::<_, AuditLogEntry>(
+            r#"
+            SELECT id, changed_at, changed_by, db_transaction_id, table_name, operation,
+                   record_id, transaction_id, account_id, old_data, new_data
+            FROM audit_log
+            WHERE transaction_id = $1
+            ORDER BY id
+            "#,
+        )
+        .bind(transaction_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(entries)
+    }
+
+    /// All changes to an account and to journal entries posted to it, oldest first
+    pub async fn history_for_account(&self, account_id: Uuid) -> Result<Vec<AuditLogEntry>> {
+        let entries = sqlx::query_as::<_, AuditLogEntry>(
+            r#"
+            SELECT id, changed_at, changed_by, db_transaction_id, table_name, operation,
+                   record_id, transaction_id, account_id, old_data, new_data
+            FROM audit_log
+            WHERE account_id = $1
+            ORDER BY id
+            "#,
+        )
+        .bind(account_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(entries)
+    }
+
+    /// Most recent changes across the whole ledger, newest first
+    pub async fn recent_changes(
+        &self,
+        table_name: Option<&str>,
+        limit: u32,
+    ) -> Result<Vec<AuditLogEntry>> {
+        let entries = sqlx::query_as::<_, AuditLogEntry>(
+            r#"
+            SELECT id, changed_at, changed_by, db_transaction_id, table_name, operation,
+                   record_id, transaction_id, account_id, old_data, new_data
+            FROM audit_log
+            WHERE $1::text IS NULL OR table_name = $1
+            ORDER BY id DESC
+            LIMIT $2
+            "#,
+        )
+        .bind(table_name)
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(entries)
+    }
+}
+
+#[cfg(test)]
+mod tests;
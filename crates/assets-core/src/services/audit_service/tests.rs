@@ -0,0 +1,132 @@
+use super::*;
+use crate::models::{AccountSubtype, AccountType};
+use crate::services::{AccountService, AccountUpdates, TransactionService};
+use crate::tests::utils::*;
+use rust_decimal::Decimal;
+
+#[tokio::test]
+async fn test_transaction_history_records_create_and_delete() {
+    let (pool, _container) = setup_test_db().await;
+    let audit_service = AuditService::new(pool.clone());
+    let account_service = AccountService::new(pool.clone());
+    let tx_service = TransactionService::new(pool.clone());
+
+    let checking = account_service
+        .create_account(create_test_new_account_with_type(
+            "Checking",
+            AccountType::Asset,
+            AccountSubtype::Checking,
+        ))
+        .await
+        .unwrap();
+    let groceries = account_service
+        .create_account(create_test_new_account_with_type(
+            "Groceries",
+            AccountType::Expense,
+            AccountSubtype::Food,
+        ))
+        .await
+        .unwrap();
+
+    let created = tx_service
+        .create_transaction(TransactionService::create_simple_transaction(
+            "Supermarket".to_string(),
+            groceries.id,
+            checking.id,
+            Decimal::new(4550, 2),
+            Utc::now(),
+            None,
+        ))
+        .await
+        .unwrap();
+    tx_service
+        .delete_transaction(created.transaction.id)
+        .await
+        .unwrap();
+
+    let history = audit_service
+        .history_for_transaction(created.transaction.id)
+        .await
+        .unwrap();
+    let operations: Vec<_> = history
+        .iter()
+        .map(|e| (e.table_name.as_str(), e.operation.as_str()))
+        .collect();
+    assert_eq!(
+        operations,
+        vec![
+            ("transactions", "INSERT"),
+            ("journal_entries", "INSERT"),
+            ("journal_entries", "INSERT"),
+            ("journal_entries", "DELETE"),
+            ("journal_entries", "DELETE"),
+            ("transactions", "DELETE"),
+        ]
+    );
+
+    // The deleted transaction can still be read back from the trail
+    let deleted = history.last().unwrap();
+    assert!(deleted.new_data.is_none());
+    assert_eq!(
+        deleted.old_data.as_ref().unwrap()["description"],
+        "Supermarket"
+    );
+}
+
+#[tokio::test]
+async fn test_account_history_shows_changed_fields() {
+    let (pool, _container) = setup_test_db().await;
+    let audit_service = AuditService::new(pool.clone());
+    let account_service = AccountService::new(pool.clone());
+
+    let account = account_service
+        .create_account(create_test_new_account_with_name("Savings"))
+        .await
+        .unwrap();
+    account_service
+        .update_account(
+            account.id,
+            AccountUpdates {
+                notes: Some("Emergency fund".to_string()),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+    let history = audit_service.history_for_account(account.id).await.unwrap();
+    assert_eq!(history.len(), 2);
+    assert_eq!(history[1].operation, "UPDATE");
+    assert!(history[1].changed_fields().contains(&"notes".to_string()));
+    assert!(!history[1].changed_fields().contains(&"name".to_string()));
+}
+
+#[tokio::test]
+async fn test_audit_log_is_append_only() {
+    let (pool, _container) = setup_test_db().await;
+    let account_service = AccountService::new(pool.clone());
+    account_service
+        .create_account(create_test_new_account())
+        .await
+        .unwrap();
+
+    assert!(
+        sqlx::query("DELETE FROM audit_log")
+            .execute(&pool)
+            .await
+            .is_err()
+    );
+    assert!(
+        sqlx::query("UPDATE audit_log SET changed_by = 'someone else'")
+            .execute(&pool)
+            .await
+            .is_err()
+    );
+
+    let audit_service = AuditService::new(pool);
+    let recent = audit_service
+        .recent_changes(Some("accounts"), 10)
+        .await
+        .unwrap();
+    assert_eq!(recent.len(), 1);
+}
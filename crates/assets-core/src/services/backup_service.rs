@@ -0,0 +1,734 @@
+use crate::error::{CoreError, Result};
+use crate::models::{
+    Account, Attachment, BalanceAssertion, CategorizationRule, ImportMapping, ImportedFile,
+    JournalEntry, JournalEntryStatus, JournalEntryTag, PeriodClose, PriceHistory, Reconciliation,
+    RecurringInstance, RecurringTransaction, RefundLink, Tag, Transaction, TransactionTag,
+    TransferLink,
+};
+use crate::services::{
+    CategorizationService, InboxService, IntegrityService, PeriodService, ReconciliationService,
+    RecurringService, RefundService, TransactionMatch, TransferService, sorted_parents_first,
+};
+use chrono::{DateTime, Utc};
+use flate2::Compression;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::Path;
+use uuid::Uuid;
+
+/// Version of the archive layout itself (bumped when `BackupData` changes shape)
+pub const BACKUP_FORMAT_VERSION: u32 = 2;
+
+/// Every table a restore writes to; all must be empty beforehand
+const RESTORED_TABLES: &[&str] = &[
+    "accounts",
+    "transactions",
+    "journal_entries",
+    "price_history",
+    "imported_files",
+    "transaction_matches",
+    "reconciliations",
+    "balance_assertions",
+    "transfer_links",
+    "refund_links",
+    "period_closes",
+    "attachments",
+    "tags",
+    "transaction_tags",
+    "journal_entry_tags",
+    "recurring_transactions",
+    "recurring_transaction_instances",
+    "categorization_rules",
+    "import_mappings",
+];
+
+const GZIP_MAGIC: &[u8] = &[0x1f, 0x8b];
+
+/// A portable, versioned snapshot of the whole ledger
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupArchive {
+    pub format_version: u32,
+    /// Latest applied migration when the archive was taken
+    pub schema_version: i64,
+    pub created_at: DateTime<Utc>,
+    /// SHA-256 of the serialized `data` section
+    pub checksum: String,
+    pub data: BackupData,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BackupData {
+    pub accounts: Vec<Account>,
+    pub transactions: Vec<Transaction>,
+    pub journal_entries: Vec<JournalEntry>,
+    pub price_history: Vec<PriceHistory>,
+    pub imported_files: Vec<ImportedFile>,
+    pub transaction_matches: Vec<TransactionMatch>,
+    pub reconciliations: Vec<Reconciliation>,
+    /// Entries that are cleared or reconciled; all others are uncleared
+    pub entry_statuses: Vec<JournalEntryStatus>,
+    pub balance_assertions: Vec<BalanceAssertion>,
+    pub transfer_links: Vec<TransferLink>,
+    pub refund_links: Vec<RefundLink>,
+    pub period_closes: Vec<PeriodClose>,
+    /// Attachment records only; the files themselves stay in the attachment store
+    pub attachments: Vec<Attachment>,
+    pub tags: Vec<Tag>,
+    pub transaction_tags: Vec<TransactionTag>,
+    pub journal_entry_tags: Vec<JournalEntryTag>,
+    pub recurring_transactions: Vec<RecurringTransaction>,
+    pub recurring_instances: Vec<RecurringInstance>,
+    pub categorization_rules: Vec<CategorizationRule>,
+    pub import_mappings: Vec<ImportMapping>,
+}
+
+impl BackupData {
+    /// SHA-256 of the canonical JSON serialization
+    pub fn checksum(&self) -> Result<String> {
+        let bytes = serde_json::to_vec(self)
+            .map_err(|e| CoreError::Generic(format!("Failed to serialize backup: {}", e)))?;
+        let mut hasher = Sha256::new();
+        hasher.update(&bytes);
+        Ok(format!("{:x}", hasher.finalize()))
+    }
+}
+
+pub struct BackupService {
+    pool: PgPool,
+}
+
+impl BackupService {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Latest successfully applied migration version
+    pub async fn schema_version(&self) -> Result<i64> {
+        let version: Option<i64> =
+            sqlx::query_scalar("SELECT MAX(version) FROM _sqlx_migrations WHERE success = true")
+                .fetch_one(&self.pool)
+                .await?;
+
+        version.ok_or_else(|| {
+            CoreError::Generic("Database has no migrations applied, run 'db init' first".into())
+        })
+    }
+
+    /// Snapshot every ledger table into an archive
+    pub async fn export_archive(&self) -> Result<BackupArchive> {
+        let accounts = sqlx::query_as::<_, Account>(
+            r#"
+            SELECT
+                id, name, full_path,
+                account_type, account_subtype,
+                parent_id, symbol, quantity, average_cost, address,
+                purchase_date, purchase_price, currency, is_category, is_active,
+                notes, created_at, updated_at
+            FROM accounts
+            ORDER BY full_path
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let transactions = sqlx::query_as::<_, Transaction>(
+            "SELECT id, description, reference, transaction_date, created_at, import_source, import_batch_id, external_reference, is_duplicate, merged_into_transaction_id, is_voided, voided_at, reversal_of_transaction_id FROM transactions ORDER BY transaction_date, created_at, id",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let journal_entries = sqlx::query_as::<_, JournalEntry>(
+            "SELECT id, transaction_id, account_id, amount, memo, created_at FROM journal_entries ORDER BY transaction_id, created_at, id",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let price_history = sqlx::query_as::<_, PriceHistory>(
+            "SELECT id, symbol, price, price_date, currency, source, created_at FROM price_history ORDER BY symbol, price_date",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let imported_files =
+            sqlx::query_as::<_, ImportedFile>("SELECT * FROM imported_files ORDER BY imported_at")
+                .fetch_all(&self.pool)
+                .await?;
+
+        let transaction_matches = sqlx::query_as::<_, TransactionMatch>(
+            r#"
+            SELECT id, primary_transaction_id, duplicate_transaction_id,
+                   match_confidence, match_criteria, match_type,
+                   status, created_at, updated_at
+            FROM transaction_matches
+            ORDER BY created_at, id
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let reconciliations = sqlx::query_as::<_, Reconciliation>(
+            "SELECT id, account_id, statement_date, statement_balance, reconciled_at FROM reconciliations ORDER BY statement_date, reconciled_at, id",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let entry_statuses = ReconciliationService::new(self.pool.clone())
+            .entry_statuses()
+            .await?;
+
+        let balance_assertions = sqlx::query_as::<_, BalanceAssertion>(
+            "SELECT id, account_id, assertion_date, expected_balance, source, import_batch_id, created_at FROM balance_assertions ORDER BY assertion_date, account_id",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let transfer_links = TransferService::new(self.pool.clone()).list_links().await?;
+        let refund_links = RefundService::new(self.pool.clone()).list_links().await?;
+        let period_closes = PeriodService::new(self.pool.clone()).list_closes().await?;
+        let attachments = sqlx::query_as::<_, Attachment>(
+            "SELECT id, content_hash, file_name, mime_type, file_size, transaction_id, account_id, notes, created_at FROM attachments ORDER BY created_at, id",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let tags = sqlx::query_as::<_, Tag>(
+            "SELECT id, name, description, created_at FROM tags ORDER BY created_at, id",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        let transaction_tags = sqlx::query_as::<_, TransactionTag>(
+            "SELECT transaction_id, tag_id FROM transaction_tags ORDER BY transaction_id, tag_id",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        let journal_entry_tags = sqlx::query_as::<_, JournalEntryTag>(
+            "SELECT journal_entry_id, tag_id FROM journal_entry_tags ORDER BY journal_entry_id, tag_id",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let recurring_transactions = RecurringService::new(self.pool.clone())
+            .list_recurring()
+            .await?;
+        let recurring_instances = sqlx::query_as::<_, RecurringInstance>(
+            "SELECT recurring_transaction_id, due_date, transaction_id, matched_at, created_at FROM recurring_transaction_instances ORDER BY recurring_transaction_id, due_date",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        let categorization_rules = CategorizationService::new(self.pool.clone())
+            .list_rules()
+            .await?;
+        let import_mappings = InboxService::new(self.pool.clone()).list_mappings().await?;
+
+        let data = BackupData {
+            accounts,
+            transactions,
+            journal_entries,
+            price_history,
+            imported_files,
+            transaction_matches,
+            reconciliations,
+            entry_statuses,
+            balance_assertions,
+            transfer_links,
+            refund_links,
+            period_closes,
+            attachments,
+            tags,
+            transaction_tags,
+            journal_entry_tags,
+            recurring_transactions,
+            recurring_instances,
+            categorization_rules,
+            import_mappings,
+        };
+
+        Ok(BackupArchive {
+            format_version: BACKUP_FORMAT_VERSION,
+            schema_version: self.schema_version().await?,
+            created_at: Utc::now(),
+            checksum: data.checksum()?,
+            data,
+        })
+    }
+
+    /// Write an archive as JSON, gzip-compressed if requested
+    pub fn write_archive<P: AsRef<Path>>(
+        archive: &BackupArchive,
+        path: P,
+        compress: bool,
+    ) -> Result<()> {
+        let file = BufWriter::new(std::fs::File::create(path)?);
+
+        if compress {
+            let mut encoder = GzEncoder::new(file, Compression::default());
+            serde_json::to_writer(&mut encoder, archive)
+                .map_err(|e| CoreError::Generic(format!("Failed to write backup: {}", e)))?;
+            encoder.finish()?.flush()?;
+        } else {
+            let mut writer = file;
+            serde_json::to_writer(&mut writer, archive)
+                .map_err(|e| CoreError::Generic(format!("Failed to write backup: {}", e)))?;
+            writer.flush()?;
+        }
+
+        Ok(())
+    }
+
+    /// Read an archive (plain or gzipped JSON) and verify its checksum
+    pub fn read_archive<P: AsRef<Path>>(path: P) -> Result<BackupArchive> {
+        let mut header = [0u8; 2];
+        let read = std::fs::File::open(&path)?.read(&mut header)?;
+        let file = std::fs::File::open(&path)?;
+
+        let reader: Box<dyn Read> = if header[..read].starts_with(GZIP_MAGIC) {
+            Box::new(GzDecoder::new(BufReader::new(file)))
+        } else {
+            Box::new(BufReader::new(file))
+        };
+
+        let archive: BackupArchive = serde_json::from_reader(reader)
+            .map_err(|e| CoreError::InvalidInput(format!("Invalid backup archive: {}", e)))?;
+
+        let checksum = archive.data.checksum()?;
+        if checksum != archive.checksum {
+            return Err(CoreError::ValidationError(format!(
+                "Backup checksum mismatch: archive says {}, content hashes to {}",
+                archive.checksum, checksum
+            )));
+        }
+
+        Ok(archive)
+    }
+
+    /// Restore an archive into an empty database, preserving all IDs
+    pub async fn restore_archive(&self, archive: &BackupArchive) -> Result<()> {
+        if archive.format_version != BACKUP_FORMAT_VERSION {
+            return Err(CoreError::ValidationError(format!(
+                "Unsupported backup format version {} (expected {})",
+                archive.format_version, BACKUP_FORMAT_VERSION
+            )));
+        }
+
+        let schema_version = self.schema_version().await?;
+        if archive.schema_version != schema_version {
+            return Err(CoreError::ValidationError(format!(
+                "Backup was taken at schema version {} but the database is at {}",
+                archive.schema_version, schema_version
+            )));
+        }
+
+        for table in RESTORED_TABLES {
+            let populated: bool =
+                sqlx::query_scalar(&format!("SELECT EXISTS (SELECT 1 FROM {})", table))
+                    .fetch_one(&self.pool)
+                    .await?;
+            if populated {
+                return Err(CoreError::ValidationError(format!(
+                    "Backups can only be restored into an empty database, but {} has rows",
+                    table
+                )));
+            }
+        }
+
+        let data = &archive.data;
+        let mut tx = self.pool.begin().await?;
+
+        // Parents must exist before their children (full_path is maintained by trigger)
+        for account in sorted_parents_first(&data.accounts, |a| a.id, |a| a.parent_id) {
+            sqlx::query(
+                r#"
+                INSERT INTO accounts (
+                    id, name, account_type, account_subtype, parent_id,
+                    symbol, quantity, average_cost, address, purchase_date,
+                    purchase_price, currency, is_category, is_active, notes, created_at, updated_at
+                )
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17)
+                "#,
+            )
+            .bind(account.id)
+            .bind(&account.name)
+            .bind(account.account_type)
+            .bind(account.account_subtype)
+            .bind(account.parent_id)
+            .bind(&account.symbol)
+            .bind(account.quantity)
+            .bind(account.average_cost)
+            .bind(&account.address)
+            .bind(account.purchase_date)
+            .bind(account.purchase_price)
+            .bind(&account.currency)
+            .bind(account.is_category)
+            .bind(account.is_active)
+            .bind(&account.notes)
+            .bind(account.created_at)
+            .bind(account.updated_at)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        // Merge and reversal links point at other transactions, set them once all rows exist
+        for transaction in &data.transactions {
+            sqlx::query(
+                r#"
+                INSERT INTO transactions (
+                    id, description, reference, transaction_date, created_at,
+                    import_source, import_batch_id, external_reference, is_duplicate,
+                    is_voided, voided_at
+                )
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+                "#,
+            )
+            .bind(transaction.id)
+            .bind(&transaction.description)
+            .bind(&transaction.reference)
+            .bind(transaction.transaction_date)
+            .bind(transaction.created_at)
+            .bind(&transaction.import_source)
+            .bind(transaction.import_batch_id)
+            .bind(&transaction.external_reference)
+            .bind(transaction.is_duplicate)
+            .bind(transaction.is_voided)
+            .bind(transaction.voided_at)
+            .execute(&mut *tx)
+            .await?;
+        }
+        for transaction in &data.transactions {
+            if let Some(merged_into) = transaction.merged_into_transaction_id {
+                sqlx::query(
+                    "UPDATE transactions SET merged_into_transaction_id = $2 WHERE id = $1",
+                )
+                .bind(transaction.id)
+                .bind(merged_into)
+                .execute(&mut *tx)
+                .await?;
+            }
+            if let Some(reversal_of) = transaction.reversal_of_transaction_id {
+                sqlx::query(
+                    "UPDATE transactions SET reversal_of_transaction_id = $2 WHERE id = $1",
+                )
+                .bind(transaction.id)
+                .bind(reversal_of)
+                .execute(&mut *tx)
+                .await?;
+            }
+        }
+
+        // The balance check is deferred until commit
+        for entry in &data.journal_entries {
+            sqlx::query(
+                r#"
+                INSERT INTO journal_entries (id, transaction_id, account_id, amount, memo, created_at)
+                VALUES ($1, $2, $3, $4, $5, $6)
+                "#,
+            )
+            .bind(entry.id)
+            .bind(entry.transaction_id)
+            .bind(entry.account_id)
+            .bind(entry.amount)
+            .bind(&entry.memo)
+            .bind(entry.created_at)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        for link in &data.transfer_links {
+            sqlx::query(
+                r#"
+                INSERT INTO transfer_links (id, outgoing_transaction_id, incoming_transaction_id, outgoing_entry_id, incoming_entry_id, amount, created_at)
+                VALUES ($1, $2, $3, $4, $5, $6, $7)
+                "#,
+            )
+            .bind(link.id)
+            .bind(link.outgoing_transaction_id)
+            .bind(link.incoming_transaction_id)
+            .bind(link.outgoing_entry_id)
+            .bind(link.incoming_entry_id)
+            .bind(link.amount)
+            .bind(link.created_at)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        for link in &data.refund_links {
+            sqlx::query(
+                r#"
+                INSERT INTO refund_links (id, refund_transaction_id, purchase_transaction_id, refund_entry_id, purchase_entry_id, amount, created_at)
+                VALUES ($1, $2, $3, $4, $5, $6, $7)
+                "#,
+            )
+            .bind(link.id)
+            .bind(link.refund_transaction_id)
+            .bind(link.purchase_transaction_id)
+            .bind(link.refund_entry_id)
+            .bind(link.purchase_entry_id)
+            .bind(link.amount)
+            .bind(link.created_at)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        for reconciliation in &data.reconciliations {
+            sqlx::query(
+                r#"
+                INSERT INTO reconciliations (id, account_id, statement_date, statement_balance, reconciled_at)
+                VALUES ($1, $2, $3, $4, $5)
+                "#,
+            )
+            .bind(reconciliation.id)
+            .bind(reconciliation.account_id)
+            .bind(reconciliation.statement_date)
+            .bind(reconciliation.statement_balance)
+            .bind(reconciliation.reconciled_at)
+            .execute(&mut *tx)
+            .await?;
+        }
+        for entry_status in &data.entry_statuses {
+            sqlx::query(
+                "UPDATE journal_entries SET status = $2, reconciliation_id = $3 WHERE id = $1",
+            )
+            .bind(entry_status.journal_entry_id)
+            .bind(entry_status.status)
+            .bind(entry_status.reconciliation_id)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        for assertion in &data.balance_assertions {
+            sqlx::query(
+                r#"
+                INSERT INTO balance_assertions (id, account_id, assertion_date, expected_balance, source, import_batch_id, created_at)
+                VALUES ($1, $2, $3, $4, $5, $6, $7)
+                "#,
+            )
+            .bind(assertion.id)
+            .bind(assertion.account_id)
+            .bind(assertion.assertion_date)
+            .bind(assertion.expected_balance)
+            .bind(&assertion.source)
+            .bind(assertion.import_batch_id)
+            .bind(assertion.created_at)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        for price in &data.price_history {
+            sqlx::query(
+                r#"
+                INSERT INTO price_history (id, symbol, price, price_date, currency, source, created_at)
+                VALUES ($1, $2, $3, $4, $5, $6, $7)
+                "#,
+            )
+            .bind(price.id)
+            .bind(&price.symbol)
+            .bind(price.price)
+            .bind(price.price_date)
+            .bind(&price.currency)
+            .bind(&price.source)
+            .bind(price.created_at)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        for file in &data.imported_files {
+            sqlx::query(
+                r#"
+                INSERT INTO imported_files (
+                    id, file_path, file_name, file_hash, file_size, import_source,
+                    import_batch_id, imported_at, transaction_count, notes
+                )
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+                "#,
+            )
+            .bind(file.id)
+            .bind(&file.file_path)
+            .bind(&file.file_name)
+            .bind(&file.file_hash)
+            .bind(file.file_size)
+            .bind(&file.import_source)
+            .bind(file.import_batch_id)
+            .bind(file.imported_at)
+            .bind(file.transaction_count)
+            .bind(&file.notes)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        for attachment in &data.attachments {
+            sqlx::query(
+                r#"
+                INSERT INTO attachments (id, content_hash, file_name, mime_type, file_size, transaction_id, account_id, notes, created_at)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+                "#,
+            )
+            .bind(attachment.id)
+            .bind(&attachment.content_hash)
+            .bind(&attachment.file_name)
+            .bind(&attachment.mime_type)
+            .bind(attachment.file_size)
+            .bind(attachment.transaction_id)
+            .bind(attachment.account_id)
+            .bind(&attachment.notes)
+            .bind(attachment.created_at)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        for transaction_match in &data.transaction_matches {
+            sqlx::query(
+                r#"
+                INSERT INTO transaction_matches (
+                    id, primary_transaction_id, duplicate_transaction_id, match_confidence,
+                    match_criteria, match_type, status, created_at, updated_at
+                )
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+                "#,
+            )
+            .bind(transaction_match.id)
+            .bind(transaction_match.primary_transaction_id)
+            .bind(transaction_match.duplicate_transaction_id)
+            .bind(transaction_match.match_confidence)
+            .bind(&transaction_match.match_criteria)
+            .bind(transaction_match.match_type.clone())
+            .bind(transaction_match.status.clone())
+            .bind(transaction_match.created_at)
+            .bind(transaction_match.updated_at)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        for tag in &data.tags {
+            sqlx::query(
+                "INSERT INTO tags (id, name, description, created_at) VALUES ($1, $2, $3, $4)",
+            )
+            .bind(tag.id)
+            .bind(&tag.name)
+            .bind(&tag.description)
+            .bind(tag.created_at)
+            .execute(&mut *tx)
+            .await?;
+        }
+        for transaction_tag in &data.transaction_tags {
+            sqlx::query("INSERT INTO transaction_tags (transaction_id, tag_id) VALUES ($1, $2)")
+                .bind(transaction_tag.transaction_id)
+                .bind(transaction_tag.tag_id)
+                .execute(&mut *tx)
+                .await?;
+        }
+        for entry_tag in &data.journal_entry_tags {
+            sqlx::query(
+                "INSERT INTO journal_entry_tags (journal_entry_id, tag_id) VALUES ($1, $2)",
+            )
+            .bind(entry_tag.journal_entry_id)
+            .bind(entry_tag.tag_id)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        for recurring in &data.recurring_transactions {
+            sqlx::query(
+                r#"
+                INSERT INTO recurring_transactions (
+                    id, name, description, reference, entries, schedule,
+                    start_date, end_date, amount_tolerance, is_active, created_at
+                )
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+                "#,
+            )
+            .bind(recurring.id)
+            .bind(&recurring.name)
+            .bind(&recurring.description)
+            .bind(&recurring.reference)
+            .bind(&recurring.entries)
+            .bind(&recurring.schedule)
+            .bind(recurring.start_date)
+            .bind(recurring.end_date)
+            .bind(recurring.amount_tolerance)
+            .bind(recurring.is_active)
+            .bind(recurring.created_at)
+            .execute(&mut *tx)
+            .await?;
+        }
+        for instance in &data.recurring_instances {
+            sqlx::query(
+                r#"
+                INSERT INTO recurring_transaction_instances (recurring_transaction_id, due_date, transaction_id, matched_at, created_at)
+                VALUES ($1, $2, $3, $4, $5)
+                "#,
+            )
+            .bind(instance.recurring_transaction_id)
+            .bind(instance.due_date)
+            .bind(instance.transaction_id)
+            .bind(instance.matched_at)
+            .bind(instance.created_at)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        for rule in &data.categorization_rules {
+            sqlx::query(
+                r#"
+                INSERT INTO categorization_rules (id, pattern, account_id, priority, created_at)
+                VALUES ($1, $2, $3, $4, $5)
+                "#,
+            )
+            .bind(rule.id)
+            .bind(&rule.pattern)
+            .bind(rule.account_id)
+            .bind(rule.priority)
+            .bind(rule.created_at)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        for mapping in &data.import_mappings {
+            sqlx::query(
+                r#"
+                INSERT INTO import_mappings (id, file_pattern, importer, target_account, payslip_destinations, spreadsheet_columns, priority, created_at)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+                "#,
+            )
+            .bind(mapping.id)
+            .bind(&mapping.file_pattern)
+            .bind(&mapping.importer)
+            .bind(&mapping.target_account)
+            .bind(&mapping.payslip_destinations)
+            .bind(&mapping.spreadsheet_columns)
+            .bind(mapping.priority)
+            .bind(mapping.created_at)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        // The chain is not part of the archive; the restored journal starts a new one
+        let transaction_ids: Vec<Uuid> = data.transactions.iter().map(|t| t.id).collect();
+        IntegrityService::seal(&mut tx, &transaction_ids).await?;
+
+        // Last, since the lock date rejects inserting anything in a closed period
+        for close in &data.period_closes {
+            sqlx::query(
+                r#"
+                INSERT INTO period_closes (id, period_end, closing_transaction_id, net_income, closed_at)
+                VALUES ($1, $2, $3, $4, $5)
+                "#,
+            )
+            .bind(close.id)
+            .bind(close.period_end)
+            .bind(close.closing_transaction_id)
+            .bind(close.net_income)
+            .bind(close.closed_at)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests;
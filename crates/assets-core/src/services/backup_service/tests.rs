@@ -0,0 +1,269 @@
+use super::*;
+use crate::models::{
+    AccountSubtype, AccountType, ImporterKind, JournalEntryByPath, NewAccountByPath,
+    NewImportMapping, NewRecurringTransaction, RecurrenceSchedule,
+};
+use crate::services::{AccountService, TagService, TransactionService};
+use crate::tests::utils::*;
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use uuid::Uuid;
+
+fn sample_account(name: &str, parent_id: Option<Uuid>) -> Account {
+    Account {
+        id: Uuid::new_v4(),
+        name: name.to_string(),
+        account_type: AccountType::Asset,
+        account_subtype: AccountSubtype::Category,
+        parent_id,
+        full_path: None,
+        symbol: None,
+        quantity: None,
+        average_cost: None,
+        address: None,
+        purchase_date: None,
+        purchase_price: None,
+        currency: "EUR".to_string(),
+        is_active: true,
+        is_category: false,
+        notes: None,
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+    }
+}
+
+fn sample_archive() -> BackupArchive {
+    let root = sample_account("Assets", None);
+    let child = sample_account("Checking", Some(root.id));
+    let data = BackupData {
+        accounts: vec![child, root],
+        price_history: vec![PriceHistory {
+            id: Uuid::new_v4(),
+            symbol: "AAPL".to_string(),
+            price: Decimal::new(19525, 2),
+            price_date: Utc::now().date_naive(),
+            currency: "USD".to_string(),
+            source: None,
+            created_at: Utc::now(),
+        }],
+        tags: vec![Tag {
+            id: Uuid::new_v4(),
+            name: "italy-2025".to_string(),
+            description: None,
+            created_at: Utc::now(),
+        }],
+        import_mappings: vec![ImportMapping {
+            id: Uuid::new_v4(),
+            file_pattern: "export-*.csv".to_string(),
+            importer: "boursobank".to_string(),
+            target_account: Some("Assets:Checking".to_string()),
+            payslip_destinations: None,
+            spreadsheet_columns: None,
+            priority: 0,
+            created_at: Utc::now(),
+        }],
+        ..Default::default()
+    };
+
+    BackupArchive {
+        format_version: BACKUP_FORMAT_VERSION,
+        schema_version: 20250616130857,
+        created_at: Utc::now(),
+        checksum: data.checksum().unwrap(),
+        data,
+    }
+}
+
+#[test]
+fn test_archive_round_trip() {
+    let dir = std::env::temp_dir();
+    for compress in [false, true] {
+        let path = dir.join(format!("backup-{}.json", Uuid::new_v4()));
+        let archive = sample_archive();
+
+        BackupService::write_archive(&archive, &path, compress).unwrap();
+        let bytes = std::fs::read(&path).unwrap();
+        assert_eq!(bytes.starts_with(GZIP_MAGIC), compress);
+
+        let restored = BackupService::read_archive(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(restored.checksum, archive.checksum);
+        assert_eq!(restored.schema_version, archive.schema_version);
+        assert_eq!(restored.data.accounts.len(), 2);
+        assert_eq!(restored.data.price_history[0].price, Decimal::new(19525, 2));
+        assert_eq!(restored.data.tags[0].name, "italy-2025");
+        assert_eq!(
+            restored.data.import_mappings[0].file_pattern,
+            "export-*.csv"
+        );
+    }
+}
+
+#[test]
+fn test_read_archive_rejects_tampered_content() {
+    let path = std::env::temp_dir().join(format!("backup-{}.json", Uuid::new_v4()));
+    let mut archive = sample_archive();
+    archive.data.price_history[0].price = Decimal::new(1, 0);
+
+    BackupService::write_archive(&archive, &path, false).unwrap();
+    let result = BackupService::read_archive(&path);
+    std::fs::remove_file(&path).unwrap();
+
+    assert!(matches!(result, Err(CoreError::ValidationError(_))));
+}
+
+#[test]
+fn test_sorted_parents_first() {
+    let archive = sample_archive();
+    let sorted = sorted_parents_first(&archive.data.accounts, |a| a.id, |a| a.parent_id);
+
+    assert_eq!(sorted[0].name, "Assets");
+    assert_eq!(sorted[1].name, "Checking");
+}
+
+#[tokio::test]
+async fn test_restore_keeps_tags_schedules_rules_and_mappings() {
+    let (source, _source_container) = setup_test_db().await;
+    let account_service = AccountService::new(source.clone());
+    let mut ids = Vec::new();
+    for (path, account_type, subtype) in [
+        (
+            "Assets:Checking",
+            AccountType::Asset,
+            AccountSubtype::Checking,
+        ),
+        (
+            "Expenses:Rent",
+            AccountType::Expense,
+            AccountSubtype::Housing,
+        ),
+    ] {
+        let account = account_service
+            .create_account_by_path(
+                NewAccountByPath::builder()
+                    .full_path(path)
+                    .account_type(account_type)
+                    .account_subtype(subtype)
+                    .build(),
+            )
+            .await
+            .unwrap();
+        ids.push(account.id);
+    }
+
+    let created = TransactionService::new(source.clone())
+        .create_transaction(TransactionService::create_simple_transaction(
+            "Hotel".to_string(),
+            ids[1],
+            ids[0],
+            Decimal::from(300),
+            Utc::now(),
+            None,
+        ))
+        .await
+        .unwrap();
+    let tag_service = TagService::new(source.clone());
+    tag_service.create_tag("italy-2025", None).await.unwrap();
+    tag_service.create_tag("business", None).await.unwrap();
+    tag_service
+        .tag_transaction(created.transaction.id, "italy-2025")
+        .await
+        .unwrap();
+    tag_service
+        .tag_entry(created.entries[0].id, "business")
+        .await
+        .unwrap();
+
+    let recurring_service = RecurringService::new(source.clone());
+    recurring_service
+        .create_recurring(NewRecurringTransaction {
+            name: "Rent".to_string(),
+            description: "Monthly rent".to_string(),
+            reference: None,
+            entries: vec![
+                JournalEntryByPath::builder()
+                    .account_path("Expenses:Rent")
+                    .amount(Decimal::from(900))
+                    .build(),
+                JournalEntryByPath::builder()
+                    .account_path("Assets:Checking")
+                    .amount(Decimal::from(-900))
+                    .build(),
+            ],
+            schedule: RecurrenceSchedule::Monthly { day: 1 },
+            start_date: NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+            end_date: None,
+            amount_tolerance: Decimal::ZERO,
+        })
+        .await
+        .unwrap();
+    recurring_service
+        .post_due_instances(NaiveDate::from_ymd_opt(2025, 2, 15).unwrap())
+        .await
+        .unwrap();
+    CategorizationService::new(source.clone())
+        .create_rule("loyer", ids[1], 0)
+        .await
+        .unwrap();
+    InboxService::new(source.clone())
+        .add_mapping(NewImportMapping {
+            file_pattern: "export-*.csv".to_string(),
+            importer: ImporterKind::BoursoBank,
+            target_account: Some("Assets:Checking".to_string()),
+            payslip_destinations: None,
+            spreadsheet_columns: None,
+            priority: 0,
+        })
+        .await
+        .unwrap();
+
+    let archive = BackupService::new(source).export_archive().await.unwrap();
+    assert_eq!(archive.format_version, BACKUP_FORMAT_VERSION);
+    let data = &archive.data;
+    assert_eq!(data.tags.len(), 2);
+    assert_eq!(data.transaction_tags.len(), 1);
+    assert_eq!(data.journal_entry_tags.len(), 1);
+    assert_eq!(data.recurring_transactions.len(), 1);
+    assert_eq!(data.recurring_instances.len(), 2);
+    assert_eq!(data.categorization_rules.len(), 1);
+    assert_eq!(data.import_mappings.len(), 1);
+
+    // Any leftover row, not only accounts or transactions, blocks the restore
+    let (target, _target_container) = setup_test_db().await;
+    InboxService::new(target.clone())
+        .add_mapping(NewImportMapping {
+            file_pattern: "other-*.csv".to_string(),
+            importer: ImporterKind::SocieteGenerale,
+            target_account: Some("Assets:SG".to_string()),
+            payslip_destinations: None,
+            spreadsheet_columns: None,
+            priority: 0,
+        })
+        .await
+        .unwrap();
+    let target_service = BackupService::new(target.clone());
+    let refused = target_service.restore_archive(&archive).await;
+    assert!(matches!(refused, Err(CoreError::ValidationError(_))));
+
+    sqlx::query("DELETE FROM import_mappings")
+        .execute(&target)
+        .await
+        .unwrap();
+    target_service.restore_archive(&archive).await.unwrap();
+
+    let restored = target_service.export_archive().await.unwrap().data;
+    assert_eq!(restored.tags.len(), 2);
+    assert_eq!(
+        restored.transaction_tags[0].transaction_id,
+        created.transaction.id
+    );
+    assert_eq!(
+        restored.journal_entry_tags[0].journal_entry_id,
+        created.entries[0].id
+    );
+    assert_eq!(restored.recurring_transactions[0].name, "Rent");
+    assert_eq!(restored.recurring_instances.len(), 2);
+    assert_eq!(restored.categorization_rules[0].pattern, "loyer");
+    assert_eq!(restored.import_mappings[0].file_pattern, "export-*.csv");
+}
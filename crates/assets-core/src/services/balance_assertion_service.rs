@@ -0,0 +1,152 @@
+use crate::error::{CoreError, Result};
+use crate::models::{BalanceAssertion, BalanceAssertionCheck, NewBalanceAssertion};
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Known-good balances, one per account and day, checked against `account_running_balances`
+///
+/// An assertion holds at the end of its day: every transaction dated that day counts.
+pub struct BalanceAssertionService {
+    pool: PgPool,
+}
+
+impl BalanceAssertionService {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Record an assertion; an account has at most one per day
+    pub async fn add_assertion(&self, new: NewBalanceAssertion) -> Result<BalanceAssertion> {
+        let exists: bool =
+            sqlx::query_scalar("SELECT EXISTS (SELECT 1 FROM accounts WHERE id = $1)")
+                .bind(new.account_id)
+                .fetch_one(&self.pool)
+                .await?;
+        if !exists {
+            return Err(CoreError::AccountNotFound(new.account_id.to_string()));
+        }
+
+        self.insert(&new).await?.ok_or_else(|| {
+            CoreError::ValidationError(format!(
+                "A balance assertion already exists for this account on {}",
+                new.assertion_date
+            ))
+        })
+    }
+
+    /// Record a balance read from an imported statement
+    ///
+    /// Returns `None` when the account already has an assertion for that day, so a
+    /// hand-entered value is never overwritten by an import.
+    pub async fn record_statement_balance(
+        &self,
+        account_id: Uuid,
+        date: NaiveDate,
+        balance: Decimal,
+        source: &str,
+        import_batch_id: Uuid,
+    ) -> Result<Option<BalanceAssertion>> {
+        self.insert(
+            &NewBalanceAssertion::builder()
+                .account_id(account_id)
+                .assertion_date(date)
+                .expected_balance(balance)
+                .source(source)
+                .import_batch_id(import_batch_id)
+                .build(),
+        )
+        .await
+    }
+
+    /// Assertions ordered by account and date, optionally for one account only
+    pub async fn list_assertions(&self, account_id: Option<Uuid>) -> Result<Vec<BalanceAssertion>> {
+        let assertions = sqlx::query_as::<_, BalanceAssertion>(
+            r#"
+            SELECT id, account_id, assertion_date, expected_balance, source, import_batch_id,
+                   created_at
+            FROM balance_assertions
+            WHERE $1::uuid IS NULL OR account_id = $1
+            ORDER BY account_id, assertion_date
+            "#,
+        )
+        .bind(account_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(assertions)
+    }
+
+    pub async fn remove_assertion(&self, id: Uuid) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM balance_assertions WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Evaluate assertions against the ledger, in account path and date order
+    ///
+    /// The actual balance is the running balance on the last day with activity up to
+    /// the assertion date, or zero when the account had none yet.
+    pub async fn check_assertions(
+        &self,
+        account_id: Option<Uuid>,
+    ) -> Result<Vec<BalanceAssertionCheck>> {
+        let checks = sqlx::query_as::<_, BalanceAssertionCheck>(
+            r#"
+            SELECT ba.id, ba.account_id, ba.assertion_date, ba.expected_balance,
+                   ba.source, ba.import_batch_id, ba.created_at,
+                   a.full_path AS account_path,
+                   actual.balance AS actual_balance,
+                   actual.balance - ba.expected_balance AS difference
+            FROM balance_assertions ba
+            JOIN accounts a ON a.id = ba.account_id
+            CROSS JOIN LATERAL (
+                SELECT COALESCE((
+                    SELECT arb.running_balance
+                    FROM account_running_balances arb
+                    WHERE arb.account_id = ba.account_id
+                      AND arb.balance_day <= ba.assertion_date
+                    ORDER BY arb.balance_day DESC
+                    LIMIT 1
+                ), 0)::DECIMAL(19, 4) AS balance
+            ) actual
+            WHERE $1::uuid IS NULL OR ba.account_id = $1
+            ORDER BY a.full_path, ba.assertion_date
+            "#,
+        )
+        .bind(account_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(checks)
+    }
+
+    async fn insert(&self, new: &NewBalanceAssertion) -> Result<Option<BalanceAssertion>> {
+        let assertion = sqlx::query_as::<_, BalanceAssertion>(
+            r#"
+            INSERT INTO balance_assertions
+                (account_id, assertion_date, expected_balance, source, import_batch_id)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (account_id, assertion_date) DO NOTHING
+            RETURNING id, account_id, assertion_date, expected_balance, source, import_batch_id,
+                      created_at
+            "#,
+        )
+        .bind(new.account_id)
+        .bind(new.assertion_date)
+        .bind(new.expected_balance)
+        .bind(&new.source)
+        .bind(new.import_batch_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(assertion)
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,255 @@
+use super::*;
+use crate::importers::BoursoBankImporter;
+use crate::models::{AccountSubtype, AccountType, MANUAL_ASSERTION_SOURCE, NewAccountByPath};
+use crate::services::{AccountService, ImportService, TransactionService};
+use crate::tests::utils::*;
+use chrono::{Duration, Local, NaiveDate};
+use sqlx::PgPool;
+
+/// A salary on June 1st and groceries on June 10th
+const MOVEMENTS: [((u32, u32), i64); 2] = [((6, 1), 2000), ((6, 10), -150)];
+
+/// A checking account holding `MOVEMENTS` against an expense account
+///
+/// Returns the checking account and the transaction IDs in order.
+async fn setup_checking(pool: &PgPool) -> (Uuid, Vec<Uuid>) {
+    let checking = account_at(
+        pool,
+        "Checking",
+        AccountType::Asset,
+        AccountSubtype::Checking,
+    )
+    .await;
+    let other = account_at(
+        pool,
+        "Other",
+        AccountType::Expense,
+        AccountSubtype::OtherExpense,
+    )
+    .await;
+    let tx_service = TransactionService::new(pool.clone());
+
+    let mut transaction_ids = Vec::new();
+    for ((month, day_of_month), amount) in MOVEMENTS {
+        let created = post(
+            &tx_service,
+            "Movement",
+            day(2025, month, day_of_month),
+            checking,
+            other,
+            amount,
+        )
+        .await;
+        transaction_ids.push(created.transaction.id);
+    }
+
+    (checking, transaction_ids)
+}
+
+fn assertion(account_id: Uuid, day: &str, balance: i64) -> NewBalanceAssertion {
+    NewBalanceAssertion::builder()
+        .account_id(account_id)
+        .assertion_date(date(day))
+        .expected_balance(Decimal::from(balance))
+        .build()
+}
+
+#[tokio::test]
+async fn test_check_assertions() {
+    let (pool, _container) = setup_test_db().await;
+    let service = BalanceAssertionService::new(pool.clone());
+    let (checking, _) = setup_checking(&pool).await;
+
+    // Before any activity, on the day of the salary, and a wrong one after the groceries
+    service
+        .add_assertion(assertion(checking, "2025-05-31", 0))
+        .await
+        .unwrap();
+    let added = service
+        .add_assertion(assertion(checking, "2025-06-01", 2000))
+        .await
+        .unwrap();
+    assert_eq!(added.source, MANUAL_ASSERTION_SOURCE);
+    service
+        .add_assertion(assertion(checking, "2025-06-30", 1900))
+        .await
+        .unwrap();
+
+    let checks = service.check_assertions(Some(checking)).await.unwrap();
+    assert_eq!(checks.len(), 3);
+    assert!(checks[0].passed());
+    assert!(checks[1].passed());
+    assert_eq!(checks[1].account_path, "Checking");
+    assert!(!checks[2].passed());
+    assert_eq!(checks[2].actual_balance, Decimal::from(1850));
+    assert_eq!(checks[2].difference, Decimal::from(-50));
+
+    // One assertion per account and day
+    let duplicate = service
+        .add_assertion(assertion(checking, "2025-06-30", 1850))
+        .await;
+    assert!(matches!(duplicate, Err(CoreError::ValidationError(_))));
+
+    // Removing the wrong one leaves only passing checks
+    assert!(
+        service
+            .remove_assertion(checks[2].assertion.id)
+            .await
+            .unwrap()
+    );
+    assert!(
+        !service
+            .remove_assertion(checks[2].assertion.id)
+            .await
+            .unwrap()
+    );
+    let checks = service.check_assertions(None).await.unwrap();
+    assert!(checks.iter().all(|c| c.passed()));
+    assert_eq!(service.list_assertions(None).await.unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn test_voided_transactions_are_ignored() {
+    let (pool, _container) = setup_test_db().await;
+    let service = BalanceAssertionService::new(pool.clone());
+    let (checking, transaction_ids) = setup_checking(&pool).await;
+
+    service
+        .add_assertion(assertion(checking, "2025-06-30", 2000))
+        .await
+        .unwrap();
+    assert!(!service.check_assertions(None).await.unwrap()[0].passed());
+
+    TransactionService::new(pool.clone())
+        .void_transaction(transaction_ids[1])
+        .await
+        .unwrap();
+    assert!(service.check_assertions(None).await.unwrap()[0].passed());
+}
+
+#[tokio::test]
+async fn test_statement_balance_does_not_overwrite() {
+    let (pool, _container) = setup_test_db().await;
+    let service = BalanceAssertionService::new(pool.clone());
+    let (checking, _) = setup_checking(&pool).await;
+
+    service
+        .add_assertion(assertion(checking, "2025-06-10", 1850))
+        .await
+        .unwrap();
+
+    let batch = Uuid::new_v4();
+    let recorded = service
+        .record_statement_balance(
+            checking,
+            date("2025-06-10"),
+            Decimal::from(99),
+            "Bank",
+            batch,
+        )
+        .await
+        .unwrap();
+    assert!(recorded.is_none());
+
+    let recorded = service
+        .record_statement_balance(
+            checking,
+            date("2025-06-11"),
+            Decimal::from(1850),
+            "Bank",
+            batch,
+        )
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(recorded.source, "Bank");
+    assert_eq!(recorded.import_batch_id, Some(batch));
+
+    let assertions = service.list_assertions(Some(checking)).await.unwrap();
+    assert_eq!(assertions.len(), 2);
+    assert_eq!(assertions[0].expected_balance, Decimal::from(1850));
+}
+
+/// Import a BoursoBank export whose last operation is on `last_op`
+async fn import_bourso_export(pool: &PgPool, last_op: NaiveDate) -> usize {
+    let account_service = AccountService::new(pool.clone());
+    for (path, account_type, subtype) in [
+        (
+            "Assets:Bourso",
+            AccountType::Asset,
+            AccountSubtype::Checking,
+        ),
+        (
+            "Equity:Uncategorized",
+            AccountType::Equity,
+            AccountSubtype::OpeningBalance,
+        ),
+    ] {
+        account_service
+            .create_account_by_path(
+                NewAccountByPath::builder()
+                    .full_path(path)
+                    .account_type(account_type)
+                    .account_subtype(subtype)
+                    .build(),
+            )
+            .await
+            .unwrap();
+    }
+
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("export.csv");
+    std::fs::write(
+        &file,
+        format!(
+            "dateOp;dateVal;label;category;categoryParent;supplierFound;amount;comment;accountNum;accountLabel;accountbalance\n\
+             {first};{first};SALAIRE;;;;1500,00;;0001;BoursoBank;\"1 234,56\"\n\
+             {last};{last};PRLV EDF;;;;-265,44;;0001;BoursoBank;\"1 234,56\"\n",
+            first = last_op - Duration::days(3),
+            last = last_op,
+        ),
+    )
+    .unwrap();
+
+    ImportService::new(pool.clone())
+        .import_transactions(
+            &BoursoBankImporter::default(),
+            file.to_str().unwrap(),
+            "Assets:Bourso",
+        )
+        .await
+        .unwrap()
+        .assertions_created
+}
+
+#[tokio::test]
+async fn test_import_records_statement_balance() {
+    let (pool, _container) = setup_test_db().await;
+    let today = Local::now().date_naive();
+    assert_eq!(import_bourso_export(&pool, today).await, 1);
+
+    let checks = BalanceAssertionService::new(pool)
+        .check_assertions(None)
+        .await
+        .unwrap();
+    assert_eq!(checks.len(), 1);
+    assert_eq!(checks[0].assertion.assertion_date, today);
+    assert_eq!(
+        checks[0].assertion.expected_balance,
+        Decimal::new(123456, 2)
+    );
+    assert!(checks[0].passed());
+}
+
+#[tokio::test]
+async fn test_import_ignores_balance_of_older_export() {
+    let (pool, _container) = setup_test_db().await;
+    let last_op = date("2025-06-05");
+    assert_eq!(import_bourso_export(&pool, last_op).await, 0);
+
+    let assertions = BalanceAssertionService::new(pool)
+        .list_assertions(None)
+        .await
+        .unwrap();
+    assert!(assertions.is_empty());
+}
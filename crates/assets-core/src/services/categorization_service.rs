@@ -0,0 +1,278 @@
+use crate::error::{CoreError, Result};
+use crate::models::{
+    CategorizationRule, CategorySuggestion, JournalEntry, SuggestionSource, UncategorizedEntry,
+};
+use crate::services::{TransactionService, UNCATEGORIZED_ACCOUNT_PATH};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Minimum trigram word similarity for a past description to suggest its account
+pub const SUGGESTION_SIMILARITY_THRESHOLD: f64 = 0.5;
+
+/// Triage of entries left in the uncategorized account after imports
+///
+/// Categorizing moves the uncategorized leg to its target account in place; the
+/// transaction and its other legs are untouched.
+pub struct CategorizationService {
+    pool: PgPool,
+    transaction_service: TransactionService,
+}
+
+impl CategorizationService {
+    pub fn new(pool: PgPool) -> Self {
+        Self {
+            transaction_service: TransactionService::new(pool.clone()),
+            pool,
+        }
+    }
+
+    /// Uncategorized entries of non-voided transactions, oldest first
+    pub async fn uncategorized_entries(&self) -> Result<Vec<UncategorizedEntry>> {
+        self.fetch_uncategorized(None).await
+    }
+
+    /// Uncategorized entries whose description contains `pattern`, case-insensitively
+    pub async fn uncategorized_entries_matching(
+        &self,
+        pattern: &str,
+    ) -> Result<Vec<UncategorizedEntry>> {
+        self.fetch_uncategorized(Some(pattern)).await
+    }
+
+    async fn fetch_uncategorized(&self, pattern: Option<&str>) -> Result<Vec<UncategorizedEntry>> {
+        let entries = sqlx::query_as::<_, UncategorizedEntry>(
+            r#"
+            SELECT je.id, je.transaction_id, t.transaction_date, t.description, je.amount,
+                   COALESCE((
+                       SELECT STRING_AGG(DISTINCT oa.full_path, ', ')
+                       FROM journal_entries other
+                       JOIN accounts oa ON oa.id = other.account_id
+                       WHERE other.transaction_id = je.transaction_id
+                         AND other.id <> je.id
+                   ), '') AS counterpart_paths
+            FROM journal_entries je
+            JOIN transactions t ON t.id = je.transaction_id
+            JOIN accounts a ON a.id = je.account_id
+            WHERE a.full_path = $1
+              AND NOT t.is_voided
+              AND ($2::text IS NULL OR POSITION(LOWER($2) IN LOWER(t.description)) > 0)
+            ORDER BY t.transaction_date, t.created_at, je.created_at
+            "#,
+        )
+        .bind(UNCATEGORIZED_ACCOUNT_PATH)
+        .bind(pattern.map(str::trim))
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(entries)
+    }
+
+    /// Candidate accounts for an entry: the matching rule first, then accounts used by
+    /// past transactions with a similar description, most similar and frequent first
+    ///
+    /// Only legs with the same sign as the uncategorized one are considered, so a
+    /// refund from a shop does not suggest the bank account that paid it.
+    pub async fn suggest_accounts(
+        &self,
+        entry: &UncategorizedEntry,
+        limit: i64,
+    ) -> Result<Vec<CategorySuggestion>> {
+        let mut suggestions = Vec::new();
+        if let Some(rule) = self.rule_for(&entry.description).await? {
+            suggestions.push(CategorySuggestion {
+                account_id: rule.account_id,
+                account_path: rule.account_path,
+                source: SuggestionSource::Rule,
+                occurrences: 0,
+                similarity: 1.0,
+            });
+        }
+
+        // `<%` compares against pg_trgm.word_similarity_threshold and can use the
+        // description trigram index; the setting only lasts for this transaction
+        let mut tx = self.pool.begin().await?;
+        sqlx::query("SELECT set_config('pg_trgm.word_similarity_threshold', $1, true)")
+            .bind(SUGGESTION_SIMILARITY_THRESHOLD.to_string())
+            .execute(&mut *tx)
+            .await?;
+        let history: Vec<(Uuid, String, i64, f64)> = sqlx::query_as(
+            r#"
+            SELECT je.account_id, a.full_path, COUNT(DISTINCT t.id),
+                   MAX(word_similarity($1, t.description))::float8 AS similarity
+            FROM transactions t
+            JOIN journal_entries je ON je.transaction_id = t.id
+            JOIN accounts a ON a.id = je.account_id
+            WHERE NOT t.is_voided
+              AND t.id <> $2
+              AND $1 <% t.description
+              AND SIGN(je.amount) = SIGN($3::numeric)
+              AND a.full_path <> $4
+              AND a.full_path NOT LIKE $4 || ':%'
+              AND je.account_id NOT IN (
+                  SELECT account_id FROM journal_entries WHERE transaction_id = $2
+              )
+            GROUP BY je.account_id, a.full_path
+            ORDER BY similarity DESC, COUNT(DISTINCT t.id) DESC, a.full_path
+            LIMIT $5
+            "#,
+        )
+        .bind(&entry.description)
+        .bind(entry.transaction_id)
+        .bind(entry.amount)
+        .bind(UNCATEGORIZED_ACCOUNT_PATH)
+        .bind(limit)
+        .fetch_all(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        for (account_id, account_path, occurrences, similarity) in history {
+            if suggestions.iter().any(|s| s.account_id == account_id) {
+                continue;
+            }
+            suggestions.push(CategorySuggestion {
+                account_id,
+                account_path,
+                source: SuggestionSource::History,
+                occurrences,
+                similarity,
+            });
+        }
+        suggestions.truncate(limit.max(0) as usize);
+
+        Ok(suggestions)
+    }
+
+    /// Move an uncategorized entry to its target account
+    pub async fn categorize_entry(&self, entry_id: Uuid, account_id: Uuid) -> Result<JournalEntry> {
+        let current_path: Option<String> = sqlx::query_scalar(
+            "SELECT a.full_path FROM journal_entries je JOIN accounts a ON a.id = je.account_id WHERE je.id = $1",
+        )
+        .bind(entry_id)
+        .fetch_optional(&self.pool)
+        .await?;
+        match current_path {
+            None => return Err(CoreError::NotFound(format!("Journal entry {}", entry_id))),
+            Some(path) if path != UNCATEGORIZED_ACCOUNT_PATH => {
+                return Err(CoreError::ValidationError(format!(
+                    "Journal entry {} is already categorized in {}",
+                    entry_id, path
+                )));
+            }
+            Some(_) => {}
+        }
+
+        self.transaction_service
+            .reassign_entry(entry_id, account_id)
+            .await
+    }
+
+    /// Categorize every uncategorized entry whose description contains `pattern`
+    pub async fn categorize_matching(&self, pattern: &str, account_id: Uuid) -> Result<usize> {
+        let entries = self.uncategorized_entries_matching(pattern).await?;
+        for entry in &entries {
+            self.transaction_service
+                .reassign_entry(entry.id, account_id)
+                .await?;
+        }
+        Ok(entries.len())
+    }
+
+    /// Save a rule; patterns are unique regardless of case
+    pub async fn create_rule(
+        &self,
+        pattern: &str,
+        account_id: Uuid,
+        priority: i32,
+    ) -> Result<CategorizationRule> {
+        let pattern = pattern.trim();
+        if pattern.is_empty() {
+            return Err(CoreError::InvalidInput(
+                "Rule pattern cannot be empty".to_string(),
+            ));
+        }
+        let exists: bool = sqlx::query_scalar(
+            "SELECT EXISTS (SELECT 1 FROM categorization_rules WHERE LOWER(pattern) = LOWER($1))",
+        )
+        .bind(pattern)
+        .fetch_one(&self.pool)
+        .await?;
+        if exists {
+            return Err(CoreError::ValidationError(format!(
+                "A rule for '{}' already exists",
+                pattern
+            )));
+        }
+
+        let id: Uuid = sqlx::query_scalar(
+            "INSERT INTO categorization_rules (pattern, account_id, priority) VALUES ($1, $2, $3) RETURNING id",
+        )
+        .bind(pattern)
+        .bind(account_id)
+        .bind(priority)
+        .fetch_one(&self.pool)
+        .await?;
+
+        self.list_rules()
+            .await?
+            .into_iter()
+            .find(|rule| rule.id == id)
+            .ok_or_else(|| CoreError::NotFound(format!("Categorization rule {}", id)))
+    }
+
+    /// Rules in the order they are tried
+    pub async fn list_rules(&self) -> Result<Vec<CategorizationRule>> {
+        let rules = sqlx::query_as::<_, CategorizationRule>(
+            r#"
+            SELECT r.id, r.pattern, r.account_id, a.full_path AS account_path,
+                   r.priority, r.created_at
+            FROM categorization_rules r
+            JOIN accounts a ON a.id = r.account_id
+            ORDER BY r.priority DESC, LENGTH(r.pattern) DESC, LOWER(r.pattern)
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rules)
+    }
+
+    pub async fn delete_rule(&self, id: Uuid) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM categorization_rules WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// The first rule matching a description
+    pub async fn rule_for(&self, description: &str) -> Result<Option<CategorizationRule>> {
+        Ok(self
+            .list_rules()
+            .await?
+            .into_iter()
+            .find(|rule| rule.matches(description)))
+    }
+
+    /// Categorize every uncategorized entry some rule matches; returns how many moved
+    pub async fn apply_rules(&self) -> Result<usize> {
+        let rules = self.list_rules().await?;
+        if rules.is_empty() {
+            return Ok(0);
+        }
+
+        let mut categorized = 0;
+        for entry in self.uncategorized_entries().await? {
+            if let Some(rule) = rules.iter().find(|rule| rule.matches(&entry.description)) {
+                self.transaction_service
+                    .reassign_entry(entry.id, rule.account_id)
+                    .await?;
+                categorized += 1;
+            }
+        }
+        Ok(categorized)
+    }
+}
+
+#[cfg(test)]
+mod tests;
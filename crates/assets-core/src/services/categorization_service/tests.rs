@@ -0,0 +1,212 @@
+use super::*;
+use crate::models::{AccountSubtype, AccountType};
+use crate::services::ReconciliationService;
+use crate::services::{TransactionService, UNCATEGORIZED_ACCOUNT_PATH};
+use crate::tests::utils::*;
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use sqlx::PgPool;
+
+/// The accounts these tests post to
+struct Ledger {
+    checking: Uuid,
+    groceries: Uuid,
+    transport: Uuid,
+    uncategorized: Uuid,
+    tx_service: TransactionService,
+}
+
+impl Ledger {
+    async fn new(pool: &PgPool) -> Self {
+        Self {
+            checking: account_at(
+                pool,
+                "Assets:Checking",
+                AccountType::Asset,
+                AccountSubtype::Checking,
+            )
+            .await,
+            groceries: account_at(
+                pool,
+                "Expenses:Groceries",
+                AccountType::Expense,
+                AccountSubtype::Food,
+            )
+            .await,
+            transport: account_at(
+                pool,
+                "Expenses:Transport",
+                AccountType::Expense,
+                AccountSubtype::Transportation,
+            )
+            .await,
+            uncategorized: account_at(
+                pool,
+                UNCATEGORIZED_ACCOUNT_PATH,
+                AccountType::Equity,
+                AccountSubtype::OpeningBalance,
+            )
+            .await,
+            tx_service: TransactionService::new(pool.clone()),
+        }
+    }
+
+    /// A card payment from checking, paid to `other`
+    async fn spend(&self, day_of_month: u32, description: &str, amount: i64, other: Uuid) -> Uuid {
+        post(
+            &self.tx_service,
+            description,
+            day(2025, 6, day_of_month),
+            self.checking,
+            other,
+            -amount,
+        )
+        .await
+        .transaction
+        .id
+    }
+}
+
+#[tokio::test]
+async fn test_triage_oldest_first_with_suggestions() {
+    let (pool, _container) = setup_test_db().await;
+    let service = CategorizationService::new(pool.clone());
+    let ledger = Ledger::new(&pool).await;
+
+    ledger
+        .spend(1, "CB CARREFOUR MARKET 01/06", 80, ledger.groceries)
+        .await;
+    ledger
+        .spend(2, "CB SNCF VOYAGES 02/06", 45, ledger.transport)
+        .await;
+    let newest = ledger
+        .spend(20, "CB CARREFOUR MARKET 20/06", 62, ledger.uncategorized)
+        .await;
+    let oldest = ledger
+        .spend(10, "CB CARREFOUR MARKET 10/06", 35, ledger.uncategorized)
+        .await;
+
+    let entries = service.uncategorized_entries().await.unwrap();
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0].transaction_id, oldest);
+    assert_eq!(entries[1].transaction_id, newest);
+    assert_eq!(entries[0].amount, Decimal::from(35));
+    assert_eq!(entries[0].counterpart_paths, "Assets:Checking");
+
+    // Groceries was used for similar descriptions; neither checking nor transport is offered
+    let suggestions = service.suggest_accounts(&entries[0], 5).await.unwrap();
+    assert_eq!(suggestions.len(), 1);
+    assert_eq!(suggestions[0].account_id, ledger.groceries);
+    assert_eq!(suggestions[0].source, SuggestionSource::History);
+    assert_eq!(suggestions[0].occurrences, 1);
+
+    // Moving the leg keeps the entry and the transaction
+    let moved = service
+        .categorize_entry(entries[0].id, ledger.groceries)
+        .await
+        .unwrap();
+    assert_eq!(moved.id, entries[0].id);
+    assert_eq!(moved.transaction_id, oldest);
+    assert_eq!(moved.account_id, ledger.groceries);
+
+    let again = service
+        .categorize_entry(entries[0].id, ledger.transport)
+        .await;
+    assert!(matches!(again, Err(CoreError::ValidationError(_))));
+    assert_eq!(service.uncategorized_entries().await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_bulk_categorize_and_rules() {
+    let (pool, _container) = setup_test_db().await;
+    let service = CategorizationService::new(pool.clone());
+    let ledger = Ledger::new(&pool).await;
+
+    for day in [3, 9, 17] {
+        ledger
+            .spend(
+                day,
+                &format!("PRLV NAVIGO {:02}/06", day),
+                86,
+                ledger.uncategorized,
+            )
+            .await;
+    }
+    ledger
+        .spend(5, "CB MONOPRIX", 12, ledger.uncategorized)
+        .await;
+
+    let moved = service
+        .categorize_matching("navigo", ledger.transport)
+        .await
+        .unwrap();
+    assert_eq!(moved, 3);
+    assert_eq!(service.uncategorized_entries().await.unwrap().len(), 1);
+
+    let rule = service
+        .create_rule("monoprix", ledger.groceries, 0)
+        .await
+        .unwrap();
+    assert_eq!(rule.account_path, "Expenses:Groceries");
+    let duplicate = service.create_rule("MONOPRIX", ledger.transport, 0).await;
+    assert!(matches!(duplicate, Err(CoreError::ValidationError(_))));
+
+    // The rule is suggested first, then applied to what is left
+    let entries = service.uncategorized_entries().await.unwrap();
+    let suggestions = service.suggest_accounts(&entries[0], 5).await.unwrap();
+    assert_eq!(suggestions[0].source, SuggestionSource::Rule);
+    assert_eq!(suggestions[0].account_id, ledger.groceries);
+    assert_eq!(service.apply_rules().await.unwrap(), 1);
+    assert!(service.uncategorized_entries().await.unwrap().is_empty());
+
+    assert!(service.delete_rule(rule.id).await.unwrap());
+    assert!(service.list_rules().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_reconciled_entries_cannot_be_moved() {
+    let (pool, _container) = setup_test_db().await;
+    let ledger = Ledger::new(&pool).await;
+    let transaction_id = ledger
+        .spend(4, "CB BOULANGERIE", 5, ledger.uncategorized)
+        .await;
+
+    let entries = ledger
+        .tx_service
+        .get_transaction(transaction_id)
+        .await
+        .unwrap()
+        .unwrap()
+        .entries;
+    let checking_entry = entries
+        .iter()
+        .find(|e| e.account_id == ledger.checking)
+        .unwrap();
+
+    let reconciliation = ReconciliationService::new(pool.clone());
+    let statement_date = NaiveDate::from_ymd_opt(2025, 6, 30).unwrap();
+    reconciliation
+        .set_cleared(&[checking_entry.id], true)
+        .await
+        .unwrap();
+    reconciliation
+        .finish(ledger.checking, statement_date, Decimal::from(-5))
+        .await
+        .unwrap();
+
+    let moved = ledger
+        .tx_service
+        .reassign_entry(checking_entry.id, ledger.groceries)
+        .await;
+    assert!(matches!(moved, Err(CoreError::ValidationError(_))));
+
+    // The uncategorized leg of the same transaction can still be categorized
+    let uncategorized = entries
+        .iter()
+        .find(|e| e.account_id == ledger.uncategorized)
+        .unwrap();
+    CategorizationService::new(pool)
+        .categorize_entry(uncategorized.id, ledger.groceries)
+        .await
+        .unwrap();
+}
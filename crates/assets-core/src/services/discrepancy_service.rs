@@ -0,0 +1,220 @@
+use crate::error::{CoreError, Result};
+use crate::models::{
+    BalanceCheckpoint, DiscrepancyEntry, DiscrepancyReport, DiscrepancySuspect, KnownBalance,
+    SuspectReason,
+};
+use crate::services::UNCATEGORIZED_ACCOUNT_PATH;
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Entries of the same account and amount this many days apart are flagged as possible duplicates
+pub const DUPLICATE_WINDOW_DAYS: i32 = 3;
+
+/// Locates the day where an account's ledger balance stops matching known bank balances
+///
+/// Ledger balances come from `account_daily_balance_changes`, so voided transactions are
+/// ignored. A known balance holds at the end of its day.
+pub struct DiscrepancyService {
+    pool: PgPool,
+}
+
+impl DiscrepancyService {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Compare the ledger to known balances and investigate the first disagreement
+    ///
+    /// The divergence window runs from the last agreeing balance (excluded) to the first
+    /// disagreeing one (included). Unless bank balances are known for every day, the exact
+    /// day cannot be proven: the diverging day is the first one whose entries explain the
+    /// difference, otherwise the first suspicious day, otherwise the first day with activity.
+    pub async fn find_discrepancy(
+        &self,
+        account_id: Uuid,
+        known_balances: &[KnownBalance],
+    ) -> Result<DiscrepancyReport> {
+        if known_balances.is_empty() {
+            return Err(CoreError::InvalidInput(
+                "At least one known balance is needed".to_string(),
+            ));
+        }
+        let mut known_balances = known_balances.to_vec();
+        known_balances.sort_by_key(|known| known.date);
+
+        let daily_changes: Vec<(NaiveDate, Decimal)> = sqlx::query_as(
+            r#"
+            SELECT balance_day, net_change_on_day
+            FROM account_daily_balance_changes
+            WHERE account_id = $1
+            ORDER BY balance_day
+            "#,
+        )
+        .bind(account_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut last_agreement = None;
+        let mut first_divergence = None;
+        for known in &known_balances {
+            let actual: Decimal = daily_changes
+                .iter()
+                .take_while(|(day, _)| *day <= known.date)
+                .map(|(_, change)| *change)
+                .sum();
+            let checkpoint = BalanceCheckpoint {
+                date: known.date,
+                expected: known.balance,
+                actual,
+                difference: actual - known.balance,
+            };
+            if checkpoint.difference == Decimal::ZERO {
+                last_agreement = Some(checkpoint);
+            } else {
+                first_divergence = Some(checkpoint);
+                break;
+            }
+        }
+
+        let Some(divergence) = first_divergence else {
+            return Ok(DiscrepancyReport {
+                account_id,
+                last_agreement,
+                first_divergence: None,
+                diverging_day: None,
+                day_entries: Vec::new(),
+                suspects: Vec::new(),
+            });
+        };
+
+        let window = self
+            .window_entries(
+                account_id,
+                last_agreement.as_ref().map(|checkpoint| checkpoint.date),
+                divergence.date,
+            )
+            .await?;
+
+        let suspects: Vec<DiscrepancySuspect> = window
+            .iter()
+            .filter_map(|entry| {
+                let reasons = suspect_reasons(entry, divergence.difference);
+                (!reasons.is_empty()).then(|| DiscrepancySuspect {
+                    entry: entry.clone(),
+                    reasons,
+                })
+            })
+            .collect();
+
+        let explains_difference = |suspect: &&DiscrepancySuspect| {
+            suspect.reasons.iter().any(|reason| {
+                matches!(
+                    reason,
+                    SuspectReason::MatchesDifference | SuspectReason::MisSigned
+                )
+            })
+        };
+        let diverging_day = suspects
+            .iter()
+            .find(explains_difference)
+            .or(suspects.first())
+            .map(|suspect| &suspect.entry)
+            .or(window.first())
+            .map(|entry| entry.transaction_date.date_naive());
+
+        let day_entries = window
+            .iter()
+            .filter(|entry| Some(entry.transaction_date.date_naive()) == diverging_day)
+            .cloned()
+            .collect();
+
+        Ok(DiscrepancyReport {
+            account_id,
+            last_agreement,
+            first_divergence: Some(divergence),
+            diverging_day,
+            day_entries,
+            suspects,
+        })
+    }
+
+    /// Entries of the account after `after` (excluded) up to `until` (included)
+    async fn window_entries(
+        &self,
+        account_id: Uuid,
+        after: Option<NaiveDate>,
+        until: NaiveDate,
+    ) -> Result<Vec<DiscrepancyEntry>> {
+        let entries = sqlx::query_as::<_, DiscrepancyEntry>(
+            r#"
+            SELECT je.id, je.transaction_id, t.transaction_date, t.description,
+                   je.amount, je.memo,
+                   (
+                       t.is_duplicate
+                       OR EXISTS (
+                           SELECT 1 FROM transaction_matches tm
+                           WHERE tm.status <> 'REJECTED'
+                             AND t.id IN (tm.primary_transaction_id, tm.duplicate_transaction_id)
+                       )
+                       OR EXISTS (
+                           SELECT 1
+                           FROM journal_entries other
+                           JOIN transactions ot ON ot.id = other.transaction_id
+                           WHERE other.account_id = je.account_id
+                             AND other.transaction_id <> je.transaction_id
+                             AND other.amount = je.amount
+                             AND NOT ot.is_voided
+                             AND ABS(ot.transaction_date::date - t.transaction_date::date) <= $4
+                       )
+                   ) AS possible_duplicate,
+                   EXISTS (
+                       SELECT 1
+                       FROM journal_entries counterpart
+                       JOIN accounts a ON a.id = counterpart.account_id
+                       WHERE counterpart.transaction_id = je.transaction_id
+                         AND counterpart.id <> je.id
+                         AND (a.full_path = $5 OR a.full_path LIKE $5 || ':%')
+                   ) AS uncategorized
+            FROM journal_entries je
+            JOIN transactions t ON t.id = je.transaction_id
+            WHERE je.account_id = $1
+              AND NOT t.is_voided
+              AND ($2::date IS NULL OR t.transaction_date >= $2::date + 1)
+              AND t.transaction_date < $3::date + 1
+            ORDER BY t.transaction_date, t.created_at, je.created_at
+            "#,
+        )
+        .bind(account_id)
+        .bind(after)
+        .bind(until)
+        .bind(DUPLICATE_WINDOW_DAYS)
+        .bind(UNCATEGORIZED_ACCOUNT_PATH)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(entries)
+    }
+}
+
+/// Reasons an entry may explain a ledger-minus-bank `difference`
+fn suspect_reasons(entry: &DiscrepancyEntry, difference: Decimal) -> Vec<SuspectReason> {
+    let mut reasons = Vec::new();
+    if entry.amount == difference {
+        reasons.push(SuspectReason::MatchesDifference);
+    }
+    if entry.amount * Decimal::TWO == difference {
+        reasons.push(SuspectReason::MisSigned);
+    }
+    if entry.possible_duplicate {
+        reasons.push(SuspectReason::Duplicate);
+    }
+    if entry.uncategorized {
+        reasons.push(SuspectReason::Uncategorized);
+    }
+    reasons
+}
+
+#[cfg(test)]
+mod tests;
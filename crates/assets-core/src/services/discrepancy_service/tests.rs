@@ -0,0 +1,160 @@
+use super::*;
+use crate::models::{AccountSubtype, AccountType};
+use crate::services::{TransactionService, UNCATEGORIZED_ACCOUNT_PATH};
+use crate::tests::utils::*;
+use sqlx::PgPool;
+
+fn known(day: &str, balance: i64) -> KnownBalance {
+    KnownBalance {
+        date: date(day),
+        balance: Decimal::from(balance),
+    }
+}
+
+/// The accounts these tests post to
+struct Ledger {
+    checking: Uuid,
+    groceries: Uuid,
+    uncategorized: Uuid,
+    tx_service: TransactionService,
+}
+
+impl Ledger {
+    async fn new(pool: &PgPool) -> Self {
+        Self {
+            checking: account_at(
+                pool,
+                "Assets:Checking",
+                AccountType::Asset,
+                AccountSubtype::Checking,
+            )
+            .await,
+            groceries: account_at(
+                pool,
+                "Expenses:Groceries",
+                AccountType::Expense,
+                AccountSubtype::Food,
+            )
+            .await,
+            uncategorized: account_at(
+                pool,
+                UNCATEGORIZED_ACCOUNT_PATH,
+                AccountType::Equity,
+                AccountSubtype::OpeningBalance,
+            )
+            .await,
+            tx_service: TransactionService::new(pool.clone()),
+        }
+    }
+
+    /// A movement of the checking account against `other` on a day of June 2025
+    async fn movement(&self, day_of_month: u32, amount: i64, other: Uuid) -> Uuid {
+        post(
+            &self.tx_service,
+            &format!("Movement on day {}", day_of_month),
+            day(2025, 6, day_of_month),
+            self.checking,
+            other,
+            amount,
+        )
+        .await
+        .transaction
+        .id
+    }
+}
+
+#[tokio::test]
+async fn test_consistent_ledger() {
+    let (pool, _container) = setup_test_db().await;
+    let ledger = Ledger::new(&pool).await;
+    ledger.movement(1, 1000, ledger.uncategorized).await;
+    ledger.movement(5, -200, ledger.groceries).await;
+
+    let report = DiscrepancyService::new(pool)
+        .find_discrepancy(
+            ledger.checking,
+            &[known("2025-06-30", 800), known("2025-06-01", 1000)],
+        )
+        .await
+        .unwrap();
+
+    assert!(report.is_consistent());
+    assert_eq!(report.last_agreement.unwrap().date, date("2025-06-30"));
+    assert!(report.suspects.is_empty());
+}
+
+#[tokio::test]
+async fn test_finds_mis_signed_entry() {
+    let (pool, _container) = setup_test_db().await;
+    let ledger = Ledger::new(&pool).await;
+    ledger.movement(1, 1000, ledger.groceries).await;
+    ledger.movement(5, -200, ledger.groceries).await;
+    // The bank debited 45, the ledger credited it
+    let wrong = ledger.movement(12, 45, ledger.groceries).await;
+    ledger.movement(20, -300, ledger.groceries).await;
+
+    let report = DiscrepancyService::new(pool)
+        .find_discrepancy(
+            ledger.checking,
+            &[
+                known("2025-06-05", 800),
+                known("2025-06-10", 800),
+                known("2025-06-30", 455),
+            ],
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(report.last_agreement.unwrap().date, date("2025-06-10"));
+    let divergence = report.first_divergence.unwrap();
+    assert_eq!(divergence.date, date("2025-06-30"));
+    assert_eq!(divergence.actual, Decimal::from(545));
+    assert_eq!(divergence.difference, Decimal::from(90));
+
+    assert_eq!(report.diverging_day, Some(date("2025-06-12")));
+    assert_eq!(report.day_entries.len(), 1);
+    assert_eq!(report.day_entries[0].transaction_id, wrong);
+    assert_eq!(report.suspects.len(), 1);
+    assert_eq!(report.suspects[0].reasons, vec![SuspectReason::MisSigned]);
+}
+
+#[tokio::test]
+async fn test_flags_duplicates_and_uncategorized() {
+    let (pool, _container) = setup_test_db().await;
+    let ledger = Ledger::new(&pool).await;
+    ledger.movement(1, 1000, ledger.groceries).await;
+    // The same groceries imported twice, two days apart, plus an uncategorized line
+    ledger.movement(8, -60, ledger.groceries).await;
+    ledger.movement(10, -60, ledger.groceries).await;
+    ledger.movement(15, -25, ledger.uncategorized).await;
+
+    let report = DiscrepancyService::new(pool.clone())
+        .find_discrepancy(
+            ledger.checking,
+            &[known("2025-06-01", 1000), known("2025-06-30", 915)],
+        )
+        .await
+        .unwrap();
+
+    let divergence = report.first_divergence.unwrap();
+    assert_eq!(divergence.difference, Decimal::from(-60));
+    // Both copies match the difference; the first one is where the ledger went wrong
+    assert_eq!(report.diverging_day, Some(date("2025-06-08")));
+    assert_eq!(report.suspects.len(), 3);
+    assert_eq!(
+        report.suspects[0].reasons,
+        vec![SuspectReason::MatchesDifference, SuspectReason::Duplicate]
+    );
+    assert_eq!(
+        report.suspects[2].reasons,
+        vec![SuspectReason::Uncategorized]
+    );
+
+    // Without any agreeing balance the window starts at the beginning of the ledger
+    let report = DiscrepancyService::new(pool)
+        .find_discrepancy(ledger.checking, &[known("2025-06-30", 0)])
+        .await
+        .unwrap();
+    assert!(report.last_agreement.is_none());
+    assert_eq!(report.diverging_day, Some(date("2025-06-08")));
+}
@@ -1,7 +1,7 @@
 use crate::error::Result;
 use crate::models::{ImportedFile, NewImportedFile};
 use sha2::{Digest, Sha256};
-use sqlx::PgPool;
+use sqlx::{PgConnection, PgPool};
 use std::fs;
 use std::path::Path;
 use uuid::Uuid;
@@ -66,6 +66,15 @@ impl FileImportService {
     }
     /// Record a file import
     pub async fn record_file_import(&self, new_file: NewImportedFile) -> Result<ImportedFile> {
+        let mut conn = self.pool.acquire().await?;
+        Self::record_file_import_in(&mut conn, new_file).await
+    }
+
+    /// Record a file import on `conn`, e.g. inside a caller's database transaction
+    pub(crate) async fn record_file_import_in(
+        conn: &mut PgConnection,
+        new_file: NewImportedFile,
+    ) -> Result<ImportedFile> {
         let file = sqlx::query_as::<_, ImportedFile>(
             r#"
             INSERT INTO imported_files (
@@ -84,7 +93,7 @@ impl FileImportService {
         .bind(new_file.import_batch_id)
         .bind(new_file.transaction_count)
         .bind(&new_file.notes)
-        .fetch_one(&self.pool)
+        .fetch_one(conn)
         .await?;
 
         Ok(file)
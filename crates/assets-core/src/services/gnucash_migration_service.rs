@@ -0,0 +1,501 @@
+use crate::error::{CoreError, Result};
+use crate::importers::gnucash::{GnuCashBook, GnuCashCommodity, GnuCashImporter, map_account_type};
+use crate::models::{
+    Account, AccountSubtype, AccountType, NewAccount, NewJournalEntry, NewPriceHistory,
+    NewTransaction,
+};
+use crate::services::{
+    AccountService, FileImportService, PriceHistoryService, TransactionService,
+    sorted_parents_first,
+};
+use crate::validation::TransactionValidator;
+use log::{info, warn};
+use rust_decimal::Decimal;
+use sqlx::{Connection, PgPool, Postgres};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+const IMPORT_SOURCE: &str = "GnuCash";
+
+/// One-shot migration of a GnuCash book into RustyAssets
+///
+/// The whole book is migrated in one database transaction; items that cannot be
+/// mapped are rolled back to a savepoint and listed in the report.
+pub struct GnuCashMigrationService {
+    pool: PgPool,
+    account_service: AccountService,
+    validator: TransactionValidator,
+    file_import_service: FileImportService,
+}
+
+/// Outcome of a GnuCash migration, including everything that could not be mapped
+#[derive(Debug, Default)]
+pub struct GnuCashMigrationReport {
+    pub import_batch_id: Uuid,
+    pub accounts_created: usize,
+    pub accounts_reused: usize,
+    pub transactions_imported: usize,
+    pub prices_imported: usize,
+    pub unmapped: Vec<String>,
+}
+
+impl GnuCashMigrationReport {
+    pub fn print_summary(&self) {
+        info!("\n📊 GnuCash Migration Summary:");
+        info!("   Import batch ID: {}", self.import_batch_id);
+        info!("   Accounts created: ✅ {}", self.accounts_created);
+        if self.accounts_reused > 0 {
+            info!("   Accounts reused: {}", self.accounts_reused);
+        }
+        info!(
+            "   Transactions imported: ✅ {}",
+            self.transactions_imported
+        );
+        info!("   Prices imported: ✅ {}", self.prices_imported);
+
+        if !self.unmapped.is_empty() {
+            warn!("\n⚠️ Could not be mapped ({}):", self.unmapped.len());
+            for item in &self.unmapped {
+                warn!("   {}", item);
+            }
+        }
+    }
+}
+
+impl GnuCashMigrationService {
+    pub fn new(pool: PgPool) -> Self {
+        Self {
+            account_service: AccountService::new(pool.clone()),
+            validator: TransactionValidator::new(),
+            file_import_service: FileImportService::new(pool.clone()),
+            pool,
+        }
+    }
+
+    /// Migrate a GnuCash book: account tree, transactions with their splits and prices
+    pub async fn migrate(
+        &self,
+        importer: &GnuCashImporter,
+        file_path: &str,
+    ) -> Result<GnuCashMigrationReport> {
+        let file_hash = FileImportService::calculate_file_hash(file_path)?;
+        if let Some(existing_file) = self
+            .file_import_service
+            .get_imported_file_by_hash(&file_hash)
+            .await?
+        {
+            return Err(CoreError::ImportError(format!(
+                "GnuCash book already migrated on {} ({} transactions). File: {}",
+                existing_file.imported_at.format("%Y-%m-%d %H:%M:%S"),
+                existing_file.transaction_count,
+                existing_file.file_name
+            )));
+        }
+
+        info!("📁 Reading GnuCash book: {}", file_path);
+        let book = importer.import_from_file(file_path).await?;
+        info!(
+            "📊 Found {} accounts, {} transactions, {} prices",
+            book.accounts.len(),
+            book.transactions.len(),
+            book.prices.len()
+        );
+
+        let mut report = GnuCashMigrationReport {
+            import_batch_id: Uuid::new_v4(),
+            ..Default::default()
+        };
+
+        let mut tx = self.pool.begin().await?;
+        let account_ids = self.migrate_accounts(&mut tx, &book, &mut report).await?;
+        self.migrate_transactions(&mut tx, &book, &account_ids, &mut report)
+            .await?;
+        self.migrate_prices(&mut tx, &book, &mut report).await?;
+
+        if report.transactions_imported > 0 || report.accounts_created > 0 {
+            let file_metadata = self.file_import_service.prepare_file_metadata(
+                file_path,
+                IMPORT_SOURCE,
+                report.import_batch_id,
+                report.transactions_imported as i32,
+                Some(format!(
+                    "Migrated {} accounts, {} transactions, {} prices ({} unmapped items)",
+                    report.accounts_created,
+                    report.transactions_imported,
+                    report.prices_imported,
+                    report.unmapped.len()
+                )),
+            )?;
+            FileImportService::record_file_import_in(&mut tx, file_metadata).await?;
+        }
+        tx.commit().await?;
+
+        Ok(report)
+    }
+
+    /// Recreate the account tree, parents first. Returns GnuCash GUID -> account ID.
+    async fn migrate_accounts(
+        &self,
+        tx: &mut sqlx::Transaction<'_, Postgres>,
+        book: &GnuCashBook,
+        report: &mut GnuCashMigrationReport,
+    ) -> Result<HashMap<String, Uuid>> {
+        let mut account_ids: HashMap<String, Uuid> = HashMap::new();
+        let mut account_types: HashMap<String, AccountType> = HashMap::new();
+        let mut paths: HashMap<String, String> = HashMap::new();
+
+        for gnc_account in sorted_parents_first(
+            &book.accounts,
+            |a| a.guid.as_str(),
+            |a| a.parent_guid.as_deref(),
+        ) {
+            if gnc_account.account_type == "ROOT" {
+                continue;
+            }
+
+            let parent_guid = gnc_account.parent_guid.as_ref().filter(|guid| {
+                book.find_account(guid)
+                    .is_some_and(|p| p.account_type != "ROOT")
+            });
+            if let Some(parent_guid) = parent_guid {
+                if !account_ids.contains_key(parent_guid) {
+                    report.unmapped.push(format!(
+                        "Account '{}': parent account was not migrated",
+                        gnc_account.name
+                    ));
+                    continue;
+                }
+            }
+
+            let Some((mut account_type, mut account_subtype)) =
+                map_account_type(&gnc_account.account_type)
+            else {
+                report.unmapped.push(format!(
+                    "Account '{}': unsupported GnuCash type {}",
+                    gnc_account.name, gnc_account.account_type
+                ));
+                continue;
+            };
+
+            // Our hierarchy cannot mix account types, keep the parent's type
+            if let Some(parent_type) = parent_guid.and_then(|guid| account_types.get(guid)) {
+                if *parent_type != account_type {
+                    report.unmapped.push(format!(
+                        "Account '{}': GnuCash type {} under a {:?} parent, migrated as {:?}",
+                        gnc_account.name, gnc_account.account_type, parent_type, parent_type
+                    ));
+                    account_type = *parent_type;
+                    account_subtype = account_type.default_subtype();
+                }
+            }
+
+            let name = sanitize_account_name(&gnc_account.name);
+            if name != gnc_account.name {
+                report.unmapped.push(format!(
+                    "Account '{}': renamed to '{}'",
+                    gnc_account.name, name
+                ));
+            }
+
+            let full_path = match parent_guid.and_then(|guid| paths.get(guid)) {
+                Some(parent_path) => format!("{}:{}", parent_path, name),
+                None => name.clone(),
+            };
+
+            if let Some(existing) = self
+                .account_service
+                .get_account_by_path_optional_in(tx, &full_path)
+                .await?
+            {
+                report.accounts_reused += 1;
+                account_ids.insert(gnc_account.guid.clone(), existing.id);
+                account_types.insert(gnc_account.guid.clone(), existing.account_type);
+                paths.insert(gnc_account.guid.clone(), full_path);
+                continue;
+            }
+
+            let mut new_account = NewAccount::builder()
+                .name(name)
+                .account_type(account_type)
+                .account_subtype(account_subtype)
+                .maybe_parent_id(parent_guid.and_then(|guid| account_ids.get(guid).copied()))
+                .maybe_notes(gnc_account.description.clone().filter(|d| !d.is_empty()))
+                .build();
+
+            match &gnc_account.commodity {
+                Some(commodity) if commodity.is_currency() => {
+                    new_account.currency = commodity.id.clone();
+                }
+                Some(commodity) if account_type == AccountType::Asset => {
+                    let quantity = account_quantity(book, &gnc_account.guid);
+                    if quantity > Decimal::ZERO {
+                        new_account.symbol = Some(commodity_symbol(commodity));
+                        new_account.quantity = Some(quantity);
+                    } else {
+                        report.unmapped.push(format!(
+                            "Account '{}': no remaining quantity of {}, symbol not set",
+                            gnc_account.name, commodity.id
+                        ));
+                    }
+                }
+                _ => {}
+            }
+
+            let created = match self.create_account(tx, new_account.clone()).await {
+                Ok(account) => account,
+                Err(e) => {
+                    // Retry without the fields our validator is strict about
+                    warn!(
+                        "Account '{}' rejected ({}), retrying with defaults",
+                        gnc_account.name, e
+                    );
+                    new_account.currency = "EUR".to_string();
+                    new_account.symbol = None;
+                    new_account.quantity = None;
+                    if new_account.account_subtype != AccountSubtype::Category {
+                        new_account.account_subtype = account_type.default_subtype();
+                    }
+                    match self.create_account(tx, new_account).await {
+                        Ok(account) => {
+                            report.unmapped.push(format!(
+                                "Account '{}': {} (migrated with default currency and subtype)",
+                                gnc_account.name, e
+                            ));
+                            account
+                        }
+                        Err(e) => {
+                            report
+                                .unmapped
+                                .push(format!("Account '{}': {}", gnc_account.name, e));
+                            continue;
+                        }
+                    }
+                }
+            };
+
+            report.accounts_created += 1;
+            account_ids.insert(gnc_account.guid.clone(), created.id);
+            account_types.insert(gnc_account.guid.clone(), created.account_type);
+            paths.insert(gnc_account.guid.clone(), full_path);
+        }
+
+        Ok(account_ids)
+    }
+
+    /// Create an account behind a savepoint, so a rejected one does not abort the migration
+    async fn create_account(
+        &self,
+        tx: &mut sqlx::Transaction<'_, Postgres>,
+        new_account: NewAccount,
+    ) -> Result<Account> {
+        let mut savepoint = tx.begin().await?;
+        match self
+            .account_service
+            .create_account_in(&mut savepoint, new_account)
+            .await
+        {
+            Ok(account) => {
+                savepoint.commit().await?;
+                Ok(account)
+            }
+            Err(e) => {
+                savepoint.rollback().await?;
+                Err(e)
+            }
+        }
+    }
+
+    /// Import every GnuCash transaction as a balanced set of journal entries
+    async fn migrate_transactions(
+        &self,
+        tx: &mut sqlx::Transaction<'_, Postgres>,
+        book: &GnuCashBook,
+        account_ids: &HashMap<String, Uuid>,
+        report: &mut GnuCashMigrationReport,
+    ) -> Result<()> {
+        'transactions: for gnc_tx in &book.transactions {
+            let label = format!("Transaction '{}' ({})", gnc_tx.description, gnc_tx.guid);
+
+            let Some(date_posted) = gnc_tx.date_posted else {
+                report.unmapped.push(format!("{}: no posting date", label));
+                continue;
+            };
+            // Posted at noon like the statement importers
+            let transaction_date = date_posted.and_hms_opt(12, 0, 0).unwrap().and_utc();
+
+            let mut entries = Vec::new();
+            for split in &gnc_tx.splits {
+                let Some(account_id) = account_ids.get(&split.account_guid) else {
+                    report
+                        .unmapped
+                        .push(format!("{}: split account was not migrated", label));
+                    continue 'transactions;
+                };
+                entries.push(NewJournalEntry {
+                    account_id: *account_id,
+                    amount: split.value,
+                    memo: split.memo.clone().filter(|m| !m.is_empty()),
+                });
+            }
+            round_to_cents(&mut entries);
+            entries.retain(|entry| !entry.amount.is_zero());
+
+            if entries.is_empty() {
+                report
+                    .unmapped
+                    .push(format!("{}: no non-zero splits", label));
+                continue;
+            }
+
+            let new_transaction = NewTransaction {
+                description: gnc_tx.description.clone(),
+                reference: gnc_tx.num.clone(),
+                transaction_date,
+                entries,
+                import_source: Some(IMPORT_SOURCE.to_string()),
+                import_batch_id: Some(report.import_batch_id),
+                external_reference: Some(gnc_tx.guid.clone()),
+            };
+
+            if !new_transaction.is_balanced() {
+                report.unmapped.push(format!(
+                    "{}: splits do not balance ({})",
+                    label,
+                    new_transaction
+                        .entries
+                        .iter()
+                        .map(|e| e.amount)
+                        .sum::<Decimal>()
+                ));
+                continue;
+            }
+
+            let mut savepoint = tx.begin().await?;
+            match TransactionService::insert_transaction(
+                &mut savepoint,
+                &self.validator,
+                &new_transaction,
+            )
+            .await
+            {
+                Ok(_) => {
+                    savepoint.commit().await?;
+                    report.transactions_imported += 1;
+                    if report.transactions_imported.is_multiple_of(100) {
+                        info!(
+                            "  ✅ Migrated {} transactions...",
+                            report.transactions_imported
+                        );
+                    }
+                }
+                Err(e) => {
+                    savepoint.rollback().await?;
+                    report.unmapped.push(format!("{}: {}", label, e));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Bring commodity prices over into price_history
+    async fn migrate_prices(
+        &self,
+        tx: &mut sqlx::Transaction<'_, Postgres>,
+        book: &GnuCashBook,
+        report: &mut GnuCashMigrationReport,
+    ) -> Result<()> {
+        for price in &book.prices {
+            let Some(price_date) = price.date else {
+                report
+                    .unmapped
+                    .push(format!("Price of {}: no date", price.commodity.id));
+                continue;
+            };
+            if !price.currency.is_currency() {
+                report.unmapped.push(format!(
+                    "Price of {} on {}: quoted in non-currency {}",
+                    price.commodity.id, price_date, price.currency.id
+                ));
+                continue;
+            }
+
+            let new_price = NewPriceHistory {
+                symbol: commodity_symbol(&price.commodity),
+                price: price.value,
+                price_date,
+                currency: price.currency.id.clone(),
+                source: Some(IMPORT_SOURCE.to_string()),
+            };
+            let mut savepoint = tx.begin().await?;
+            match PriceHistoryService::add_price_in(&mut savepoint, new_price).await {
+                Ok(_) => {
+                    savepoint.commit().await?;
+                    report.prices_imported += 1;
+                }
+                Err(e) => {
+                    savepoint.rollback().await?;
+                    report.unmapped.push(format!(
+                        "Price of {} on {}: {}",
+                        price.commodity.id, price_date, e
+                    ));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The symbol a GnuCash commodity is stored under, for accounts and prices alike
+fn commodity_symbol(commodity: &GnuCashCommodity) -> String {
+    commodity.id.trim().to_uppercase()
+}
+
+/// Round split values to cents, moving the rounding remainder onto the last split
+///
+/// GnuCash values can carry more decimals than journal entries; rounding each one
+/// separately could leave an otherwise balanced transaction a cent off.
+fn round_to_cents(entries: &mut [NewJournalEntry]) {
+    if entries.iter().all(|entry| entry.amount.scale() <= 2) {
+        return;
+    }
+    let total: Decimal = entries.iter().map(|entry| entry.amount).sum();
+    for entry in entries.iter_mut() {
+        entry.amount = entry.amount.round_dp(2);
+    }
+    let remainder = total.round_dp(2) - entries.iter().map(|entry| entry.amount).sum::<Decimal>();
+    if let Some(last) = entries.last_mut() {
+        last.amount += remainder;
+    }
+}
+
+/// Total commodity quantity held in an account
+fn account_quantity(book: &GnuCashBook, account_guid: &str) -> Decimal {
+    book.transactions
+        .iter()
+        .flat_map(|tx| tx.splits.iter())
+        .filter(|split| split.account_guid == account_guid)
+        .map(|split| split.quantity)
+        .sum()
+}
+
+/// Replace characters rejected by the account validator
+fn sanitize_account_name(name: &str) -> String {
+    let sanitized: String = name
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || " -_.,()".contains(c) {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    let sanitized = sanitized.trim().to_string();
+    if sanitized.is_empty() {
+        "Unnamed".to_string()
+    } else {
+        sanitized
+    }
+}
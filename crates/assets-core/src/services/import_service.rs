@@ -1,28 +1,55 @@
 use crate::error::Result;
 use crate::importers::{ImportedTransaction, TransactionImporter};
+use crate::models::TransferDetection;
 use crate::services::{
-    AccountService, DeduplicationService, FileImportService, TransactionService,
+    AccountService, BalanceAssertionService, CategorizationService, DeduplicationService,
+    FileImportService, RecurringService, TransactionService, TransferService,
 };
 use log::{error, info, warn};
 use rust_decimal::Decimal;
+use sqlx::{PgConnection, PgPool};
 use uuid::Uuid;
 
+/// Counterpart account of imported lines until they are categorized
+pub const UNCATEGORIZED_ACCOUNT_PATH: &str = "Equity:Uncategorized";
+
 pub struct ImportService {
+    pool: PgPool,
     account_service: AccountService,
     transaction_service: TransactionService,
     file_import_service: FileImportService,
     deduplication_service: DeduplicationService,
+    recurring_service: RecurringService,
+    balance_assertion_service: BalanceAssertionService,
+    categorization_service: CategorizationService,
+    transfer_service: TransferService,
+    ensure_accounts: bool,
 }
 
 impl ImportService {
-    pub fn new(db: sqlx::PgPool) -> Self {
+    pub fn new(db: PgPool) -> Self {
         Self {
+            pool: db.clone(),
             account_service: AccountService::new(db.clone()),
             transaction_service: TransactionService::new(db.clone()),
             file_import_service: FileImportService::new(db.clone()),
-            deduplication_service: DeduplicationService::new(db),
+            deduplication_service: DeduplicationService::new(db.clone()),
+            recurring_service: RecurringService::new(db.clone()),
+            balance_assertion_service: BalanceAssertionService::new(db.clone()),
+            categorization_service: CategorizationService::new(db.clone()),
+            transfer_service: TransferService::new(db),
+            ensure_accounts: false,
         }
     }
+
+    /// Create missing accounts instead of falling back to catch-all ones
+    ///
+    /// Applies to the target account and to the counterpart accounts chosen for
+    /// each line; their types are inferred from the path's root segment.
+    pub fn ensure_accounts(mut self, ensure_accounts: bool) -> Self {
+        self.ensure_accounts = ensure_accounts;
+        self
+    }
     /// Import transactions using the specified importer
     pub async fn import_transactions<T: TransactionImporter>(
         &self,
@@ -62,27 +89,74 @@ impl ImportService {
             }
         }
 
-        // Verify target account exists
-        let target_account = self
-            .account_service
-            .get_account_by_path(target_account_path)
-            .await?;
+        // Verify target account exists, or create it in ensure mode
+        let mut accounts_created = Vec::new();
+        let target_account = if self.ensure_accounts {
+            let (account, created) = self
+                .account_service
+                .ensure_account_by_path(target_account_path)
+                .await?;
+            accounts_created.extend(created);
+            account
+        } else {
+            self.account_service
+                .get_account_by_path(target_account_path)
+                .await?
+        };
 
         // Import raw transactions
         let imported = importer.import_from_file(file_path).await?;
         info!("📊 Found {} transactions", imported.len());
 
+        // The latest statement balance known to the importer becomes a balance assertion
+        let statement_balance = imported
+            .iter()
+            .filter_map(ImportedTransaction::statement_closing_balance)
+            .max_by_key(|(date, _)| *date);
+
         let mut created_count = 0;
         let mut skipped_count = 0;
+        let mut matched_count = 0;
+        let mut assertions_created = 0;
+        let mut transfers_linked = 0;
         let mut errors = Vec::new();
         let total_count = imported.len();
         for imported_tx in imported {
+            // Lines already posted from a recurring template are matched, not duplicated
+            if !self.is_card_transaction(&imported_tx.description)
+                && !self.is_card_settlement_transaction(&imported_tx.description)
+            {
+                match self
+                    .recurring_service
+                    .match_imported_transaction(
+                        target_account.id,
+                        imported_tx.date_op,
+                        imported_tx.amount,
+                        &import_source,
+                        import_batch_id,
+                        Some(&imported_tx.description),
+                    )
+                    .await
+                {
+                    Ok(Some(_)) => {
+                        matched_count += 1;
+                        continue;
+                    }
+                    Ok(None) => {}
+                    Err(e) => warn!(
+                        "Recurring match failed for '{}': {}",
+                        imported_tx.description, e
+                    ),
+                }
+            }
+
             match self
                 .create_transaction_from_import(
                     &imported_tx,
                     &target_account.id,
                     import_batch_id,
                     &import_source,
+                    &mut accounts_created,
                 )
                 .await
             {
@@ -100,15 +174,15 @@ impl ImportService {
         }
 
         // Record the file import (only if we had successful imports)
-        if created_count > 0 {
+        if created_count + matched_count > 0 {
             let file_metadata = self.file_import_service.prepare_file_metadata(
                 file_path,
                 &import_source,
                 import_batch_id,
-                created_count as i32,
+                (created_count + matched_count) as i32,
                 Some(format!(
-                    "Imported {} transactions, skipped {}",
-                    created_count, skipped_count
+                    "Imported {} transactions, matched {} recurring, skipped {}",
+                    created_count, matched_count, skipped_count
                 )),
             )?;
             self.file_import_service
@@ -116,6 +190,27 @@ impl ImportService {
                 .await?;
             info!("📝 File import recorded in database");
 
+            if let Some((date, balance)) = statement_balance {
+                match self
+                    .balance_assertion_service
+                    .record_statement_balance(
+                        target_account.id,
+                        date,
+                        balance,
+                        &import_source,
+                        import_batch_id,
+                    )
+                    .await
+                {
+                    Ok(Some(_)) => {
+                        assertions_created += 1;
+                        info!("⚖️  Balance assertion recorded: {} on {}", balance, date);
+                    }
+                    Ok(None) => info!("⚖️  A balance assertion already exists on {}", date),
+                    Err(e) => warn!("Could not record statement balance: {}", e),
+                }
+            }
+
             // Automatically run duplicate detection on the imported batch
             info!("🔍 Running automatic duplicate detection...");
             match self
@@ -175,12 +270,40 @@ impl ImportService {
                     );
                 }
             }
+
+            // Pair this batch's lines with the other side of internal transfers
+            match self
+                .transfer_service
+                .auto_link(
+                    &TransferDetection::builder()
+                        .import_batch_id(import_batch_id)
+                        .build(),
+                )
+                .await
+            {
+                Ok(links) => {
+                    transfers_linked = links.len();
+                    if transfers_linked > 0 {
+                        info!("🔄 Linked {} internal transfer(s)", transfers_linked);
+                    }
+                }
+                Err(e) => {
+                    warn!("Transfer detection failed: {}", e);
+                    warn!(
+                        "Import was successful, but you may want to run 'assets-cli transactions merge-transfers' manually"
+                    );
+                }
+            }
         }
 
         Ok(ImportSummary {
             total: total_count,
             created: created_count,
+            matched: matched_count,
             skipped: skipped_count,
+            assertions_created,
+            transfers_linked,
+            accounts_created,
             errors,
         })
     }
@@ -190,14 +313,25 @@ impl ImportService {
         target_account_id: &Uuid,
         import_batch_id: Uuid,
         import_source: &str,
+        accounts_created: &mut Vec<String>,
     ) -> Result<Uuid> {
+        // Accounts created in ensure mode are rolled back with a rejected line
+        let mut tx = self.pool.begin().await?;
+        let mut created_paths = Vec::new();
+
         // Convert naive date to DateTime<Utc>
         let transaction_date = imported.date_op.and_hms_opt(12, 0, 0).unwrap().and_utc();
         let new_transaction = if self.is_card_transaction(&imported.description) {
             // Handle deferred debit card transactions
-            let card_account_id = self.get_or_create_deferred_card_account().await?;
+            let card_account_id = self
+                .get_or_create_deferred_card_account(&mut tx, &mut created_paths)
+                .await?;
             let expense_account_id = self
-                .determine_expense_account_for_card_transaction(imported)
+                .determine_expense_account_for_card_transaction(
+                    &mut tx,
+                    imported,
+                    &mut created_paths,
+                )
                 .await?;
 
             // Card purchase: Expense account (debit) / Card liability account (credit)
@@ -216,7 +350,9 @@ impl ImportService {
             )
         } else if self.is_card_settlement_transaction(&imported.description) {
             // Handle monthly card settlement
-            let card_account_id = self.get_or_create_deferred_card_account().await?;
+            let card_account_id = self
+                .get_or_create_deferred_card_account(&mut tx, &mut created_paths)
+                .await?;
 
             // Card settlement: Card liability account (debit) / Bank account (credit)
             let amount = -imported.amount;
@@ -233,7 +369,9 @@ impl ImportService {
             )
         } else {
             // Handle regular transactions (not card-related)
-            let other_account_id = self.determine_other_account(imported).await?;
+            let other_account_id = self
+                .determine_other_account(&mut tx, imported, &mut created_paths)
+                .await?;
 
             if imported.amount > Decimal::ZERO {
                 // Money coming in: debit target account, credit other account
@@ -267,38 +405,50 @@ impl ImportService {
 
         let transaction_with_entries = self
             .transaction_service
-            .create_transaction(new_transaction)
+            .create_transaction_in(&mut tx, new_transaction)
             .await?;
-        Ok(transaction_with_entries.transaction.id)
-    }
-    async fn determine_other_account(&self, imported: &ImportedTransaction) -> Result<Uuid> {
-        // Modified to put everything in the account
-        let account_path = "Equity:Uncategorized";
+        tx.commit().await?;
 
-        // Try to get the account, create if it doesn't exist
-        match self.account_service.get_account_by_path(account_path).await {
-            Ok(account) => Ok(account.id),
-            Err(_) => {
-                // Account doesn't exist, for now use a default
-                // In the future, we could auto-create the account hierarchy
-                let fallback_path = if imported.amount > Decimal::ZERO {
-                    "Income:Other"
-                } else {
-                    "Expenses:Uncategorized"
-                };
-                let account = self
-                    .account_service
-                    .get_account_by_path(fallback_path)
-                    .await?;
-                Ok(account.id)
+        for created_path in created_paths {
+            if !accounts_created.contains(&created_path) {
+                info!("➕ Created account {}", created_path);
+                accounts_created.push(created_path);
             }
         }
+        Ok(transaction_with_entries.transaction.id)
+    }
+    async fn determine_other_account(
+        &self,
+        conn: &mut PgConnection,
+        imported: &ImportedTransaction,
+        accounts_created: &mut Vec<String>,
+    ) -> Result<Uuid> {
+        // Saved categorization rules skip the uncategorized account altogether
+        if let Some(rule) = self
+            .categorization_service
+            .rule_for(&imported.description)
+            .await?
+        {
+            return Ok(rule.account_id);
+        }
+
+        // Modified to put everything in the account
+        let account_path = UNCATEGORIZED_ACCOUNT_PATH;
+        let fallback_path = if imported.amount > Decimal::ZERO {
+            "Income:Other"
+        } else {
+            "Expenses:Uncategorized"
+        };
+        self.resolve_account(conn, account_path, fallback_path, accounts_created)
+            .await
     }
 
     /// Determine the expense account for a card transaction based on BoursoBank categorization
     async fn determine_expense_account_for_card_transaction(
         &self,
+        conn: &mut PgConnection,
         imported: &ImportedTransaction,
+        accounts_created: &mut Vec<String>,
     ) -> Result<Uuid> {
         // Use the same logic as determine_other_account but only for expenses
         let account_path = match (&imported.category_parent, &imported.category) {
@@ -332,14 +482,40 @@ impl ImportService {
             _ => "Expenses:Uncategorized",
         };
 
-        // Try to get the account, create if it doesn't exist
-        match self.account_service.get_account_by_path(account_path).await {
+        self.resolve_account(
+            conn,
+            account_path,
+            "Expenses:Uncategorized",
+            accounts_created,
+        )
+        .await
+    }
+
+    /// Find the account at `path`, creating it on `conn` in ensure mode
+    ///
+    /// Otherwise a missing account falls back to `fallback_path`.
+    async fn resolve_account(
+        &self,
+        conn: &mut PgConnection,
+        path: &str,
+        fallback_path: &str,
+        accounts_created: &mut Vec<String>,
+    ) -> Result<Uuid> {
+        if self.ensure_accounts {
+            let (account, created) = self
+                .account_service
+                .ensure_account_by_path_in(conn, path)
+                .await?;
+            accounts_created.extend(created);
+            return Ok(account.id);
+        }
+
+        match self.account_service.get_account_by_path(path).await {
             Ok(account) => Ok(account.id),
             Err(_) => {
-                // Account doesn't exist, use fallback
                 let account = self
                     .account_service
-                    .get_account_by_path("Expenses:Uncategorized")
+                    .get_account_by_path(fallback_path)
                     .await?;
                 Ok(account.id)
             }
@@ -361,29 +537,32 @@ impl ImportService {
     }
 
     /// Get or create the deferred debit card account
-    async fn get_or_create_deferred_card_account(&self) -> Result<Uuid> {
+    async fn get_or_create_deferred_card_account(
+        &self,
+        conn: &mut PgConnection,
+        accounts_created: &mut Vec<String>,
+    ) -> Result<Uuid> {
         let card_account_path = "Liabilities:Current Liabilities:Deferred Debit Card";
 
-        match self
-            .account_service
-            .get_account_by_path(card_account_path)
-            .await
+        if !self.ensure_accounts
+            && self
+                .account_service
+                .get_account_by_path_optional(card_account_path)
+                .await?
+                .is_none()
         {
-            Ok(account) => Ok(account.id),
-            Err(_) => {
-                // Account doesn't exist, create it
-                // For now, return a fallback account - in production you'd want to create the hierarchy
-                warn!(
-                    "Deferred card account '{}' doesn't exist. Using fallback.",
-                    card_account_path
-                );
-                let fallback_account = self
-                    .account_service
-                    .get_account_by_path("Expenses:Uncategorized")
-                    .await?;
-                Ok(fallback_account.id)
-            }
+            warn!(
+                "Deferred card account '{}' doesn't exist. Using fallback.",
+                card_account_path
+            );
         }
+        self.resolve_account(
+            conn,
+            card_account_path,
+            "Expenses:Uncategorized",
+            accounts_created,
+        )
+        .await
     }
 
     /// Get a clean import source name from the importer type
@@ -394,6 +573,8 @@ impl ImportService {
             "BoursoBank".to_string()
         } else if description.contains("Société Générale") {
             "SocieteGenerale".to_string()
+        } else if description.contains("Spreadsheet") {
+            "Spreadsheet".to_string()
         } else {
             "Unknown".to_string()
         }
@@ -404,7 +585,15 @@ impl ImportService {
 pub struct ImportSummary {
     pub total: usize,
     pub created: usize,
+    /// Lines matched to an already posted recurring transaction
+    pub matched: usize,
     pub skipped: usize,
+    /// Balance assertions recorded from the statement balance
+    pub assertions_created: usize,
+    /// Internal transfers linked with lines already in the ledger
+    pub transfers_linked: usize,
+    /// Paths of the accounts created in ensure mode
+    pub accounts_created: Vec<String>,
     pub errors: Vec<String>,
 }
 
@@ -413,9 +602,24 @@ impl ImportSummary {
         info!("\n📊 Import Summary:");
         info!("   Total transactions: {}", self.total);
         info!("   Created: ✅ {}", self.created);
+        if self.matched > 0 {
+            info!("   Matched to recurring: 🔁 {}", self.matched);
+        }
         if self.skipped > 0 {
             info!("   Skipped: ⚠️ {}", self.skipped);
         }
+        if self.transfers_linked > 0 {
+            info!("   Transfers linked: 🔄 {}", self.transfers_linked);
+        }
+        if self.assertions_created > 0 {
+            info!("   Balance assertions: ⚖️ {}", self.assertions_created);
+        }
+        if !self.accounts_created.is_empty() {
+            info!("   Accounts created: ➕ {}", self.accounts_created.len());
+            for path in &self.accounts_created {
+                info!("      {}", path);
+            }
+        }
 
         if !self.errors.is_empty() {
             error!("\n❌ Errors:");
@@ -0,0 +1,520 @@
+use crate::error::{CoreError, Result};
+use crate::importers::{
+    BoursoBankImporter, MathWorksPayslipImporter, PayslipImporter, PdfStatementImporter,
+    QtPayslipImporter, SocietegeneraleImporter, SpreadsheetColumnMapping, SpreadsheetImporter,
+    TransactionImporter,
+};
+use crate::models::{ImportMapping, ImporterKind, NewImportMapping};
+use crate::services::{
+    DestinationAccount, FileImportService, ImportService, ImportSummary, PayslipImportService,
+};
+use chrono::Utc;
+use log::{info, warn};
+use sqlx::PgPool;
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use uuid::Uuid;
+
+/// Subfolder receiving successfully imported (or already known) files
+pub const INBOX_PROCESSED_DIR: &str = "processed";
+/// Subfolder receiving files that could not be imported
+pub const INBOX_FAILED_DIR: &str = "failed";
+/// Log file appended to on every processed file
+pub const INBOX_LOG_FILE: &str = "inbox.log";
+
+/// What happened to a single inbox file
+#[derive(Debug, Clone)]
+pub enum InboxOutcome {
+    Imported { created: usize, skipped: usize },
+    AlreadyImported,
+    Failed(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct InboxFileResult {
+    pub file_name: String,
+    pub importer: Option<ImporterKind>,
+    pub outcome: InboxOutcome,
+    /// Where the file was moved to
+    pub moved_to: Option<PathBuf>,
+}
+
+#[derive(Debug, Default)]
+pub struct InboxReport {
+    pub files: Vec<InboxFileResult>,
+}
+
+impl InboxReport {
+    pub fn imported(&self) -> usize {
+        self.files
+            .iter()
+            .filter(|f| matches!(f.outcome, InboxOutcome::Imported { .. }))
+            .count()
+    }
+
+    pub fn already_imported(&self) -> usize {
+        self.files
+            .iter()
+            .filter(|f| matches!(f.outcome, InboxOutcome::AlreadyImported))
+            .count()
+    }
+
+    pub fn failed(&self) -> usize {
+        self.files
+            .iter()
+            .filter(|f| matches!(f.outcome, InboxOutcome::Failed(_)))
+            .count()
+    }
+}
+
+/// Size and modification time of the inbox files seen by the previous poll
+///
+/// Used by watch mode so a file still being written or synced is only
+/// imported once it has stayed unchanged for a whole poll interval.
+#[derive(Debug, Default)]
+pub struct InboxSnapshot {
+    files: HashMap<PathBuf, (u64, Option<SystemTime>)>,
+}
+
+impl InboxSnapshot {
+    /// Files unchanged since the previous poll; the others are remembered for the next one
+    fn settled(&mut self, files: Vec<PathBuf>) -> Vec<PathBuf> {
+        let mut current = HashMap::new();
+        let mut settled = Vec::new();
+
+        for path in files {
+            let Ok(metadata) = fs::metadata(&path) else {
+                continue;
+            };
+            let stamp = (metadata.len(), metadata.modified().ok());
+            if self.files.get(&path) == Some(&stamp) {
+                settled.push(path.clone());
+            }
+            current.insert(path, stamp);
+        }
+
+        self.files = current;
+        settled
+    }
+}
+
+pub struct InboxService {
+    pool: PgPool,
+    import_service: ImportService,
+    payslip_import_service: PayslipImportService,
+    file_import_service: FileImportService,
+}
+
+impl InboxService {
+    pub fn new(pool: PgPool) -> Self {
+        Self {
+            import_service: ImportService::new(pool.clone()),
+            payslip_import_service: PayslipImportService::new(pool.clone()),
+            file_import_service: FileImportService::new(pool.clone()),
+            pool,
+        }
+    }
+
+    /// Store a new file-name mapping
+    pub async fn add_mapping(&self, new_mapping: NewImportMapping) -> Result<ImportMapping> {
+        if new_mapping.importer.is_payslip() {
+            let destinations = new_mapping.payslip_destinations.clone().ok_or_else(|| {
+                CoreError::InvalidInput(format!(
+                    "Importer '{}' requires payslip destination accounts",
+                    new_mapping.importer
+                ))
+            })?;
+            serde_json::from_value::<DestinationAccount>(destinations).map_err(|e| {
+                CoreError::InvalidInput(format!("Invalid payslip destinations: {}", e))
+            })?;
+        } else if new_mapping.target_account.is_none() {
+            return Err(CoreError::InvalidInput(format!(
+                "Importer '{}' requires a target account",
+                new_mapping.importer
+            )));
+        }
+
+        if new_mapping.importer == ImporterKind::Spreadsheet {
+            let columns = new_mapping.spreadsheet_columns.clone().ok_or_else(|| {
+                CoreError::InvalidInput(format!(
+                    "Importer '{}' requires spreadsheet columns",
+                    new_mapping.importer
+                ))
+            })?;
+            spreadsheet_columns(columns)?;
+        }
+
+        let mapping = sqlx::query_as::<_, ImportMapping>(
+            r#"
+            INSERT INTO import_mappings (file_pattern, importer, target_account, payslip_destinations, spreadsheet_columns, priority)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING id, file_pattern, importer, target_account, payslip_destinations, spreadsheet_columns, priority, created_at
+            "#,
+        )
+        .bind(&new_mapping.file_pattern)
+        .bind(new_mapping.importer.as_str())
+        .bind(&new_mapping.target_account)
+        .bind(&new_mapping.payslip_destinations)
+        .bind(&new_mapping.spreadsheet_columns)
+        .bind(new_mapping.priority)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(mapping)
+    }
+
+    /// List mappings in the order they are tried
+    pub async fn list_mappings(&self) -> Result<Vec<ImportMapping>> {
+        let mappings = sqlx::query_as::<_, ImportMapping>(
+            r#"
+            SELECT id, file_pattern, importer, target_account, payslip_destinations, spreadsheet_columns, priority, created_at
+            FROM import_mappings
+            ORDER BY priority DESC, created_at
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(mappings)
+    }
+
+    pub async fn remove_mapping(&self, mapping_id: Uuid) -> Result<()> {
+        let result = sqlx::query("DELETE FROM import_mappings WHERE id = $1")
+            .bind(mapping_id)
+            .execute(&self.pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(CoreError::NotFound(format!(
+                "Import mapping {} not found",
+                mapping_id
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// First mapping (in priority order) whose pattern matches the file name
+    pub fn find_mapping<'a>(
+        mappings: &'a [ImportMapping],
+        file_name: &str,
+    ) -> Option<&'a ImportMapping> {
+        mappings.iter().find(|mapping| mapping.matches(file_name))
+    }
+
+    /// Import every new file sitting at the top level of the inbox directory
+    pub async fn process_inbox<P: AsRef<Path>>(&self, inbox_dir: P) -> Result<InboxReport> {
+        let inbox_dir = inbox_dir.as_ref();
+        let files = pending_files(inbox_dir)?;
+        self.process_files(inbox_dir, files).await
+    }
+
+    /// Poll the inbox for watch mode, skipping files whose size or modification
+    /// time changed since the previous poll (they may still be being written)
+    pub async fn poll_inbox<P: AsRef<Path>>(
+        &self,
+        inbox_dir: P,
+        snapshot: &mut InboxSnapshot,
+    ) -> Result<InboxReport> {
+        let inbox_dir = inbox_dir.as_ref();
+        let files = snapshot.settled(pending_files(inbox_dir)?);
+        self.process_files(inbox_dir, files).await
+    }
+
+    async fn process_files(&self, inbox_dir: &Path, files: Vec<PathBuf>) -> Result<InboxReport> {
+        let mappings = self.list_mappings().await?;
+        let mut report = InboxReport::default();
+
+        for path in files {
+            let file_name = path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default();
+            info!("📥 Inbox file: {}", file_name);
+
+            let mapping = Self::find_mapping(&mappings, &file_name);
+            let importer = mapping.and_then(|m| m.importer.parse::<ImporterKind>().ok());
+
+            let outcome = match mapping {
+                Some(mapping) => self.process_file(&path, mapping).await,
+                None => InboxOutcome::Failed("No import mapping matches this file".to_string()),
+            };
+
+            let target_dir = match outcome {
+                InboxOutcome::Failed(_) => INBOX_FAILED_DIR,
+                _ => INBOX_PROCESSED_DIR,
+            };
+            let moved_to = match move_into(&path, &inbox_dir.join(target_dir)) {
+                Ok(destination) => Some(destination),
+                Err(e) => {
+                    warn!("Could not move {}: {}", file_name, e);
+                    None
+                }
+            };
+
+            append_log(inbox_dir, &file_name, importer, &outcome)?;
+
+            report.files.push(InboxFileResult {
+                file_name,
+                importer,
+                outcome,
+                moved_to,
+            });
+        }
+
+        Ok(report)
+    }
+
+    async fn process_file(&self, path: &Path, mapping: &ImportMapping) -> InboxOutcome {
+        match self.try_process_file(path, mapping).await {
+            Ok(outcome) => outcome,
+            Err(e) => InboxOutcome::Failed(e.to_string()),
+        }
+    }
+
+    async fn try_process_file(&self, path: &Path, mapping: &ImportMapping) -> Result<InboxOutcome> {
+        let file_hash = FileImportService::calculate_file_hash(path)?;
+        if self
+            .file_import_service
+            .is_file_already_imported(&file_hash)
+            .await?
+        {
+            return Ok(InboxOutcome::AlreadyImported);
+        }
+
+        let file_path = path.to_string_lossy().to_string();
+        let importer: ImporterKind = mapping.importer.parse().map_err(CoreError::InvalidInput)?;
+
+        match importer {
+            ImporterKind::BoursoBank => {
+                self.import_statement(&BoursoBankImporter::default(), &file_path, mapping)
+                    .await
+            }
+            ImporterKind::SocieteGenerale => {
+                self.import_statement(&SocietegeneraleImporter::default(), &file_path, mapping)
+                    .await
+            }
+            ImporterKind::QtPayslip => {
+                self.import_payslip(&QtPayslipImporter::new(), &file_path, mapping)
+                    .await
+            }
+            ImporterKind::MathWorksPayslip => {
+                self.import_payslip(&MathWorksPayslipImporter::new(), &file_path, mapping)
+                    .await
+            }
+            ImporterKind::Spreadsheet => {
+                let columns = mapping.spreadsheet_columns.clone().ok_or_else(|| {
+                    CoreError::InvalidInput("Mapping has no spreadsheet columns".to_string())
+                })?;
+                let importer = SpreadsheetImporter::new(spreadsheet_columns(columns)?);
+                self.import_statement(&importer, &file_path, mapping).await
+            }
+            ImporterKind::BoursoBankPdf => {
+                self.import_statement(&PdfStatementImporter::boursobank(), &file_path, mapping)
+                    .await
+            }
+            ImporterKind::SocieteGeneralePdf => {
+                self.import_statement(
+                    &PdfStatementImporter::societe_generale(),
+                    &file_path,
+                    mapping,
+                )
+                .await
+            }
+        }
+    }
+
+    async fn import_statement<T: TransactionImporter>(
+        &self,
+        importer: &T,
+        file_path: &str,
+        mapping: &ImportMapping,
+    ) -> Result<InboxOutcome> {
+        if !importer.can_handle_file(file_path)? {
+            return Err(CoreError::ImportError(format!(
+                "File does not look like: {}",
+                importer.format_description()
+            )));
+        }
+
+        let target_account = mapping
+            .target_account
+            .as_deref()
+            .ok_or_else(|| CoreError::InvalidInput("Mapping has no target account".to_string()))?;
+
+        let summary = self
+            .import_service
+            .import_transactions(importer, file_path, target_account)
+            .await?;
+
+        outcome_from_summary(&summary)
+    }
+
+    async fn import_payslip<T: PayslipImporter>(
+        &self,
+        importer: &T,
+        file_path: &str,
+        mapping: &ImportMapping,
+    ) -> Result<InboxOutcome> {
+        if !importer.can_handle_file(file_path)? {
+            return Err(CoreError::ImportError(format!(
+                "File does not look like: {}",
+                importer.format_description()
+            )));
+        }
+
+        let destinations: DestinationAccount = mapping
+            .payslip_destinations
+            .clone()
+            .map(serde_json::from_value)
+            .transpose()
+            .map_err(|e| CoreError::InvalidInput(format!("Invalid payslip destinations: {}", e)))?
+            .ok_or_else(|| {
+                CoreError::InvalidInput("Mapping has no payslip destinations".to_string())
+            })?;
+
+        let result = self
+            .payslip_import_service
+            .import_payslip(importer, file_path, &destinations)
+            .await?;
+
+        // Payslips are recorded here so the inbox can skip them next time
+        let file_metadata = self.file_import_service.prepare_file_metadata(
+            file_path,
+            "Payslip",
+            Uuid::new_v4(),
+            1,
+            Some(format!(
+                "Payslip from {} paid {}",
+                result.payslip_info.employer_name, result.payslip_info.pay_date
+            )),
+        )?;
+        self.file_import_service
+            .record_file_import(file_metadata)
+            .await?;
+
+        Ok(InboxOutcome::Imported {
+            created: 1,
+            skipped: 0,
+        })
+    }
+}
+
+fn spreadsheet_columns(columns: serde_json::Value) -> Result<SpreadsheetColumnMapping> {
+    let columns: SpreadsheetColumnMapping = serde_json::from_value(columns)
+        .map_err(|e| CoreError::InvalidInput(format!("Invalid spreadsheet columns: {}", e)))?;
+    if columns.amount_column.is_none()
+        && columns.debit_column.is_none()
+        && columns.credit_column.is_none()
+    {
+        return Err(CoreError::InvalidInput(
+            "An amount column or debit/credit columns are required".to_string(),
+        ));
+    }
+    Ok(columns)
+}
+
+/// A statement dropped again has all its lines skipped; it is already imported, not failed
+fn outcome_from_summary(summary: &ImportSummary) -> Result<InboxOutcome> {
+    if summary.created + summary.matched == 0 {
+        if summary.skipped > 0 {
+            return Ok(InboxOutcome::AlreadyImported);
+        }
+        return Err(CoreError::ImportError(
+            summary
+                .errors
+                .first()
+                .cloned()
+                .unwrap_or_else(|| "No transactions found in file".to_string()),
+        ));
+    }
+
+    Ok(InboxOutcome::Imported {
+        created: summary.created,
+        skipped: summary.skipped,
+    })
+}
+
+/// Regular, non-hidden files at the top level of the inbox, oldest name first
+fn pending_files(inbox_dir: &Path) -> Result<Vec<PathBuf>> {
+    if !inbox_dir.is_dir() {
+        return Err(CoreError::InvalidInput(format!(
+            "Inbox '{}' is not a directory",
+            inbox_dir.display()
+        )));
+    }
+
+    let mut files = Vec::new();
+    for entry in fs::read_dir(inbox_dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        if !path.is_file() || file_name.starts_with('.') || file_name == INBOX_LOG_FILE {
+            continue;
+        }
+        files.push(path);
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Move a file into `target_dir`, never overwriting an existing file
+fn move_into(path: &Path, target_dir: &Path) -> Result<PathBuf> {
+    fs::create_dir_all(target_dir)?;
+
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| CoreError::InvalidInput(format!("Invalid file: {}", path.display())))?
+        .to_string_lossy()
+        .to_string();
+
+    let mut destination = target_dir.join(&file_name);
+    if destination.exists() {
+        destination = target_dir.join(format!(
+            "{}-{}",
+            Utc::now().format("%Y%m%d%H%M%S"),
+            file_name
+        ));
+    }
+
+    fs::rename(path, &destination)?;
+    Ok(destination)
+}
+
+fn append_log(
+    inbox_dir: &Path,
+    file_name: &str,
+    importer: Option<ImporterKind>,
+    outcome: &InboxOutcome,
+) -> Result<()> {
+    let (status, detail) = match outcome {
+        InboxOutcome::Imported { created, skipped } => (
+            "IMPORTED",
+            format!("{} created, {} skipped", created, skipped),
+        ),
+        InboxOutcome::AlreadyImported => ("SKIPPED", "already imported".to_string()),
+        InboxOutcome::Failed(reason) => ("FAILED", reason.clone()),
+    };
+
+    let mut log = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(inbox_dir.join(INBOX_LOG_FILE))?;
+    writeln!(
+        log,
+        "{}\t{}\t{}\t{}\t{}",
+        Utc::now().format("%Y-%m-%d %H:%M:%S"),
+        status,
+        importer.map(|kind| kind.as_str()).unwrap_or("-"),
+        file_name,
+        detail
+    )?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,127 @@
+use super::*;
+use crate::importers::AmountSign;
+
+fn mapping(pattern: &str, importer: ImporterKind) -> ImportMapping {
+    ImportMapping {
+        id: Uuid::new_v4(),
+        file_pattern: pattern.to_string(),
+        importer: importer.as_str().to_string(),
+        target_account: Some("Assets:Current Assets:Checking".to_string()),
+        payslip_destinations: None,
+        spreadsheet_columns: None,
+        priority: 0,
+        created_at: Utc::now(),
+    }
+}
+
+#[test]
+fn test_mapping_glob_matching() {
+    let csv = mapping("export-*.csv", ImporterKind::BoursoBank);
+    assert!(csv.matches("export-2025-01.csv"));
+    assert!(csv.matches("EXPORT-2025-01.CSV"));
+    assert!(!csv.matches("statement.pdf"));
+
+    let payslip = mapping("bulletin_??.pdf", ImporterKind::QtPayslip);
+    assert!(payslip.matches("bulletin_03.pdf"));
+    assert!(!payslip.matches("bulletin_3.pdf"));
+
+    assert!(mapping("*", ImporterKind::SocieteGenerale).matches("anything"));
+}
+
+#[test]
+fn test_find_mapping_uses_first_match() {
+    let mappings = vec![
+        mapping("export-sg-*.csv", ImporterKind::SocieteGenerale),
+        mapping("export-*.csv", ImporterKind::BoursoBank),
+    ];
+
+    let found = InboxService::find_mapping(&mappings, "export-sg-2025.csv").unwrap();
+    assert_eq!(found.importer, "sg");
+    let found = InboxService::find_mapping(&mappings, "export-2025.csv").unwrap();
+    assert_eq!(found.importer, "boursobank");
+    assert!(InboxService::find_mapping(&mappings, "notes.txt").is_none());
+}
+
+#[test]
+fn test_pending_files_and_move() {
+    let inbox = tempfile::tempdir().unwrap();
+    fs::write(inbox.path().join("b.csv"), "b").unwrap();
+    fs::write(inbox.path().join("a.csv"), "a").unwrap();
+    fs::write(inbox.path().join(".hidden"), "").unwrap();
+    fs::write(inbox.path().join(INBOX_LOG_FILE), "").unwrap();
+    fs::create_dir(inbox.path().join(INBOX_PROCESSED_DIR)).unwrap();
+
+    let files = pending_files(inbox.path()).unwrap();
+    let names: Vec<_> = files
+        .iter()
+        .map(|p| p.file_name().unwrap().to_string_lossy().to_string())
+        .collect();
+    assert_eq!(names, vec!["a.csv", "b.csv"]);
+
+    // A second file with the same name must not overwrite the first
+    let processed = inbox.path().join(INBOX_PROCESSED_DIR);
+    let first = move_into(&files[0], &processed).unwrap();
+    fs::write(inbox.path().join("a.csv"), "again").unwrap();
+    let second = move_into(&inbox.path().join("a.csv"), &processed).unwrap();
+
+    assert_ne!(first, second);
+    assert_eq!(fs::read_to_string(first).unwrap(), "a");
+    assert_eq!(fs::read_to_string(second).unwrap(), "again");
+}
+
+#[test]
+fn test_snapshot_waits_for_unchanged_files() {
+    let inbox = tempfile::tempdir().unwrap();
+    let path = inbox.path().join("export.csv");
+    fs::write(&path, "partial").unwrap();
+
+    // A file seen for the first time may still be being written
+    let mut snapshot = InboxSnapshot::default();
+    assert!(snapshot.settled(vec![path.clone()]).is_empty());
+
+    // It grew since the previous poll
+    fs::write(&path, "partial, now complete").unwrap();
+    assert!(snapshot.settled(vec![path.clone()]).is_empty());
+
+    assert_eq!(snapshot.settled(vec![path.clone()]), vec![path]);
+}
+
+#[test]
+fn test_spreadsheet_columns_need_an_amount() {
+    let columns = serde_json::json!({ "date_column": "Date", "label_column": "Label" });
+    assert!(spreadsheet_columns(columns.clone()).is_err());
+
+    let mut with_amount = columns;
+    with_amount["amount_column"] = "Amount".into();
+    let mapping = spreadsheet_columns(with_amount).unwrap();
+    assert_eq!(mapping.header_row, 1);
+    assert_eq!(mapping.sign, AmountSign::AsIs);
+}
+
+#[test]
+fn test_outcome_from_summary() {
+    let summary = |created, matched, skipped| ImportSummary {
+        total: created + matched + skipped,
+        created,
+        matched,
+        skipped,
+        assertions_created: 0,
+        transfers_linked: 0,
+        accounts_created: Vec::new(),
+        errors: Vec::new(),
+    };
+
+    assert!(matches!(
+        outcome_from_summary(&summary(3, 0, 1)),
+        Ok(InboxOutcome::Imported {
+            created: 3,
+            skipped: 1
+        })
+    ));
+    // Re-dropping a statement skips every line
+    assert!(matches!(
+        outcome_from_summary(&summary(0, 0, 4)),
+        Ok(InboxOutcome::AlreadyImported)
+    ));
+    assert!(outcome_from_summary(&summary(0, 0, 0)).is_err());
+}
@@ -0,0 +1,202 @@
+use crate::error::Result;
+use crate::models::{ChainIssue, ChainIssueKind, ChainLink, ChainVerification};
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// `previous_hash` of the first link
+pub const CHAIN_GENESIS_HASH: &str =
+    "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// Tamper-evident hash chain over the journal
+///
+/// Every service that writes a transaction or its entries seals it in the same
+/// database transaction: a link is appended with the SHA-256 of the transaction's
+/// canonical serialization (computed by `fn_transaction_content_hash`) chained to
+/// the previous link. Verification recomputes both, so rows edited by hand and
+/// links edited after the fact are pinpointed.
+pub struct IntegrityService {
+    pool: PgPool,
+}
+
+impl IntegrityService {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Append a link for the current state of each transaction, deleted ones included
+    pub(crate) async fn seal(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        transaction_ids: &[Uuid],
+    ) -> Result<()> {
+        if transaction_ids.is_empty() {
+            return Ok(());
+        }
+
+        // One writer at a time, so each link really follows the current head
+        sqlx::query("LOCK TABLE ledger_chain IN EXCLUSIVE MODE")
+            .execute(&mut **tx)
+            .await?;
+        let mut previous_hash: String =
+            sqlx::query_scalar("SELECT chain_hash FROM ledger_chain ORDER BY seq DESC LIMIT 1")
+                .fetch_optional(&mut **tx)
+                .await?
+                .unwrap_or_else(|| CHAIN_GENESIS_HASH.to_string());
+
+        for &transaction_id in transaction_ids {
+            let content_hash: Option<String> =
+                sqlx::query_scalar("SELECT fn_transaction_content_hash($1)")
+                    .bind(transaction_id)
+                    .fetch_one(&mut **tx)
+                    .await?;
+            let chain_hash = link_hash(&previous_hash, transaction_id, content_hash.as_deref());
+            sqlx::query(
+                r#"
+                INSERT INTO ledger_chain (transaction_id, content_hash, previous_hash, chain_hash)
+                VALUES ($1, $2, $3, $4)
+                "#,
+            )
+            .bind(transaction_id)
+            .bind(&content_hash)
+            .bind(&previous_hash)
+            .bind(&chain_hash)
+            .execute(&mut **tx)
+            .await?;
+            previous_hash = chain_hash;
+        }
+
+        Ok(())
+    }
+
+    /// Accept the current state of transactions flagged by [`Self::verify`]
+    ///
+    /// For changes made outside the services on purpose; broken links stay broken.
+    pub async fn reseal(&self, transaction_ids: &[Uuid]) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+        Self::seal(&mut tx, transaction_ids).await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Latest link of the chain
+    pub async fn head(&self) -> Result<Option<ChainLink>> {
+        let head = sqlx::query_as::<_, ChainLink>(
+            r#"
+            SELECT seq, transaction_id, content_hash, previous_hash, chain_hash, sealed_at
+            FROM ledger_chain
+            ORDER BY seq DESC
+            LIMIT 1
+            "#,
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(head)
+    }
+
+    /// Recompute the chain and every transaction's hash, reporting what does not match
+    pub async fn verify(&self) -> Result<ChainVerification> {
+        let links = sqlx::query_as::<_, ChainLink>(
+            r#"
+            SELECT seq, transaction_id, content_hash, previous_hash, chain_hash, sealed_at
+            FROM ledger_chain
+            ORDER BY seq
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        let current: Vec<(Uuid, Option<String>)> = sqlx::query_as(
+            "SELECT id, fn_transaction_content_hash(id) FROM transactions ORDER BY created_at, id",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(check_chain(&links, &current))
+    }
+}
+
+/// Hash of a link given the one before it
+fn link_hash(previous_hash: &str, transaction_id: Uuid, content_hash: Option<&str>) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(
+        format!(
+            "{}:{}:{}",
+            previous_hash,
+            transaction_id,
+            content_hash.unwrap_or("-")
+        )
+        .as_bytes(),
+    );
+    format!("{:x}", hasher.finalize())
+}
+
+/// Compare the chain with the transactions' current hashes
+fn check_chain(links: &[ChainLink], current: &[(Uuid, Option<String>)]) -> ChainVerification {
+    let mut issues = Vec::new();
+
+    // Keep following the stored hashes after a break so each tampered link shows once
+    let mut previous_hash = CHAIN_GENESIS_HASH;
+    let mut latest: HashMap<Uuid, &ChainLink> = HashMap::new();
+    for link in links {
+        let expected = link_hash(
+            &link.previous_hash,
+            link.transaction_id,
+            link.content_hash.as_deref(),
+        );
+        if link.previous_hash != previous_hash || link.chain_hash != expected {
+            issues.push(ChainIssue {
+                kind: ChainIssueKind::BrokenLink,
+                transaction_id: link.transaction_id,
+                seq: Some(link.seq),
+                expected_hash: Some(link.chain_hash.clone()),
+                actual_hash: Some(expected),
+            });
+        }
+        previous_hash = &link.chain_hash;
+        latest.insert(link.transaction_id, link);
+    }
+
+    for (transaction_id, actual_hash) in current {
+        match latest.remove(transaction_id) {
+            Some(link) if link.content_hash == *actual_hash => {}
+            Some(link) if link.content_hash.is_some() => issues.push(ChainIssue {
+                kind: ChainIssueKind::Modified,
+                transaction_id: *transaction_id,
+                seq: Some(link.seq),
+                expected_hash: link.content_hash.clone(),
+                actual_hash: actual_hash.clone(),
+            }),
+            // Never sealed, or sealed as deleted and back again
+            _ => issues.push(ChainIssue {
+                kind: ChainIssueKind::Unsealed,
+                transaction_id: *transaction_id,
+                seq: None,
+                expected_hash: None,
+                actual_hash: actual_hash.clone(),
+            }),
+        }
+    }
+
+    let mut deleted: Vec<&ChainLink> = latest
+        .into_values()
+        .filter(|link| link.content_hash.is_some())
+        .collect();
+    deleted.sort_by_key(|link| link.seq);
+    issues.extend(deleted.into_iter().map(|link| ChainIssue {
+        kind: ChainIssueKind::Deleted,
+        transaction_id: link.transaction_id,
+        seq: Some(link.seq),
+        expected_hash: link.content_hash.clone(),
+        actual_hash: None,
+    }));
+
+    ChainVerification {
+        links_checked: links.len(),
+        transactions_checked: current.len(),
+        head_hash: links.last().map(|link| link.chain_hash.clone()),
+        issues,
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,192 @@
+use super::*;
+use crate::models::{AccountSubtype, AccountType, NewAccountByPath};
+use crate::services::{AccountService, TransactionService};
+use crate::tests::utils::*;
+use chrono::{TimeZone, Utc};
+use rust_decimal::Decimal;
+
+fn chain(entries: &[(Uuid, Option<&str>)]) -> Vec<ChainLink> {
+    let mut previous_hash = CHAIN_GENESIS_HASH.to_string();
+    entries
+        .iter()
+        .enumerate()
+        .map(|(index, (transaction_id, content_hash))| {
+            let chain_hash = link_hash(&previous_hash, *transaction_id, *content_hash);
+            let link = ChainLink {
+                seq: index as i64 + 1,
+                transaction_id: *transaction_id,
+                content_hash: content_hash.map(str::to_string),
+                previous_hash: previous_hash.clone(),
+                chain_hash: chain_hash.clone(),
+                sealed_at: Utc::now(),
+            };
+            previous_hash = chain_hash;
+            link
+        })
+        .collect()
+}
+
+#[test]
+fn test_check_chain_pinpoints_each_issue() {
+    let (kept, edited, removed, deleted) = (
+        Uuid::new_v4(),
+        Uuid::new_v4(),
+        Uuid::new_v4(),
+        Uuid::new_v4(),
+    );
+    let mut links = chain(&[
+        (kept, Some("a")),
+        (edited, Some("b")),
+        (removed, Some("c")),
+        (deleted, Some("d")),
+        (deleted, None),
+        (kept, Some("a2")),
+    ]);
+
+    let current = vec![
+        (kept, Some("a2".to_string())),
+        (edited, Some("b".to_string())),
+    ];
+    let verification = check_chain(&links, &current);
+    assert!(
+        matches!(verification.issues.as_slice(), [issue] if issue.kind == ChainIssueKind::Deleted && issue.transaction_id == removed)
+    );
+    assert_eq!(verification.links_checked, 6);
+    assert_eq!(
+        verification.head_hash.as_deref(),
+        Some(links[5].chain_hash.as_str())
+    );
+
+    // A rewritten link breaks there, and only there
+    links[1].content_hash = Some("b2".to_string());
+    let unsealed = Uuid::new_v4();
+    let current = vec![
+        (kept, Some("a2".to_string())),
+        (edited, Some("b3".to_string())),
+        (removed, Some("c".to_string())),
+        (unsealed, Some("e".to_string())),
+    ];
+    let kinds: Vec<(ChainIssueKind, Uuid)> = check_chain(&links, &current)
+        .issues
+        .iter()
+        .map(|issue| (issue.kind, issue.transaction_id))
+        .collect();
+    assert_eq!(
+        kinds,
+        vec![
+            (ChainIssueKind::BrokenLink, edited),
+            (ChainIssueKind::Modified, edited),
+            (ChainIssueKind::Unsealed, unsealed),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_verify_detects_changes_outside_the_services() {
+    let (pool, _container) = setup_test_db().await;
+    let account_service = AccountService::new(pool.clone());
+    let mut ids = Vec::new();
+    for (path, account_type, subtype) in [
+        (
+            "Assets:Checking",
+            AccountType::Asset,
+            AccountSubtype::Checking,
+        ),
+        (
+            "Expenses:Groceries",
+            AccountType::Expense,
+            AccountSubtype::Category,
+        ),
+    ] {
+        let account = account_service
+            .create_account_by_path(
+                NewAccountByPath::builder()
+                    .full_path(path)
+                    .account_type(account_type)
+                    .account_subtype(subtype)
+                    .build(),
+            )
+            .await
+            .unwrap();
+        ids.push(account.id);
+    }
+
+    let tx_service = TransactionService::new(pool.clone());
+    let mut transactions = Vec::new();
+    for day in 1..=3 {
+        let created = tx_service
+            .create_transaction(TransactionService::create_simple_transaction(
+                format!("Groceries {}", day),
+                ids[1],
+                ids[0],
+                Decimal::from(10 * day),
+                Utc.with_ymd_and_hms(2025, 3, day, 12, 0, 0).unwrap(),
+                None,
+            ))
+            .await
+            .unwrap();
+        transactions.push(created.transaction.id);
+    }
+    tx_service.void_transaction(transactions[1]).await.unwrap();
+    tx_service
+        .delete_transaction(transactions[2])
+        .await
+        .unwrap();
+
+    let service = IntegrityService::new(pool.clone());
+    let verification = service.verify().await.unwrap();
+    assert!(verification.is_intact(), "{:?}", verification.issues);
+    assert_eq!(verification.links_checked, 5);
+    assert_eq!(verification.transactions_checked, 2);
+
+    // Edits made straight in the database
+    sqlx::query("UPDATE transactions SET description = 'Jewellery' WHERE id = $1")
+        .bind(transactions[0])
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("DELETE FROM journal_entries WHERE transaction_id = $1")
+        .bind(transactions[1])
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("DELETE FROM transactions WHERE id = $1")
+        .bind(transactions[1])
+        .execute(&pool)
+        .await
+        .unwrap();
+    // ...and the chain itself is append-only
+    assert!(
+        sqlx::query("UPDATE ledger_chain SET content_hash = NULL")
+            .execute(&pool)
+            .await
+            .is_err()
+    );
+
+    let verification = service.verify().await.unwrap();
+    let kinds: Vec<(ChainIssueKind, Uuid)> = verification
+        .issues
+        .iter()
+        .map(|issue| (issue.kind, issue.transaction_id))
+        .collect();
+    assert_eq!(
+        kinds,
+        vec![
+            (ChainIssueKind::Modified, transactions[0]),
+            (ChainIssueKind::Deleted, transactions[1]),
+        ]
+    );
+
+    // Accepting the changes makes the chain whole again
+    let flagged: Vec<Uuid> = verification
+        .issues
+        .iter()
+        .map(|issue| issue.transaction_id)
+        .collect();
+    service.reseal(&flagged).await.unwrap();
+    assert!(service.verify().await.unwrap().is_intact());
+    assert_eq!(
+        service.head().await.unwrap().unwrap().transaction_id,
+        transactions[1]
+    );
+}
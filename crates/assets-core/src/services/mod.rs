@@ -1,25 +1,65 @@
 // Re-export all services for easier imports
 mod account_service;
+mod attachment_service;
+mod audit_service;
+mod backup_service;
+mod balance_assertion_service;
+mod categorization_service;
 mod deduplication_service;
+mod discrepancy_service;
 mod file_import_service;
+mod gnucash_migration_service;
 mod import_service;
+mod inbox_service;
+mod integrity_service;
 mod ownership_service;
 mod payslip_import_service;
+mod period_service;
 mod price_history_service;
+mod reconciliation_service;
+mod recurring_service;
+mod refund_service;
 mod report_service;
+mod tag_service;
 mod transaction_service;
+mod transfer_service;
 mod user_service;
 
+pub(crate) use account_service::sorted_parents_first;
 pub use account_service::{AccountService, AccountUpdates};
+pub use attachment_service::{
+    ATTACHMENTS_DIR_ENV, AttachmentService, DEFAULT_MIME_TYPE, mime_type_for,
+};
+pub use audit_service::{AuditLogEntry, AuditService};
+pub use backup_service::{BACKUP_FORMAT_VERSION, BackupArchive, BackupData, BackupService};
+pub use balance_assertion_service::BalanceAssertionService;
+pub use categorization_service::{CategorizationService, SUGGESTION_SIMILARITY_THRESHOLD};
 pub use deduplication_service::{
     DeduplicationService, MatchStatus, MatchType, TransactionComparisonDetails, TransactionMatch,
     TransactionWithDuplicateInfo,
 };
+pub use discrepancy_service::{DUPLICATE_WINDOW_DAYS, DiscrepancyService};
 pub use file_import_service::FileImportService;
-pub use import_service::{ImportService, ImportSummary};
+pub use gnucash_migration_service::{GnuCashMigrationReport, GnuCashMigrationService};
+pub use import_service::{ImportService, ImportSummary, UNCATEGORIZED_ACCOUNT_PATH};
+pub use inbox_service::{
+    INBOX_FAILED_DIR, INBOX_LOG_FILE, INBOX_PROCESSED_DIR, InboxFileResult, InboxOutcome,
+    InboxReport, InboxService, InboxSnapshot,
+};
+pub use integrity_service::{CHAIN_GENESIS_HASH, IntegrityService};
 // OwnershipService export removed - ownership functionality eliminated
 pub use payslip_import_service::{DestinationAccount, ImportResult, PayslipImportService};
+pub use period_service::{PeriodService, RETAINED_EARNINGS_ACCOUNT_PATH};
 pub use price_history_service::PriceHistoryService;
+pub use reconciliation_service::ReconciliationService;
+pub use recurring_service::{
+    PostedRecurringInstance, RECURRING_MATCH_WINDOW_DAYS, RecurringService,
+};
+pub use refund_service::RefundService;
 pub use report_service::{AccountBalance, BalanceSheetData, ReportService};
-pub use transaction_service::TransactionService;
+pub use tag_service::TagService;
+pub use transaction_service::{
+    SEARCH_SIMILARITY_THRESHOLD, TransactionService, TransactionUpdates,
+};
+pub use transfer_service::{TRANSFER_CLEARING_ACCOUNT_PATH, TransferService};
 // UserService export removed - ownership functionality eliminated
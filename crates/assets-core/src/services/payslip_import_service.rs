@@ -3,14 +3,18 @@ use crate::importers::{ImportedPayslip, PayslipImporter};
 use crate::models::{NewJournalEntry, NewTransaction};
 use crate::services::{AccountService, TransactionService};
 use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
 use uuid::Uuid;
 
 pub struct PayslipImportService {
+    pool: PgPool,
     transaction_service: TransactionService,
     account_service: AccountService,
+    ensure_accounts: bool,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DestinationAccount {
     pub fixed_gross: String,          // e.g., "Income:Salary:Fixed"
     pub variable_gross: String,       // e.g., "Income:Salary:Variable"
@@ -28,11 +32,21 @@ impl PayslipImportService {
         let account_service = AccountService::new(pool.clone());
 
         Self {
+            pool,
             transaction_service,
             account_service,
+            ensure_accounts: false,
         }
     }
 
+    /// Create missing destination accounts instead of failing
+    ///
+    /// Their types are inferred from the path's root segment.
+    pub fn ensure_accounts(mut self, ensure_accounts: bool) -> Self {
+        self.ensure_accounts = ensure_accounts;
+        self
+    }
+
     /// Import a payslip using the specified importer and convert to transactions
     pub async fn import_payslip<T: PayslipImporter>(
         &self,
@@ -44,14 +58,15 @@ impl PayslipImportService {
         let payslip = importer.import_from_file(file_path).await?;
 
         // Convert payslip to transaction
+        let mut accounts_created = Vec::new();
         let transaction_id = self
-            .create_payslip_transaction(&payslip, destination_account)
+            .create_payslip_transaction(&payslip, destination_account, &mut accounts_created)
             .await?;
 
         Ok(ImportResult {
             payslip_info: payslip,
             transaction_id,
-            accounts_created: vec![], // We'll populate this if we create any accounts
+            accounts_created,
             warnings: vec![],
         })
     }
@@ -61,6 +76,7 @@ impl PayslipImportService {
         &self,
         payslip: &ImportedPayslip,
         destination_account: &DestinationAccount,
+        accounts_created: &mut Vec<String>,
     ) -> Result<Uuid> {
         let entries = [
             (
@@ -95,29 +111,37 @@ impl PayslipImportService {
             (&destination_account.net_pay, payslip.net_paid_salary),
         ];
 
-        let journal_entry_futures = entries.iter().map(|(path, amount)| {
-            let account_service = &self.account_service;
-            async move {
-                let account = account_service
+        // Accounts created in ensure mode are rolled back with a rejected transaction
+        let mut tx = self.pool.begin().await?;
+
+        // Resolved one at a time: destinations may share parents that ensure mode creates
+        let mut created_paths = Vec::new();
+        let mut journal_entries = Vec::with_capacity(entries.len());
+        for (path, amount) in entries {
+            let account = if self.ensure_accounts {
+                let (account, created) = self
+                    .account_service
+                    .ensure_account_by_path_in(&mut tx, path)
+                    .await?;
+                created_paths.extend(created);
+                account
+            } else {
+                self.account_service
                     .get_account_by_path(path)
                     .await
                     .map_err(|_| {
-                        // Account not found, create it
                         crate::error::CoreError::NotFound(format!(
-                            "Account not found: {}. Please create this account first.",
+                            "Account not found: {}. Please create this account first or import with --create-accounts.",
                             path
                         ))
-                    })?;
-                Ok::<_, crate::error::CoreError>(NewJournalEntry {
-                    account_id: account.id,
-                    amount: *amount,
-                    memo: None, // Memo can be added later if needed
-                })
-            }
-        });
-
-        let journal_entries: Vec<NewJournalEntry> =
-            futures::future::try_join_all(journal_entry_futures).await?;
+                    })?
+            };
+            journal_entries.push(NewJournalEntry {
+                account_id: account.id,
+                amount,
+                memo: None, // Memo can be added later if needed
+            });
+        }
 
         let transaction_request = NewTransaction {
             description: format!(
@@ -139,8 +163,10 @@ impl PayslipImportService {
 
         let result = self
             .transaction_service
-            .create_transaction(transaction_request)
+            .create_transaction_in(&mut tx, transaction_request)
             .await?;
+        tx.commit().await?;
+        accounts_created.extend(created_paths);
 
         Ok(result.transaction.id)
     }
@@ -0,0 +1,211 @@
+use crate::error::{CoreError, Result};
+use crate::models::{
+    AccountSubtype, AccountType, NewAccountByPath, NewJournalEntry, NewTransaction, PeriodClose,
+};
+use crate::services::{AccountService, IntegrityService, TransactionService};
+use crate::validation::TransactionValidator;
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Equity account receiving the net income of closed periods
+pub const RETAINED_EARNINGS_ACCOUNT_PATH: &str = "Equity:Retained Earnings";
+
+/// Closing finished periods and locking them against changes
+///
+/// Closing posts one transaction on the period's last day that zeroes every income
+/// and expense account into [`RETAINED_EARNINGS_ACCOUNT_PATH`], then records the
+/// period end as the lock date. From then on the service layer and database triggers
+/// reject creating, moving, voiding or deleting anything dated on or before it.
+/// Reopening undoes the latest close.
+pub struct PeriodService {
+    pool: PgPool,
+    account_service: AccountService,
+}
+
+impl PeriodService {
+    pub fn new(pool: PgPool) -> Self {
+        Self {
+            account_service: AccountService::new(pool.clone()),
+            pool,
+        }
+    }
+
+    /// Last day of the latest closed period, if any
+    pub async fn lock_date(&self) -> Result<Option<NaiveDate>> {
+        let lock_date = sqlx::query_scalar("SELECT fn_lock_date()")
+            .fetch_one(&self.pool)
+            .await?;
+        Ok(lock_date)
+    }
+
+    /// Close every period up to and including `period_end`
+    pub async fn close_period(&self, period_end: NaiveDate) -> Result<PeriodClose> {
+        if let Some(lock_date) = self.lock_date().await? {
+            if period_end <= lock_date {
+                return Err(CoreError::ValidationError(format!(
+                    "Periods are already closed through {}",
+                    lock_date
+                )));
+            }
+        }
+        let retained_earnings_id = self.retained_earnings_account().await?;
+
+        let mut tx = self.pool.begin().await?;
+
+        // Balances include earlier closing entries, so only what is still open remains
+        let balances: Vec<(Uuid, Decimal)> = sqlx::query_as(
+            r#"
+            SELECT a.id, SUM(je.amount)
+            FROM journal_entries je
+            JOIN accounts a ON a.id = je.account_id
+            JOIN transactions t ON t.id = je.transaction_id
+            WHERE a.account_type IN ('income', 'expense')
+              AND NOT t.is_voided
+              AND t.transaction_date < $1::date + 1
+            GROUP BY a.id, a.full_path
+            HAVING SUM(je.amount) <> 0
+            ORDER BY a.full_path
+            "#,
+        )
+        .bind(period_end)
+        .fetch_all(&mut *tx)
+        .await?;
+
+        // Income balances are credits (negative), so their sum with expenses is the net loss
+        let net_loss: Decimal = balances.iter().map(|(_, balance)| *balance).sum();
+        let closing_transaction_id = if balances.is_empty() {
+            None
+        } else {
+            let mut entries: Vec<NewJournalEntry> = balances
+                .iter()
+                .map(|(account_id, balance)| NewJournalEntry {
+                    account_id: *account_id,
+                    amount: -*balance,
+                    memo: None,
+                })
+                .collect();
+            if net_loss != Decimal::ZERO {
+                entries.push(NewJournalEntry {
+                    account_id: retained_earnings_id,
+                    amount: net_loss,
+                    memo: None,
+                });
+            }
+            let closing = NewTransaction {
+                description: format!("Closing entries for the period ending {}", period_end),
+                reference: None,
+                transaction_date: period_end.and_hms_opt(12, 0, 0).unwrap().and_utc(),
+                entries,
+                import_source: None,
+                import_batch_id: None,
+                external_reference: None,
+            };
+            Some(
+                TransactionService::insert_transaction(
+                    &mut tx,
+                    &TransactionValidator::new(),
+                    &closing,
+                )
+                .await?
+                .transaction
+                .id,
+            )
+        };
+
+        let close = sqlx::query_as::<_, PeriodClose>(
+            r#"
+            INSERT INTO period_closes (period_end, closing_transaction_id, net_income)
+            VALUES ($1, $2, $3)
+            RETURNING id, period_end, closing_transaction_id, net_income, closed_at
+            "#,
+        )
+        .bind(period_end)
+        .bind(closing_transaction_id)
+        .bind(-net_loss)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(close)
+    }
+
+    /// Undo the latest close: drop its closing entries and move the lock date back
+    ///
+    /// Returns the reopened period, or `None` when nothing is closed.
+    pub async fn reopen_latest(&self) -> Result<Option<PeriodClose>> {
+        let mut tx = self.pool.begin().await?;
+
+        let Some(close) = sqlx::query_as::<_, PeriodClose>(
+            r#"
+            SELECT id, period_end, closing_transaction_id, net_income, closed_at
+            FROM period_closes
+            ORDER BY period_end DESC
+            LIMIT 1
+            FOR UPDATE
+            "#,
+        )
+        .fetch_optional(&mut *tx)
+        .await?
+        else {
+            return Ok(None);
+        };
+
+        // The lock has to move back before the closing transaction can go
+        sqlx::query("DELETE FROM period_closes WHERE id = $1")
+            .bind(close.id)
+            .execute(&mut *tx)
+            .await?;
+        if let Some(transaction_id) = close.closing_transaction_id {
+            sqlx::query("DELETE FROM journal_entries WHERE transaction_id = $1")
+                .bind(transaction_id)
+                .execute(&mut *tx)
+                .await?;
+            sqlx::query("DELETE FROM transactions WHERE id = $1")
+                .bind(transaction_id)
+                .execute(&mut *tx)
+                .await?;
+            IntegrityService::seal(&mut tx, &[transaction_id]).await?;
+        }
+
+        tx.commit().await?;
+        Ok(Some(close))
+    }
+
+    /// All closes, oldest first
+    pub async fn list_closes(&self) -> Result<Vec<PeriodClose>> {
+        let closes = sqlx::query_as::<_, PeriodClose>(
+            "SELECT id, period_end, closing_transaction_id, net_income, closed_at FROM period_closes ORDER BY period_end",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(closes)
+    }
+
+    async fn retained_earnings_account(&self) -> Result<Uuid> {
+        if let Some(account) = self
+            .account_service
+            .get_account_by_path_optional(RETAINED_EARNINGS_ACCOUNT_PATH)
+            .await?
+        {
+            return Ok(account.id);
+        }
+
+        let account = self
+            .account_service
+            .create_account_by_path(
+                NewAccountByPath::builder()
+                    .full_path(RETAINED_EARNINGS_ACCOUNT_PATH)
+                    .account_type(AccountType::Equity)
+                    .account_subtype(AccountSubtype::RetainedEarnings)
+                    .notes("Net income of closed periods".to_string())
+                    .build(),
+            )
+            .await?;
+        Ok(account.id)
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,283 @@
+use super::*;
+use crate::models::{AccountSubtype, AccountType};
+use crate::services::{ReportService, TransactionService, TransactionUpdates};
+use crate::tests::utils::*;
+use sqlx::PgPool;
+
+/// The accounts these tests post to
+struct Ledger {
+    checking: Uuid,
+    salary: Uuid,
+    groceries: Uuid,
+    tx_service: TransactionService,
+}
+
+impl Ledger {
+    async fn new(pool: &PgPool) -> Self {
+        Self {
+            checking: account_at(
+                pool,
+                "Assets:Checking",
+                AccountType::Asset,
+                AccountSubtype::Checking,
+            )
+            .await,
+            salary: account_at(
+                pool,
+                "Income:Salary",
+                AccountType::Income,
+                AccountSubtype::Salary,
+            )
+            .await,
+            groceries: account_at(
+                pool,
+                "Expenses:Groceries",
+                AccountType::Expense,
+                AccountSubtype::Food,
+            )
+            .await,
+            tx_service: TransactionService::new(pool.clone()),
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_close_period_moves_income_to_retained_earnings_and_locks() {
+    let (pool, _container) = setup_test_db().await;
+    let service = PeriodService::new(pool.clone());
+    let ledger = Ledger::new(&pool).await;
+
+    post(
+        &ledger.tx_service,
+        "Test",
+        day(2024, 3, 1),
+        ledger.checking,
+        ledger.salary,
+        3000,
+    )
+    .await;
+    let groceries = post(
+        &ledger.tx_service,
+        "Test",
+        day(2024, 6, 1),
+        ledger.groceries,
+        ledger.checking,
+        400,
+    )
+    .await;
+    // After the period end: stays open
+    post(
+        &ledger.tx_service,
+        "Test",
+        day(2025, 1, 5),
+        ledger.groceries,
+        ledger.checking,
+        50,
+    )
+    .await;
+
+    let year_end = NaiveDate::from_ymd_opt(2024, 12, 31).unwrap();
+    let close = service.close_period(year_end).await.unwrap();
+    assert_eq!(close.net_income, Decimal::from(2600));
+    assert!(close.closing_transaction_id.is_some());
+    assert_eq!(service.lock_date().await.unwrap(), Some(year_end));
+
+    let retained_earnings = service
+        .account_service
+        .get_account_by_path(RETAINED_EARNINGS_ACCOUNT_PATH)
+        .await
+        .unwrap();
+    assert_eq!(
+        balance(&pool, retained_earnings.id).await,
+        Decimal::from(-2600)
+    );
+    assert_eq!(balance(&pool, ledger.salary).await, Decimal::ZERO);
+    assert_eq!(balance(&pool, ledger.groceries).await, Decimal::from(50));
+
+    // Nothing in the closed period can be created or changed through the service...
+    let tx_service = &ledger.tx_service;
+    let id = groceries.transaction.id;
+    let created = tx_service
+        .create_transaction(TransactionService::create_simple_transaction(
+            "Late".to_string(),
+            ledger.groceries,
+            ledger.checking,
+            Decimal::from(10),
+            day(2024, 12, 31),
+            None,
+        ))
+        .await;
+    assert!(matches!(created, Err(CoreError::ValidationError(_))));
+    assert!(matches!(
+        tx_service.delete_transaction(id).await,
+        Err(CoreError::ValidationError(_))
+    ));
+    assert!(matches!(
+        tx_service.void_transaction(id).await,
+        Err(CoreError::ValidationError(_))
+    ));
+    assert!(matches!(
+        tx_service
+            .reassign_entry(groceries.entries[0].id, ledger.salary)
+            .await,
+        Err(CoreError::ValidationError(_))
+    ));
+    let moved_in = TransactionUpdates {
+        transaction_date: Some(day(2024, 2, 1)),
+        ..Default::default()
+    };
+    let open_id = post(
+        &ledger.tx_service,
+        "Test",
+        day(2025, 2, 1),
+        ledger.groceries,
+        ledger.checking,
+        5,
+    )
+    .await
+    .transaction
+    .id;
+    assert!(matches!(
+        tx_service.update_transaction(open_id, moved_in).await,
+        Err(CoreError::ValidationError(_))
+    ));
+    // ...but descriptions stay editable and reversals go in the open period
+    let renamed = TransactionUpdates {
+        description: Some("Supermarket".to_string()),
+        ..Default::default()
+    };
+    tx_service.update_transaction(id, renamed).await.unwrap();
+    tx_service
+        .reverse_transaction(id, day(2025, 1, 10), None)
+        .await
+        .unwrap();
+
+    // ...nor behind its back
+    let direct = sqlx::query("UPDATE journal_entries SET amount = amount + 1 WHERE id = $1")
+        .bind(groceries.entries[0].id)
+        .execute(&pool)
+        .await;
+    assert!(direct.is_err());
+
+    let again = service.close_period(year_end).await;
+    assert!(matches!(again, Err(CoreError::ValidationError(_))));
+}
+
+#[tokio::test]
+async fn test_reopen_latest_close() {
+    let (pool, _container) = setup_test_db().await;
+    let service = PeriodService::new(pool.clone());
+    let ledger = Ledger::new(&pool).await;
+
+    assert!(service.reopen_latest().await.unwrap().is_none());
+
+    let purchase = post(
+        &ledger.tx_service,
+        "Test",
+        day(2023, 5, 1),
+        ledger.groceries,
+        ledger.checking,
+        100,
+    )
+    .await;
+    let year_2023 = NaiveDate::from_ymd_opt(2023, 12, 31).unwrap();
+    service.close_period(year_2023).await.unwrap();
+
+    // A year without income or expenses still locks
+    let year_2024 = NaiveDate::from_ymd_opt(2024, 12, 31).unwrap();
+    let empty = service.close_period(year_2024).await.unwrap();
+    assert!(empty.closing_transaction_id.is_none());
+    assert_eq!(empty.net_income, Decimal::ZERO);
+    assert_eq!(service.list_closes().await.unwrap().len(), 2);
+
+    let reopened = service.reopen_latest().await.unwrap().unwrap();
+    assert_eq!(reopened.period_end, year_2024);
+    assert_eq!(service.lock_date().await.unwrap(), Some(year_2023));
+
+    let reopened = service.reopen_latest().await.unwrap().unwrap();
+    assert_eq!(reopened.period_end, year_2023);
+    assert_eq!(service.lock_date().await.unwrap(), None);
+
+    // The closing entries are gone and the period can be edited again
+    assert!(
+        ledger
+            .tx_service
+            .get_transaction(reopened.closing_transaction_id.unwrap())
+            .await
+            .unwrap()
+            .is_none()
+    );
+    assert_eq!(balance(&pool, ledger.groceries).await, Decimal::from(100));
+    ledger
+        .tx_service
+        .delete_transaction(purchase.transaction.id)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_closed_year_keeps_its_income_statement() {
+    let (pool, _container) = setup_test_db().await;
+    let service = PeriodService::new(pool.clone());
+    let reports = ReportService::new(pool.clone());
+    let ledger = Ledger::new(&pool).await;
+
+    post(
+        &ledger.tx_service,
+        "Salary",
+        day(2024, 3, 1),
+        ledger.checking,
+        ledger.salary,
+        3000,
+    )
+    .await;
+    post(
+        &ledger.tx_service,
+        "Market",
+        day(2024, 6, 1),
+        ledger.groceries,
+        ledger.checking,
+        400,
+    )
+    .await;
+
+    let start = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+    let year_end = NaiveDate::from_ymd_opt(2024, 12, 31).unwrap();
+    // Report end dates are compared against midnight: reach past the closing entry
+    let end = NaiveDate::from_ymd_opt(2025, 6, 30).unwrap();
+    let income = |rows: Vec<crate::models::IncomeStatementRow>| {
+        rows.into_iter()
+            .map(|row| (row.account_path, row.total_amount))
+            .collect::<Vec<_>>()
+    };
+    let cash_flow = |rows: Vec<crate::models::CashFlowRow>| {
+        rows.into_iter()
+            .map(|row| (row.account_path, row.cash_flow))
+            .collect::<Vec<_>>()
+    };
+
+    let income_before = income(reports.income_statement(start, end).await.unwrap());
+    let by_tag_before = reports.income_statement_by_tag(start, end).await.unwrap();
+    let cash_flow_before = cash_flow(reports.cash_flow_statement(start, end).await.unwrap());
+    assert_eq!(income_before.len(), 2);
+
+    service.close_period(year_end).await.unwrap();
+
+    // The closing entries zero the accounts but are not income or spending
+    assert_eq!(
+        income(reports.income_statement(start, end).await.unwrap()),
+        income_before
+    );
+    assert_eq!(
+        reports
+            .income_statement_by_tag(start, end)
+            .await
+            .unwrap()
+            .len(),
+        by_tag_before.len()
+    );
+    assert_eq!(
+        cash_flow(reports.cash_flow_statement(start, end).await.unwrap()),
+        cash_flow_before
+    );
+}
@@ -1,7 +1,7 @@
 use crate::error::Result;
 use crate::models::{Account, AccountWithMarketValue, NewPriceHistory, PriceHistory};
 use rust_decimal::Decimal;
-use sqlx::{PgPool, Row};
+use sqlx::{PgConnection, PgPool, Row};
 
 pub struct PriceHistoryService {
     pool: PgPool,
@@ -14,6 +14,15 @@ impl PriceHistoryService {
 
     /// Add or update a price entry for a symbol on a specific date
     pub async fn add_price(&self, new_price: NewPriceHistory) -> Result<PriceHistory> {
+        let mut conn = self.pool.acquire().await?;
+        Self::add_price_in(&mut conn, new_price).await
+    }
+
+    /// Record a price on `conn`, e.g. inside a caller's database transaction
+    pub(crate) async fn add_price_in(
+        conn: &mut PgConnection,
+        new_price: NewPriceHistory,
+    ) -> Result<PriceHistory> {
         let price = sqlx::query_as::<_, PriceHistory>(
             r#"
             INSERT INTO price_history (symbol, price, price_date, currency, source)
@@ -32,7 +41,7 @@ impl PriceHistoryService {
         .bind(new_price.price_date)
         .bind(&new_price.currency)
         .bind(&new_price.source)
-        .fetch_one(&self.pool)
+        .fetch_one(conn)
         .await?;
 
         Ok(price)
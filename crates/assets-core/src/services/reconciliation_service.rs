@@ -0,0 +1,303 @@
+use crate::error::{CoreError, Result};
+use crate::models::{
+    EntryStatus, JournalEntryStatus, Reconciliation, ReconciliationEntry, ReconciliationSummary,
+};
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+
+/// Statement reconciliation: tick entries as cleared, then lock them as reconciled
+///
+/// Balances are in ledger sign, i.e. the sum of the account's entry amounts.
+/// Voided transactions are ignored throughout.
+pub struct ReconciliationService {
+    pool: PgPool,
+}
+
+impl ReconciliationService {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Entries of the account not reconciled yet, dated up to `statement_date` (included)
+    pub async fn unreconciled_entries(
+        &self,
+        account_id: Uuid,
+        statement_date: NaiveDate,
+    ) -> Result<Vec<ReconciliationEntry>> {
+        let entries = sqlx::query_as::<_, ReconciliationEntry>(
+            r#"
+            SELECT je.id, je.transaction_id, t.transaction_date, t.description,
+                   je.amount, je.memo, je.status
+            FROM journal_entries je
+            JOIN transactions t ON t.id = je.transaction_id
+            WHERE je.account_id = $1
+              AND je.status <> 'reconciled'
+              AND NOT t.is_voided
+              AND t.transaction_date < $2::date + 1
+            ORDER BY t.transaction_date, t.created_at, je.created_at
+            "#,
+        )
+        .bind(account_id)
+        .bind(statement_date)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(entries)
+    }
+
+    /// Tick (or untick) entries as cleared; reconciled entries cannot be changed
+    pub async fn set_cleared(&self, entry_ids: &[Uuid], cleared: bool) -> Result<u64> {
+        let mut tx = self.pool.begin().await?;
+        // Same lock as `finish`, so entries cannot be ticked while their account is reconciled
+        sqlx::query(
+            r#"
+            SELECT id FROM accounts
+            WHERE id IN (SELECT account_id FROM journal_entries WHERE id = ANY($1))
+            ORDER BY id
+            FOR UPDATE
+            "#,
+        )
+        .bind(entry_ids)
+        .execute(&mut *tx)
+        .await?;
+
+        let statuses: Vec<(Uuid, EntryStatus)> =
+            sqlx::query_as("SELECT id, status FROM journal_entries WHERE id = ANY($1) FOR UPDATE")
+                .bind(entry_ids)
+                .fetch_all(&mut *tx)
+                .await?;
+        if let Some(missing) = entry_ids
+            .iter()
+            .find(|id| !statuses.iter().any(|(found, _)| found == *id))
+        {
+            return Err(CoreError::NotFound(format!("Journal entry {}", missing)));
+        }
+        if let Some((id, _)) = statuses
+            .iter()
+            .find(|(_, status)| *status == EntryStatus::Reconciled)
+        {
+            return Err(CoreError::ValidationError(format!(
+                "Journal entry {} is already reconciled",
+                id
+            )));
+        }
+
+        let status = if cleared {
+            EntryStatus::Cleared
+        } else {
+            EntryStatus::Uncleared
+        };
+        let result = sqlx::query("UPDATE journal_entries SET status = $2 WHERE id = ANY($1)")
+            .bind(entry_ids)
+            .bind(status)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(result.rows_affected())
+    }
+
+    /// How far the cleared entries are from the statement's ending balance
+    pub async fn summary(
+        &self,
+        account_id: Uuid,
+        statement_date: NaiveDate,
+        statement_balance: Decimal,
+    ) -> Result<ReconciliationSummary> {
+        let mut conn = self.pool.acquire().await?;
+        Self::summary_in(&mut conn, account_id, statement_date, statement_balance).await
+    }
+
+    async fn summary_in(
+        conn: &mut PgConnection,
+        account_id: Uuid,
+        statement_date: NaiveDate,
+        statement_balance: Decimal,
+    ) -> Result<ReconciliationSummary> {
+        let (reconciled_balance, cleared_amount): (Decimal, Decimal) = sqlx::query_as(
+            r#"
+            SELECT
+                COALESCE(SUM(je.amount) FILTER (WHERE je.status = 'reconciled'), 0),
+                COALESCE(SUM(je.amount) FILTER (
+                    WHERE je.status = 'cleared' AND t.transaction_date < $2::date + 1
+                ), 0)
+            FROM journal_entries je
+            JOIN transactions t ON t.id = je.transaction_id
+            WHERE je.account_id = $1 AND NOT t.is_voided
+            "#,
+        )
+        .bind(account_id)
+        .bind(statement_date)
+        .fetch_one(conn)
+        .await?;
+
+        let cleared_balance = reconciled_balance + cleared_amount;
+        Ok(ReconciliationSummary {
+            account_id,
+            statement_date,
+            statement_balance,
+            reconciled_balance,
+            cleared_balance,
+            difference: statement_balance - cleared_balance,
+        })
+    }
+
+    /// Lock the entries cleared up to `statement_date` once they match the statement
+    pub async fn finish(
+        &self,
+        account_id: Uuid,
+        statement_date: NaiveDate,
+        statement_balance: Decimal,
+    ) -> Result<Reconciliation> {
+        let mut tx = self.pool.begin().await?;
+        // Serialize reconciliations of the same account
+        sqlx::query("SELECT id FROM accounts WHERE id = $1 FOR UPDATE")
+            .bind(account_id)
+            .fetch_optional(&mut *tx)
+            .await?
+            .ok_or_else(|| CoreError::AccountNotFound(account_id.to_string()))?;
+
+        if let Some(last) = Self::last_reconciliation_in(&mut tx, account_id).await? {
+            if statement_date <= last.statement_date {
+                return Err(CoreError::ValidationError(format!(
+                    "Statement date {} must be after the last reconciliation ({})",
+                    statement_date, last.statement_date
+                )));
+            }
+        }
+
+        let summary =
+            Self::summary_in(&mut tx, account_id, statement_date, statement_balance).await?;
+        if !summary.is_balanced() {
+            return Err(CoreError::ValidationError(format!(
+                "Cleared balance {} does not match the statement balance {} (difference {})",
+                summary.cleared_balance, statement_balance, summary.difference
+            )));
+        }
+
+        let reconciliation = sqlx::query_as::<_, Reconciliation>(
+            r#"
+            INSERT INTO reconciliations (account_id, statement_date, statement_balance)
+            VALUES ($1, $2, $3)
+            RETURNING id, account_id, statement_date, statement_balance, reconciled_at
+            "#,
+        )
+        .bind(account_id)
+        .bind(statement_date)
+        .bind(statement_balance)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        sqlx::query(
+            r#"
+            UPDATE journal_entries je
+            SET status = 'reconciled', reconciliation_id = $3
+            FROM transactions t
+            WHERE t.id = je.transaction_id
+              AND je.account_id = $1
+              AND je.status = 'cleared'
+              AND NOT t.is_voided
+              AND t.transaction_date < $2::date + 1
+            "#,
+        )
+        .bind(account_id)
+        .bind(statement_date)
+        .bind(reconciliation.id)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(reconciliation)
+    }
+
+    pub async fn list_reconciliations(&self, account_id: Uuid) -> Result<Vec<Reconciliation>> {
+        let reconciliations = sqlx::query_as::<_, Reconciliation>(
+            r#"
+            SELECT id, account_id, statement_date, statement_balance, reconciled_at
+            FROM reconciliations
+            WHERE account_id = $1
+            ORDER BY statement_date DESC, reconciled_at DESC
+            "#,
+        )
+        .bind(account_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(reconciliations)
+    }
+
+    pub async fn last_reconciliation(&self, account_id: Uuid) -> Result<Option<Reconciliation>> {
+        let mut conn = self.pool.acquire().await?;
+        Self::last_reconciliation_in(&mut conn, account_id).await
+    }
+
+    async fn last_reconciliation_in(
+        conn: &mut PgConnection,
+        account_id: Uuid,
+    ) -> Result<Option<Reconciliation>> {
+        let last = sqlx::query_as::<_, Reconciliation>(
+            r#"
+            SELECT id, account_id, statement_date, statement_balance, reconciled_at
+            FROM reconciliations
+            WHERE account_id = $1
+            ORDER BY statement_date DESC, reconciled_at DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(account_id)
+        .fetch_optional(conn)
+        .await?;
+
+        Ok(last)
+    }
+
+    /// Unlock the latest reconciliation of an account; its entries go back to cleared
+    pub async fn undo_last_reconciliation(
+        &self,
+        account_id: Uuid,
+    ) -> Result<Option<Reconciliation>> {
+        let mut tx = self.pool.begin().await?;
+        sqlx::query("SELECT id FROM accounts WHERE id = $1 FOR UPDATE")
+            .bind(account_id)
+            .execute(&mut *tx)
+            .await?;
+        let Some(last) = Self::last_reconciliation_in(&mut tx, account_id).await? else {
+            return Ok(None);
+        };
+
+        sqlx::query(
+            "UPDATE journal_entries SET status = 'cleared', reconciliation_id = NULL WHERE reconciliation_id = $1",
+        )
+        .bind(last.id)
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query("DELETE FROM reconciliations WHERE id = $1")
+            .bind(last.id)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+
+        Ok(Some(last))
+    }
+
+    /// Status of every entry that is not uncleared, for backups
+    pub async fn entry_statuses(&self) -> Result<Vec<JournalEntryStatus>> {
+        let statuses = sqlx::query_as::<_, JournalEntryStatus>(
+            r#"
+            SELECT id AS journal_entry_id, status, reconciliation_id
+            FROM journal_entries
+            WHERE status <> 'uncleared'
+            ORDER BY id
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(statuses)
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,218 @@
+use super::*;
+use crate::models::NewJournalEntry;
+use crate::models::{AccountSubtype, AccountType};
+use crate::services::{TransactionService, TransactionUpdates};
+use crate::tests::utils::*;
+use chrono::{TimeZone, Utc};
+use sqlx::PgPool;
+
+/// A salary on June 1st, groceries on June 10th and rent on July 2nd
+const MOVEMENTS: [((u32, u32), i64); 3] = [((6, 1), 2000), ((6, 10), -150), ((7, 2), -900)];
+
+/// A checking account holding `MOVEMENTS` against an expense account
+///
+/// Returns the checking account and the transaction IDs in order.
+async fn setup_checking(pool: &PgPool) -> (Uuid, Vec<Uuid>) {
+    let checking = account_at(
+        pool,
+        "Checking",
+        AccountType::Asset,
+        AccountSubtype::Checking,
+    )
+    .await;
+    let other = account_at(
+        pool,
+        "Other",
+        AccountType::Expense,
+        AccountSubtype::OtherExpense,
+    )
+    .await;
+    let tx_service = TransactionService::new(pool.clone());
+
+    let mut transaction_ids = Vec::new();
+    for ((month, day_of_month), amount) in MOVEMENTS {
+        let created = post(
+            &tx_service,
+            "Movement",
+            day(2025, month, day_of_month),
+            checking,
+            other,
+            amount,
+        )
+        .await;
+        transaction_ids.push(created.transaction.id);
+    }
+
+    (checking, transaction_ids)
+}
+
+#[tokio::test]
+async fn test_reconcile_statement() {
+    let (pool, _container) = setup_test_db().await;
+    let service = ReconciliationService::new(pool.clone());
+    let (checking, _) = setup_checking(&pool).await;
+
+    // Only entries up to the statement date are offered
+    let entries = service
+        .unreconciled_entries(checking, date("2025-06-30"))
+        .await
+        .unwrap();
+    assert_eq!(entries.len(), 2);
+    assert!(entries.iter().all(|e| e.status == EntryStatus::Uncleared));
+
+    service.set_cleared(&[entries[0].id], true).await.unwrap();
+    let summary = service
+        .summary(checking, date("2025-06-30"), Decimal::from(1850))
+        .await
+        .unwrap();
+    assert_eq!(summary.cleared_balance, Decimal::from(2000));
+    assert_eq!(summary.difference, Decimal::from(-150));
+
+    // Cannot finish while the difference is not zero
+    let early = service
+        .finish(checking, date("2025-06-30"), Decimal::from(1850))
+        .await;
+    assert!(matches!(early, Err(CoreError::ValidationError(_))));
+
+    service.set_cleared(&[entries[1].id], true).await.unwrap();
+    let reconciliation = service
+        .finish(checking, date("2025-06-30"), Decimal::from(1850))
+        .await
+        .unwrap();
+    assert_eq!(reconciliation.statement_balance, Decimal::from(1850));
+    assert!(
+        service
+            .unreconciled_entries(checking, date("2025-06-30"))
+            .await
+            .unwrap()
+            .is_empty()
+    );
+
+    // Reconciled entries cannot be unticked
+    let untick = service.set_cleared(&[entries[0].id], false).await;
+    assert!(matches!(untick, Err(CoreError::ValidationError(_))));
+
+    // The next statement starts from the reconciled balance
+    let next = service
+        .summary(checking, date("2025-07-31"), Decimal::from(950))
+        .await
+        .unwrap();
+    assert_eq!(next.reconciled_balance, Decimal::from(1850));
+    assert_eq!(next.difference, Decimal::from(-900));
+
+    let undone = service
+        .undo_last_reconciliation(checking)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(undone.id, reconciliation.id);
+    let entries = service
+        .unreconciled_entries(checking, date("2025-06-30"))
+        .await
+        .unwrap();
+    assert!(entries.iter().all(|e| e.status == EntryStatus::Cleared));
+}
+
+#[tokio::test]
+async fn test_reconciled_entries_are_protected() {
+    let (pool, _container) = setup_test_db().await;
+    let service = ReconciliationService::new(pool.clone());
+    let tx_service = TransactionService::new(pool.clone());
+    let (checking, transaction_ids) = setup_checking(&pool).await;
+
+    let entries = service
+        .unreconciled_entries(checking, date("2025-06-01"))
+        .await
+        .unwrap();
+    service.set_cleared(&[entries[0].id], true).await.unwrap();
+    service
+        .finish(checking, date("2025-06-01"), Decimal::from(2000))
+        .await
+        .unwrap();
+    let salary = transaction_ids[0];
+
+    let delete = tx_service.delete_transaction(salary).await;
+    assert!(matches!(delete, Err(CoreError::ValidationError(_))));
+    let void = tx_service.void_transaction(salary).await;
+    assert!(matches!(void, Err(CoreError::ValidationError(_))));
+    let redate = tx_service
+        .update_transaction(
+            salary,
+            TransactionUpdates {
+                transaction_date: Some(Utc.with_ymd_and_hms(2025, 6, 5, 0, 0, 0).unwrap()),
+                ..Default::default()
+            },
+        )
+        .await;
+    assert!(matches!(redate, Err(CoreError::ValidationError(_))));
+    let split = tx_service
+        .split_entry(
+            salary,
+            entries[0].id,
+            vec![
+                NewJournalEntry {
+                    account_id: checking,
+                    amount: Decimal::from(1000),
+                    memo: None,
+                },
+                NewJournalEntry {
+                    account_id: checking,
+                    amount: Decimal::from(1000),
+                    memo: None,
+                },
+            ],
+        )
+        .await;
+    assert!(matches!(split, Err(CoreError::ValidationError(_))));
+
+    // Descriptions stay editable
+    tx_service
+        .update_transaction(
+            salary,
+            TransactionUpdates {
+                description: Some("June salary".to_string()),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+    // The database refuses direct changes too
+    let direct = sqlx::query("UPDATE journal_entries SET amount = 1 WHERE id = $1")
+        .bind(entries[0].id)
+        .execute(&pool)
+        .await;
+    assert!(direct.is_err());
+}
+
+#[tokio::test]
+async fn test_transactions_in_reconciled_range_are_not_deleted() {
+    let (pool, _container) = setup_test_db().await;
+    let service = ReconciliationService::new(pool.clone());
+    let tx_service = TransactionService::new(pool.clone());
+    let (checking, transaction_ids) = setup_checking(&pool).await;
+
+    // Only the salary is on the June statement; the groceries were left unticked
+    let entries = service
+        .unreconciled_entries(checking, date("2025-06-30"))
+        .await
+        .unwrap();
+    service.set_cleared(&[entries[0].id], true).await.unwrap();
+    service
+        .finish(checking, date("2025-06-30"), Decimal::from(2000))
+        .await
+        .unwrap();
+
+    let groceries = transaction_ids[1];
+    let delete = tx_service.delete_transaction(groceries).await;
+    assert!(
+        matches!(&delete, Err(CoreError::ValidationError(message)) if message.contains("void it instead"))
+    );
+    tx_service.void_transaction(groceries).await.unwrap();
+
+    // The rent comes after the statement and was never reported
+    tx_service
+        .delete_transaction(transaction_ids[2])
+        .await
+        .unwrap();
+}
@@ -0,0 +1,371 @@
+use crate::error::{CoreError, Result};
+use crate::models::{
+    DueRecurringInstance, JournalEntryByPath, NewJournalEntry, NewRecurringTransaction,
+    NewTransaction, RecurringTransaction,
+};
+use crate::services::{AccountService, IntegrityService, TransactionService};
+use crate::validation::TransactionValidator;
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use sqlx::{PgPool, Row};
+use std::collections::HashSet;
+use uuid::Uuid;
+
+/// How many days an imported bank line may be away from a due date and still match it
+pub const RECURRING_MATCH_WINDOW_DAYS: i32 = 3;
+
+/// A recurring instance that was posted as a transaction
+#[derive(Debug, Clone)]
+pub struct PostedRecurringInstance {
+    pub recurring_id: Uuid,
+    pub name: String,
+    pub due_date: NaiveDate,
+    pub transaction_id: Uuid,
+}
+
+pub struct RecurringService {
+    pool: PgPool,
+    account_service: AccountService,
+}
+
+impl RecurringService {
+    pub fn new(pool: PgPool) -> Self {
+        Self {
+            account_service: AccountService::new(pool.clone()),
+            pool,
+        }
+    }
+
+    /// Store a new template after checking that it balances and its accounts exist
+    pub async fn create_recurring(
+        &self,
+        recurring: NewRecurringTransaction,
+    ) -> Result<RecurringTransaction> {
+        if recurring.name.trim().is_empty() {
+            return Err(CoreError::InvalidInput(
+                "A recurring transaction needs a name".to_string(),
+            ));
+        }
+        if let Some(end_date) = recurring.end_date {
+            if end_date < recurring.start_date {
+                return Err(CoreError::InvalidInput(format!(
+                    "End date {} is before start date {}",
+                    end_date, recurring.start_date
+                )));
+            }
+        }
+        self.resolve_entries(&recurring.entries).await?;
+        let entries = serde_json::to_value(&recurring.entries)
+            .map_err(|e| CoreError::Generic(format!("Failed to serialize entries: {}", e)))?;
+
+        let created = sqlx::query_as::<_, RecurringTransaction>(
+            r#"
+            INSERT INTO recurring_transactions (
+                name, description, reference, entries, schedule,
+                start_date, end_date, amount_tolerance
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            RETURNING id, name, description, reference, entries, schedule,
+                      start_date, end_date, amount_tolerance, is_active, created_at
+            "#,
+        )
+        .bind(recurring.name.trim())
+        .bind(&recurring.description)
+        .bind(&recurring.reference)
+        .bind(entries)
+        .bind(recurring.schedule.to_string())
+        .bind(recurring.start_date)
+        .bind(recurring.end_date)
+        .bind(recurring.amount_tolerance)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(created)
+    }
+
+    pub async fn list_recurring(&self) -> Result<Vec<RecurringTransaction>> {
+        let recurring = sqlx::query_as::<_, RecurringTransaction>(
+            r#"
+            SELECT id, name, description, reference, entries, schedule,
+                   start_date, end_date, amount_tolerance, is_active, created_at
+            FROM recurring_transactions
+            ORDER BY name
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(recurring)
+    }
+
+    /// Delete a template; transactions it already posted are kept
+    pub async fn remove_recurring(&self, name: &str) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM recurring_transactions WHERE name = $1")
+            .bind(name)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Due dates up to `as_of` (included) that have not been handled yet
+    pub async fn due_instances(&self, as_of: NaiveDate) -> Result<Vec<DueRecurringInstance>> {
+        let mut due = Vec::new();
+
+        for recurring in self.list_recurring().await? {
+            if !recurring.is_active || recurring.start_date > as_of {
+                continue;
+            }
+            let schedule = recurring
+                .parsed_schedule()
+                .map_err(CoreError::ValidationError)?;
+            let until = recurring.end_date.map_or(as_of, |end| end.min(as_of));
+
+            let handled: HashSet<NaiveDate> = sqlx::query_scalar(
+                "SELECT due_date FROM recurring_transaction_instances WHERE recurring_transaction_id = $1",
+            )
+            .bind(recurring.id)
+            .fetch_all(&self.pool)
+            .await?
+            .into_iter()
+            .collect();
+
+            for due_date in schedule.occurrences(recurring.start_date, until) {
+                if !handled.contains(&due_date) {
+                    due.push(DueRecurringInstance {
+                        recurring: recurring.clone(),
+                        due_date,
+                    });
+                }
+            }
+        }
+
+        due.sort_by(|a, b| {
+            a.due_date
+                .cmp(&b.due_date)
+                .then_with(|| a.recurring.name.cmp(&b.recurring.name))
+        });
+        Ok(due)
+    }
+
+    /// Post every due instance up to `as_of`; running it twice posts nothing new
+    pub async fn post_due_instances(
+        &self,
+        as_of: NaiveDate,
+    ) -> Result<Vec<PostedRecurringInstance>> {
+        let mut posted = Vec::new();
+
+        for instance in self.due_instances(as_of).await? {
+            let template = instance.recurring.template_entries().map_err(|e| {
+                CoreError::ValidationError(format!(
+                    "Invalid entries in recurring transaction '{}': {}",
+                    instance.recurring.name, e
+                ))
+            })?;
+            let entries = self.resolve_entries(&template).await?;
+            let new_transaction = NewTransaction {
+                description: instance.recurring.description.clone(),
+                reference: instance.recurring.reference.clone(),
+                transaction_date: instance.due_date.and_hms_opt(12, 0, 0).unwrap().and_utc(),
+                entries,
+                import_source: None,
+                import_batch_id: None,
+                external_reference: None,
+            };
+
+            let mut tx = self.pool.begin().await?;
+            let created = TransactionService::insert_transaction(
+                &mut tx,
+                &TransactionValidator::new(),
+                &new_transaction,
+            )
+            .await?;
+            let recorded = sqlx::query(
+                r#"
+                INSERT INTO recurring_transaction_instances (recurring_transaction_id, due_date, transaction_id)
+                VALUES ($1, $2, $3)
+                ON CONFLICT (recurring_transaction_id, due_date) DO NOTHING
+                "#,
+            )
+            .bind(instance.recurring.id)
+            .bind(instance.due_date)
+            .bind(created.transaction.id)
+            .execute(&mut *tx)
+            .await?;
+            if recorded.rows_affected() == 0 {
+                // Posted concurrently by another run
+                tx.rollback().await?;
+                continue;
+            }
+            tx.commit().await?;
+
+            posted.push(PostedRecurringInstance {
+                recurring_id: instance.recurring.id,
+                name: instance.recurring.name.clone(),
+                due_date: instance.due_date,
+                transaction_id: created.transaction.id,
+            });
+        }
+
+        Ok(posted)
+    }
+
+    /// Attach an imported bank line to the posted instance it corresponds to
+    ///
+    /// Looks for an unmatched instance with an entry on `account_id` within the
+    /// template's amount tolerance and [`RECURRING_MATCH_WINDOW_DAYS`] of `date`. The
+    /// instance transaction takes the import metadata and, if needed, the actual
+    /// amount (the difference is moved to its largest other leg). Returns the matched
+    /// transaction ID, or `None` when the line should be imported normally.
+    ///
+    /// Adjusting a reconciled instance or one in a closed period is refused with a
+    /// [`CoreError::ValidationError`].
+    pub async fn match_imported_transaction(
+        &self,
+        account_id: Uuid,
+        date: NaiveDate,
+        amount: Decimal,
+        import_source: &str,
+        import_batch_id: Uuid,
+        external_reference: Option<&str>,
+    ) -> Result<Option<Uuid>> {
+        let mut tx = self.pool.begin().await?;
+
+        let candidate = sqlx::query(
+            r#"
+            SELECT i.recurring_transaction_id, i.due_date, t.id AS transaction_id,
+                   je.id AS entry_id, je.amount
+            FROM recurring_transaction_instances i
+            JOIN recurring_transactions r ON r.id = i.recurring_transaction_id
+            JOIN transactions t ON t.id = i.transaction_id
+            JOIN journal_entries je ON je.transaction_id = t.id AND je.account_id = $1
+            WHERE i.matched_at IS NULL
+              AND NOT t.is_voided
+              AND i.due_date BETWEEN $2::date - $4::int AND $2::date + $4::int
+              AND ABS(je.amount - $3) <= r.amount_tolerance
+            ORDER BY ABS(i.due_date - $2::date), ABS(je.amount - $3)
+            LIMIT 1
+            FOR UPDATE OF i
+            "#,
+        )
+        .bind(account_id)
+        .bind(date)
+        .bind(amount)
+        .bind(RECURRING_MATCH_WINDOW_DAYS)
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        let Some(candidate) = candidate else {
+            return Ok(None);
+        };
+        let recurring_id: Uuid = candidate.get("recurring_transaction_id");
+        let due_date: NaiveDate = candidate.get("due_date");
+        let transaction_id: Uuid = candidate.get("transaction_id");
+        let entry_id: Uuid = candidate.get("entry_id");
+        let posted_amount: Decimal = candidate.get("amount");
+
+        let difference = amount - posted_amount;
+        if difference != Decimal::ZERO {
+            // The posted amounts are rewritten, so the instance must still be editable
+            TransactionService::ensure_not_reconciled(&mut tx, transaction_id, "adjusted").await?;
+            TransactionService::ensure_transaction_unlocked(&mut tx, transaction_id, "adjusted")
+                .await?;
+
+            let counter: (Uuid, Uuid) = sqlx::query_as(
+                r#"
+                SELECT id, account_id FROM journal_entries
+                WHERE transaction_id = $1 AND id != $2
+                ORDER BY ABS(amount) DESC, created_at
+                LIMIT 1
+                "#,
+            )
+            .bind(transaction_id)
+            .bind(entry_id)
+            .fetch_one(&mut *tx)
+            .await?;
+            let (counter_entry, counter_account) = counter;
+            let other_accounts =
+                TransactionService::other_entry_accounts(&mut tx, transaction_id, entry_id).await?;
+            TransactionValidator::new()
+                .validate_postings(&mut tx, &[account_id, counter_account], &other_accounts)
+                .await?;
+
+            sqlx::query("UPDATE journal_entries SET amount = amount + $2 WHERE id = $1")
+                .bind(entry_id)
+                .bind(difference)
+                .execute(&mut *tx)
+                .await?;
+            sqlx::query("UPDATE journal_entries SET amount = amount - $2 WHERE id = $1")
+                .bind(counter_entry)
+                .bind(difference)
+                .execute(&mut *tx)
+                .await?;
+            IntegrityService::seal(&mut tx, &[transaction_id]).await?;
+        }
+
+        sqlx::query(
+            r#"
+            UPDATE transactions
+            SET import_source = $2, import_batch_id = $3, external_reference = $4
+            WHERE id = $1
+            "#,
+        )
+        .bind(transaction_id)
+        .bind(import_source)
+        .bind(import_batch_id)
+        .bind(external_reference)
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query(
+            r#"
+            UPDATE recurring_transaction_instances
+            SET matched_at = NOW()
+            WHERE recurring_transaction_id = $1 AND due_date = $2
+            "#,
+        )
+        .bind(recurring_id)
+        .bind(due_date)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(Some(transaction_id))
+    }
+
+    /// Resolve template account paths, checking the template balances
+    async fn resolve_entries(
+        &self,
+        entries: &[JournalEntryByPath],
+    ) -> Result<Vec<NewJournalEntry>> {
+        if entries.len() < 2 {
+            return Err(CoreError::InvalidInput(
+                "A transaction needs at least two journal entries".to_string(),
+            ));
+        }
+        let total: Decimal = entries.iter().map(|e| e.amount).sum();
+        if total != Decimal::ZERO {
+            return Err(CoreError::UnbalancedTransaction {
+                expected: Decimal::ZERO,
+                actual: total,
+            });
+        }
+
+        let mut resolved = Vec::with_capacity(entries.len());
+        for entry in entries {
+            let account = self
+                .account_service
+                .get_account_by_path(&entry.account_path)
+                .await
+                .map_err(|_| CoreError::AccountNotFound(entry.account_path.clone()))?;
+            resolved.push(NewJournalEntry {
+                account_id: account.id,
+                amount: entry.amount,
+                memo: entry.memo.clone(),
+            });
+        }
+        Ok(resolved)
+    }
+}
+
+#[cfg(test)]
+mod tests;
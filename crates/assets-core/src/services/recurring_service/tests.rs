@@ -0,0 +1,296 @@
+use super::*;
+use crate::models::{AccountSubtype, AccountType, RecurrenceSchedule};
+use crate::services::ReconciliationService;
+use crate::tests::utils::*;
+use chrono::Weekday;
+use std::str::FromStr;
+
+#[test]
+fn test_parse_schedule() {
+    assert_eq!(
+        "monthly:5".parse::<RecurrenceSchedule>(),
+        Ok(RecurrenceSchedule::Monthly { day: 5 })
+    );
+    assert_eq!(
+        "Weekly:Friday".parse::<RecurrenceSchedule>(),
+        Ok(RecurrenceSchedule::Weekly {
+            weekday: Weekday::Fri
+        })
+    );
+    assert_eq!(
+        "yearly:02-29".parse::<RecurrenceSchedule>(),
+        Ok(RecurrenceSchedule::Yearly { month: 2, day: 29 })
+    );
+    assert_eq!(
+        "last-business-day".parse::<RecurrenceSchedule>(),
+        Ok(RecurrenceSchedule::LastBusinessDay)
+    );
+    assert!("monthly:32".parse::<RecurrenceSchedule>().is_err());
+    assert!("yearly:13-01".parse::<RecurrenceSchedule>().is_err());
+    assert!("daily".parse::<RecurrenceSchedule>().is_err());
+
+    // Display round-trips through FromStr
+    for spec in [
+        "monthly:31",
+        "weekly:mon",
+        "yearly:12-25",
+        "last-business-day",
+    ] {
+        let schedule: RecurrenceSchedule = spec.parse().unwrap();
+        assert_eq!(schedule.to_string(), spec);
+    }
+}
+
+#[test]
+fn test_schedule_occurrences() {
+    // Day 31 falls back to the last day of shorter months
+    let monthly = RecurrenceSchedule::Monthly { day: 31 };
+    assert_eq!(
+        monthly.occurrences(date("2025-01-15"), date("2025-04-30")),
+        vec![
+            date("2025-01-31"),
+            date("2025-02-28"),
+            date("2025-03-31"),
+            date("2025-04-30")
+        ]
+    );
+
+    let weekly = RecurrenceSchedule::Weekly {
+        weekday: Weekday::Mon,
+    };
+    assert_eq!(
+        weekly.occurrences(date("2025-06-01"), date("2025-06-20")),
+        vec![date("2025-06-02"), date("2025-06-09"), date("2025-06-16")]
+    );
+
+    let yearly = RecurrenceSchedule::Yearly { month: 2, day: 29 };
+    assert_eq!(
+        yearly.occurrences(date("2023-01-01"), date("2024-12-31")),
+        vec![date("2023-02-28"), date("2024-02-29")]
+    );
+
+    // May 31st 2025 is a Saturday, August 31st a Sunday
+    let last_business_day = RecurrenceSchedule::LastBusinessDay;
+    assert_eq!(
+        last_business_day.occurrences(date("2025-05-01"), date("2025-08-31")),
+        vec![
+            date("2025-05-30"),
+            date("2025-06-30"),
+            date("2025-07-31"),
+            date("2025-08-29")
+        ]
+    );
+}
+
+async fn setup_rent(pool: &PgPool) -> (RecurringService, Uuid) {
+    let account_service = AccountService::new(pool.clone());
+    let checking = account_service
+        .create_account(create_test_new_account_with_type(
+            "Checking",
+            AccountType::Asset,
+            AccountSubtype::Checking,
+        ))
+        .await
+        .unwrap();
+    account_service
+        .create_account(create_test_new_account_with_type(
+            "Rent",
+            AccountType::Expense,
+            AccountSubtype::Housing,
+        ))
+        .await
+        .unwrap();
+
+    let recurring_service = RecurringService::new(pool.clone());
+    recurring_service
+        .create_recurring(NewRecurringTransaction {
+            name: "rent".to_string(),
+            description: "Monthly rent".to_string(),
+            reference: None,
+            entries: vec![
+                JournalEntryByPath::builder()
+                    .account_path("Rent")
+                    .amount(Decimal::from(950))
+                    .build(),
+                JournalEntryByPath::builder()
+                    .account_path("Checking")
+                    .amount(Decimal::from(-950))
+                    .build(),
+            ],
+            schedule: RecurrenceSchedule::Monthly { day: 5 },
+            start_date: date("2025-01-01"),
+            end_date: Some(date("2025-12-31")),
+            amount_tolerance: Decimal::from(10),
+        })
+        .await
+        .unwrap();
+
+    (recurring_service, checking.id)
+}
+
+#[tokio::test]
+async fn test_post_due_instances_is_idempotent() {
+    let (pool, _container) = setup_test_db().await;
+    let (recurring_service, _checking) = setup_rent(&pool).await;
+
+    let due = recurring_service
+        .due_instances(date("2025-03-10"))
+        .await
+        .unwrap();
+    assert_eq!(due.len(), 3);
+
+    let posted = recurring_service
+        .post_due_instances(date("2025-03-10"))
+        .await
+        .unwrap();
+    assert_eq!(
+        posted.iter().map(|p| p.due_date).collect::<Vec<_>>(),
+        vec![date("2025-01-05"), date("2025-02-05"), date("2025-03-05")]
+    );
+
+    let again = recurring_service
+        .post_due_instances(date("2025-03-10"))
+        .await
+        .unwrap();
+    assert!(again.is_empty());
+
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM transactions")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(count, 3);
+}
+
+#[tokio::test]
+async fn test_imported_line_matches_posted_instance() {
+    let (pool, _container) = setup_test_db().await;
+    let (recurring_service, checking) = setup_rent(&pool).await;
+    let posted = recurring_service
+        .post_due_instances(date("2025-01-31"))
+        .await
+        .unwrap();
+    let transaction_id = posted[0].transaction_id;
+    let batch_id = Uuid::new_v4();
+
+    // Outside the tolerance: imported normally
+    let too_far = recurring_service
+        .match_imported_transaction(
+            checking,
+            date("2025-01-06"),
+            Decimal::from(-980),
+            "BoursoBank",
+            batch_id,
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(too_far, None);
+
+    // Within tolerance and date window: takes over the posted transaction
+    let matched = recurring_service
+        .match_imported_transaction(
+            checking,
+            date("2025-01-06"),
+            Decimal::from_str("-955.20").unwrap(),
+            "BoursoBank",
+            batch_id,
+            Some("PRLV SEPA LANDLORD"),
+        )
+        .await
+        .unwrap();
+    assert_eq!(matched, Some(transaction_id));
+
+    let transaction = TransactionService::new(pool.clone())
+        .get_transaction(transaction_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        transaction.transaction.import_source.as_deref(),
+        Some("BoursoBank")
+    );
+    let checking_entry = transaction
+        .entries
+        .iter()
+        .find(|e| e.account_id == checking)
+        .unwrap();
+    assert_eq!(checking_entry.amount, Decimal::from_str("-955.20").unwrap());
+    assert_eq!(
+        transaction
+            .entries
+            .iter()
+            .map(|e| e.amount)
+            .sum::<Decimal>(),
+        Decimal::ZERO
+    );
+
+    // An instance is only matched once
+    let second = recurring_service
+        .match_imported_transaction(
+            checking,
+            date("2025-01-06"),
+            Decimal::from_str("-955.20").unwrap(),
+            "BoursoBank",
+            batch_id,
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(second, None);
+}
+
+#[tokio::test]
+async fn test_reconciled_instance_is_not_adjusted() {
+    let (pool, _container) = setup_test_db().await;
+    let (recurring_service, checking) = setup_rent(&pool).await;
+    let posted = recurring_service
+        .post_due_instances(date("2025-01-31"))
+        .await
+        .unwrap();
+    let transaction_id = posted[0].transaction_id;
+
+    let checking_entry = TransactionService::new(pool.clone())
+        .get_transaction(transaction_id)
+        .await
+        .unwrap()
+        .unwrap()
+        .entries
+        .into_iter()
+        .find(|e| e.account_id == checking)
+        .unwrap();
+    let reconciliation = ReconciliationService::new(pool.clone());
+    reconciliation
+        .set_cleared(&[checking_entry.id], true)
+        .await
+        .unwrap();
+    reconciliation
+        .finish(checking, date("2025-01-31"), Decimal::from(-950))
+        .await
+        .unwrap();
+
+    let adjusted = recurring_service
+        .match_imported_transaction(
+            checking,
+            date("2025-01-06"),
+            Decimal::from_str("-955.20").unwrap(),
+            "BoursoBank",
+            Uuid::new_v4(),
+            None,
+        )
+        .await;
+    assert!(matches!(adjusted, Err(CoreError::ValidationError(_))));
+
+    // The exact amount needs no adjustment and still matches
+    let matched = recurring_service
+        .match_imported_transaction(
+            checking,
+            date("2025-01-06"),
+            Decimal::from(-950),
+            "BoursoBank",
+            Uuid::new_v4(),
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(matched, Some(transaction_id));
+}
@@ -0,0 +1,338 @@
+use crate::error::{CoreError, Result};
+use crate::models::{RefundDetection, RefundLink, RefundMatch, RefundSuggestion, SpendingLine};
+use crate::services::UNCATEGORIZED_ACCOUNT_PATH;
+use chrono::Duration;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Description words that say what kind of card line it is rather than who the merchant is
+const NON_MERCHANT_WORDS: &[&str] = &[
+    "ACHAT",
+    "AVOIR",
+    "CARTE",
+    "CB",
+    "PAIEMENT",
+    "REFUND",
+    "REMB",
+    "REMBOURSEMENT",
+    "REMBT",
+    "RETOUR",
+];
+
+/// Spending legs (expense or uncategorized) of live transactions, largest leg per
+/// transaction; `$2` selects purchases (`true`) or refunds (`false`)
+const SPENDING_LINES: &str = r#"
+    SELECT DISTINCT ON (t.id)
+           t.id AS transaction_id, je.id AS entry_id, t.transaction_date, t.description,
+           COALESCE(a.full_path, a.name) AS account_path, ABS(je.amount) AS amount,
+           ABS(je.amount) - COALESCE(
+               (SELECT SUM(rl.amount) FROM refund_links rl WHERE rl.purchase_entry_id = je.id), 0
+           ) AS refundable
+    FROM journal_entries je
+    JOIN transactions t ON t.id = je.transaction_id
+    JOIN accounts a ON a.id = je.account_id
+    WHERE (a.account_type = 'expense' OR a.full_path = $1)
+      AND (($2 AND je.amount > 0) OR (NOT $2 AND je.amount < 0))
+      AND NOT t.is_voided
+      AND NOT t.is_duplicate
+      AND t.reversal_of_transaction_id IS NULL
+      AND NOT EXISTS (SELECT 1 FROM transactions r WHERE r.reversal_of_transaction_id = t.id)
+"#;
+
+const REFUND_LINK_COLUMNS: &str = r#"
+    l.id, l.refund_transaction_id, l.purchase_transaction_id,
+    l.refund_entry_id, l.purchase_entry_id, l.amount, l.created_at,
+    rt.transaction_date AS refund_date, rt.description AS refund_description,
+    pt.transaction_date AS purchase_date, pt.description AS purchase_description,
+    COALESCE(pa.full_path, pa.name) AS purchase_account_path
+"#;
+
+const REFUND_LINK_JOINS: &str = r#"
+    JOIN transactions rt ON rt.id = l.refund_transaction_id
+    JOIN transactions pt ON pt.id = l.purchase_transaction_id
+    JOIN journal_entries pe ON pe.id = l.purchase_entry_id
+    JOIN accounts pa ON pa.id = pe.account_id
+"#;
+
+/// Links refunds (`AVOIR` card lines, merchant refunds) to the purchase they give money back for
+///
+/// Links only record the relationship; entries are left untouched. Reports can then
+/// net a refund against its purchase's period, see
+/// [`ReportService::income_statement_net_of_refunds`](crate::ReportService::income_statement_net_of_refunds).
+pub struct RefundService {
+    pool: PgPool,
+}
+
+impl RefundService {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Unlinked refunds with the purchases they may belong to
+    ///
+    /// A purchase matches when it is from the same merchant, dated on or before the
+    /// refund within the window, and has at least the refunded amount left to refund.
+    /// Matches giving back exactly what is left come first, then the closest dates.
+    /// Refunds without any match are left out.
+    pub async fn suggest(&self, detection: &RefundDetection) -> Result<Vec<RefundSuggestion>> {
+        let refunds = sqlx::query_as::<_, SpendingLine>(&format!(
+            r#"
+            SELECT * FROM (
+                {}
+                  AND NOT EXISTS (SELECT 1 FROM refund_links rl WHERE rl.refund_transaction_id = t.id)
+                  AND ($3::date IS NULL OR t.transaction_date >= $3::date)
+                  AND ($4::date IS NULL OR t.transaction_date < $4::date + 1)
+                ORDER BY t.id, ABS(je.amount) DESC
+            ) s
+            ORDER BY s.transaction_date, s.transaction_id
+            "#,
+            SPENDING_LINES
+        ))
+        .bind(UNCATEGORIZED_ACCOUNT_PATH)
+        .bind(false)
+        .bind(detection.from_date)
+        .bind(detection.to_date)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let (Some(first), Some(last)) = (refunds.first(), refunds.last()) else {
+            return Ok(Vec::new());
+        };
+        let window_start =
+            first.transaction_date.date_naive() - Duration::days(detection.window_days.into());
+        let window_end = last.transaction_date.date_naive();
+
+        let purchases = sqlx::query_as::<_, SpendingLine>(&format!(
+            r#"
+            SELECT * FROM (
+                {}
+                  AND t.transaction_date >= $3::date
+                  AND t.transaction_date < $4::date + 1
+                ORDER BY t.id, ABS(je.amount) DESC
+            ) s
+            WHERE s.refundable > 0
+            ORDER BY s.transaction_date DESC, s.transaction_id
+            "#,
+            SPENDING_LINES
+        ))
+        .bind(UNCATEGORIZED_ACCOUNT_PATH)
+        .bind(true)
+        .bind(window_start)
+        .bind(window_end)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut suggestions = Vec::new();
+        for refund in refunds {
+            let refund_day = refund.transaction_date.date_naive();
+            let mut matches: Vec<RefundMatch> = purchases
+                .iter()
+                .filter(|purchase| {
+                    let day_gap = (refund_day - purchase.transaction_date.date_naive()).num_days();
+                    (0..=i64::from(detection.window_days)).contains(&day_gap)
+                        && purchase.refundable >= refund.amount
+                        && same_merchant(&purchase.description, &refund.description)
+                })
+                .map(|purchase| RefundMatch {
+                    day_gap: (refund_day - purchase.transaction_date.date_naive()).num_days()
+                        as i32,
+                    full_refund: purchase.refundable == refund.amount,
+                    purchase: purchase.clone(),
+                })
+                .collect();
+            if matches.is_empty() {
+                continue;
+            }
+            matches.sort_by_key(|m| (!m.full_refund, m.day_gap));
+            suggestions.push(RefundSuggestion { refund, matches });
+        }
+
+        Ok(suggestions)
+    }
+
+    /// Link a refund to the purchase it gives money back for
+    ///
+    /// The refunded amount may not exceed what is left to refund on the purchase, and
+    /// the purchase may not be dated after the refund.
+    pub async fn link(
+        &self,
+        refund_transaction_id: Uuid,
+        purchase_transaction_id: Uuid,
+    ) -> Result<RefundLink> {
+        if refund_transaction_id == purchase_transaction_id {
+            return Err(CoreError::InvalidInput(
+                "A refund links two different transactions".to_string(),
+            ));
+        }
+
+        let mut tx = self.pool.begin().await?;
+
+        // Serializes concurrent partial refunds of the same purchase
+        for id in [refund_transaction_id, purchase_transaction_id] {
+            let exists: Option<Uuid> =
+                sqlx::query_scalar("SELECT id FROM transactions WHERE id = $1 FOR UPDATE")
+                    .bind(id)
+                    .fetch_optional(&mut *tx)
+                    .await?;
+            if exists.is_none() {
+                return Err(CoreError::NotFound(format!("Transaction {}", id)));
+            }
+        }
+
+        let already_linked: bool = sqlx::query_scalar(
+            "SELECT EXISTS (SELECT 1 FROM refund_links WHERE refund_transaction_id = $1)",
+        )
+        .bind(refund_transaction_id)
+        .fetch_one(&mut *tx)
+        .await?;
+        if already_linked {
+            return Err(CoreError::ValidationError(format!(
+                "Transaction {} is already linked as a refund",
+                refund_transaction_id
+            )));
+        }
+
+        let refund = Self::spending_line(&mut tx, refund_transaction_id, false)
+            .await?
+            .ok_or_else(|| {
+                CoreError::ValidationError(format!(
+                    "Transaction {} gives no money back on an expense or uncategorized account",
+                    refund_transaction_id
+                ))
+            })?;
+        let purchase = Self::spending_line(&mut tx, purchase_transaction_id, true)
+            .await?
+            .ok_or_else(|| {
+                CoreError::ValidationError(format!(
+                    "Transaction {} spends no money on an expense or uncategorized account",
+                    purchase_transaction_id
+                ))
+            })?;
+
+        if purchase.transaction_date.date_naive() > refund.transaction_date.date_naive() {
+            return Err(CoreError::ValidationError(format!(
+                "The purchase ({}) is dated after the refund ({})",
+                purchase.transaction_date.format("%Y-%m-%d"),
+                refund.transaction_date.format("%Y-%m-%d")
+            )));
+        }
+        if refund.amount > purchase.refundable {
+            return Err(CoreError::ValidationError(format!(
+                "The refund of {} exceeds the {} left to refund on the purchase",
+                refund.amount, purchase.refundable
+            )));
+        }
+
+        let id: Uuid = sqlx::query_scalar(
+            r#"
+            INSERT INTO refund_links
+                (refund_transaction_id, purchase_transaction_id, refund_entry_id, purchase_entry_id, amount)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING id
+            "#,
+        )
+        .bind(refund_transaction_id)
+        .bind(purchase_transaction_id)
+        .bind(refund.entry_id)
+        .bind(purchase.entry_id)
+        .bind(refund.amount)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        self.get_link(id)
+            .await?
+            .ok_or_else(|| CoreError::NotFound(format!("Refund link {}", id)))
+    }
+
+    /// Remove a link given its ID or the refund transaction's ID; returns the removed link
+    pub async fn unlink(&self, id: Uuid) -> Result<Option<RefundLink>> {
+        let Some(link) = self.find_link(id).await? else {
+            return Ok(None);
+        };
+        sqlx::query("DELETE FROM refund_links WHERE id = $1")
+            .bind(link.id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(Some(link))
+    }
+
+    /// All links, most recent refund first
+    pub async fn list_links(&self) -> Result<Vec<RefundLink>> {
+        let links = sqlx::query_as::<_, RefundLink>(&format!(
+            "SELECT {} FROM refund_links l {} ORDER BY rt.transaction_date DESC, l.created_at DESC",
+            REFUND_LINK_COLUMNS, REFUND_LINK_JOINS
+        ))
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(links)
+    }
+
+    pub async fn get_link(&self, id: Uuid) -> Result<Option<RefundLink>> {
+        let link = sqlx::query_as::<_, RefundLink>(&format!(
+            "SELECT {} FROM refund_links l {} WHERE l.id = $1",
+            REFUND_LINK_COLUMNS, REFUND_LINK_JOINS
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(link)
+    }
+
+    /// The link with this ID, or the one of the refund transaction with this ID
+    pub async fn find_link(&self, id: Uuid) -> Result<Option<RefundLink>> {
+        let link = sqlx::query_as::<_, RefundLink>(&format!(
+            "SELECT {} FROM refund_links l {} WHERE $1 IN (l.id, l.refund_transaction_id)",
+            REFUND_LINK_COLUMNS, REFUND_LINK_JOINS
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(link)
+    }
+
+    async fn spending_line(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        transaction_id: Uuid,
+        purchase: bool,
+    ) -> Result<Option<SpendingLine>> {
+        let line = sqlx::query_as::<_, SpendingLine>(&format!(
+            "{} AND t.id = $3 ORDER BY t.id, ABS(je.amount) DESC",
+            SPENDING_LINES
+        ))
+        .bind(UNCATEGORIZED_ACCOUNT_PATH)
+        .bind(purchase)
+        .bind(transaction_id)
+        .fetch_optional(&mut **tx)
+        .await?;
+
+        Ok(line)
+    }
+}
+
+/// Uppercase description words naming the merchant: no card-line keywords, dates or numbers
+fn merchant_words(description: &str) -> Vec<String> {
+    description
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| word.len() > 1 && !word.chars().any(|c| c.is_ascii_digit()))
+        .map(str::to_uppercase)
+        .filter(|word| !NON_MERCHANT_WORDS.contains(&word.as_str()))
+        .collect()
+}
+
+/// Both descriptions start with the same merchant word, or one names a subset of the other
+fn same_merchant(a: &str, b: &str) -> bool {
+    let (a, b) = (merchant_words(a), merchant_words(b));
+    if a.is_empty() || b.is_empty() {
+        return false;
+    }
+    a[0] == b[0] || a.iter().all(|word| b.contains(word)) || b.iter().all(|word| a.contains(word))
+}
+
+#[cfg(test)]
+mod tests;
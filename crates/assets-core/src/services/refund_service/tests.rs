@@ -0,0 +1,336 @@
+use super::*;
+use crate::models::NewJournalEntry;
+use crate::models::{AccountSubtype, AccountType};
+use crate::services::{ReportService, TransactionUpdates};
+use crate::services::{TransactionService, UNCATEGORIZED_ACCOUNT_PATH};
+use crate::tests::utils::*;
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use sqlx::PgPool;
+
+/// The accounts these tests post to
+struct Ledger {
+    checking: Uuid,
+    groceries: Uuid,
+    uncategorized: Uuid,
+    tx_service: TransactionService,
+}
+
+impl Ledger {
+    async fn new(pool: &PgPool) -> Self {
+        Self {
+            checking: account_at(
+                pool,
+                "Assets:Checking",
+                AccountType::Asset,
+                AccountSubtype::Checking,
+            )
+            .await,
+            groceries: account_at(
+                pool,
+                "Expenses:Groceries",
+                AccountType::Expense,
+                AccountSubtype::Food,
+            )
+            .await,
+            uncategorized: account_at(
+                pool,
+                UNCATEGORIZED_ACCOUNT_PATH,
+                AccountType::Equity,
+                AccountSubtype::OpeningBalance,
+            )
+            .await,
+            tx_service: TransactionService::new(pool.clone()),
+        }
+    }
+
+    /// A card line spending `amount` on `category`; negative amounts give money back
+    async fn card_line(
+        &self,
+        category: Uuid,
+        (month, day_of_month): (u32, u32),
+        description: &str,
+        amount: i64,
+    ) -> Uuid {
+        post(
+            &self.tx_service,
+            description,
+            day(2025, month, day_of_month),
+            category,
+            self.checking,
+            amount,
+        )
+        .await
+        .transaction
+        .id
+    }
+}
+
+#[test]
+fn test_same_merchant() {
+    assert_eq!(
+        merchant_words("AVOIR 15/03 AMAZON PAYMENTS X1234"),
+        vec!["AMAZON", "PAYMENTS"]
+    );
+    assert!(same_merchant(
+        "CARTE X1234 12/03 AMAZON PAYMENTS",
+        "AVOIR X1234 15/03 AMAZON EU"
+    ));
+    assert!(same_merchant("Decathlon Lyon", "REMBOURSEMENT DECATHLON"));
+    assert!(!same_merchant("CARTE 12/03 FNAC", "AVOIR 15/03 DARTY"));
+    assert!(!same_merchant("CARTE 12/03", "AVOIR 12/03"));
+}
+
+#[tokio::test]
+async fn test_suggest_and_link_refunds() {
+    let (pool, _container) = setup_test_db().await;
+    let service = RefundService::new(pool.clone());
+    let ledger = Ledger::new(&pool).await;
+
+    let purchase = ledger
+        .card_line(ledger.groceries, (3, 2), "CARTE 02/03 DECATHLON", 120)
+        .await;
+    let older = ledger
+        .card_line(ledger.uncategorized, (2, 20), "CARTE 20/02 DECATHLON", 45)
+        .await;
+    // Other merchant, too small, and after the refund
+    ledger
+        .card_line(ledger.groceries, (3, 3), "CARTE 03/03 FNAC", 45)
+        .await;
+    ledger
+        .card_line(ledger.groceries, (3, 4), "CARTE 04/03 DECATHLON", 20)
+        .await;
+    ledger
+        .card_line(ledger.groceries, (3, 20), "CARTE 20/03 DECATHLON", 45)
+        .await;
+    let refund = ledger
+        .card_line(ledger.uncategorized, (3, 10), "AVOIR 10/03 DECATHLON", -45)
+        .await;
+
+    let suggestions = service.suggest(&RefundDetection::default()).await.unwrap();
+    assert_eq!(suggestions.len(), 1);
+    let suggestion = &suggestions[0];
+    assert_eq!(suggestion.refund.transaction_id, refund);
+    assert_eq!(suggestion.refund.amount, Decimal::from(45));
+    // The purchase refunded in full comes first despite being older
+    let matched: Vec<Uuid> = suggestion
+        .matches
+        .iter()
+        .map(|m| m.purchase.transaction_id)
+        .collect();
+    assert_eq!(matched, vec![older, purchase]);
+    assert!(suggestion.matches[0].full_refund);
+    assert_eq!(suggestion.matches[1].day_gap, 8);
+
+    let short_window = RefundDetection::builder().window_days(10).build();
+    let suggestions = service.suggest(&short_window).await.unwrap();
+    assert_eq!(suggestions[0].matches.len(), 1);
+
+    let link = service.link(refund, purchase).await.unwrap();
+    assert_eq!(link.amount, Decimal::from(45));
+    assert_eq!(link.purchase_account_path, "Expenses:Groceries");
+    assert!(
+        service
+            .suggest(&RefundDetection::default())
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    let relinked = service.link(refund, older).await;
+    assert!(matches!(relinked, Err(CoreError::ValidationError(_))));
+
+    // Partial refunds add up to at most the purchase
+    let second = ledger
+        .card_line(ledger.groceries, (3, 12), "AVOIR DECATHLON", -75)
+        .await;
+    let third = ledger
+        .card_line(ledger.groceries, (3, 14), "AVOIR DECATHLON", -1)
+        .await;
+    service.link(second, purchase).await.unwrap();
+    let too_much = service.link(third, purchase).await;
+    assert!(matches!(too_much, Err(CoreError::ValidationError(_))));
+    let wrong_way = service.link(purchase, third).await;
+    assert!(matches!(wrong_way, Err(CoreError::ValidationError(_))));
+
+    assert_eq!(service.list_links().await.unwrap().len(), 2);
+    let removed = service.unlink(refund).await.unwrap().unwrap();
+    assert_eq!(removed.id, link.id);
+    assert!(service.unlink(link.id).await.unwrap().is_none());
+    assert_eq!(service.list_links().await.unwrap().len(), 1);
+
+    // Linked transactions must be unlinked before they are deleted
+    let deleted = ledger.tx_service.delete_transaction(second).await;
+    assert!(matches!(deleted, Err(CoreError::ValidationError(_))));
+    service.unlink(second).await.unwrap();
+    ledger.tx_service.delete_transaction(second).await.unwrap();
+    assert!(service.list_links().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_linked_transactions_keep_their_entries() {
+    let (pool, _container) = setup_test_db().await;
+    let service = RefundService::new(pool.clone());
+    let ledger = Ledger::new(&pool).await;
+
+    let purchase = ledger
+        .card_line(ledger.groceries, (3, 2), "CARTE DECATHLON", 120)
+        .await;
+    let refund = ledger
+        .card_line(ledger.groceries, (3, 10), "AVOIR DECATHLON", -45)
+        .await;
+    service.link(refund, purchase).await.unwrap();
+
+    // Replacing or splitting entries would cascade-delete the link
+    let edited = ledger
+        .tx_service
+        .update_transaction(
+            purchase,
+            TransactionUpdates {
+                entries: Some(vec![
+                    NewJournalEntry {
+                        account_id: ledger.uncategorized,
+                        amount: Decimal::from(120),
+                        memo: None,
+                    },
+                    NewJournalEntry {
+                        account_id: ledger.checking,
+                        amount: Decimal::from(-120),
+                        memo: None,
+                    },
+                ]),
+                ..Default::default()
+            },
+        )
+        .await;
+    assert!(matches!(edited, Err(CoreError::ValidationError(_))));
+
+    let refund_entry = ledger
+        .tx_service
+        .get_transaction(refund)
+        .await
+        .unwrap()
+        .unwrap()
+        .entries
+        .into_iter()
+        .find(|entry| entry.account_id == ledger.groceries)
+        .unwrap();
+    let split = ledger
+        .tx_service
+        .split_entry(
+            refund,
+            refund_entry.id,
+            vec![
+                NewJournalEntry {
+                    account_id: ledger.groceries,
+                    amount: Decimal::from(-40),
+                    memo: None,
+                },
+                NewJournalEntry {
+                    account_id: ledger.uncategorized,
+                    amount: Decimal::from(-5),
+                    memo: None,
+                },
+            ],
+        )
+        .await;
+    assert!(matches!(split, Err(CoreError::ValidationError(_))));
+
+    // Descriptions stay editable
+    ledger
+        .tx_service
+        .update_transaction(
+            purchase,
+            TransactionUpdates {
+                description: Some("Decathlon bike".to_string()),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(service.list_links().await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_income_statement_nets_refunds() {
+    let (pool, _container) = setup_test_db().await;
+    let service = RefundService::new(pool.clone());
+    let report_service = ReportService::new(pool.clone());
+    let ledger = Ledger::new(&pool).await;
+
+    let purchase = ledger
+        .card_line(ledger.groceries, (2, 25), "CARTE CARREFOUR", 100)
+        .await;
+    // Categorized and uncategorized refunds received the next month
+    let categorized = ledger
+        .card_line(ledger.groceries, (3, 5), "AVOIR CARREFOUR", -30)
+        .await;
+    let uncategorized = ledger
+        .card_line(ledger.uncategorized, (3, 6), "AVOIR CARREFOUR", -20)
+        .await;
+    service.link(categorized, purchase).await.unwrap();
+    service.link(uncategorized, purchase).await.unwrap();
+
+    let february = (
+        NaiveDate::from_ymd_opt(2025, 2, 1).unwrap(),
+        NaiveDate::from_ymd_opt(2025, 2, 28).unwrap(),
+    );
+    let march = (
+        NaiveDate::from_ymd_opt(2025, 3, 1).unwrap(),
+        NaiveDate::from_ymd_opt(2025, 3, 31).unwrap(),
+    );
+    let total = |rows: Vec<crate::models::IncomeStatementRow>| -> Decimal {
+        rows.iter().map(|row| row.total_amount).sum()
+    };
+
+    assert_eq!(
+        total(
+            report_service
+                .income_statement(february.0, february.1)
+                .await
+                .unwrap()
+        ),
+        Decimal::from(100)
+    );
+    assert_eq!(
+        total(
+            report_service
+                .income_statement(march.0, march.1)
+                .await
+                .unwrap()
+        ),
+        Decimal::from(-30)
+    );
+
+    assert_eq!(
+        total(
+            report_service
+                .income_statement_net_of_refunds(february.0, february.1, None)
+                .await
+                .unwrap()
+        ),
+        Decimal::from(50)
+    );
+    assert!(
+        report_service
+            .income_statement_net_of_refunds(march.0, march.1, None)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+
+    // A voided refund no longer reduces the purchase
+    ledger
+        .tx_service
+        .void_transaction(uncategorized)
+        .await
+        .unwrap();
+    assert_eq!(
+        total(
+            report_service
+                .income_statement_net_of_refunds(february.0, february.1, None)
+                .await
+                .unwrap()
+        ),
+        Decimal::from(70)
+    );
+}
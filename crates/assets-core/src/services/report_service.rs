@@ -1,6 +1,6 @@
 use crate::error::Result;
-use crate::models::{AccountLedgerRow, CashFlowRow, IncomeStatementRow};
-use chrono::NaiveDate;
+use crate::models::{AccountLedgerRow, CashFlowRow, IncomeStatementRow, TaggedIncomeStatementRow};
+use chrono::{DateTime, NaiveDate, Utc};
 use rust_decimal::Decimal;
 use sqlx::{PgPool, Row};
 use uuid::Uuid;
@@ -24,19 +24,36 @@ pub struct AccountBalance {
     pub level: i32,
 }
 
+/// Financial reports over the journal
+///
+/// Reports reflect the ledger as it is now, unless [`Self::known_as_of`] sets a
+/// moment to report it as it was recorded then: entries added later are left out,
+/// and edits or deletions made later are rolled back from the audit log. Accounts,
+/// tags and refund links are always read as they are now.
 pub struct ReportService {
     pool: PgPool,
+    known_as_of: Option<DateTime<Utc>>,
 }
 impl ReportService {
     pub fn new(pool: PgPool) -> Self {
-        Self { pool }
+        Self {
+            pool,
+            known_as_of: None,
+        }
+    }
+
+    /// Report the ledger as recorded at this moment; `None` for its current state
+    pub fn known_as_of(mut self, known_as_of: Option<DateTime<Utc>>) -> Self {
+        self.known_as_of = known_as_of;
+        self
     }
 
     pub async fn balance_sheet(&self, report_date: NaiveDate) -> Result<BalanceSheetData> {
         let rows = sqlx::query(
-            "SELECT account_type, name, balance, full_path, level FROM balance_sheet_data($1)",
+            "SELECT account_type, name, balance, full_path, level FROM balance_sheet_data($1, p_known_as_of => $2)",
         )
         .bind(report_date)
+        .bind(self.known_as_of)
         .fetch_all(&self.pool)
         .await?;
 
@@ -92,10 +109,11 @@ impl ReportService {
         end_date: NaiveDate,
     ) -> Result<Vec<IncomeStatementRow>> {
         let rows = sqlx::query_as::<_, IncomeStatementRow>(
-            "SELECT category_name, account_name, account_path, total_amount FROM fn_income_statement($1, $2)",
+            "SELECT category_name, account_name, account_path, total_amount FROM fn_income_statement($1, $2, p_known_as_of => $3)",
         )
         .bind(start_date)
         .bind(end_date)
+        .bind(self.known_as_of)
         .fetch_all(&self.pool)
         .await?;
 
@@ -109,11 +127,96 @@ impl ReportService {
         end_date: NaiveDate,
     ) -> Result<Vec<AccountLedgerRow>> {
         let rows = sqlx::query_as::<_, AccountLedgerRow>(
-            "SELECT transaction_date, transaction_id, description, reference, memo, debit_amount, credit_amount, running_balance FROM fn_account_ledger($1, $2, $3)",
+            "SELECT transaction_date, transaction_id, description, reference, memo, debit_amount, credit_amount, running_balance FROM fn_account_ledger($1, $2, $3, p_known_as_of => $4)",
+        )
+        .bind(account_id)
+        .bind(start_date)
+        .bind(end_date)
+        .bind(self.known_as_of)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows)
+    }
+
+    /// Income statement restricted to entries carrying `tag`, directly or through their transaction
+    pub async fn income_statement_for_tag(
+        &self,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+        tag: &str,
+    ) -> Result<Vec<IncomeStatementRow>> {
+        let rows = sqlx::query_as::<_, IncomeStatementRow>(
+            "SELECT category_name, account_name, account_path, total_amount FROM fn_income_statement($1, $2, $3, p_known_as_of => $4)",
+        )
+        .bind(start_date)
+        .bind(end_date)
+        .bind(tag)
+        .bind(self.known_as_of)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows)
+    }
+
+    /// Income statement with linked refunds netted against their purchase's period
+    ///
+    /// A refund leaves the period it was received in and credits the purchase's account
+    /// on the purchase date, so spending is not overstated in one period and understated
+    /// in the next. `tag` filters entries as in [`Self::income_statement_for_tag`].
+    pub async fn income_statement_net_of_refunds(
+        &self,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+        tag: Option<&str>,
+    ) -> Result<Vec<IncomeStatementRow>> {
+        let rows = sqlx::query_as::<_, IncomeStatementRow>(
+            "SELECT category_name, account_name, account_path, total_amount FROM fn_income_statement($1, $2, $3, true, p_known_as_of => $4)",
+        )
+        .bind(start_date)
+        .bind(end_date)
+        .bind(tag)
+        .bind(self.known_as_of)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows)
+    }
+
+    /// Income statement split per tag; untagged amounts come last with no tag name
+    pub async fn income_statement_by_tag(
+        &self,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> Result<Vec<TaggedIncomeStatementRow>> {
+        let rows = sqlx::query_as::<_, TaggedIncomeStatementRow>(
+            "SELECT tag_name, category_name, account_name, account_path, total_amount FROM fn_income_statement_by_tag($1, $2, p_known_as_of => $3)",
+        )
+        .bind(start_date)
+        .bind(end_date)
+        .bind(self.known_as_of)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows)
+    }
+
+    /// Account ledger restricted to entries carrying `tag`
+    pub async fn account_ledger_for_tag(
+        &self,
+        account_id: Uuid,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+        tag: &str,
+    ) -> Result<Vec<AccountLedgerRow>> {
+        let rows = sqlx::query_as::<_, AccountLedgerRow>(
+            "SELECT transaction_date, transaction_id, description, reference, memo, debit_amount, credit_amount, running_balance FROM fn_account_ledger($1, $2, $3, $4, p_known_as_of => $5)",
         )
         .bind(account_id)
         .bind(start_date)
         .bind(end_date)
+        .bind(tag)
+        .bind(self.known_as_of)
         .fetch_all(&self.pool)
         .await?;
 
@@ -126,13 +229,17 @@ impl ReportService {
         end_date: NaiveDate,
     ) -> Result<Vec<CashFlowRow>> {
         let rows = sqlx::query_as::<_, CashFlowRow>(
-            "SELECT activity_type, category_name, account_name, account_path, cash_flow FROM fn_cash_flow_statement($1, $2)",
+            "SELECT activity_type, category_name, account_name, account_path, cash_flow FROM fn_cash_flow_statement($1, $2, p_known_as_of => $3)",
         )
         .bind(start_date)
         .bind(end_date)
+        .bind(self.known_as_of)
         .fetch_all(&self.pool)
         .await?;
 
         Ok(rows)
     }
 }
+
+#[cfg(test)]
+mod tests;
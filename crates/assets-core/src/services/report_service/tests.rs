@@ -0,0 +1,223 @@
+use super::*;
+use crate::models::{AccountSubtype, AccountType, NewAccountByPath, NewJournalEntry};
+use crate::services::{AccountService, TransactionService, TransactionUpdates};
+use crate::tests::utils::*;
+
+async fn create_account(
+    service: &AccountService,
+    path: &str,
+    account_type: AccountType,
+    subtype: AccountSubtype,
+) -> Uuid {
+    service
+        .create_account_by_path(
+            NewAccountByPath::builder()
+                .full_path(path)
+                .account_type(account_type)
+                .account_subtype(subtype)
+                .build(),
+        )
+        .await
+        .unwrap()
+        .id
+}
+
+#[tokio::test]
+async fn test_reports_known_as_of_ignore_later_entries_edits_and_deletions() {
+    let (pool, _container) = setup_test_db().await;
+    let account_service = AccountService::new(pool.clone());
+    let tx_service = TransactionService::new(pool.clone());
+    let checking = create_account(
+        &account_service,
+        "Assets:Checking",
+        AccountType::Asset,
+        AccountSubtype::Checking,
+    )
+    .await;
+    let salary = create_account(
+        &account_service,
+        "Income:Salary",
+        AccountType::Income,
+        AccountSubtype::Salary,
+    )
+    .await;
+    let groceries = create_account(
+        &account_service,
+        "Expenses:Groceries",
+        AccountType::Expense,
+        AccountSubtype::Category,
+    )
+    .await;
+
+    let post = |description: &str, debit: Uuid, credit: Uuid, amount: i64, date| {
+        tx_service.create_transaction(TransactionService::create_simple_transaction(
+            description.to_string(),
+            debit,
+            credit,
+            Decimal::from(amount),
+            date,
+            None,
+        ))
+    };
+    post("Salary", checking, salary, 3000, day(2024, 3, 1))
+        .await
+        .unwrap();
+    let edited = post("Market", groceries, checking, 400, day(2024, 6, 1))
+        .await
+        .unwrap();
+    let deleted = post("Bakery", groceries, checking, 50, day(2024, 6, 10))
+        .await
+        .unwrap();
+
+    let recorded_at: DateTime<Utc> = sqlx::query_scalar("SELECT clock_timestamp()")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+
+    tx_service
+        .update_transaction(
+            edited.transaction.id,
+            TransactionUpdates {
+                description: Some("Market (corrected)".to_string()),
+                entries: Some(vec![
+                    NewJournalEntry {
+                        account_id: groceries,
+                        amount: Decimal::from(450),
+                        memo: None,
+                    },
+                    NewJournalEntry {
+                        account_id: checking,
+                        amount: Decimal::from(-450),
+                        memo: None,
+                    },
+                ]),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    tx_service
+        .delete_transaction(deleted.transaction.id)
+        .await
+        .unwrap();
+    post("Corner shop", groceries, checking, 20, day(2024, 6, 5))
+        .await
+        .unwrap();
+
+    let current = ReportService::new(pool.clone());
+    let as_recorded = ReportService::new(pool.clone()).known_as_of(Some(recorded_at));
+    let start = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+    let end = NaiveDate::from_ymd_opt(2024, 12, 31).unwrap();
+
+    let groceries_total = |rows: Vec<IncomeStatementRow>| {
+        rows.into_iter()
+            .find(|row| row.account_path == "Expenses:Groceries")
+            .map(|row| row.total_amount)
+    };
+    assert_eq!(
+        groceries_total(current.income_statement(start, end).await.unwrap()),
+        Some(Decimal::from(470))
+    );
+    assert_eq!(
+        groceries_total(as_recorded.income_statement(start, end).await.unwrap()),
+        Some(Decimal::from(450))
+    );
+
+    let balance_sheet = as_recorded.balance_sheet(end).await.unwrap();
+    assert_eq!(balance_sheet.total_assets, Decimal::from(2550));
+    let balance_sheet = current.balance_sheet(end).await.unwrap();
+    assert_eq!(balance_sheet.total_assets, Decimal::from(2530));
+
+    let ledger = as_recorded
+        .account_ledger(groceries, start, end)
+        .await
+        .unwrap();
+    let descriptions: Vec<&str> = ledger.iter().map(|row| row.description.as_str()).collect();
+    assert_eq!(descriptions, vec!["Market", "Bakery"]);
+    assert_eq!(ledger[0].debit_amount, Decimal::from(400));
+    assert_eq!(ledger[1].running_balance, Decimal::from(450));
+
+    // Before anything was recorded, there is nothing to report
+    let before: DateTime<Utc> = day(2000, 1, 1);
+    let empty = ReportService::new(pool.clone())
+        .known_as_of(Some(before))
+        .cash_flow_statement(start, end)
+        .await
+        .unwrap();
+    assert!(empty.is_empty());
+}
+
+#[tokio::test]
+async fn test_reports_known_as_of_read_images_logged_before_later_columns() {
+    let (pool, _container) = setup_test_db().await;
+    let account_service = AccountService::new(pool.clone());
+    let checking = create_account(
+        &account_service,
+        "Assets:Checking",
+        AccountType::Asset,
+        AccountSubtype::Checking,
+    )
+    .await;
+    let salary = create_account(
+        &account_service,
+        "Income:Salary",
+        AccountType::Income,
+        AccountSubtype::Salary,
+    )
+    .await;
+
+    // A transaction deleted after `recorded_at`, logged before transactions had
+    // is_voided and journal entries had a status
+    let recorded_at: DateTime<Utc> = sqlx::query_scalar("SELECT clock_timestamp()")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    let transaction_id = Uuid::new_v4();
+    let created_at = recorded_at - chrono::Duration::days(30);
+    let mut images = vec![(
+        "transactions",
+        transaction_id,
+        serde_json::json!({
+            "id": transaction_id,
+            "description": "Salary",
+            "transaction_date": day(2024, 3, 1),
+            "is_duplicate": false,
+            "created_at": created_at,
+        }),
+    )];
+    for (account_id, amount) in [(checking, 3000), (salary, -3000)] {
+        let entry_id = Uuid::new_v4();
+        images.push((
+            "journal_entries",
+            entry_id,
+            serde_json::json!({
+                "id": entry_id,
+                "transaction_id": transaction_id,
+                "account_id": account_id,
+                "amount": amount,
+                "created_at": created_at,
+            }),
+        ));
+    }
+    for (table_name, record_id, image) in images {
+        sqlx::query(
+            r#"
+            INSERT INTO audit_log (changed_at, changed_by, table_name, operation, record_id, old_data)
+            VALUES (clock_timestamp(), 'test', $1, 'DELETE', $2, $3)
+            "#,
+        )
+        .bind(table_name)
+        .bind(record_id)
+        .bind(image)
+        .execute(&pool)
+        .await
+        .unwrap();
+    }
+
+    let end = NaiveDate::from_ymd_opt(2024, 12, 31).unwrap();
+    let as_recorded = ReportService::new(pool.clone()).known_as_of(Some(recorded_at));
+    let balance_sheet = as_recorded.balance_sheet(end).await.unwrap();
+    assert_eq!(balance_sheet.total_assets, Decimal::from(3000));
+    let balance_sheet = ReportService::new(pool).balance_sheet(end).await.unwrap();
+    assert_eq!(balance_sheet.total_assets, Decimal::ZERO);
+}
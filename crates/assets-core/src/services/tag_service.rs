@@ -0,0 +1,223 @@
+use crate::error::{CoreError, Result};
+use crate::models::{Tag, TagUsage};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Tag CRUD and tagging of transactions and journal entries
+///
+/// Tag names are unique regardless of case and looked up case-insensitively.
+pub struct TagService {
+    pool: PgPool,
+}
+
+impl TagService {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn create_tag(&self, name: &str, description: Option<&str>) -> Result<Tag> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(CoreError::InvalidInput(
+                "Tag name cannot be empty".to_string(),
+            ));
+        }
+        if self.get_tag(name).await?.is_some() {
+            return Err(CoreError::ValidationError(format!(
+                "Tag '{}' already exists",
+                name
+            )));
+        }
+
+        let tag = sqlx::query_as::<_, Tag>(
+            r#"
+            INSERT INTO tags (name, description)
+            VALUES ($1, $2)
+            RETURNING id, name, description, created_at
+            "#,
+        )
+        .bind(name)
+        .bind(description)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(tag)
+    }
+
+    pub async fn get_tag(&self, name: &str) -> Result<Option<Tag>> {
+        let tag = sqlx::query_as::<_, Tag>(
+            "SELECT id, name, description, created_at FROM tags WHERE LOWER(name) = LOWER($1)",
+        )
+        .bind(name.trim())
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(tag)
+    }
+
+    /// All tags with how many transactions and entries carry them
+    pub async fn list_tags(&self) -> Result<Vec<TagUsage>> {
+        let tags = sqlx::query_as::<_, TagUsage>(
+            r#"
+            SELECT
+                tg.id, tg.name, tg.description, tg.created_at,
+                (SELECT COUNT(*) FROM transaction_tags tt WHERE tt.tag_id = tg.id) AS transaction_count,
+                (SELECT COUNT(*) FROM journal_entry_tags jt WHERE jt.tag_id = tg.id) AS entry_count
+            FROM tags tg
+            ORDER BY LOWER(tg.name)
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(tags)
+    }
+
+    pub async fn rename_tag(&self, name: &str, new_name: &str) -> Result<Tag> {
+        let tag = self.require_tag(name).await?;
+        let new_name = new_name.trim();
+        if new_name.is_empty() {
+            return Err(CoreError::InvalidInput(
+                "Tag name cannot be empty".to_string(),
+            ));
+        }
+        if let Some(existing) = self.get_tag(new_name).await? {
+            if existing.id != tag.id {
+                return Err(CoreError::ValidationError(format!(
+                    "Tag '{}' already exists",
+                    new_name
+                )));
+            }
+        }
+
+        let renamed = sqlx::query_as::<_, Tag>(
+            "UPDATE tags SET name = $2 WHERE id = $1 RETURNING id, name, description, created_at",
+        )
+        .bind(tag.id)
+        .bind(new_name)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(renamed)
+    }
+
+    /// Delete a tag and detach it from everything it was attached to
+    pub async fn delete_tag(&self, name: &str) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM tags WHERE LOWER(name) = LOWER($1)")
+            .bind(name.trim())
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Attach a tag to a whole transaction; attaching it twice is a no-op
+    pub async fn tag_transaction(&self, transaction_id: Uuid, name: &str) -> Result<()> {
+        let tag = self.require_tag(name).await?;
+        let exists: bool =
+            sqlx::query_scalar("SELECT EXISTS (SELECT 1 FROM transactions WHERE id = $1)")
+                .bind(transaction_id)
+                .fetch_one(&self.pool)
+                .await?;
+        if !exists {
+            return Err(CoreError::NotFound(format!(
+                "Transaction {}",
+                transaction_id
+            )));
+        }
+
+        sqlx::query(
+            "INSERT INTO transaction_tags (transaction_id, tag_id) VALUES ($1, $2) ON CONFLICT DO NOTHING",
+        )
+        .bind(transaction_id)
+        .bind(tag.id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn untag_transaction(&self, transaction_id: Uuid, name: &str) -> Result<bool> {
+        let tag = self.require_tag(name).await?;
+        let result =
+            sqlx::query("DELETE FROM transaction_tags WHERE transaction_id = $1 AND tag_id = $2")
+                .bind(transaction_id)
+                .bind(tag.id)
+                .execute(&self.pool)
+                .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Attach a tag to a single journal entry, e.g. one reimbursable line of a receipt
+    pub async fn tag_entry(&self, journal_entry_id: Uuid, name: &str) -> Result<()> {
+        let tag = self.require_tag(name).await?;
+        let exists: bool =
+            sqlx::query_scalar("SELECT EXISTS (SELECT 1 FROM journal_entries WHERE id = $1)")
+                .bind(journal_entry_id)
+                .fetch_one(&self.pool)
+                .await?;
+        if !exists {
+            return Err(CoreError::NotFound(format!(
+                "Journal entry {}",
+                journal_entry_id
+            )));
+        }
+
+        sqlx::query(
+            "INSERT INTO journal_entry_tags (journal_entry_id, tag_id) VALUES ($1, $2) ON CONFLICT DO NOTHING",
+        )
+        .bind(journal_entry_id)
+        .bind(tag.id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn untag_entry(&self, journal_entry_id: Uuid, name: &str) -> Result<bool> {
+        let tag = self.require_tag(name).await?;
+        let result = sqlx::query(
+            "DELETE FROM journal_entry_tags WHERE journal_entry_id = $1 AND tag_id = $2",
+        )
+        .bind(journal_entry_id)
+        .bind(tag.id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Tags attached to the transaction itself or to any of its entries
+    pub async fn tags_for_transaction(&self, transaction_id: Uuid) -> Result<Vec<Tag>> {
+        let tags = sqlx::query_as::<_, Tag>(
+            r#"
+            SELECT DISTINCT tg.id, tg.name, tg.description, tg.created_at
+            FROM tags tg
+            JOIN v_journal_entry_tags jt ON jt.tag_id = tg.id
+            JOIN journal_entries je ON je.id = jt.journal_entry_id
+            WHERE je.transaction_id = $1
+            UNION
+            SELECT tg.id, tg.name, tg.description, tg.created_at
+            FROM tags tg
+            JOIN transaction_tags tt ON tt.tag_id = tg.id
+            WHERE tt.transaction_id = $1
+            ORDER BY name
+            "#,
+        )
+        .bind(transaction_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(tags)
+    }
+
+    async fn require_tag(&self, name: &str) -> Result<Tag> {
+        self.get_tag(name)
+            .await?
+            .ok_or_else(|| CoreError::NotFound(format!("Tag '{}'", name.trim())))
+    }
+}
+
+#[cfg(test)]
+mod tests;
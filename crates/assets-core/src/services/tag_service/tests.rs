@@ -0,0 +1,331 @@
+use super::*;
+use crate::models::{AccountSubtype, AccountType, NewJournalEntry, NewTransaction};
+use crate::services::{AccountService, ReportService, TransactionService, TransactionUpdates};
+use crate::tests::utils::*;
+use chrono::{TimeZone, Utc};
+use rust_decimal::Decimal;
+
+#[tokio::test]
+async fn test_tag_crud() {
+    let (pool, _container) = setup_test_db().await;
+    let tag_service = TagService::new(pool.clone());
+
+    let tag = tag_service
+        .create_tag("Vacation-2025", Some("Summer trip"))
+        .await
+        .unwrap();
+    assert_eq!(tag.name, "Vacation-2025");
+
+    // Names are unique regardless of case
+    let duplicate = tag_service.create_tag("vacation-2025", None).await;
+    assert!(matches!(duplicate, Err(CoreError::ValidationError(_))));
+    let empty = tag_service.create_tag("  ", None).await;
+    assert!(matches!(empty, Err(CoreError::InvalidInput(_))));
+
+    let renamed = tag_service
+        .rename_tag("VACATION-2025", "italy-2025")
+        .await
+        .unwrap();
+    assert_eq!(renamed.id, tag.id);
+    assert_eq!(renamed.name, "italy-2025");
+
+    let unknown = tag_service
+        .tag_transaction(Uuid::new_v4(), "does-not-exist")
+        .await;
+    assert!(matches!(unknown, Err(CoreError::NotFound(_))));
+
+    let tags = tag_service.list_tags().await.unwrap();
+    assert_eq!(tags.len(), 1);
+    assert_eq!(tags[0].transaction_count, 0);
+
+    assert!(tag_service.delete_tag("Italy-2025").await.unwrap());
+    assert!(!tag_service.delete_tag("italy-2025").await.unwrap());
+    assert!(tag_service.list_tags().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_tags_filter_reports_and_listing() {
+    let (pool, _container) = setup_test_db().await;
+    let account_service = AccountService::new(pool.clone());
+    let tx_service = TransactionService::new(pool.clone());
+    let tag_service = TagService::new(pool.clone());
+    let report_service = ReportService::new(pool.clone());
+
+    let checking = account_service
+        .create_account(create_test_new_account_with_type(
+            "Checking",
+            AccountType::Asset,
+            AccountSubtype::Checking,
+        ))
+        .await
+        .unwrap();
+    let dining = account_service
+        .create_account(create_test_new_account_with_type(
+            "Dining",
+            AccountType::Expense,
+            AccountSubtype::Food,
+        ))
+        .await
+        .unwrap();
+    let hotel = account_service
+        .create_account(create_test_new_account_with_type(
+            "Hotel",
+            AccountType::Expense,
+            AccountSubtype::Housing,
+        ))
+        .await
+        .unwrap();
+
+    let transaction_date = Utc.with_ymd_and_hms(2025, 7, 10, 12, 0, 0).unwrap();
+    // A hotel stay paid along with a dinner on the same card payment
+    let trip = tx_service
+        .create_transaction(NewTransaction {
+            description: "Hotel Roma".to_string(),
+            reference: None,
+            transaction_date,
+            entries: vec![
+                NewJournalEntry {
+                    account_id: hotel.id,
+                    amount: Decimal::from(300),
+                    memo: None,
+                },
+                NewJournalEntry {
+                    account_id: dining.id,
+                    amount: Decimal::from(60),
+                    memo: None,
+                },
+                NewJournalEntry {
+                    account_id: checking.id,
+                    amount: Decimal::from(-360),
+                    memo: None,
+                },
+            ],
+            import_source: None,
+            import_batch_id: None,
+            external_reference: None,
+        })
+        .await
+        .unwrap();
+    tx_service
+        .create_transaction(TransactionService::create_simple_transaction(
+            "Pizzeria at home".to_string(),
+            dining.id,
+            checking.id,
+            Decimal::from(25),
+            transaction_date,
+            None,
+        ))
+        .await
+        .unwrap();
+
+    tag_service.create_tag("italy-2025", None).await.unwrap();
+    tag_service.create_tag("business", None).await.unwrap();
+    tag_service
+        .tag_transaction(trip.transaction.id, "Italy-2025")
+        .await
+        .unwrap();
+    // Tagging twice is a no-op
+    tag_service
+        .tag_transaction(trip.transaction.id, "italy-2025")
+        .await
+        .unwrap();
+    let hotel_entry = trip
+        .entries
+        .iter()
+        .find(|e| e.account_id == hotel.id)
+        .unwrap();
+    tag_service
+        .tag_entry(hotel_entry.id, "business")
+        .await
+        .unwrap();
+
+    let tags = tag_service
+        .tags_for_transaction(trip.transaction.id)
+        .await
+        .unwrap();
+    assert_eq!(
+        tags.iter().map(|t| t.name.as_str()).collect::<Vec<_>>(),
+        vec!["business", "italy-2025"]
+    );
+
+    // Transaction-level tags cover every entry
+    let italy = report_service
+        .income_statement_for_tag(date("2025-07-01"), date("2025-07-31"), "italy-2025")
+        .await
+        .unwrap();
+    assert_eq!(
+        italy.iter().map(|r| r.total_amount).sum::<Decimal>(),
+        Decimal::from(360)
+    );
+
+    // Entry-level tags only cover that entry
+    let business = report_service
+        .income_statement_for_tag(date("2025-07-01"), date("2025-07-31"), "business")
+        .await
+        .unwrap();
+    assert_eq!(business.len(), 1);
+    assert_eq!(business[0].account_name, "Hotel");
+
+    let by_tag = report_service
+        .income_statement_by_tag(date("2025-07-01"), date("2025-07-31"))
+        .await
+        .unwrap();
+    let total_for = |tag: Option<&str>| -> Decimal {
+        by_tag
+            .iter()
+            .filter(|r| r.tag_name.as_deref() == tag)
+            .map(|r| r.total_amount)
+            .sum()
+    };
+    assert_eq!(total_for(Some("business")), Decimal::from(300));
+    assert_eq!(total_for(Some("italy-2025")), Decimal::from(360));
+    assert_eq!(total_for(None), Decimal::from(25));
+    assert_eq!(by_tag.last().unwrap().tag_name, None);
+
+    let ledger = report_service
+        .account_ledger_for_tag(
+            checking.id,
+            date("2025-07-01"),
+            date("2025-07-31"),
+            "italy-2025",
+        )
+        .await
+        .unwrap();
+    assert_eq!(ledger.len(), 1);
+    assert_eq!(ledger[0].transaction_id, trip.transaction.id);
+
+    let listed = tx_service
+        .get_transactions_with_filters_and_accounts(None, None, None, Some("business"), 50)
+        .await
+        .unwrap();
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0].transaction.id, trip.transaction.id);
+
+    // Detaching and deleting remove the tag from reports
+    assert!(
+        tag_service
+            .untag_entry(hotel_entry.id, "business")
+            .await
+            .unwrap()
+    );
+    assert!(
+        report_service
+            .income_statement_for_tag(date("2025-07-01"), date("2025-07-31"), "business")
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    assert!(tag_service.delete_tag("italy-2025").await.unwrap());
+    assert!(
+        tag_service
+            .tags_for_transaction(trip.transaction.id)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[tokio::test]
+async fn test_entry_tags_survive_edits_and_splits() {
+    let (pool, _container) = setup_test_db().await;
+    let account_service = AccountService::new(pool.clone());
+    let tx_service = TransactionService::new(pool.clone());
+    let tag_service = TagService::new(pool.clone());
+
+    let mut ids = Vec::new();
+    for (name, account_type, subtype) in [
+        ("Checking", AccountType::Asset, AccountSubtype::Checking),
+        ("Hotel", AccountType::Expense, AccountSubtype::Housing),
+        ("Dining", AccountType::Expense, AccountSubtype::Food),
+    ] {
+        let account = account_service
+            .create_account(create_test_new_account_with_type(
+                name,
+                account_type,
+                subtype,
+            ))
+            .await
+            .unwrap();
+        ids.push(account.id);
+    }
+    let (checking, hotel, dining) = (ids[0], ids[1], ids[2]);
+
+    let stay = tx_service
+        .create_transaction(TransactionService::create_simple_transaction(
+            "Hotel Roma".to_string(),
+            hotel,
+            checking,
+            Decimal::from(300),
+            Utc.with_ymd_and_hms(2025, 7, 10, 12, 0, 0).unwrap(),
+            None,
+        ))
+        .await
+        .unwrap();
+    let hotel_entry = stay.entries.iter().find(|e| e.account_id == hotel).unwrap();
+    tag_service.create_tag("business", None).await.unwrap();
+    tag_service
+        .tag_entry(hotel_entry.id, "business")
+        .await
+        .unwrap();
+
+    let tagged_accounts = |pool: PgPool| async move {
+        sqlx::query_scalar::<_, Uuid>(
+            "SELECT je.account_id FROM journal_entry_tags jet JOIN journal_entries je ON je.id = jet.journal_entry_id WHERE je.transaction_id = $1 ORDER BY je.amount",
+        )
+        .bind(stay.transaction.id)
+        .fetch_all(&pool)
+        .await
+        .unwrap()
+    };
+
+    // New entries on the tagged account take over its tags
+    let edited = tx_service
+        .update_transaction(
+            stay.transaction.id,
+            TransactionUpdates {
+                entries: Some(vec![
+                    NewJournalEntry {
+                        account_id: hotel,
+                        amount: Decimal::from(320),
+                        memo: None,
+                    },
+                    NewJournalEntry {
+                        account_id: checking,
+                        amount: Decimal::from(-320),
+                        memo: None,
+                    },
+                ]),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(tagged_accounts(pool.clone()).await, vec![hotel]);
+
+    // Both parts of a split keep the tag
+    let hotel_entry = edited
+        .entries
+        .iter()
+        .find(|e| e.account_id == hotel)
+        .unwrap();
+    tx_service
+        .split_entry(
+            stay.transaction.id,
+            hotel_entry.id,
+            vec![
+                NewJournalEntry {
+                    account_id: dining,
+                    amount: Decimal::from(40),
+                    memo: None,
+                },
+                NewJournalEntry {
+                    account_id: hotel,
+                    amount: Decimal::from(280),
+                    memo: None,
+                },
+            ],
+        )
+        .await
+        .unwrap();
+    assert_eq!(tagged_accounts(pool.clone()).await, vec![dining, hotel]);
+}
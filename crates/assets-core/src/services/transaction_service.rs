@@ -1,21 +1,63 @@
 use crate::error::Result;
 use crate::models::{
-    JournalEntry, JournalEntryWithAccount, NewJournalEntry, NewTransaction, Transaction,
-    TransactionWithEntries, TransactionWithEntriesAndAccounts,
+    AmountFilter, EntryStatus, JournalEntry, JournalEntryWithAccount, NewJournalEntry,
+    NewTransaction, Transaction, TransactionCursor, TransactionFilter, TransactionPage,
+    TransactionSearch, TransactionSearchResult, TransactionWithEntries,
+    TransactionWithEntriesAndAccounts,
 };
-use crate::{AccountService, CoreError, NewTransactionByPath};
-use chrono::{DateTime, Utc};
+use crate::validation::TransactionValidator;
+use crate::{AccountService, CoreError, IntegrityService, NewTransactionByPath};
+use chrono::{DateTime, NaiveDate, Utc};
+use futures::{Stream, TryStreamExt, stream};
 use rust_decimal::Decimal;
-use sqlx::PgPool;
+use sqlx::{FromRow, PgPool, Row};
+use std::collections::HashMap;
 use uuid::Uuid;
 
+/// Minimum trigram word similarity for a transaction to match a search query
+pub const SEARCH_SIMILARITY_THRESHOLD: f64 = 0.3;
+
+/// Transactions fetched per query by [`TransactionService::stream_transactions`]
+const STREAM_PAGE_SIZE: u32 = 500;
+
+/// Fields to change on an existing transaction; `None` leaves the field untouched
+#[derive(Debug, Clone, Default)]
+pub struct TransactionUpdates {
+    pub description: Option<String>,
+    pub transaction_date: Option<DateTime<Utc>>,
+    /// `Some(None)` clears the reference
+    pub reference: Option<Option<String>>,
+    /// Replaces all journal entries when set; a replacement entry on the same account
+    /// and amount as a cleared one stays cleared
+    pub entries: Option<Vec<NewJournalEntry>>,
+}
+
+impl TransactionUpdates {
+    /// Check if any fields are set for update
+    pub fn has_updates(&self) -> bool {
+        self.description.is_some()
+            || self.transaction_date.is_some()
+            || self.reference.is_some()
+            || self.entries.is_some()
+    }
+}
+
 pub struct TransactionService {
     pool: PgPool,
+    validator: TransactionValidator,
 }
 
 impl TransactionService {
     pub fn new(pool: PgPool) -> Self {
-        Self { pool }
+        Self {
+            pool,
+            validator: TransactionValidator::new(),
+        }
+    }
+
+    /// Use a validator with its own configuration
+    pub fn with_validator(pool: PgPool, validator: TransactionValidator) -> Self {
+        Self { pool, validator }
     }
 
     /// Create transaction using account paths
@@ -24,15 +66,25 @@ impl TransactionService {
         account_service: &AccountService,
         transaction: NewTransactionByPath,
     ) -> Result<TransactionWithEntries> {
+        // Accounts created in ensure mode are rolled back with a rejected transaction
+        let mut tx = self.pool.begin().await?;
+
         // Resolve all account paths to IDs and convert to NewJournalEntry format
         let mut resolved_entries = Vec::new();
 
         for entry in transaction.entries {
-            // Get account by path
-            let account = account_service
-                .get_account_by_path(&entry.account_path)
-                .await
-                .map_err(|_| CoreError::AccountNotFound(entry.account_path.clone()))?;
+            // Get account by path, creating it first in ensure mode
+            let account = if transaction.ensure_accounts {
+                account_service
+                    .ensure_account_by_path_in(&mut tx, &entry.account_path)
+                    .await?
+                    .0
+            } else {
+                account_service
+                    .get_account_by_path(&entry.account_path)
+                    .await
+                    .map_err(|_| CoreError::AccountNotFound(entry.account_path.clone()))?
+            };
 
             resolved_entries.push(NewJournalEntry {
                 account_id: account.id,
@@ -52,7 +104,10 @@ impl TransactionService {
             import_batch_id: None,
         };
 
-        self.create_transaction(new_transaction).await
+        let created = self.create_transaction_in(&mut tx, new_transaction).await?;
+        tx.commit().await?;
+
+        Ok(created)
     }
 
     /// Create a new transaction with journal entries
@@ -60,6 +115,19 @@ impl TransactionService {
     pub async fn create_transaction(
         &self,
         new_transaction: NewTransaction,
+    ) -> Result<TransactionWithEntries> {
+        let mut tx = self.pool.begin().await?;
+        let created = self.create_transaction_in(&mut tx, new_transaction).await?;
+        tx.commit().await?;
+
+        Ok(created)
+    }
+
+    /// [`Self::create_transaction`] inside the caller's database transaction
+    pub(crate) async fn create_transaction_in(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        new_transaction: NewTransaction,
     ) -> Result<TransactionWithEntries> {
         if !new_transaction.is_balanced() {
             return Err(crate::error::CoreError::UnbalancedTransaction {
@@ -68,13 +136,30 @@ impl TransactionService {
             });
         }
 
-        let mut tx = self.pool.begin().await?; // Insert transaction header
+        Self::insert_transaction(tx, &self.validator, &new_transaction).await
+    }
+
+    /// Insert a transaction header and its entries inside an open database transaction
+    ///
+    /// Lets other services record a transaction atomically with their own rows; the
+    /// caller is responsible for checking the balance first.
+    pub(crate) async fn insert_transaction(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        validator: &TransactionValidator,
+        new_transaction: &NewTransaction,
+    ) -> Result<TransactionWithEntries> {
+        validator
+            .validate_new_transaction(tx, new_transaction)
+            .await?;
+        Self::ensure_date_unlocked(tx, new_transaction.transaction_date).await?;
+
+        // Insert transaction header
         let transaction_id = Uuid::new_v4();
         let transaction = sqlx::query_as::<_, Transaction>(
             r#"
             INSERT INTO transactions (id, description, reference, transaction_date, import_source, import_batch_id, external_reference)
             VALUES ($1, $2, $3, $4, $5, $6, $7)
-            RETURNING id, description, reference, transaction_date, created_at, import_source, import_batch_id, external_reference, is_duplicate, merged_into_transaction_id
+            RETURNING id, description, reference, transaction_date, created_at, import_source, import_batch_id, external_reference, is_duplicate, merged_into_transaction_id, is_voided, voided_at, reversal_of_transaction_id
             "#,
         )
         .bind(transaction_id)
@@ -84,27 +169,11 @@ impl TransactionService {
         .bind(&new_transaction.import_source)
         .bind(new_transaction.import_batch_id)
         .bind(&new_transaction.external_reference)
-        .fetch_one(&mut *tx)
-        .await?; // Insert journal entries
-        let mut entries = Vec::new();
-        for entry in new_transaction.entries {
-            let journal_entry = sqlx::query_as::<_, JournalEntry>(
-                r#"
-                INSERT INTO journal_entries (transaction_id, account_id, amount, memo)
-                VALUES ($1, $2, $3, $4)
-                RETURNING id, transaction_id, account_id, amount, memo, created_at
-                "#,
-            )
-            .bind(transaction_id)
-            .bind(entry.account_id)
-            .bind(entry.amount)
-            .bind(&entry.memo)
-            .fetch_one(&mut *tx)
-            .await?;
-            entries.push(journal_entry);
-        }
-
-        tx.commit().await?;
+        .fetch_one(&mut **tx)
+        .await?;
+        let entries =
+            Self::insert_journal_entries(tx, transaction_id, &new_transaction.entries).await?;
+        IntegrityService::seal(tx, &[transaction_id]).await?;
 
         Ok(TransactionWithEntries {
             transaction,
@@ -118,7 +187,7 @@ impl TransactionService {
         transaction_id: Uuid,
     ) -> Result<Option<TransactionWithEntries>> {
         let transaction = sqlx::query_as::<_, Transaction>(
-            "SELECT id, description, reference, transaction_date, created_at, import_source, import_batch_id, external_reference, is_duplicate, merged_into_transaction_id FROM transactions WHERE id = $1",
+            "SELECT id, description, reference, transaction_date, created_at, import_source, import_batch_id, external_reference, is_duplicate, merged_into_transaction_id, is_voided, voided_at, reversal_of_transaction_id FROM transactions WHERE id = $1",
         )
         .bind(transaction_id)
         .fetch_optional(&self.pool)
@@ -147,7 +216,7 @@ impl TransactionService {
         transaction_id: Uuid,
     ) -> Result<Option<TransactionWithEntriesAndAccounts>> {
         let transaction = sqlx::query_as::<_, Transaction>(
-            "SELECT id, description, reference, transaction_date, created_at, import_source, import_batch_id, external_reference, is_duplicate, merged_into_transaction_id FROM transactions WHERE id = $1",
+            "SELECT id, description, reference, transaction_date, created_at, import_source, import_batch_id, external_reference, is_duplicate, merged_into_transaction_id, is_voided, voided_at, reversal_of_transaction_id FROM transactions WHERE id = $1",
         )
         .bind(transaction_id)
         .fetch_optional(&self.pool)
@@ -272,111 +341,417 @@ impl TransactionService {
     }
 
     /// Get transactions with optional filtering, including account information
+    ///
+    /// Only the newest `limit` transactions are returned; use
+    /// [`Self::get_transactions_page`] or [`Self::stream_transactions`] to go further.
     pub async fn get_transactions_with_filters_and_accounts(
         &self,
         from_date: Option<DateTime<Utc>>,
         to_date: Option<DateTime<Utc>>,
         account_path: Option<&str>,
+        tag: Option<&str>,
         limit: u32,
     ) -> Result<Vec<TransactionWithEntriesAndAccounts>> {
-        // Build the base query
+        let filter = TransactionFilter::builder()
+            .maybe_from_date(from_date)
+            .maybe_to_date(to_date)
+            .maybe_account_path(account_path)
+            .maybe_tag(tag)
+            .build();
+
+        Ok(self
+            .get_transactions_page(&filter, None, limit)
+            .await?
+            .transactions)
+    }
+
+    /// One page of transactions, newest first, resuming after `after`
+    ///
+    /// Keyset pagination on `(transaction_date, id)`: pages stay stable when
+    /// transactions are added or removed between calls, and deep pages cost the same
+    /// as the first one.
+    pub async fn get_transactions_page(
+        &self,
+        filter: &TransactionFilter,
+        after: Option<&TransactionCursor>,
+        limit: u32,
+    ) -> Result<TransactionPage> {
+        self.fetch_transactions_page(filter, after, 0, limit).await
+    }
+
+    /// Page `page` (1-based) of transactions, newest first
+    ///
+    /// For callers that only have a page number, such as `transactions list --page`:
+    /// the page is reached with one OFFSET query instead of walking the cursors of
+    /// every page before it. The returned cursor continues with keyset pagination.
+    pub async fn get_transactions_page_number(
+        &self,
+        filter: &TransactionFilter,
+        page: u32,
+        limit: u32,
+    ) -> Result<TransactionPage> {
+        if page == 0 {
+            return Err(CoreError::InvalidInput(
+                "Page numbers start at 1".to_string(),
+            ));
+        }
+        let offset = u64::from(page - 1) * u64::from(limit);
+        self.fetch_transactions_page(filter, None, offset, limit)
+            .await
+    }
+
+    async fn fetch_transactions_page(
+        &self,
+        filter: &TransactionFilter,
+        after: Option<&TransactionCursor>,
+        offset: u64,
+        limit: u32,
+    ) -> Result<TransactionPage> {
+        if limit == 0 {
+            return Err(CoreError::InvalidInput(
+                "Page size must be at least 1".to_string(),
+            ));
+        }
+
         let mut query = String::from(
             r#"
-            SELECT DISTINCT t.id, t.description, t.reference, t.transaction_date, t.created_at, 
-                   t.import_source, t.import_batch_id, t.external_reference, t.is_duplicate, t.merged_into_transaction_id
+            SELECT t.id, t.description, t.reference, t.transaction_date, t.created_at,
+                   t.import_source, t.import_batch_id, t.external_reference, t.is_duplicate, t.merged_into_transaction_id, t.is_voided, t.voided_at, t.reversal_of_transaction_id
             FROM transactions t
+            WHERE 1=1
             "#,
         );
+        let mut bind_index = 1;
+
+        if filter.from_date.is_some() {
+            query.push_str(&format!(" AND t.transaction_date >= ${}", bind_index));
+            bind_index += 1;
+        }
+
+        if filter.to_date.is_some() {
+            query.push_str(&format!(" AND t.transaction_date <= ${}", bind_index));
+            bind_index += 1;
+        }
+
+        if filter.account_path.is_some() {
+            query.push_str(&format!(
+                " AND EXISTS (SELECT 1 FROM journal_entries pje JOIN accounts a ON a.id = pje.account_id WHERE pje.transaction_id = t.id AND a.full_path LIKE ${})",
+                bind_index
+            ));
+            bind_index += 1;
+        }
+
+        // Tagged transactions, or transactions with a tagged entry
+        if filter.tag.is_some() {
+            query.push_str(&format!(
+                " AND EXISTS (SELECT 1 FROM journal_entries tje JOIN v_journal_entry_tags jt ON jt.journal_entry_id = tje.id WHERE tje.transaction_id = t.id AND LOWER(jt.tag_name) = LOWER(${}))",
+                bind_index
+            ));
+            bind_index += 1;
+        }
+
+        if after.is_some() {
+            query.push_str(&format!(
+                " AND (t.transaction_date, t.id) < (${}, ${})",
+                bind_index,
+                bind_index + 1
+            ));
+            bind_index += 2;
+        }
+
+        query.push_str(" ORDER BY t.transaction_date DESC, t.id DESC");
+        query.push_str(&format!(
+            " LIMIT ${} OFFSET ${}",
+            bind_index,
+            bind_index + 1
+        ));
+
+        let mut query_builder = sqlx::query_as::<_, Transaction>(&query);
+        if let Some(from) = filter.from_date {
+            query_builder = query_builder.bind(from);
+        }
+        if let Some(to) = filter.to_date {
+            query_builder = query_builder.bind(to);
+        }
+        if let Some(path) = &filter.account_path {
+            query_builder = query_builder.bind(format!("{}%", path));
+        }
+        if let Some(tag) = &filter.tag {
+            query_builder = query_builder.bind(tag);
+        }
+        if let Some(cursor) = after {
+            query_builder = query_builder.bind(cursor.transaction_date).bind(cursor.id);
+        }
+        // One extra row tells whether another page follows
+        query_builder = query_builder
+            .bind(i64::from(limit) + 1)
+            .bind(i64::try_from(offset).unwrap_or(i64::MAX));
+
+        let mut transactions = query_builder.fetch_all(&self.pool).await?;
+        let next_cursor = if transactions.len() > limit as usize {
+            transactions.truncate(limit as usize);
+            transactions.last().map(|last| TransactionCursor {
+                transaction_date: last.transaction_date,
+                id: last.id,
+            })
+        } else {
+            None
+        };
+
+        let transactions = self.with_accounts(transactions).await?;
+
+        Ok(TransactionPage {
+            transactions,
+            next_cursor,
+        })
+    }
+
+    /// Attach entries and their accounts to transactions, in one query, keeping their order
+    async fn with_accounts(
+        &self,
+        transactions: Vec<Transaction>,
+    ) -> Result<Vec<TransactionWithEntriesAndAccounts>> {
+        let ids: Vec<Uuid> = transactions.iter().map(|t| t.id).collect();
+        let mut entries_by_transaction: HashMap<Uuid, Vec<JournalEntryWithAccount>> =
+            HashMap::new();
+        for entry in sqlx::query_as::<_, JournalEntryWithAccount>(
+            r#"
+            SELECT
+                je.id,
+                je.transaction_id,
+                je.account_id,
+                a.full_path as account_path,
+                a.name as account_name,
+                je.amount,
+                je.memo,
+                je.created_at
+            FROM journal_entries je
+            INNER JOIN accounts a ON je.account_id = a.id
+            WHERE je.transaction_id = ANY($1)
+            ORDER BY je.created_at
+            "#,
+        )
+        .bind(&ids)
+        .fetch_all(&self.pool)
+        .await?
+        {
+            entries_by_transaction
+                .entry(entry.transaction_id)
+                .or_default()
+                .push(entry);
+        }
+
+        Ok(transactions
+            .into_iter()
+            .map(|transaction| TransactionWithEntriesAndAccounts {
+                entries: entries_by_transaction
+                    .remove(&transaction.id)
+                    .unwrap_or_default(),
+                transaction,
+            })
+            .collect())
+    }
+
+    /// Every matching transaction, newest first, fetched page by page
+    ///
+    /// Meant for exports: memory stays bounded by one page however long the history.
+    pub fn stream_transactions(
+        &self,
+        filter: TransactionFilter,
+    ) -> impl Stream<Item = Result<TransactionWithEntriesAndAccounts>> + '_ {
+        stream::try_unfold(
+            (filter, None::<TransactionCursor>, false),
+            move |(filter, cursor, done)| async move {
+                if done {
+                    return Ok::<_, CoreError>(None);
+                }
+                let page = self
+                    .get_transactions_page(&filter, cursor.as_ref(), STREAM_PAGE_SIZE)
+                    .await?;
+                let next = page.next_cursor;
+                let transactions = stream::iter(page.transactions.into_iter().map(Ok));
+                Ok(Some((transactions, (filter, next, next.is_none()))))
+            },
+        )
+        .try_flatten()
+    }
+
+    /// Find transactions by fuzzy text and filters, most relevant first
+    ///
+    /// The query is matched with trigram word similarity against the description,
+    /// the reference and entry memos, so typos and partial words still match. Without
+    /// a query, results are ordered by date like `transactions list`.
+    pub async fn search(&self, search: &TransactionSearch) -> Result<Vec<TransactionSearchResult>> {
+        let text = search
+            .query
+            .as_deref()
+            .map(str::trim)
+            .filter(|q| !q.is_empty());
 
         let mut conditions = Vec::new();
         let mut bind_index = 1;
 
-        // Join with journal entries and accounts if needed
-        if account_path.is_some() {
-            query.push_str(" INNER JOIN journal_entries je ON t.id = je.transaction_id");
-            query.push_str(" INNER JOIN accounts a ON je.account_id = a.id");
+        let score = if text.is_some() {
+            bind_index += 1;
+            r#"GREATEST(
+                word_similarity($1, t.description),
+                word_similarity($1, COALESCE(t.reference, '')),
+                COALESCE((SELECT MAX(word_similarity($1, sje.memo)) FROM journal_entries sje WHERE sje.transaction_id = t.id), 0)
+            )"#
+        } else {
+            "0"
+        };
+        // `<%` applies pg_trgm.word_similarity_threshold and can use the trigram indexes,
+        // unlike comparing word_similarity() against the threshold
+        if text.is_some() {
+            conditions.push(
+                r#" AND t.id IN (
+                    SELECT id FROM transactions WHERE $1 <% description
+                    UNION SELECT id FROM transactions WHERE $1 <% reference
+                    UNION SELECT transaction_id FROM journal_entries WHERE $1 <% memo
+                )"#
+                .to_string(),
+            );
         }
 
-        query.push_str(" WHERE 1=1");
+        if let Some(amount) = search.amount {
+            let condition = match amount {
+                AmountFilter::GreaterThan(_) => format!("ABS(aje.amount) > ${}", bind_index),
+                AmountFilter::GreaterOrEqual(_) => format!("ABS(aje.amount) >= ${}", bind_index),
+                AmountFilter::LessThan(_) => format!("ABS(aje.amount) < ${}", bind_index),
+                AmountFilter::LessOrEqual(_) => format!("ABS(aje.amount) <= ${}", bind_index),
+                AmountFilter::Equal(_) => format!("ABS(aje.amount) = ${}", bind_index),
+                AmountFilter::Between(_, _) => {
+                    bind_index += 1;
+                    format!(
+                        "ABS(aje.amount) BETWEEN ${} AND ${}",
+                        bind_index - 1,
+                        bind_index
+                    )
+                }
+            };
+            bind_index += 1;
+            conditions.push(format!(
+                " AND EXISTS (SELECT 1 FROM journal_entries aje WHERE aje.transaction_id = t.id AND {})",
+                condition
+            ));
+        }
 
-        // Add date filters
-        if from_date.is_some() {
-            conditions.push(format!(" AND t.transaction_date >= ${}", bind_index));
+        if search.account_path.is_some() {
+            conditions.push(format!(
+                " AND EXISTS (SELECT 1 FROM journal_entries pje JOIN accounts a ON a.id = pje.account_id WHERE pje.transaction_id = t.id AND (a.full_path = ${0} OR a.full_path LIKE ${0} || ':%'))",
+                bind_index
+            ));
             bind_index += 1;
         }
 
-        if to_date.is_some() {
-            conditions.push(format!(" AND t.transaction_date <= ${}", bind_index));
+        if search.import_source.is_some() {
+            conditions.push(format!(" AND t.import_source ILIKE ${}", bind_index));
             bind_index += 1;
         }
 
-        // Add account path filter
-        if account_path.is_some() {
-            conditions.push(format!(" AND a.full_path LIKE ${}", bind_index));
+        if search.from_date.is_some() {
+            conditions.push(format!(" AND t.transaction_date >= ${}", bind_index));
             bind_index += 1;
         }
 
-        // Add all conditions to query
+        // The end date is included: compare against the start of the next day
+        if search.to_date.is_some() {
+            conditions.push(format!(
+                " AND t.transaction_date < ${}::date + 1",
+                bind_index
+            ));
+            bind_index += 1;
+        }
+
+        let mut query = format!(
+            r#"
+            SELECT t.id, t.description, t.reference, t.transaction_date, t.created_at,
+                   t.import_source, t.import_batch_id, t.external_reference, t.is_duplicate, t.merged_into_transaction_id, t.is_voided, t.voided_at, t.reversal_of_transaction_id,
+                   ({})::float8 AS score
+            FROM transactions t
+            WHERE 1=1
+            "#,
+            score
+        );
         for condition in conditions {
             query.push_str(&condition);
         }
-
-        query.push_str(" ORDER BY t.transaction_date DESC, t.created_at DESC");
+        query.push_str(" ORDER BY score DESC, t.transaction_date DESC, t.created_at DESC");
         query.push_str(&format!(" LIMIT ${}", bind_index));
 
-        // Execute the query with proper parameter binding
-        let mut query_builder = sqlx::query_as::<_, Transaction>(&query);
-
-        if let Some(from) = from_date {
+        let mut query_builder = sqlx::query(&query);
+        if let Some(text) = text {
+            query_builder = query_builder.bind(text);
+        }
+        match search.amount {
+            Some(AmountFilter::Between(low, high)) => {
+                query_builder = query_builder.bind(low).bind(high);
+            }
+            Some(
+                AmountFilter::GreaterThan(value)
+                | AmountFilter::GreaterOrEqual(value)
+                | AmountFilter::LessThan(value)
+                | AmountFilter::LessOrEqual(value)
+                | AmountFilter::Equal(value),
+            ) => {
+                query_builder = query_builder.bind(value.abs());
+            }
+            None => {}
+        }
+        if let Some(path) = &search.account_path {
+            query_builder = query_builder.bind(path);
+        }
+        if let Some(source) = &search.import_source {
+            query_builder = query_builder.bind(source);
+        }
+        if let Some(from) = search.from_date {
             query_builder = query_builder.bind(from);
         }
-        if let Some(to) = to_date {
+        if let Some(to) = search.to_date {
             query_builder = query_builder.bind(to);
         }
-        if let Some(path) = account_path {
-            query_builder = query_builder.bind(format!("{}%", path));
-        }
-        query_builder = query_builder.bind(limit as i64);
+        query_builder = query_builder.bind(search.limit as i64);
 
-        let transactions = query_builder.fetch_all(&self.pool).await?;
-
-        // Fetch journal entries with account information for each transaction
-        let mut result = Vec::new();
-        for transaction in transactions {
-            let entries = sqlx::query_as::<_, JournalEntryWithAccount>(
-                r#"
-                SELECT 
-                    je.id, 
-                    je.transaction_id, 
-                    je.account_id, 
-                    a.full_path as account_path,
-                    a.name as account_name,
-                    je.amount, 
-                    je.memo, 
-                    je.created_at 
-                FROM journal_entries je
-                INNER JOIN accounts a ON je.account_id = a.id
-                WHERE je.transaction_id = $1 
-                ORDER BY je.created_at
-                "#,
-            )
-            .bind(transaction.id)
-            .fetch_all(&self.pool)
+        let mut tx = self.pool.begin().await?;
+        sqlx::query("SELECT set_config('pg_trgm.word_similarity_threshold', $1, true)")
+            .bind(SEARCH_SIMILARITY_THRESHOLD.to_string())
+            .execute(&mut *tx)
             .await?;
+        let rows = query_builder.fetch_all(&mut *tx).await?;
+        tx.commit().await?;
 
-            result.push(TransactionWithEntriesAndAccounts {
-                transaction,
-                entries,
-            });
+        let mut transactions = Vec::with_capacity(rows.len());
+        let mut scores = Vec::with_capacity(rows.len());
+        for row in rows {
+            transactions.push(Transaction::from_row(&row)?);
+            scores.push(row.get::<f64, _>("score"));
         }
 
-        Ok(result)
+        let results = self
+            .with_accounts(transactions)
+            .await?
+            .into_iter()
+            .zip(scores)
+            .map(|(transaction, score)| TransactionSearchResult { transaction, score })
+            .collect();
+
+        Ok(results)
     }
 
     /// Delete a transaction and all its journal entries
+    ///
+    /// Only meant for data that never made it into a report, such as a fresh import
+    /// being redone: transactions in a closed period or a reconciled range are refused
+    /// and should be voided instead, so history stays intact.
     pub async fn delete_transaction(&self, transaction_id: Uuid) -> Result<()> {
         let mut tx = self.pool.begin().await?;
 
+        Self::ensure_not_reversal_linked(&mut tx, transaction_id, "deleted").await?;
+        Self::ensure_never_reported(&mut tx, transaction_id).await?;
+        Self::ensure_not_transfer_linked(&mut tx, transaction_id, "deleted").await?;
+        Self::ensure_not_refund_linked(&mut tx, transaction_id, "deleted").await?;
+
         // Delete journal entries first (due to foreign key constraint)
         sqlx::query("DELETE FROM journal_entries WHERE transaction_id = $1")
             .bind(transaction_id)
@@ -394,11 +769,727 @@ impl TransactionService {
                 transaction_id
             )));
         }
+        IntegrityService::seal(&mut tx, &[transaction_id]).await?;
 
         tx.commit().await?;
         Ok(())
     }
 
+    /// Cancel a transaction by posting its mirror image on `reversal_date`
+    ///
+    /// Balances before the reversal date are unchanged; from that day on the two
+    /// transactions net to zero. A transaction can only be reversed once.
+    pub async fn reverse_transaction(
+        &self,
+        transaction_id: Uuid,
+        reversal_date: DateTime<Utc>,
+        description: Option<String>,
+    ) -> Result<TransactionWithEntries> {
+        let mut tx = self.pool.begin().await?;
+
+        let original = sqlx::query_as::<_, Transaction>(
+            "SELECT id, description, reference, transaction_date, created_at, import_source, import_batch_id, external_reference, is_duplicate, merged_into_transaction_id, is_voided, voided_at, reversal_of_transaction_id FROM transactions WHERE id = $1 FOR UPDATE",
+        )
+        .bind(transaction_id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| CoreError::NotFound(format!("Transaction {}", transaction_id)))?;
+
+        if original.is_voided {
+            return Err(CoreError::ValidationError(format!(
+                "Transaction {} is voided",
+                transaction_id
+            )));
+        }
+        if original.reversal_of_transaction_id.is_some() {
+            return Err(CoreError::ValidationError(format!(
+                "Transaction {} is itself a reversal",
+                transaction_id
+            )));
+        }
+        if reversal_date < original.transaction_date {
+            return Err(CoreError::InvalidInput(format!(
+                "Reversal date {} is before the original transaction date {}",
+                reversal_date.format("%Y-%m-%d"),
+                original.transaction_date.format("%Y-%m-%d")
+            )));
+        }
+
+        let already_reversed: bool = sqlx::query_scalar(
+            "SELECT EXISTS (SELECT 1 FROM transactions WHERE reversal_of_transaction_id = $1)",
+        )
+        .bind(transaction_id)
+        .fetch_one(&mut *tx)
+        .await?;
+        if already_reversed {
+            return Err(CoreError::ValidationError(format!(
+                "Transaction {} has already been reversed",
+                transaction_id
+            )));
+        }
+        // Reversing is how a closed period gets corrected, as long as the reversal is not in it
+        Self::ensure_date_unlocked(&mut tx, reversal_date).await?;
+
+        let mirrored: Vec<NewJournalEntry> = Self::fetch_journal_entries(&mut tx, transaction_id)
+            .await?
+            .into_iter()
+            .map(|entry| NewJournalEntry {
+                account_id: entry.account_id,
+                amount: -entry.amount,
+                memo: entry.memo,
+            })
+            .collect();
+        let account_ids: Vec<Uuid> = mirrored.iter().map(|entry| entry.account_id).collect();
+        self.validator
+            .validate_transaction_date(reversal_date)
+            .map_err(CoreError::TransactionValidation)?;
+        self.validator
+            .validate_postings(&mut tx, &account_ids, &[])
+            .await?;
+
+        let reversal_id = Uuid::new_v4();
+        let transaction = sqlx::query_as::<_, Transaction>(
+            r#"
+            INSERT INTO transactions (id, description, reference, transaction_date, reversal_of_transaction_id)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING id, description, reference, transaction_date, created_at, import_source, import_batch_id, external_reference, is_duplicate, merged_into_transaction_id, is_voided, voided_at, reversal_of_transaction_id
+            "#,
+        )
+        .bind(reversal_id)
+        .bind(description.unwrap_or_else(|| format!("Reversal of: {}", original.description)))
+        .bind(&original.reference)
+        .bind(reversal_date)
+        .bind(transaction_id)
+        .fetch_one(&mut *tx)
+        .await?;
+        let entries = Self::insert_journal_entries(&mut tx, reversal_id, &mirrored).await?;
+        IntegrityService::seal(&mut tx, &[reversal_id]).await?;
+
+        tx.commit().await?;
+
+        Ok(TransactionWithEntries {
+            transaction,
+            entries,
+        })
+    }
+
+    /// Flag a transaction as voided so reports and balances ignore it entirely
+    ///
+    /// Unlike a reversal this rewrites history as if the transaction never happened,
+    /// but the row and its entries are kept. Transactions linked by a reversal
+    /// cannot be voided.
+    pub async fn void_transaction(&self, transaction_id: Uuid) -> Result<Transaction> {
+        let mut tx = self.pool.begin().await?;
+
+        let current = sqlx::query_as::<_, Transaction>(
+            "SELECT id, description, reference, transaction_date, created_at, import_source, import_batch_id, external_reference, is_duplicate, merged_into_transaction_id, is_voided, voided_at, reversal_of_transaction_id FROM transactions WHERE id = $1 FOR UPDATE",
+        )
+        .bind(transaction_id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| CoreError::NotFound(format!("Transaction {}", transaction_id)))?;
+
+        if current.is_voided {
+            return Err(CoreError::ValidationError(format!(
+                "Transaction {} is already voided",
+                transaction_id
+            )));
+        }
+        let reversed: bool = sqlx::query_scalar(
+            "SELECT EXISTS (SELECT 1 FROM transactions WHERE reversal_of_transaction_id = $1)",
+        )
+        .bind(transaction_id)
+        .fetch_one(&mut *tx)
+        .await?;
+        if reversed || current.reversal_of_transaction_id.is_some() {
+            return Err(CoreError::ValidationError(format!(
+                "Transaction {} is part of a reversal and cannot be voided",
+                transaction_id
+            )));
+        }
+        Self::ensure_not_reconciled(&mut tx, transaction_id, "voided").await?;
+        Self::ensure_not_transfer_linked(&mut tx, transaction_id, "voided").await?;
+        Self::ensure_transaction_unlocked(&mut tx, transaction_id, "voided").await?;
+
+        let transaction = sqlx::query_as::<_, Transaction>(
+            r#"
+            UPDATE transactions
+            SET is_voided = TRUE, voided_at = NOW()
+            WHERE id = $1
+            RETURNING id, description, reference, transaction_date, created_at, import_source, import_batch_id, external_reference, is_duplicate, merged_into_transaction_id, is_voided, voided_at, reversal_of_transaction_id
+            "#,
+        )
+        .bind(transaction_id)
+        .fetch_one(&mut *tx)
+        .await?;
+        IntegrityService::seal(&mut tx, &[transaction_id]).await?;
+
+        tx.commit().await?;
+        Ok(transaction)
+    }
+
+    /// Update a transaction in place, keeping its ID, import provenance and duplicate links
+    ///
+    /// When `entries` is set, all journal entries are replaced atomically and the new
+    /// set must balance.
+    pub async fn update_transaction(
+        &self,
+        transaction_id: Uuid,
+        updates: TransactionUpdates,
+    ) -> Result<TransactionWithEntries> {
+        if let Some(entries) = &updates.entries {
+            Self::validate_entries(entries)?;
+        }
+
+        let mut tx = self.pool.begin().await?;
+
+        // Descriptions and references stay editable; dates and amounts are frozen
+        if updates.entries.is_some() || updates.transaction_date.is_some() {
+            Self::ensure_not_reversal_linked(
+                &mut tx,
+                transaction_id,
+                "given a new date or entries",
+            )
+            .await?;
+            Self::ensure_not_reconciled(&mut tx, transaction_id, "given a new date or entries")
+                .await?;
+            Self::ensure_transaction_unlocked(
+                &mut tx,
+                transaction_id,
+                "given a new date or entries",
+            )
+            .await?;
+        }
+        if let Some(transaction_date) = updates.transaction_date {
+            self.validator
+                .validate_transaction_date(transaction_date)
+                .map_err(CoreError::TransactionValidation)?;
+            Self::ensure_date_unlocked(&mut tx, transaction_date).await?;
+        }
+        if let Some(entries) = &updates.entries {
+            Self::ensure_not_transfer_linked(&mut tx, transaction_id, "given new entries").await?;
+            Self::ensure_not_refund_linked(&mut tx, transaction_id, "given new entries").await?;
+            let account_ids: Vec<Uuid> = entries.iter().map(|entry| entry.account_id).collect();
+            self.validator
+                .validate_postings(&mut tx, &account_ids, &[])
+                .await?;
+        }
+
+        let transaction = sqlx::query_as::<_, Transaction>(
+            r#"
+            UPDATE transactions
+            SET
+                description = COALESCE($2, description),
+                transaction_date = COALESCE($3, transaction_date),
+                reference = CASE WHEN $4 THEN $5 ELSE reference END
+            WHERE id = $1
+            RETURNING id, description, reference, transaction_date, created_at, import_source, import_batch_id, external_reference, is_duplicate, merged_into_transaction_id, is_voided, voided_at, reversal_of_transaction_id
+            "#,
+        )
+        .bind(transaction_id)
+        .bind(&updates.description)
+        .bind(updates.transaction_date)
+        .bind(updates.reference.is_some())
+        .bind(updates.reference.as_ref().and_then(Option::as_deref))
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| CoreError::NotFound(format!("Transaction {}", transaction_id)))?;
+
+        if let Some(entries) = &updates.entries {
+            // Entry tags follow their account onto the replacement entries
+            let (tag_accounts, tag_ids): (Vec<Uuid>, Vec<Uuid>) =
+                sqlx::query_as::<_, (Uuid, Uuid)>(
+                    r#"
+                SELECT DISTINCT je.account_id, jet.tag_id
+                FROM journal_entry_tags jet
+                JOIN journal_entries je ON je.id = jet.journal_entry_id
+                WHERE je.transaction_id = $1
+                "#,
+                )
+                .bind(transaction_id)
+                .fetch_all(&mut *tx)
+                .await?
+                .into_iter()
+                .unzip();
+            let (cleared_accounts, cleared_amounts): (Vec<Uuid>, Vec<Decimal>) =
+                sqlx::query_as::<_, (Uuid, Decimal)>(
+                    "SELECT account_id, amount FROM journal_entries WHERE transaction_id = $1 AND status = 'cleared'",
+                )
+                .bind(transaction_id)
+                .fetch_all(&mut *tx)
+                .await?
+                .into_iter()
+                .unzip();
+
+            sqlx::query("DELETE FROM journal_entries WHERE transaction_id = $1")
+                .bind(transaction_id)
+                .execute(&mut *tx)
+                .await?;
+            Self::insert_journal_entries(&mut tx, transaction_id, entries).await?;
+
+            sqlx::query(
+                r#"
+                INSERT INTO journal_entry_tags (journal_entry_id, tag_id)
+                SELECT je.id, t.tag_id
+                FROM journal_entries je
+                JOIN UNNEST($2::uuid[], $3::uuid[]) AS t(account_id, tag_id) ON t.account_id = je.account_id
+                WHERE je.transaction_id = $1
+                ON CONFLICT DO NOTHING
+                "#,
+            )
+            .bind(transaction_id)
+            .bind(&tag_accounts)
+            .bind(&tag_ids)
+            .execute(&mut *tx)
+            .await?;
+
+            // A cleared entry stays cleared while its account and amount are unchanged
+            sqlx::query(
+                r#"
+                UPDATE journal_entries je
+                SET status = 'cleared'
+                FROM UNNEST($2::uuid[], $3::numeric[]) AS c(account_id, amount)
+                WHERE je.transaction_id = $1 AND je.account_id = c.account_id AND je.amount = c.amount
+                "#,
+            )
+            .bind(transaction_id)
+            .bind(&cleared_accounts)
+            .bind(&cleared_amounts)
+            .execute(&mut *tx)
+            .await?;
+        }
+        IntegrityService::seal(&mut tx, &[transaction_id]).await?;
+
+        let entries = Self::fetch_journal_entries(&mut tx, transaction_id).await?;
+        tx.commit().await?;
+
+        Ok(TransactionWithEntries {
+            transaction,
+            entries,
+        })
+    }
+
+    /// Replace one journal entry by several entries summing to the same amount
+    ///
+    /// e.g. a single supermarket line split into groceries and household.
+    pub async fn split_entry(
+        &self,
+        transaction_id: Uuid,
+        entry_id: Uuid,
+        parts: Vec<NewJournalEntry>,
+    ) -> Result<TransactionWithEntries> {
+        if parts.len() < 2 {
+            return Err(CoreError::InvalidInput(
+                "A split needs at least two parts".to_string(),
+            ));
+        }
+
+        let mut tx = self.pool.begin().await?;
+
+        let original = sqlx::query_as::<_, JournalEntry>(
+            "SELECT id, transaction_id, account_id, amount, memo, created_at FROM journal_entries WHERE id = $1 AND transaction_id = $2",
+        )
+        .bind(entry_id)
+        .bind(transaction_id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| {
+            CoreError::NotFound(format!(
+                "Journal entry {} in transaction {}",
+                entry_id, transaction_id
+            ))
+        })?;
+
+        let status: EntryStatus =
+            sqlx::query_scalar("SELECT status FROM journal_entries WHERE id = $1")
+                .bind(entry_id)
+                .fetch_one(&mut *tx)
+                .await?;
+        if status == EntryStatus::Reconciled {
+            return Err(CoreError::ValidationError(format!(
+                "Journal entry {} is reconciled and cannot be split",
+                entry_id
+            )));
+        }
+
+        Self::ensure_not_reversal_linked(&mut tx, transaction_id, "split").await?;
+        Self::ensure_not_transfer_linked(&mut tx, transaction_id, "split").await?;
+        Self::ensure_not_refund_linked(&mut tx, transaction_id, "split").await?;
+        Self::ensure_transaction_unlocked(&mut tx, transaction_id, "split").await?;
+
+        let total: Decimal = parts.iter().map(|part| part.amount).sum();
+        if total != original.amount {
+            return Err(CoreError::UnbalancedTransaction {
+                expected: original.amount,
+                actual: total,
+            });
+        }
+
+        let account_ids: Vec<Uuid> = parts.iter().map(|part| part.account_id).collect();
+        let other_account_ids =
+            Self::other_entry_accounts(&mut tx, transaction_id, entry_id).await?;
+        self.validator
+            .validate_postings(&mut tx, &account_ids, &other_account_ids)
+            .await?;
+
+        // Every part keeps the tags of the entry it was split from
+        let tag_ids: Vec<Uuid> =
+            sqlx::query_scalar("SELECT tag_id FROM journal_entry_tags WHERE journal_entry_id = $1")
+                .bind(entry_id)
+                .fetch_all(&mut *tx)
+                .await?;
+
+        sqlx::query("DELETE FROM journal_entries WHERE id = $1")
+            .bind(entry_id)
+            .execute(&mut *tx)
+            .await?;
+        let inserted = Self::insert_journal_entries(&mut tx, transaction_id, &parts).await?;
+
+        let part_ids: Vec<Uuid> = inserted.iter().map(|entry| entry.id).collect();
+        sqlx::query(
+            r#"
+            INSERT INTO journal_entry_tags (journal_entry_id, tag_id)
+            SELECT part_id, tag_id FROM UNNEST($1::uuid[]) AS part_id CROSS JOIN UNNEST($2::uuid[]) AS tag_id
+            "#,
+        )
+        .bind(&part_ids)
+        .bind(&tag_ids)
+        .execute(&mut *tx)
+        .await?;
+        IntegrityService::seal(&mut tx, &[transaction_id]).await?;
+
+        let entries = Self::fetch_journal_entries(&mut tx, transaction_id).await?;
+        let transaction = sqlx::query_as::<_, Transaction>(
+            "SELECT id, description, reference, transaction_date, created_at, import_source, import_batch_id, external_reference, is_duplicate, merged_into_transaction_id, is_voided, voided_at, reversal_of_transaction_id FROM transactions WHERE id = $1",
+        )
+        .bind(transaction_id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(TransactionWithEntries {
+            transaction,
+            entries,
+        })
+    }
+
+    /// Move one journal entry to another account, keeping its ID, amount and status
+    ///
+    /// e.g. categorizing an imported line by moving its uncategorized leg to an expense.
+    pub async fn reassign_entry(&self, entry_id: Uuid, account_id: Uuid) -> Result<JournalEntry> {
+        let mut tx = self.pool.begin().await?;
+
+        let (status, transaction_id): (EntryStatus, Uuid) = sqlx::query_as(
+            "SELECT status, transaction_id FROM journal_entries WHERE id = $1 FOR UPDATE",
+        )
+        .bind(entry_id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| CoreError::NotFound(format!("Journal entry {}", entry_id)))?;
+        if status == EntryStatus::Reconciled {
+            return Err(CoreError::ValidationError(format!(
+                "Journal entry {} is reconciled and cannot be moved",
+                entry_id
+            )));
+        }
+        Self::ensure_transaction_unlocked(&mut tx, transaction_id, "recategorized").await?;
+
+        let account_exists: bool =
+            sqlx::query_scalar("SELECT EXISTS (SELECT 1 FROM accounts WHERE id = $1)")
+                .bind(account_id)
+                .fetch_one(&mut *tx)
+                .await?;
+        if !account_exists {
+            return Err(CoreError::AccountNotFound(account_id.to_string()));
+        }
+        let other_account_ids =
+            Self::other_entry_accounts(&mut tx, transaction_id, entry_id).await?;
+        self.validator
+            .validate_postings(&mut tx, &[account_id], &other_account_ids)
+            .await?;
+
+        let entry = sqlx::query_as::<_, JournalEntry>(
+            "UPDATE journal_entries SET account_id = $2 WHERE id = $1 RETURNING id, transaction_id, account_id, amount, memo, created_at",
+        )
+        .bind(entry_id)
+        .bind(account_id)
+        .fetch_one(&mut *tx)
+        .await?;
+        IntegrityService::seal(&mut tx, &[transaction_id]).await?;
+
+        tx.commit().await?;
+        Ok(entry)
+    }
+
+    /// Reject changes to a transaction with entries locked by a reconciliation
+    pub(crate) async fn ensure_not_reconciled(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        transaction_id: Uuid,
+        action: &str,
+    ) -> Result<()> {
+        let reconciled: bool = sqlx::query_scalar(
+            "SELECT EXISTS (SELECT 1 FROM journal_entries WHERE transaction_id = $1 AND status = 'reconciled')",
+        )
+        .bind(transaction_id)
+        .fetch_one(&mut **tx)
+        .await?;
+        if reconciled {
+            return Err(CoreError::ValidationError(format!(
+                "Transaction {} has reconciled entries and cannot be {}",
+                transaction_id, action
+            )));
+        }
+        Ok(())
+    }
+
+    /// Reject deleting a transaction a report or statement may already have shown
+    ///
+    /// A transaction counts as reported once it falls in a closed period, or on or
+    /// before the statement date of a reconciliation of one of its accounts, whether
+    /// or not its own entries were ticked. Being sealed in the ledger chain does not
+    /// count: every transaction is sealed when created, and the chain records the
+    /// deletion itself.
+    async fn ensure_never_reported(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        transaction_id: Uuid,
+    ) -> Result<()> {
+        let lock_date: Option<NaiveDate> = sqlx::query_scalar(
+            r#"
+            SELECT l.lock_date
+            FROM (SELECT fn_lock_date() AS lock_date) l
+            JOIN transactions t ON t.transaction_date::date <= l.lock_date
+            WHERE t.id = $1
+            "#,
+        )
+        .bind(transaction_id)
+        .fetch_optional(&mut **tx)
+        .await?;
+        if let Some(lock_date) = lock_date {
+            return Err(CoreError::ValidationError(format!(
+                "Transaction {} is in the period closed through {} and cannot be deleted; void it instead",
+                transaction_id, lock_date
+            )));
+        }
+
+        let reconciled: Option<(String, NaiveDate)> = sqlx::query_as(
+            r#"
+            SELECT a.full_path, r.statement_date
+            FROM transactions t
+            JOIN journal_entries je ON je.transaction_id = t.id
+            JOIN accounts a ON a.id = je.account_id
+            JOIN reconciliations r ON r.account_id = je.account_id
+            WHERE t.id = $1
+              AND (je.reconciliation_id = r.id OR r.statement_date >= t.transaction_date::date)
+            ORDER BY r.statement_date DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(transaction_id)
+        .fetch_optional(&mut **tx)
+        .await?;
+        if let Some((account_path, statement_date)) = reconciled {
+            return Err(CoreError::ValidationError(format!(
+                "Transaction {} falls in the reconciliation of {} through {} and cannot be deleted; void it instead",
+                transaction_id, account_path, statement_date
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Reject changes to a voided transaction, a reversal, or a reversed original
+    ///
+    /// A reversal mirrors its original entry for entry; changing either side would
+    /// stop the pair from netting to zero.
+    async fn ensure_not_reversal_linked(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        transaction_id: Uuid,
+        action: &str,
+    ) -> Result<()> {
+        let linked: bool = sqlx::query_scalar(
+            r#"
+            SELECT EXISTS (
+                SELECT 1 FROM transactions
+                WHERE (id = $1 AND (is_voided OR reversal_of_transaction_id IS NOT NULL))
+                   OR reversal_of_transaction_id = $1
+            )
+            "#,
+        )
+        .bind(transaction_id)
+        .fetch_one(&mut **tx)
+        .await?;
+        if linked {
+            return Err(CoreError::ValidationError(format!(
+                "Transaction {} is voided or part of a reversal and cannot be {}",
+                transaction_id, action
+            )));
+        }
+        Ok(())
+    }
+
+    /// Reject changes that would leave the other side of an internal transfer dangling
+    async fn ensure_not_transfer_linked(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        transaction_id: Uuid,
+        action: &str,
+    ) -> Result<()> {
+        let linked: bool = sqlx::query_scalar(
+            "SELECT EXISTS (SELECT 1 FROM transfer_links WHERE $1 IN (outgoing_transaction_id, incoming_transaction_id))",
+        )
+        .bind(transaction_id)
+        .fetch_one(&mut **tx)
+        .await?;
+        if linked {
+            return Err(CoreError::ValidationError(format!(
+                "Transaction {} is linked as an internal transfer and cannot be {}; unmerge it first",
+                transaction_id, action
+            )));
+        }
+        Ok(())
+    }
+
+    /// Reject changes that would drop the refund links of a transaction's entries
+    async fn ensure_not_refund_linked(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        transaction_id: Uuid,
+        action: &str,
+    ) -> Result<()> {
+        let linked: bool = sqlx::query_scalar(
+            "SELECT EXISTS (SELECT 1 FROM refund_links WHERE $1 IN (refund_transaction_id, purchase_transaction_id))",
+        )
+        .bind(transaction_id)
+        .fetch_one(&mut **tx)
+        .await?;
+        if linked {
+            return Err(CoreError::ValidationError(format!(
+                "Transaction {} is linked to a refund and cannot be {}; unlink the refund first",
+                transaction_id, action
+            )));
+        }
+        Ok(())
+    }
+
+    /// Reject a transaction dated inside a closed period
+    async fn ensure_date_unlocked(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        transaction_date: DateTime<Utc>,
+    ) -> Result<()> {
+        let lock_date: Option<NaiveDate> = sqlx::query_scalar(
+            "SELECT lock_date FROM (SELECT fn_lock_date() AS lock_date) l WHERE lock_date >= $1::timestamptz::date",
+        )
+        .bind(transaction_date)
+        .fetch_optional(&mut **tx)
+        .await?;
+        if let Some(lock_date) = lock_date {
+            return Err(CoreError::ValidationError(format!(
+                "{} falls in the period closed through {}; reopen it first",
+                transaction_date.format("%Y-%m-%d"),
+                lock_date
+            )));
+        }
+        Ok(())
+    }
+
+    /// Reject changes to a transaction inside a closed period
+    pub(crate) async fn ensure_transaction_unlocked(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        transaction_id: Uuid,
+        action: &str,
+    ) -> Result<()> {
+        let lock_date: Option<NaiveDate> = sqlx::query_scalar(
+            r#"
+            SELECT l.lock_date
+            FROM (SELECT fn_lock_date() AS lock_date) l
+            JOIN transactions t ON t.transaction_date::date <= l.lock_date
+            WHERE t.id = $1
+            "#,
+        )
+        .bind(transaction_id)
+        .fetch_optional(&mut **tx)
+        .await?;
+        if let Some(lock_date) = lock_date {
+            return Err(CoreError::ValidationError(format!(
+                "Transaction {} is in the period closed through {} and cannot be {}; reopen it first",
+                transaction_id, lock_date, action
+            )));
+        }
+        Ok(())
+    }
+
+    /// A replacement entry set must have at least two legs and balance
+    fn validate_entries(entries: &[NewJournalEntry]) -> Result<()> {
+        if entries.len() < 2 {
+            return Err(CoreError::InvalidInput(
+                "A transaction needs at least two journal entries".to_string(),
+            ));
+        }
+
+        let total: Decimal = entries.iter().map(|e| e.amount).sum();
+        if total != Decimal::ZERO {
+            return Err(CoreError::UnbalancedTransaction {
+                expected: Decimal::ZERO,
+                actual: total,
+            });
+        }
+
+        Ok(())
+    }
+
+    async fn insert_journal_entries(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        transaction_id: Uuid,
+        entries: &[NewJournalEntry],
+    ) -> Result<Vec<JournalEntry>> {
+        let mut inserted = Vec::with_capacity(entries.len());
+        for entry in entries {
+            let journal_entry = sqlx::query_as::<_, JournalEntry>(
+                r#"
+                INSERT INTO journal_entries (transaction_id, account_id, amount, memo)
+                VALUES ($1, $2, $3, $4)
+                RETURNING id, transaction_id, account_id, amount, memo, created_at
+                "#,
+            )
+            .bind(transaction_id)
+            .bind(entry.account_id)
+            .bind(entry.amount)
+            .bind(&entry.memo)
+            .fetch_one(&mut **tx)
+            .await?;
+            inserted.push(journal_entry);
+        }
+        Ok(inserted)
+    }
+
+    async fn fetch_journal_entries(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        transaction_id: Uuid,
+    ) -> Result<Vec<JournalEntry>> {
+        let entries = sqlx::query_as::<_, JournalEntry>(
+            "SELECT id, transaction_id, account_id, amount, memo, created_at FROM journal_entries WHERE transaction_id = $1 ORDER BY created_at",
+        )
+        .bind(transaction_id)
+        .fetch_all(&mut **tx)
+        .await?;
+        Ok(entries)
+    }
+
+    /// Accounts of a transaction's entries other than `entry_id`
+    pub(crate) async fn other_entry_accounts(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        transaction_id: Uuid,
+        entry_id: Uuid,
+    ) -> Result<Vec<Uuid>> {
+        let account_ids = sqlx::query_scalar(
+            "SELECT account_id FROM journal_entries WHERE transaction_id = $1 AND id <> $2",
+        )
+        .bind(transaction_id)
+        .bind(entry_id)
+        .fetch_all(&mut **tx)
+        .await?;
+        Ok(account_ids)
+    }
+
     /// Helper: Create a simple two-account transaction (most common case)
     pub fn create_simple_transaction(
         description: String,
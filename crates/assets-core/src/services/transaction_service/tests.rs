@@ -1,7 +1,10 @@
 use super::*;
 use crate::CoreError;
-use crate::models::{AccountSubtype, AccountType, NewJournalEntry, NewTransaction};
-use crate::services::AccountService;
+use crate::models::{
+    AccountSubtype, AccountType, AmountFilter, NewJournalEntry, NewTransaction, TransactionSearch,
+    TransactionWithEntries,
+};
+use crate::services::{AccountService, ReconciliationService};
 use crate::tests::utils::*;
 use chrono::Utc;
 use rust_decimal::Decimal;
@@ -353,3 +356,712 @@ async fn test_double_entry_bookkeeping_principles() {
     let result = tx_service.create_transaction(rent_transaction).await;
     assert!(result.is_ok(), "Rent transaction should succeed");
 }
+
+/// Create checking/groceries/household accounts and a 45.50 supermarket purchase
+async fn setup_supermarket_purchase(
+    pool: &sqlx::PgPool,
+) -> (TransactionWithEntries, Uuid, Uuid, Uuid) {
+    let tx_service = TransactionService::new(pool.clone());
+    let account_service = AccountService::new(pool.clone());
+
+    let checking = account_service
+        .create_account(create_test_new_account_with_type(
+            "Checking",
+            AccountType::Asset,
+            AccountSubtype::Checking,
+        ))
+        .await
+        .unwrap();
+    let groceries = account_service
+        .create_account(create_test_new_account_with_type(
+            "Groceries",
+            AccountType::Expense,
+            AccountSubtype::Food,
+        ))
+        .await
+        .unwrap();
+    let household = account_service
+        .create_account(create_test_new_account_with_type(
+            "Household",
+            AccountType::Expense,
+            AccountSubtype::Housing,
+        ))
+        .await
+        .unwrap();
+
+    let mut purchase = TransactionService::create_simple_transaction(
+        "Supermarket".to_string(),
+        groceries.id,
+        checking.id,
+        Decimal::from_str("45.50").unwrap(),
+        Utc::now(),
+        None,
+    );
+    purchase.import_source = Some("BoursoBank".to_string());
+    let created = tx_service.create_transaction(purchase).await.unwrap();
+
+    (created, checking.id, groceries.id, household.id)
+}
+
+#[tokio::test]
+async fn test_update_transaction_keeps_provenance() {
+    let (pool, _container) = setup_test_db().await;
+    let tx_service = TransactionService::new(pool.clone());
+    let (created, checking, _groceries, household) = setup_supermarket_purchase(&pool).await;
+
+    let updated = tx_service
+        .update_transaction(
+            created.transaction.id,
+            TransactionUpdates {
+                description: Some("Supermarket (household)".to_string()),
+                entries: Some(vec![
+                    NewJournalEntry {
+                        account_id: household,
+                        amount: Decimal::from_str("45.50").unwrap(),
+                        memo: None,
+                    },
+                    NewJournalEntry {
+                        account_id: checking,
+                        amount: Decimal::from_str("-45.50").unwrap(),
+                        memo: None,
+                    },
+                ]),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(updated.transaction.id, created.transaction.id);
+    assert_eq!(updated.transaction.description, "Supermarket (household)");
+    assert_eq!(
+        updated.transaction.import_source.as_deref(),
+        Some("BoursoBank")
+    );
+    assert_eq!(updated.entries.len(), 2);
+    assert!(updated.entries.iter().any(|e| e.account_id == household));
+}
+
+#[tokio::test]
+async fn test_update_transaction_clears_reference_and_keeps_cleared_entries() {
+    let (pool, _container) = setup_test_db().await;
+    let tx_service = TransactionService::new(pool.clone());
+    let reconciliation_service = ReconciliationService::new(pool.clone());
+    let (created, checking, _groceries, household) = setup_supermarket_purchase(&pool).await;
+    let id = created.transaction.id;
+
+    let referenced = tx_service
+        .update_transaction(
+            id,
+            TransactionUpdates {
+                reference: Some(Some("R-42".to_string())),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(referenced.transaction.reference.as_deref(), Some("R-42"));
+
+    let checking_entry = created
+        .entries
+        .iter()
+        .find(|e| e.account_id == checking)
+        .unwrap();
+    reconciliation_service
+        .set_cleared(&[checking_entry.id], true)
+        .await
+        .unwrap();
+
+    let updated = tx_service
+        .update_transaction(
+            id,
+            TransactionUpdates {
+                reference: Some(None),
+                entries: Some(vec![
+                    NewJournalEntry {
+                        account_id: household,
+                        amount: Decimal::from_str("45.50").unwrap(),
+                        memo: None,
+                    },
+                    NewJournalEntry {
+                        account_id: checking,
+                        amount: Decimal::from_str("-45.50").unwrap(),
+                        memo: None,
+                    },
+                ]),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(updated.transaction.reference, None);
+
+    let cleared: Vec<Uuid> = reconciliation_service
+        .entry_statuses()
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|status| status.journal_entry_id)
+        .collect();
+    let new_checking_entry = updated
+        .entries
+        .iter()
+        .find(|e| e.account_id == checking)
+        .unwrap();
+    assert_eq!(cleared, vec![new_checking_entry.id]);
+}
+
+#[tokio::test]
+async fn test_update_transaction_rejects_unbalanced_entries() {
+    let (pool, _container) = setup_test_db().await;
+    let tx_service = TransactionService::new(pool.clone());
+    let (created, checking, groceries, _household) = setup_supermarket_purchase(&pool).await;
+
+    let result = tx_service
+        .update_transaction(
+            created.transaction.id,
+            TransactionUpdates {
+                entries: Some(vec![
+                    NewJournalEntry {
+                        account_id: groceries,
+                        amount: Decimal::from_str("40.00").unwrap(),
+                        memo: None,
+                    },
+                    NewJournalEntry {
+                        account_id: checking,
+                        amount: Decimal::from_str("-45.50").unwrap(),
+                        memo: None,
+                    },
+                ]),
+                ..Default::default()
+            },
+        )
+        .await;
+    assert!(matches!(
+        result,
+        Err(CoreError::UnbalancedTransaction { .. })
+    ));
+
+    // The original entries are untouched
+    let unchanged = tx_service
+        .get_transaction(created.transaction.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(unchanged.entries.len(), 2);
+}
+
+#[tokio::test]
+async fn test_split_entry() {
+    let (pool, _container) = setup_test_db().await;
+    let tx_service = TransactionService::new(pool.clone());
+    let (created, _checking, groceries, household) = setup_supermarket_purchase(&pool).await;
+    let expense_entry = created
+        .entries
+        .iter()
+        .find(|e| e.account_id == groceries)
+        .unwrap();
+
+    let parts = |household_amount: &str| {
+        vec![
+            NewJournalEntry {
+                account_id: groceries,
+                amount: Decimal::from_str("30.00").unwrap(),
+                memo: None,
+            },
+            NewJournalEntry {
+                account_id: household,
+                amount: Decimal::from_str(household_amount).unwrap(),
+                memo: Some("Cleaning products".to_string()),
+            },
+        ]
+    };
+
+    let result = tx_service
+        .split_entry(created.transaction.id, expense_entry.id, parts("10.00"))
+        .await;
+    assert!(matches!(
+        result,
+        Err(CoreError::UnbalancedTransaction { .. })
+    ));
+
+    let split = tx_service
+        .split_entry(created.transaction.id, expense_entry.id, parts("15.50"))
+        .await
+        .unwrap();
+    assert_eq!(split.entries.len(), 3);
+    assert!(!split.entries.iter().any(|e| e.id == expense_entry.id));
+    assert_eq!(
+        split.entries.iter().map(|e| e.amount).sum::<Decimal>(),
+        Decimal::ZERO
+    );
+}
+
+#[tokio::test]
+async fn test_reverse_transaction_mirrors_entries() {
+    let (pool, _container) = setup_test_db().await;
+    let tx_service = TransactionService::new(pool.clone());
+    let (created, checking, groceries, _household) = setup_supermarket_purchase(&pool).await;
+    let reversal_date = created.transaction.transaction_date + chrono::Duration::days(3);
+
+    let reversal = tx_service
+        .reverse_transaction(created.transaction.id, reversal_date, None)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        reversal.transaction.reversal_of_transaction_id,
+        Some(created.transaction.id)
+    );
+    assert_eq!(reversal.transaction.description, "Reversal of: Supermarket");
+    assert_eq!(reversal.transaction.transaction_date, reversal_date);
+    let amount_on = |account_id: Uuid| {
+        reversal
+            .entries
+            .iter()
+            .find(|e| e.account_id == account_id)
+            .unwrap()
+            .amount
+    };
+    assert_eq!(amount_on(groceries), Decimal::from_str("-45.50").unwrap());
+    assert_eq!(amount_on(checking), Decimal::from_str("45.50").unwrap());
+
+    // Only one reversal, and linked transactions cannot be deleted or voided
+    let again = tx_service
+        .reverse_transaction(created.transaction.id, reversal_date, None)
+        .await;
+    assert!(matches!(again, Err(CoreError::ValidationError(_))));
+    let delete = tx_service.delete_transaction(created.transaction.id).await;
+    assert!(matches!(delete, Err(CoreError::ValidationError(_))));
+    let void = tx_service.void_transaction(reversal.transaction.id).await;
+    assert!(matches!(void, Err(CoreError::ValidationError(_))));
+}
+
+#[tokio::test]
+async fn test_reversal_pair_cannot_be_edited_or_split() {
+    let (pool, _container) = setup_test_db().await;
+    let tx_service = TransactionService::new(pool.clone());
+    let (created, checking, groceries, household) = setup_supermarket_purchase(&pool).await;
+    let reversal_date = created.transaction.transaction_date + chrono::Duration::days(3);
+    let reversal = tx_service
+        .reverse_transaction(created.transaction.id, reversal_date, None)
+        .await
+        .unwrap();
+
+    for side in [&created, &reversal] {
+        let id = side.transaction.id;
+        let groceries_entry = side
+            .entries
+            .iter()
+            .find(|e| e.account_id == groceries)
+            .unwrap();
+        let moved = groceries_entry.amount;
+
+        let redated = tx_service
+            .update_transaction(
+                id,
+                TransactionUpdates {
+                    transaction_date: Some(reversal_date + chrono::Duration::days(1)),
+                    ..Default::default()
+                },
+            )
+            .await;
+        assert!(matches!(redated, Err(CoreError::ValidationError(_))));
+
+        let replaced = tx_service
+            .update_transaction(
+                id,
+                TransactionUpdates {
+                    entries: Some(vec![
+                        NewJournalEntry {
+                            account_id: household,
+                            amount: moved,
+                            memo: None,
+                        },
+                        NewJournalEntry {
+                            account_id: checking,
+                            amount: -moved,
+                            memo: None,
+                        },
+                    ]),
+                    ..Default::default()
+                },
+            )
+            .await;
+        assert!(matches!(replaced, Err(CoreError::ValidationError(_))));
+
+        let half = moved / Decimal::from(2);
+        let split = tx_service
+            .split_entry(
+                id,
+                groceries_entry.id,
+                vec![
+                    NewJournalEntry {
+                        account_id: groceries,
+                        amount: half,
+                        memo: None,
+                    },
+                    NewJournalEntry {
+                        account_id: household,
+                        amount: moved - half,
+                        memo: None,
+                    },
+                ],
+            )
+            .await;
+        assert!(matches!(split, Err(CoreError::ValidationError(_))));
+
+        // Texts stay editable
+        tx_service
+            .update_transaction(
+                id,
+                TransactionUpdates {
+                    description: Some("Supermarket (disputed)".to_string()),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+    }
+}
+
+#[tokio::test]
+async fn test_voided_transaction_is_excluded_from_reports() {
+    let (pool, _container) = setup_test_db().await;
+    let tx_service = TransactionService::new(pool.clone());
+    let report_service = crate::ReportService::new(pool.clone());
+    let (created, checking, _groceries, _household) = setup_supermarket_purchase(&pool).await;
+    // Report end dates are compared against midnight, so look one day ahead
+    let day = created.transaction.transaction_date.date_naive();
+    let next_day = day.succ_opt().unwrap();
+
+    let statement = report_service
+        .income_statement(day, next_day)
+        .await
+        .unwrap();
+    assert_eq!(statement.len(), 1);
+
+    let voided = tx_service
+        .void_transaction(created.transaction.id)
+        .await
+        .unwrap();
+    assert!(voided.is_voided);
+    assert!(voided.voided_at.is_some());
+
+    assert!(
+        report_service
+            .income_statement(day, next_day)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    assert!(
+        report_service
+            .balance_sheet(next_day)
+            .await
+            .unwrap()
+            .assets
+            .is_empty()
+    );
+    assert!(
+        report_service
+            .account_ledger(checking, day, next_day)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+
+    // The row itself is kept
+    let kept = tx_service
+        .get_transaction(created.transaction.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(kept.entries.len(), 2);
+}
+
+#[test]
+fn test_parse_amount_filter() {
+    let d = |s: &str| Decimal::from_str(s).unwrap();
+    assert_eq!(">100".parse(), Ok(AmountFilter::GreaterThan(d("100"))));
+    assert_eq!(">= 100".parse(), Ok(AmountFilter::GreaterOrEqual(d("100"))));
+    assert_eq!("<20".parse(), Ok(AmountFilter::LessThan(d("20"))));
+    assert_eq!("<=20".parse(), Ok(AmountFilter::LessOrEqual(d("20"))));
+    assert_eq!("=42.50".parse(), Ok(AmountFilter::Equal(d("42.50"))));
+    assert_eq!("42.50".parse(), Ok(AmountFilter::Equal(d("42.50"))));
+    assert_eq!(
+        "100..200".parse(),
+        Ok(AmountFilter::Between(d("100"), d("200")))
+    );
+    assert!("200..100".parse::<AmountFilter>().is_err());
+    assert!(">abc".parse::<AmountFilter>().is_err());
+}
+
+#[tokio::test]
+async fn test_search_transactions() {
+    let (pool, _container) = setup_test_db().await;
+    let tx_service = TransactionService::new(pool.clone());
+    let (created, checking, groceries, household) = setup_supermarket_purchase(&pool).await;
+    let today = created.transaction.transaction_date.date_naive();
+
+    let mut rent = TransactionService::create_simple_transaction(
+        "Monthly rent".to_string(),
+        household,
+        checking,
+        Decimal::from(950),
+        Utc::now(),
+        Some("LEASE-2025".to_string()),
+    );
+    rent.entries[0].memo = Some("apartment".to_string());
+    tx_service.create_transaction(rent).await.unwrap();
+
+    // Typos still match, best match first
+    let results = tx_service
+        .search(&TransactionSearch::builder().query("supermarkt").build())
+        .await
+        .unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(
+        results[0].transaction.transaction.id,
+        created.transaction.id
+    );
+    assert!(results[0].score >= SEARCH_SIMILARITY_THRESHOLD);
+
+    // References and memos are searched too
+    for query in ["lease", "apartment"] {
+        let results = tx_service
+            .search(&TransactionSearch::builder().query(query).build())
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 1, "query {}", query);
+        assert_eq!(
+            results[0].transaction.transaction.description,
+            "Monthly rent"
+        );
+    }
+
+    let by_amount = |filter: &str| {
+        TransactionSearch::builder()
+            .amount(filter.parse().unwrap())
+            .build()
+    };
+    assert_eq!(
+        tx_service.search(&by_amount(">100")).await.unwrap().len(),
+        1
+    );
+    assert_eq!(
+        tx_service.search(&by_amount("=45.50")).await.unwrap().len(),
+        1
+    );
+    assert_eq!(
+        tx_service
+            .search(&by_amount("40..1000"))
+            .await
+            .unwrap()
+            .len(),
+        2
+    );
+    assert!(
+        tx_service
+            .search(&by_amount("<10"))
+            .await
+            .unwrap()
+            .is_empty()
+    );
+
+    let in_groceries = tx_service
+        .search(
+            &TransactionSearch::builder()
+                .account_path("Groceries")
+                .import_source("boursobank")
+                .from_date(today)
+                .to_date(today)
+                .build(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(in_groceries.len(), 1);
+    assert!(
+        in_groceries[0]
+            .transaction
+            .entries
+            .iter()
+            .any(|e| e.account_id == groceries)
+    );
+
+    let no_match = tx_service
+        .search(&TransactionSearch::builder().query("cinema").build())
+        .await
+        .unwrap();
+    assert!(no_match.is_empty());
+}
+
+#[tokio::test]
+async fn test_transaction_pages_are_stable() {
+    use futures::TryStreamExt;
+
+    let (pool, _container) = setup_test_db().await;
+    let tx_service = TransactionService::new(pool.clone());
+    let (created, checking, groceries, _household) = setup_supermarket_purchase(&pool).await;
+
+    // Several transactions share a date, so the ID has to break ties
+    let same_day = created.transaction.transaction_date;
+    for i in 0..4 {
+        tx_service
+            .create_transaction(TransactionService::create_simple_transaction(
+                format!("Groceries {}", i),
+                groceries,
+                checking,
+                Decimal::from(10 + i),
+                same_day,
+                None,
+            ))
+            .await
+            .unwrap();
+    }
+
+    let filter = TransactionFilter::default();
+    let mut seen = Vec::new();
+    let mut cursor = None;
+    loop {
+        let page = tx_service
+            .get_transactions_page(&filter, cursor.as_ref(), 2)
+            .await
+            .unwrap();
+        assert!(page.transactions.len() <= 2);
+        seen.extend(page.transactions.iter().map(|t| t.transaction.id));
+        if seen.len() == 2 {
+            // A newer transaction added while paging does not shift later pages
+            tx_service
+                .create_transaction(TransactionService::create_simple_transaction(
+                    "Late groceries".to_string(),
+                    groceries,
+                    checking,
+                    Decimal::from(99),
+                    same_day + chrono::Duration::days(1),
+                    None,
+                ))
+                .await
+                .unwrap();
+        }
+        match page.next_cursor {
+            Some(next) => cursor = Some(next),
+            None => break,
+        }
+    }
+    assert_eq!(seen.len(), 5);
+    let unique: std::collections::HashSet<_> = seen.iter().collect();
+    assert_eq!(unique.len(), 5);
+
+    // The stream walks every page in the same order, newest first
+    let streamed: Vec<Uuid> = tx_service
+        .stream_transactions(filter.clone())
+        .map_ok(|t| t.transaction.id)
+        .try_collect()
+        .await
+        .unwrap();
+    assert_eq!(streamed.len(), 6);
+    assert_eq!(streamed[1..], seen[..]);
+
+    // A page number lands on the same transactions as walking the cursors
+    let third = tx_service
+        .get_transactions_page_number(&filter, 3, 2)
+        .await
+        .unwrap();
+    let third_ids: Vec<Uuid> = third
+        .transactions
+        .iter()
+        .map(|t| t.transaction.id)
+        .collect();
+    assert_eq!(third_ids[..], streamed[4..]);
+    assert!(third.next_cursor.is_none());
+    let beyond = tx_service
+        .get_transactions_page_number(&filter, 4, 2)
+        .await
+        .unwrap();
+    assert!(beyond.transactions.is_empty());
+    assert_eq!(
+        tx_service
+            .get_transactions_with_filters_and_accounts(None, None, None, None, 3)
+            .await
+            .unwrap()
+            .len(),
+        3
+    );
+
+    let nowhere = TransactionFilter::builder()
+        .account_path("Assets:Nowhere")
+        .build();
+    let empty = tx_service
+        .get_transactions_page(&nowhere, None, 10)
+        .await
+        .unwrap();
+    assert!(empty.transactions.is_empty());
+    assert!(empty.next_cursor.is_none());
+}
+
+#[tokio::test]
+async fn test_create_transaction_by_path_ensure_accounts() {
+    let (pool, _container) = setup_test_db().await;
+    let service = TransactionService::new(pool.clone());
+    let account_service = AccountService::new(pool);
+
+    let transaction = NewTransactionByPath::simple_transfer(
+        "Bakery",
+        Utc::now(),
+        "Assets:Wallet",
+        "Expenses:Food:Bakery",
+        Decimal::from_str("4.20").unwrap(),
+    );
+
+    // Missing accounts fail unless asked to create them
+    let result = service
+        .create_transaction_by_path(&account_service, transaction.clone())
+        .await;
+    assert!(matches!(result, Err(CoreError::AccountNotFound(_))));
+
+    // A rejected transaction leaves none of the accounts it created behind
+    let mut unbalanced = NewTransactionByPath {
+        ensure_accounts: true,
+        ..transaction.clone()
+    };
+    unbalanced.entries[0].amount += Decimal::ONE;
+    let result = service
+        .create_transaction_by_path(&account_service, unbalanced)
+        .await;
+    assert!(matches!(
+        result,
+        Err(CoreError::UnbalancedTransaction { .. })
+    ));
+    assert!(
+        account_service
+            .get_account_by_path_optional("Expenses:Food")
+            .await
+            .unwrap()
+            .is_none()
+    );
+
+    let created = service
+        .create_transaction_by_path(
+            &account_service,
+            NewTransactionByPath {
+                ensure_accounts: true,
+                ..transaction
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(created.entries.len(), 2);
+
+    let wallet = account_service
+        .get_account_by_path("Assets:Wallet")
+        .await
+        .unwrap();
+    assert_eq!(wallet.account_type, AccountType::Asset);
+    assert_eq!(wallet.account_subtype, AccountSubtype::OtherAsset);
+    let bakery = account_service
+        .get_account_by_path("Expenses:Food:Bakery")
+        .await
+        .unwrap();
+    assert_eq!(bakery.account_type, AccountType::Expense);
+}
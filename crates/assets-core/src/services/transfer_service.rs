@@ -0,0 +1,409 @@
+use crate::error::{CoreError, Result};
+use crate::models::{
+    AccountSubtype, AccountType, EntryStatus, NewAccountByPath, TransferCandidate,
+    TransferDetection, TransferLink,
+};
+use crate::services::{
+    AccountService, IntegrityService, TransactionService, UNCATEGORIZED_ACCOUNT_PATH,
+};
+use crate::validation::TransactionValidator;
+use log::warn;
+use rust_decimal::Decimal;
+use sqlx::PgPool;
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+/// Clearing account holding linked transfers; it is non-zero only while money is in transit
+pub const TRANSFER_CLEARING_ACCOUNT_PATH: &str = "Assets:Transfers in Transit";
+
+const TRANSFER_LINK_COLUMNS: &str = r#"
+    l.id, l.outgoing_transaction_id, l.incoming_transaction_id,
+    l.outgoing_entry_id, l.incoming_entry_id, l.amount, l.created_at,
+    oa.full_path AS outgoing_account_path, ia.full_path AS incoming_account_path,
+    ot.transaction_date AS outgoing_date
+"#;
+
+const TRANSFER_LINK_JOINS: &str = r#"
+    JOIN transactions ot ON ot.id = l.outgoing_transaction_id
+    JOIN journal_entries obe ON obe.transaction_id = l.outgoing_transaction_id AND obe.id <> l.outgoing_entry_id
+    JOIN accounts oa ON oa.id = obe.account_id
+    JOIN journal_entries ibe ON ibe.transaction_id = l.incoming_transaction_id AND ibe.id <> l.incoming_entry_id
+    JOIN accounts ia ON ia.id = ibe.account_id
+"#;
+
+/// Detection and linking of internal transfers between the user's own accounts
+///
+/// An imported transfer shows up twice: money leaving one bank account and arriving
+/// in another, both against the uncategorized account. Linking moves the two
+/// uncategorized legs to [`TRANSFER_CLEARING_ACCOUNT_PATH`], so both transactions are
+/// kept as imported and unmerging simply moves the legs back.
+pub struct TransferService {
+    pool: PgPool,
+    account_service: AccountService,
+    validator: TransactionValidator,
+}
+
+impl TransferService {
+    pub fn new(pool: PgPool) -> Self {
+        Self {
+            account_service: AccountService::new(pool.clone()),
+            pool,
+            validator: TransactionValidator::new(),
+        }
+    }
+
+    /// Opposite uncategorized movements of two different accounts, closest dates first
+    ///
+    /// Only two-legged transactions (an account against the uncategorized account) that
+    /// are not voided or linked yet are considered. Each line appears in at most one
+    /// candidate; when a line could pair with several others the candidate is flagged
+    /// as ambiguous.
+    pub async fn find_candidates(
+        &self,
+        detection: &TransferDetection,
+    ) -> Result<Vec<TransferCandidate>> {
+        let pairs = sqlx::query_as::<_, TransferCandidate>(
+            r#"
+            WITH legs AS (
+                SELECT t.id AS transaction_id, t.transaction_date, t.description,
+                       t.import_batch_id, bje.account_id, ba.full_path AS account_path,
+                       bje.amount
+                FROM transactions t
+                JOIN journal_entries uje ON uje.transaction_id = t.id
+                JOIN accounts ua ON ua.id = uje.account_id
+                JOIN journal_entries bje ON bje.transaction_id = t.id AND bje.id <> uje.id
+                JOIN accounts ba ON ba.id = bje.account_id
+                WHERE ua.full_path = $1
+                  AND ba.full_path <> $1
+                  AND NOT t.is_voided
+                  AND (SELECT COUNT(*) FROM journal_entries c WHERE c.transaction_id = t.id) = 2
+                  AND NOT EXISTS (
+                      SELECT 1 FROM transfer_links l
+                      WHERE t.id IN (l.outgoing_transaction_id, l.incoming_transaction_id)
+                  )
+                  AND ($2::date IS NULL OR t.transaction_date >= $2::date)
+                  AND ($3::date IS NULL OR t.transaction_date < $3::date + 1)
+            )
+            SELECT o.transaction_id AS outgoing_transaction_id,
+                   o.account_path AS outgoing_account_path,
+                   o.transaction_date AS outgoing_date,
+                   o.description AS outgoing_description,
+                   i.transaction_id AS incoming_transaction_id,
+                   i.account_path AS incoming_account_path,
+                   i.transaction_date AS incoming_date,
+                   i.description AS incoming_description,
+                   i.amount,
+                   ABS(i.transaction_date::date - o.transaction_date::date) AS day_gap
+            FROM legs o
+            JOIN legs i ON i.amount = -o.amount AND i.account_id <> o.account_id
+            WHERE o.amount < 0
+              AND ABS(i.transaction_date::date - o.transaction_date::date) <= $4
+              AND (NOT $5 OR LOWER(TRIM(i.description)) = LOWER(TRIM(o.description)))
+              AND ($6::uuid IS NULL OR $6 IN (o.import_batch_id, i.import_batch_id))
+            ORDER BY day_gap, o.transaction_date, o.transaction_id, i.transaction_date, i.transaction_id
+            "#,
+        )
+        .bind(UNCATEGORIZED_ACCOUNT_PATH)
+        .bind(detection.from_date)
+        .bind(detection.to_date)
+        .bind(detection.tolerance_days)
+        .bind(detection.same_description)
+        .bind(detection.import_batch_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut partners: HashMap<Uuid, usize> = HashMap::new();
+        for pair in &pairs {
+            *partners.entry(pair.outgoing_transaction_id).or_default() += 1;
+            *partners.entry(pair.incoming_transaction_id).or_default() += 1;
+        }
+
+        // Closest pairs win; a line is never offered twice
+        let mut used = HashSet::new();
+        let mut candidates = Vec::new();
+        for mut pair in pairs {
+            if used.contains(&pair.outgoing_transaction_id)
+                || used.contains(&pair.incoming_transaction_id)
+            {
+                continue;
+            }
+            used.insert(pair.outgoing_transaction_id);
+            used.insert(pair.incoming_transaction_id);
+            pair.ambiguous = partners[&pair.outgoing_transaction_id] > 1
+                || partners[&pair.incoming_transaction_id] > 1;
+            candidates.push(pair);
+        }
+
+        Ok(candidates)
+    }
+
+    /// Link an outgoing and an incoming transaction as one internal transfer
+    pub async fn link(
+        &self,
+        outgoing_transaction_id: Uuid,
+        incoming_transaction_id: Uuid,
+    ) -> Result<TransferLink> {
+        if outgoing_transaction_id == incoming_transaction_id {
+            return Err(CoreError::InvalidInput(
+                "A transfer links two different transactions".to_string(),
+            ));
+        }
+        let clearing_account_id = self.clearing_account().await?;
+
+        let mut tx = self.pool.begin().await?;
+
+        let already_linked: bool = sqlx::query_scalar(
+            "SELECT EXISTS (SELECT 1 FROM transfer_links WHERE outgoing_transaction_id IN ($1, $2) OR incoming_transaction_id IN ($1, $2))",
+        )
+        .bind(outgoing_transaction_id)
+        .bind(incoming_transaction_id)
+        .fetch_one(&mut *tx)
+        .await?;
+        if already_linked {
+            return Err(CoreError::ValidationError(
+                "One of the transactions is already linked as a transfer".to_string(),
+            ));
+        }
+
+        let outgoing = Self::uncategorized_leg(&mut tx, outgoing_transaction_id).await?;
+        let incoming = Self::uncategorized_leg(&mut tx, incoming_transaction_id).await?;
+        // The uncategorized leg mirrors the bank side: positive when money left
+        if outgoing.1 <= Decimal::ZERO || incoming.1 != -outgoing.1 {
+            return Err(CoreError::ValidationError(format!(
+                "Transactions {} and {} are not opposite movements of the same amount",
+                outgoing_transaction_id, incoming_transaction_id
+            )));
+        }
+
+        self.move_entries(
+            &mut tx,
+            &[
+                (outgoing_transaction_id, outgoing.0),
+                (incoming_transaction_id, incoming.0),
+            ],
+            clearing_account_id,
+        )
+        .await?;
+        IntegrityService::seal(&mut tx, &[outgoing_transaction_id, incoming_transaction_id])
+            .await?;
+
+        let id: Uuid = sqlx::query_scalar(
+            r#"
+            INSERT INTO transfer_links
+                (outgoing_transaction_id, incoming_transaction_id, outgoing_entry_id, incoming_entry_id, amount)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING id
+            "#,
+        )
+        .bind(outgoing_transaction_id)
+        .bind(incoming_transaction_id)
+        .bind(outgoing.0)
+        .bind(incoming.0)
+        .bind(outgoing.1)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        self.get_link(id)
+            .await?
+            .ok_or_else(|| CoreError::NotFound(format!("Transfer link {}", id)))
+    }
+
+    /// Link every unambiguous candidate, e.g. right after an import
+    ///
+    /// A pair that cannot be linked is logged and skipped.
+    pub async fn auto_link(&self, detection: &TransferDetection) -> Result<Vec<TransferLink>> {
+        let mut links = Vec::new();
+        for candidate in self.find_candidates(detection).await? {
+            if candidate.ambiguous {
+                continue;
+            }
+            match self
+                .link(
+                    candidate.outgoing_transaction_id,
+                    candidate.incoming_transaction_id,
+                )
+                .await
+            {
+                Ok(link) => links.push(link),
+                Err(e) => warn!(
+                    "Could not link transfer {} -> {}: {}",
+                    candidate.outgoing_transaction_id, candidate.incoming_transaction_id, e
+                ),
+            }
+        }
+        Ok(links)
+    }
+
+    /// Undo a link given its ID or the ID of either linked transaction
+    ///
+    /// Both legs go back to the uncategorized account; returns the removed link.
+    pub async fn unlink(&self, id: Uuid) -> Result<Option<TransferLink>> {
+        let Some(link) = self.find_link(id).await? else {
+            return Ok(None);
+        };
+        let uncategorized_id = self
+            .account_service
+            .get_account_by_path(UNCATEGORIZED_ACCOUNT_PATH)
+            .await?
+            .id;
+
+        let mut tx = self.pool.begin().await?;
+        sqlx::query("DELETE FROM transfer_links WHERE id = $1")
+            .bind(link.id)
+            .execute(&mut *tx)
+            .await?;
+        self.move_entries(
+            &mut tx,
+            &[
+                (link.outgoing_transaction_id, link.outgoing_entry_id),
+                (link.incoming_transaction_id, link.incoming_entry_id),
+            ],
+            uncategorized_id,
+        )
+        .await?;
+        IntegrityService::seal(
+            &mut tx,
+            &[link.outgoing_transaction_id, link.incoming_transaction_id],
+        )
+        .await?;
+        tx.commit().await?;
+
+        Ok(Some(link))
+    }
+
+    /// All links, most recent transfer first
+    pub async fn list_links(&self) -> Result<Vec<TransferLink>> {
+        let links = sqlx::query_as::<_, TransferLink>(&format!(
+            "SELECT {} FROM transfer_links l {} ORDER BY ot.transaction_date DESC, l.created_at DESC",
+            TRANSFER_LINK_COLUMNS, TRANSFER_LINK_JOINS
+        ))
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(links)
+    }
+
+    pub async fn get_link(&self, id: Uuid) -> Result<Option<TransferLink>> {
+        let link = sqlx::query_as::<_, TransferLink>(&format!(
+            "SELECT {} FROM transfer_links l {} WHERE l.id = $1",
+            TRANSFER_LINK_COLUMNS, TRANSFER_LINK_JOINS
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(link)
+    }
+
+    /// The link with this ID, or the one either transaction with this ID belongs to
+    pub async fn find_link(&self, id: Uuid) -> Result<Option<TransferLink>> {
+        let link = sqlx::query_as::<_, TransferLink>(&format!(
+            "SELECT {} FROM transfer_links l {} WHERE $1 IN (l.id, l.outgoing_transaction_id, l.incoming_transaction_id)",
+            TRANSFER_LINK_COLUMNS, TRANSFER_LINK_JOINS
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(link)
+    }
+
+    async fn clearing_account(&self) -> Result<Uuid> {
+        if let Some(account) = self
+            .account_service
+            .get_account_by_path_optional(TRANSFER_CLEARING_ACCOUNT_PATH)
+            .await?
+        {
+            return Ok(account.id);
+        }
+
+        let account = self
+            .account_service
+            .create_account_by_path(
+                NewAccountByPath::builder()
+                    .full_path(TRANSFER_CLEARING_ACCOUNT_PATH)
+                    .account_type(AccountType::Asset)
+                    .account_subtype(AccountSubtype::Cash)
+                    .notes(
+                        "Internal transfers between linked accounts; zero unless in transit"
+                            .to_string(),
+                    )
+                    .build(),
+            )
+            .await?;
+        Ok(account.id)
+    }
+
+    /// Move `(transaction, entry)` legs to `account_id`, under the same posting rules as new entries
+    async fn move_entries(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        legs: &[(Uuid, Uuid)],
+        account_id: Uuid,
+    ) -> Result<()> {
+        for (transaction_id, entry_id) in legs {
+            let other_account_ids =
+                TransactionService::other_entry_accounts(tx, *transaction_id, *entry_id).await?;
+            self.validator
+                .validate_postings(tx, &[account_id], &other_account_ids)
+                .await?;
+        }
+
+        let entry_ids: Vec<Uuid> = legs.iter().map(|(_, entry_id)| *entry_id).collect();
+        sqlx::query("UPDATE journal_entries SET account_id = $2 WHERE id = ANY($1)")
+            .bind(entry_ids)
+            .bind(account_id)
+            .execute(&mut **tx)
+            .await?;
+        Ok(())
+    }
+
+    /// ID and amount of the uncategorized leg of a two-legged, non-voided transaction
+    async fn uncategorized_leg(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        transaction_id: Uuid,
+    ) -> Result<(Uuid, Decimal)> {
+        let legs: Vec<(Uuid, Decimal, String, EntryStatus, bool)> = sqlx::query_as(
+            r#"
+            SELECT je.id, je.amount, a.full_path, je.status, t.is_voided
+            FROM journal_entries je
+            JOIN accounts a ON a.id = je.account_id
+            JOIN transactions t ON t.id = je.transaction_id
+            WHERE je.transaction_id = $1
+            FOR UPDATE OF je
+            "#,
+        )
+        .bind(transaction_id)
+        .fetch_all(&mut **tx)
+        .await?;
+
+        if legs.is_empty() {
+            return Err(CoreError::NotFound(format!(
+                "Transaction {}",
+                transaction_id
+            )));
+        }
+        if legs.iter().any(|leg| leg.4) {
+            return Err(CoreError::ValidationError(format!(
+                "Transaction {} is voided",
+                transaction_id
+            )));
+        }
+        let uncategorized: Vec<_> = legs
+            .iter()
+            .filter(|leg| leg.2 == UNCATEGORIZED_ACCOUNT_PATH)
+            .collect();
+        match (legs.len(), uncategorized.as_slice()) {
+            (2, [leg]) if leg.3 != EntryStatus::Reconciled => Ok((leg.0, leg.1)),
+            _ => Err(CoreError::ValidationError(format!(
+                "Transaction {} is not a single movement against {}",
+                transaction_id, UNCATEGORIZED_ACCOUNT_PATH
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,262 @@
+use super::*;
+use crate::models::{AccountSubtype, AccountType};
+use crate::services::{PeriodService, TransactionService, UNCATEGORIZED_ACCOUNT_PATH};
+use crate::tests::utils::*;
+use chrono::NaiveDate;
+use sqlx::PgPool;
+
+/// The accounts these tests post to
+struct Ledger {
+    checking: Uuid,
+    savings: Uuid,
+    uncategorized: Uuid,
+    tx_service: TransactionService,
+}
+
+impl Ledger {
+    async fn new(pool: &PgPool) -> Self {
+        Self {
+            checking: account_at(
+                pool,
+                "Assets:Checking",
+                AccountType::Asset,
+                AccountSubtype::Checking,
+            )
+            .await,
+            savings: account_at(
+                pool,
+                "Assets:Savings",
+                AccountType::Asset,
+                AccountSubtype::Savings,
+            )
+            .await,
+            uncategorized: account_at(
+                pool,
+                UNCATEGORIZED_ACCOUNT_PATH,
+                AccountType::Equity,
+                AccountSubtype::OpeningBalance,
+            )
+            .await,
+            tx_service: TransactionService::new(pool.clone()),
+        }
+    }
+
+    /// An imported line of `account` against the uncategorized account
+    async fn import_line(
+        &self,
+        account: Uuid,
+        day_of_month: u32,
+        description: &str,
+        amount: i64,
+    ) -> Uuid {
+        post(
+            &self.tx_service,
+            description,
+            day(2025, 6, day_of_month),
+            account,
+            self.uncategorized,
+            amount,
+        )
+        .await
+        .transaction
+        .id
+    }
+}
+
+#[tokio::test]
+async fn test_link_and_unlink_transfer() {
+    let (pool, _container) = setup_test_db().await;
+    let service = TransferService::new(pool.clone());
+    let ledger = Ledger::new(&pool).await;
+
+    // The savings bank books the transfer two days later, under another label
+    let outgoing = ledger
+        .import_line(ledger.checking, 3, "VIR VERS LIVRET A", -500)
+        .await;
+    let incoming = ledger
+        .import_line(ledger.savings, 5, "VIREMENT RECU COMPTE COURANT", 500)
+        .await;
+    // Same amount but too far apart
+    ledger
+        .import_line(ledger.savings, 20, "VIREMENT RECU", 500)
+        .await;
+
+    let candidates = service
+        .find_candidates(&TransferDetection::default())
+        .await
+        .unwrap();
+    assert_eq!(candidates.len(), 1);
+    let candidate = &candidates[0];
+    assert_eq!(candidate.outgoing_transaction_id, outgoing);
+    assert_eq!(candidate.incoming_transaction_id, incoming);
+    assert_eq!(candidate.outgoing_account_path, "Assets:Checking");
+    assert_eq!(candidate.amount, Decimal::from(500));
+    assert_eq!(candidate.day_gap, 2);
+    assert!(!candidate.ambiguous);
+
+    let same_description = TransferDetection::builder().same_description(true).build();
+    assert!(
+        service
+            .find_candidates(&same_description)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+
+    let link = service.link(outgoing, incoming).await.unwrap();
+    assert_eq!(link.amount, Decimal::from(500));
+    assert_eq!(link.incoming_account_path, "Assets:Savings");
+
+    // Both transactions are kept; the clearing account nets to zero
+    let clearing = AccountService::new(pool.clone())
+        .get_account_by_path(TRANSFER_CLEARING_ACCOUNT_PATH)
+        .await
+        .unwrap();
+    assert_eq!(balance(&pool, clearing.id).await, Decimal::ZERO);
+    assert_eq!(
+        balance(&pool, ledger.uncategorized).await,
+        Decimal::from(-500)
+    );
+    assert!(
+        ledger
+            .tx_service
+            .get_transaction(outgoing)
+            .await
+            .unwrap()
+            .is_some()
+    );
+    assert!(
+        service
+            .find_candidates(&TransferDetection::default())
+            .await
+            .unwrap()
+            .is_empty()
+    );
+
+    let deleted = ledger.tx_service.delete_transaction(incoming).await;
+    assert!(matches!(deleted, Err(CoreError::ValidationError(_))));
+    let relinked = service.link(outgoing, incoming).await;
+    assert!(matches!(relinked, Err(CoreError::ValidationError(_))));
+
+    // Unmerging by either transaction ID puts the legs back
+    let removed = service.unlink(incoming).await.unwrap().unwrap();
+    assert_eq!(removed.id, link.id);
+    assert!(service.unlink(incoming).await.unwrap().is_none());
+    assert_eq!(balance(&pool, clearing.id).await, Decimal::ZERO);
+    assert_eq!(
+        balance(&pool, ledger.uncategorized).await,
+        Decimal::from(-500)
+    );
+    assert!(service.list_links().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_link_follows_posting_rules() {
+    let (pool, _container) = setup_test_db().await;
+    let service = TransferService::new(pool.clone());
+    let ledger = Ledger::new(&pool).await;
+
+    let outgoing = ledger
+        .import_line(ledger.checking, 3, "VIR VERS LIVRET", -200)
+        .await;
+    let incoming = ledger.import_line(ledger.savings, 4, "VIR RECU", 200).await;
+
+    // A clearing account turned into a category can no longer take the legs
+    let clearing = service.clearing_account().await.unwrap();
+    sqlx::query("UPDATE accounts SET is_category = TRUE WHERE id = $1")
+        .bind(clearing)
+        .execute(&pool)
+        .await
+        .unwrap();
+    let linked = service.link(outgoing, incoming).await;
+    assert!(matches!(linked, Err(CoreError::TransactionValidation(_))));
+    assert!(service.list_links().await.unwrap().is_empty());
+    assert_eq!(balance(&pool, clearing).await, Decimal::ZERO);
+}
+
+#[tokio::test]
+async fn test_auto_link_skips_pairs_that_cannot_be_linked() {
+    let (pool, _container) = setup_test_db().await;
+    let service = TransferService::new(pool.clone());
+    let ledger = Ledger::new(&pool).await;
+
+    // The first pair falls in a closed period, the second one is still open
+    ledger
+        .import_line(ledger.checking, 5, "VIR EPARGNE", -200)
+        .await;
+    ledger
+        .import_line(ledger.savings, 5, "VIR EPARGNE", 200)
+        .await;
+    let outgoing = ledger
+        .import_line(ledger.savings, 15, "VIR COURANT", -75)
+        .await;
+    let incoming = ledger
+        .import_line(ledger.checking, 15, "VIR COURANT", 75)
+        .await;
+    PeriodService::new(pool.clone())
+        .close_period(NaiveDate::from_ymd_opt(2025, 6, 10).unwrap())
+        .await
+        .unwrap();
+
+    let links = service
+        .auto_link(&TransferDetection::default())
+        .await
+        .unwrap();
+    assert_eq!(links.len(), 1);
+    assert_eq!(links[0].outgoing_transaction_id, outgoing);
+    assert_eq!(links[0].incoming_transaction_id, incoming);
+}
+
+#[tokio::test]
+async fn test_auto_link_skips_ambiguous_pairs() {
+    let (pool, _container) = setup_test_db().await;
+    let service = TransferService::new(pool.clone());
+    let ledger = Ledger::new(&pool).await;
+
+    // Two identical incoming lines could both match one outgoing line
+    ledger
+        .import_line(ledger.checking, 10, "VIR EPARGNE", -200)
+        .await;
+    ledger
+        .import_line(ledger.savings, 10, "VIR EPARGNE", 200)
+        .await;
+    ledger
+        .import_line(ledger.savings, 11, "VIR EPARGNE", 200)
+        .await;
+    // An unambiguous pair on the same day, and a same-account pair that is no transfer
+    let outgoing = ledger
+        .import_line(ledger.savings, 15, "VIR COURANT", -75)
+        .await;
+    let incoming = ledger
+        .import_line(ledger.checking, 15, "VIR COURANT", 75)
+        .await;
+    ledger.import_line(ledger.checking, 16, "CB", -30).await;
+    ledger
+        .import_line(ledger.checking, 16, "CB REMBOURSEMENT", 30)
+        .await;
+
+    let candidates = service
+        .find_candidates(&TransferDetection::default())
+        .await
+        .unwrap();
+    assert_eq!(candidates.len(), 2);
+    assert!(candidates.iter().any(|c| c.ambiguous));
+
+    let links = service
+        .auto_link(&TransferDetection::default())
+        .await
+        .unwrap();
+    assert_eq!(links.len(), 1);
+    assert_eq!(links[0].outgoing_transaction_id, outgoing);
+    assert_eq!(links[0].incoming_transaction_id, incoming);
+
+    // Date bounds restrict the lines considered
+    let june_10 = NaiveDate::from_ymd_opt(2025, 6, 10).unwrap();
+    let bounded = TransferDetection::builder()
+        .from_date(june_10)
+        .to_date(june_10)
+        .build();
+    let candidates = service.find_candidates(&bounded).await.unwrap();
+    assert_eq!(candidates.len(), 1);
+    assert!(!candidates[0].ambiguous);
+}
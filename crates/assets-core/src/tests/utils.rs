@@ -1,6 +1,12 @@
-use crate::models::{AccountSubtype, AccountType, NewAccount};
+use crate::models::{
+    AccountSubtype, AccountType, NewAccount, NewAccountByPath, TransactionWithEntries,
+};
+use crate::services::{AccountService, TransactionService};
+use chrono::{DateTime, NaiveDate, TimeZone, Utc};
+use rust_decimal::Decimal;
 use sqlx::PgPool;
 use testcontainers::{ContainerAsync, GenericImage, ImageExt, runners::AsyncRunner};
+use uuid::Uuid;
 
 /// Test helper to create a test database with migrations
 /// Returns (pool, container) - keep container alive for the test duration
@@ -72,6 +78,7 @@ pub fn create_test_new_account_with_name(name: &str) -> NewAccount {
         address: None,
         purchase_date: None,
         purchase_price: None,
+        is_category: false,
         notes: None,
     }
 }
@@ -94,6 +101,68 @@ pub fn create_test_new_account_with_type(
         address: None,
         purchase_date: None,
         purchase_price: None,
+        is_category: false,
         notes: None,
     }
 }
+
+/// Parse a `YYYY-MM-DD` date
+pub fn date(s: &str) -> NaiveDate {
+    NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+}
+
+/// Noon UTC on the given day
+pub fn day(year: i32, month: u32, day: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(year, month, day, 12, 0, 0).unwrap()
+}
+
+/// Sum of every entry posted to an account, voided transactions included
+pub async fn balance(pool: &PgPool, account_id: Uuid) -> Decimal {
+    sqlx::query_scalar("SELECT COALESCE(SUM(amount), 0) FROM journal_entries WHERE account_id = $1")
+        .bind(account_id)
+        .fetch_one(pool)
+        .await
+        .unwrap()
+}
+
+/// Create an account, and any missing parent, from its full path
+pub async fn account_at(
+    pool: &PgPool,
+    path: &str,
+    account_type: AccountType,
+    account_subtype: AccountSubtype,
+) -> Uuid {
+    AccountService::new(pool.clone())
+        .create_account_by_path(
+            NewAccountByPath::builder()
+                .full_path(path)
+                .account_type(account_type)
+                .account_subtype(account_subtype)
+                .build(),
+        )
+        .await
+        .unwrap()
+        .id
+}
+
+/// A two-leg transaction: `amount` to `debit`, taken from `credit`
+pub async fn post(
+    tx_service: &TransactionService,
+    description: &str,
+    date: DateTime<Utc>,
+    debit: Uuid,
+    credit: Uuid,
+    amount: i64,
+) -> TransactionWithEntries {
+    tx_service
+        .create_transaction(TransactionService::create_simple_transaction(
+            description.to_string(),
+            debit,
+            credit,
+            Decimal::from(amount),
+            date,
+            None,
+        ))
+        .await
+        .unwrap()
+}
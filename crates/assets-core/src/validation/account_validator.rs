@@ -4,7 +4,7 @@ use crate::models::account::{
     types::{AccountSubtype, AccountType},
 };
 use rust_decimal::Decimal;
-use sqlx::PgPool;
+use sqlx::{Acquire, PgConnection, PgPool, Postgres};
 use std::collections::HashSet;
 use uuid::Uuid;
 
@@ -49,7 +49,22 @@ impl AccountValidator {
     }
 
     /// Validate a new account for creation
-    pub async fn validate_new_account(&self, account: &NewAccount) -> Result<(), ValidationError> {
+    pub async fn validate_new_account(&self, account: &NewAccount) -> crate::error::Result<()> {
+        self.validate_new_account_in(&self.pool, account).await
+    }
+
+    /// Validate a new account against the state seen by `conn`
+    ///
+    /// Lets a caller creating several accounts in one database transaction see the
+    /// parents it has created so far.
+    pub async fn validate_new_account_in<'c, A>(
+        &self,
+        conn: A,
+        account: &NewAccount,
+    ) -> crate::error::Result<()>
+    where
+        A: Acquire<'c, Database = Postgres>,
+    {
         let mut context = ValidationContext::new();
 
         // Basic field validation
@@ -95,17 +110,20 @@ impl AccountValidator {
         }
 
         // Hierarchy validation (if parent specified)
+        let mut conn = conn.acquire().await?;
         if let Some(parent_id) = account.parent_id {
-            self.validate_hierarchy(parent_id, account.account_type, &mut context)
-                .await;
-            self.validate_name_uniqueness_in_parent(&account.name, Some(parent_id), &mut context)
-                .await;
-        } else {
-            self.validate_name_uniqueness_in_parent(&account.name, None, &mut context)
+            self.validate_hierarchy(&mut conn, parent_id, account.account_type, &mut context)
                 .await;
         }
+        self.validate_name_uniqueness_in_parent(
+            &mut conn,
+            &account.name,
+            account.parent_id,
+            &mut context,
+        )
+        .await;
 
-        context.into_result()
+        Ok(context.into_result()?)
     }
 
     /// Validate account updates
@@ -118,7 +136,7 @@ impl AccountValidator {
 
         // Get existing account to validate updates against
         let existing_account_result = sqlx::query_as::<_, Account>(
-            "SELECT id, name, full_path, account_type, account_subtype, parent_id, symbol, quantity, average_cost, address, purchase_date, purchase_price, currency, is_active, notes, created_at, updated_at FROM accounts WHERE id = $1"
+            "SELECT id, name, full_path, account_type, account_subtype, parent_id, symbol, quantity, average_cost, address, purchase_date, purchase_price, currency, is_category, is_active, notes, created_at, updated_at FROM accounts WHERE id = $1"
         )
         .bind(account_id)
         .fetch_optional(&self.pool)
@@ -146,12 +164,15 @@ impl AccountValidator {
 
             // Check uniqueness within parent (only if name is actually changing)
             if name != &existing_account.name {
-                self.validate_name_uniqueness_in_parent(
-                    name,
-                    existing_account.parent_id,
-                    &mut context,
-                )
-                .await;
+                if let Ok(mut conn) = self.pool.acquire().await {
+                    self.validate_name_uniqueness_in_parent(
+                        &mut conn,
+                        name,
+                        existing_account.parent_id,
+                        &mut context,
+                    )
+                    .await;
+                }
             }
         }
 
@@ -460,16 +481,17 @@ impl AccountValidator {
     /// Validate account hierarchy
     async fn validate_hierarchy(
         &self,
+        conn: &mut PgConnection,
         parent_id: Uuid,
         child_type: AccountType,
         context: &mut ValidationContext,
     ) {
         // Check if parent exists and is active
         let parent_result = sqlx::query_as::<_, Account>(
-            "SELECT id, name, full_path, account_type, account_subtype, parent_id, symbol, quantity, average_cost, address, purchase_date, purchase_price, currency, is_active, notes, created_at, updated_at FROM accounts WHERE id = $1"
+            "SELECT id, name, full_path, account_type, account_subtype, parent_id, symbol, quantity, average_cost, address, purchase_date, purchase_price, currency, is_category, is_active, notes, created_at, updated_at FROM accounts WHERE id = $1"
         )
         .bind(parent_id)
-        .fetch_optional(&self.pool)
+        .fetch_optional(conn)
         .await;
 
         match parent_result {
@@ -514,6 +536,7 @@ impl AccountValidator {
     /// Validate name uniqueness within parent
     async fn validate_name_uniqueness_in_parent(
         &self,
+        conn: &mut PgConnection,
         name: &str,
         parent_id: Option<Uuid>,
         context: &mut ValidationContext,
@@ -528,13 +551,10 @@ impl AccountValidator {
             sqlx::query_scalar(query)
                 .bind(name)
                 .bind(pid)
-                .fetch_one(&self.pool)
+                .fetch_one(conn)
                 .await
         } else {
-            sqlx::query_scalar(query)
-                .bind(name)
-                .fetch_one(&self.pool)
-                .await
+            sqlx::query_scalar(query).bind(name).fetch_one(conn).await
         };
 
         match count_result {
@@ -1,3 +1,4 @@
+use chrono::NaiveDate;
 use rust_decimal::Decimal;
 use thiserror::Error;
 use uuid::Uuid;
@@ -116,6 +117,30 @@ pub enum ValidationError {
         "Non-real-estate accounts cannot have real estate fields (address, purchase_date, purchase_price)"
     )]
     RealEstateFieldsOnNonRealEstate,
+
+    // Transaction validation
+    #[error("Account with ID {account_id} does not exist")]
+    PostingAccountNotFound { account_id: Uuid },
+
+    #[error(
+        "Account '{account}' is a category and cannot take entries; post to one of its accounts"
+    )]
+    PostingToCategoryAccount { account: String },
+
+    #[error("Account '{account}' is inactive and cannot take entries")]
+    PostingToInactiveAccount { account: String },
+
+    #[error("Entries mix accounts kept in different currencies ({currencies})")]
+    MixedCurrencies { currencies: String },
+
+    #[error("Transaction date {date} is before {earliest}")]
+    TransactionDateTooEarly {
+        date: NaiveDate,
+        earliest: NaiveDate,
+    },
+
+    #[error("Transaction date {date} is more than {max_days} days in the future")]
+    TransactionDateTooFarAhead { date: NaiveDate, max_days: i64 },
 }
 
 /// Validation context for collecting multiple errors
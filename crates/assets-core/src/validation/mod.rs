@@ -1,8 +1,10 @@
 pub mod account_validator;
 pub mod errors;
+pub mod transaction_validator;
 
 #[cfg(test)]
 mod tests;
 
 pub use account_validator::AccountValidator;
 pub use errors::ValidationError;
+pub use transaction_validator::{TransactionValidationConfig, TransactionValidator};
@@ -1,9 +1,15 @@
 use super::account_validator::{AccountValidator, ValidationConfig};
 use super::errors::ValidationError;
+use super::transaction_validator::{TransactionValidationConfig, TransactionValidator};
+use crate::CoreError;
 use crate::models::account::{NewAccount, types::*};
+use crate::models::{NewAccountByPath, NewTransaction};
+use crate::services::{AccountService, TransactionService};
 use crate::tests::utils::*;
+use chrono::{DateTime, TimeZone, Utc};
 use rust_decimal::Decimal;
 use std::str::FromStr;
+use uuid::Uuid;
 
 #[tokio::test]
 async fn test_validate_empty_name() {
@@ -22,12 +28,16 @@ async fn test_validate_empty_name() {
         address: None,
         purchase_date: None,
         purchase_price: None,
+        is_category: false,
         notes: None,
     };
 
     let result = validator.validate_new_account(&account).await;
     assert!(result.is_err());
-    assert!(matches!(result.unwrap_err(), ValidationError::EmptyName));
+    assert!(matches!(
+        result.unwrap_err(),
+        CoreError::AccountValidation(ValidationError::EmptyName)
+    ));
 }
 
 #[tokio::test]
@@ -47,6 +57,7 @@ async fn test_validate_invalid_currency() {
         address: None,
         purchase_date: None,
         purchase_price: None,
+        is_category: false,
         notes: None,
     };
 
@@ -54,7 +65,7 @@ async fn test_validate_invalid_currency() {
     assert!(result.is_err());
     assert!(matches!(
         result.unwrap_err(),
-        ValidationError::InvalidCurrency { .. }
+        CoreError::AccountValidation(ValidationError::InvalidCurrency { .. })
     ));
 }
 
@@ -75,6 +86,7 @@ async fn test_validate_invalid_type_subtype_combination() {
         address: None,
         purchase_date: None,
         purchase_price: None,
+        is_category: false,
         notes: None,
     };
 
@@ -82,7 +94,7 @@ async fn test_validate_invalid_type_subtype_combination() {
     assert!(result.is_err());
     assert!(matches!(
         result.unwrap_err(),
-        ValidationError::InvalidTypeSubtypeCombination { .. }
+        CoreError::AccountValidation(ValidationError::InvalidTypeSubtypeCombination { .. })
     ));
 }
 
@@ -103,6 +115,7 @@ async fn test_validate_investment_fields_on_non_investment() {
         address: None,
         purchase_date: None,
         purchase_price: None,
+        is_category: false,
         notes: None,
     };
 
@@ -110,7 +123,7 @@ async fn test_validate_investment_fields_on_non_investment() {
     assert!(result.is_err());
     assert!(matches!(
         result.unwrap_err(),
-        ValidationError::InvestmentFieldsOnNonInvestment
+        CoreError::AccountValidation(ValidationError::InvestmentFieldsOnNonInvestment)
     ));
 }
 
@@ -131,6 +144,7 @@ async fn test_validate_real_estate_fields_on_non_real_estate() {
         address: Some("123 Main St".to_string()), // Real estate field on non-real-estate account
         purchase_date: None,
         purchase_price: None,
+        is_category: false,
         notes: None,
     };
 
@@ -138,7 +152,7 @@ async fn test_validate_real_estate_fields_on_non_real_estate() {
     assert!(result.is_err());
     assert!(matches!(
         result.unwrap_err(),
-        ValidationError::RealEstateFieldsOnNonRealEstate
+        CoreError::AccountValidation(ValidationError::RealEstateFieldsOnNonRealEstate)
     ));
 }
 
@@ -159,6 +173,7 @@ async fn test_validate_invalid_symbol_format() {
         address: None,
         purchase_date: None,
         purchase_price: None,
+        is_category: false,
         notes: None,
     };
 
@@ -166,7 +181,7 @@ async fn test_validate_invalid_symbol_format() {
     assert!(result.is_err());
     assert!(matches!(
         result.unwrap_err(),
-        ValidationError::InvalidSymbol { .. }
+        CoreError::AccountValidation(ValidationError::InvalidSymbol { .. })
     ));
 }
 
@@ -187,6 +202,7 @@ async fn test_validate_missing_quantity_for_symbol() {
         address: None,
         purchase_date: None,
         purchase_price: None,
+        is_category: false,
         notes: None,
     };
 
@@ -194,7 +210,7 @@ async fn test_validate_missing_quantity_for_symbol() {
     assert!(result.is_err());
     assert!(matches!(
         result.unwrap_err(),
-        ValidationError::MissingQuantityForSymbol
+        CoreError::AccountValidation(ValidationError::MissingQuantityForSymbol)
     ));
 }
 
@@ -215,6 +231,7 @@ async fn test_validate_negative_quantity() {
         address: None,
         purchase_date: None,
         purchase_price: None,
+        is_category: false,
         notes: None,
     };
 
@@ -222,7 +239,7 @@ async fn test_validate_negative_quantity() {
     assert!(result.is_err());
     assert!(matches!(
         result.unwrap_err(),
-        ValidationError::InvalidQuantity { .. }
+        CoreError::AccountValidation(ValidationError::InvalidQuantity { .. })
     ));
 }
 
@@ -243,6 +260,7 @@ async fn test_validate_valid_account() {
         address: None,
         purchase_date: None,
         purchase_price: None,
+        is_category: false,
         notes: Some("A valid account".to_string()),
     };
 
@@ -267,6 +285,7 @@ async fn test_validate_valid_investment_account() {
         address: None,
         purchase_date: None,
         purchase_price: None,
+        is_category: false,
         notes: Some("Apple stock".to_string()),
     };
 
@@ -296,9 +315,218 @@ async fn test_validation_config_strict_currency_disabled() {
         address: None,
         purchase_date: None,
         purchase_price: None,
+        is_category: false,
         notes: None,
     };
 
     let result = validator.validate_new_account(&account).await;
     assert!(result.is_ok()); // Should pass with relaxed validation
 }
+
+async fn create_posting_account(
+    service: &AccountService,
+    path: &str,
+    account_type: AccountType,
+    subtype: AccountSubtype,
+    currency: &str,
+    is_category: bool,
+) -> Uuid {
+    service
+        .create_account_by_path(
+            NewAccountByPath::builder()
+                .full_path(path)
+                .account_type(account_type)
+                .account_subtype(subtype)
+                .currency(currency)
+                .is_category(is_category)
+                .build(),
+        )
+        .await
+        .unwrap()
+        .id
+}
+
+fn payment(debit: Uuid, credit: Uuid, date: DateTime<Utc>) -> NewTransaction {
+    TransactionService::create_simple_transaction(
+        "Payment".to_string(),
+        debit,
+        credit,
+        Decimal::from(25),
+        date,
+        None,
+    )
+}
+
+fn transaction_validation_error(result: crate::Result<impl std::fmt::Debug>) -> ValidationError {
+    match result {
+        Err(CoreError::TransactionValidation(error)) => error,
+        other => panic!("expected a transaction validation error, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_transaction_rejects_category_and_inactive_accounts() {
+    let (pool, _container) = setup_test_db().await;
+    let account_service = AccountService::new(pool.clone());
+    let tx_service = TransactionService::new(pool.clone());
+    let checking = create_posting_account(
+        &account_service,
+        "Assets:Checking",
+        AccountType::Asset,
+        AccountSubtype::Checking,
+        "EUR",
+        false,
+    )
+    .await;
+    let food = create_posting_account(
+        &account_service,
+        "Expenses:Food",
+        AccountType::Expense,
+        AccountSubtype::Food,
+        "EUR",
+        true,
+    )
+    .await;
+    let groceries = create_posting_account(
+        &account_service,
+        "Expenses:Food:Groceries",
+        AccountType::Expense,
+        AccountSubtype::Food,
+        "EUR",
+        false,
+    )
+    .await;
+    let today = Utc::now();
+
+    let error = transaction_validation_error(
+        tx_service
+            .create_transaction(payment(food, checking, today))
+            .await,
+    );
+    assert_eq!(
+        error,
+        ValidationError::PostingToCategoryAccount {
+            account: "Expenses:Food".to_string()
+        }
+    );
+
+    // Moving an entry onto the category is rejected too
+    let posted = tx_service
+        .create_transaction(payment(groceries, checking, today))
+        .await
+        .unwrap();
+    let expense_entry = posted
+        .entries
+        .iter()
+        .find(|entry| entry.account_id == groceries)
+        .unwrap();
+    assert!(matches!(
+        transaction_validation_error(tx_service.reassign_entry(expense_entry.id, food).await),
+        ValidationError::PostingToCategoryAccount { .. }
+    ));
+
+    account_service.deactivate_account(groceries).await.unwrap();
+    let error = transaction_validation_error(
+        tx_service
+            .create_transaction(payment(groceries, checking, today))
+            .await,
+    );
+    assert_eq!(
+        error,
+        ValidationError::PostingToInactiveAccount {
+            account: "Expenses:Food:Groceries".to_string()
+        }
+    );
+}
+
+#[tokio::test]
+async fn test_transaction_checks_currencies_on_request_and_rejects_implausible_dates() {
+    let (pool, _container) = setup_test_db().await;
+    let account_service = AccountService::new(pool.clone());
+    let tx_service = TransactionService::new(pool.clone());
+    let checking = create_posting_account(
+        &account_service,
+        "Assets:Checking",
+        AccountType::Asset,
+        AccountSubtype::Checking,
+        "EUR",
+        false,
+    )
+    .await;
+    let dollars = create_posting_account(
+        &account_service,
+        "Assets:Dollar Account",
+        AccountType::Asset,
+        AccountSubtype::Checking,
+        "USD",
+        false,
+    )
+    .await;
+    let rent = create_posting_account(
+        &account_service,
+        "Expenses:Rent",
+        AccountType::Expense,
+        AccountSubtype::Housing,
+        "EUR",
+        false,
+    )
+    .await;
+
+    // A transfer between a euro and a dollar account is accepted by default...
+    tx_service
+        .create_transaction(payment(dollars, checking, Utc::now()))
+        .await
+        .unwrap();
+
+    // ...and only rejected when the strict currency check is asked for
+    let strict = TransactionService::with_validator(
+        pool.clone(),
+        TransactionValidator::with_config(TransactionValidationConfig {
+            strict_currency_validation: true,
+            ..TransactionValidationConfig::default()
+        }),
+    );
+    let error = transaction_validation_error(
+        strict
+            .create_transaction(payment(dollars, checking, Utc::now()))
+            .await,
+    );
+    assert_eq!(
+        error,
+        ValidationError::MixedCurrencies {
+            currencies: "EUR, USD".to_string()
+        }
+    );
+
+    let too_early = Utc.with_ymd_and_hms(1850, 1, 1, 12, 0, 0).unwrap();
+    assert!(matches!(
+        transaction_validation_error(
+            tx_service
+                .create_transaction(payment(rent, checking, too_early))
+                .await
+        ),
+        ValidationError::TransactionDateTooEarly { .. }
+    ));
+    let too_late = Utc::now() + chrono::Duration::days(400);
+    assert!(matches!(
+        transaction_validation_error(
+            tx_service
+                .create_transaction(payment(rent, checking, too_late))
+                .await
+        ),
+        ValidationError::TransactionDateTooFarAhead { .. }
+    ));
+
+    // A wider window lets far-ahead dates through
+    let relaxed = TransactionService::with_validator(
+        pool.clone(),
+        TransactionValidator::with_config(TransactionValidationConfig {
+            max_days_in_future: 1000,
+            ..TransactionValidationConfig::default()
+        }),
+    );
+    relaxed
+        .create_transaction(payment(rent, checking, too_late))
+        .await
+        .unwrap();
+}
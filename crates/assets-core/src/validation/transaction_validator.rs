@@ -0,0 +1,202 @@
+use super::errors::{ValidationContext, ValidationError};
+use crate::error::{CoreError, Result};
+use crate::models::NewTransaction;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use sqlx::PgConnection;
+use std::collections::{BTreeSet, HashMap};
+use uuid::Uuid;
+
+/// Configuration for transaction validation
+#[derive(Debug, Clone)]
+pub struct TransactionValidationConfig {
+    /// Earliest transaction date accepted
+    pub earliest_date: NaiveDate,
+    /// How far past today a transaction may be dated
+    pub max_days_in_future: i64,
+    /// Reject transactions whose accounts are kept in different currencies
+    ///
+    /// Off by default: FX transfers and migrated multi-currency books post across
+    /// currencies on purpose.
+    pub strict_currency_validation: bool,
+}
+
+impl Default for TransactionValidationConfig {
+    fn default() -> Self {
+        Self {
+            earliest_date: NaiveDate::from_ymd_opt(1900, 1, 1).unwrap(),
+            max_days_in_future: 366,
+            strict_currency_validation: false,
+        }
+    }
+}
+
+/// Account fields that decide whether it can take entries
+#[derive(Debug, sqlx::FromRow)]
+struct PostingAccount {
+    id: Uuid,
+    name: String,
+    full_path: Option<String>,
+    currency: String,
+    is_category: bool,
+    is_active: bool,
+}
+
+impl PostingAccount {
+    fn label(&self) -> String {
+        self.full_path.clone().unwrap_or_else(|| self.name.clone())
+    }
+}
+
+/// Validator for anything that posts journal entries
+///
+/// Checks run inside the caller's database transaction, so they see the same
+/// accounts as the insert that follows.
+#[derive(Debug, Clone, Default)]
+pub struct TransactionValidator {
+    config: TransactionValidationConfig,
+}
+
+impl TransactionValidator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_config(config: TransactionValidationConfig) -> Self {
+        Self { config }
+    }
+
+    /// Validate a new transaction: its date and every account it posts to
+    ///
+    /// Rule violations come back as `CoreError::TransactionValidation`; a failed
+    /// account lookup is returned as the database error it is.
+    pub async fn validate_new_transaction(
+        &self,
+        conn: &mut PgConnection,
+        transaction: &NewTransaction,
+    ) -> Result<()> {
+        let mut context = ValidationContext::new();
+
+        self.validate_date(transaction.transaction_date, &mut context);
+        let account_ids: Vec<Uuid> = transaction
+            .entries
+            .iter()
+            .map(|entry| entry.account_id)
+            .collect();
+        self.validate_accounts(conn, &account_ids, &[], &mut context)
+            .await?;
+
+        context
+            .into_result()
+            .map_err(CoreError::TransactionValidation)
+    }
+
+    /// Validate accounts receiving new entries in an existing transaction
+    ///
+    /// `other_account_ids` are the transaction's untouched legs; they only take
+    /// part in the currency check.
+    pub async fn validate_postings(
+        &self,
+        conn: &mut PgConnection,
+        account_ids: &[Uuid],
+        other_account_ids: &[Uuid],
+    ) -> Result<()> {
+        let mut context = ValidationContext::new();
+        self.validate_accounts(conn, account_ids, other_account_ids, &mut context)
+            .await?;
+        context
+            .into_result()
+            .map_err(CoreError::TransactionValidation)
+    }
+
+    /// Validate a new date for a transaction
+    pub fn validate_transaction_date(
+        &self,
+        transaction_date: DateTime<Utc>,
+    ) -> std::result::Result<(), ValidationError> {
+        let mut context = ValidationContext::new();
+        self.validate_date(transaction_date, &mut context);
+        context.into_result()
+    }
+
+    /// Flag dates before the configured earliest date or too far in the future
+    fn validate_date(&self, transaction_date: DateTime<Utc>, context: &mut ValidationContext) {
+        let date = transaction_date.date_naive();
+        if date < self.config.earliest_date {
+            context.add_error(ValidationError::TransactionDateTooEarly {
+                date,
+                earliest: self.config.earliest_date,
+            });
+        }
+
+        let latest = Utc::now().date_naive() + Duration::days(self.config.max_days_in_future);
+        if date > latest {
+            context.add_error(ValidationError::TransactionDateTooFarAhead {
+                date,
+                max_days: self.config.max_days_in_future,
+            });
+        }
+    }
+
+    /// Check that accounts exist, can take entries and, when configured, share one currency
+    async fn validate_accounts(
+        &self,
+        conn: &mut PgConnection,
+        account_ids: &[Uuid],
+        other_account_ids: &[Uuid],
+        context: &mut ValidationContext,
+    ) -> Result<()> {
+        let all_ids: Vec<Uuid> = account_ids
+            .iter()
+            .chain(other_account_ids)
+            .copied()
+            .collect();
+        let accounts = sqlx::query_as::<_, PostingAccount>(
+            "SELECT id, name, full_path, currency, is_category, is_active FROM accounts WHERE id = ANY($1)",
+        )
+        .bind(&all_ids)
+        .fetch_all(&mut *conn)
+        .await?;
+        let accounts: HashMap<Uuid, PostingAccount> = accounts
+            .into_iter()
+            .map(|account| (account.id, account))
+            .collect();
+
+        let mut seen = BTreeSet::new();
+        for account_id in account_ids {
+            if !seen.insert(*account_id) {
+                continue;
+            }
+            match accounts.get(account_id) {
+                None => context.add_error(ValidationError::PostingAccountNotFound {
+                    account_id: *account_id,
+                }),
+                Some(account) if account.is_category => {
+                    context.add_error(ValidationError::PostingToCategoryAccount {
+                        account: account.label(),
+                    })
+                }
+                Some(account) if !account.is_active => {
+                    context.add_error(ValidationError::PostingToInactiveAccount {
+                        account: account.label(),
+                    })
+                }
+                Some(_) => {}
+            }
+        }
+
+        if self.config.strict_currency_validation {
+            let currencies: BTreeSet<&str> = all_ids
+                .iter()
+                .filter_map(|id| accounts.get(id))
+                .map(|account| account.currency.as_str())
+                .collect();
+            if currencies.len() > 1 {
+                context.add_error(ValidationError::MixedCurrencies {
+                    currencies: currencies.into_iter().collect::<Vec<_>>().join(", "),
+                });
+            }
+        }
+
+        Ok(())
+    }
+}